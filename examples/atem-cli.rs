@@ -0,0 +1,198 @@
+use std::time::Duration;
+
+use anyhow::{bail, Result};
+use atem_rs::{
+    command::{Command, SetAuxSource, SetProgramInput},
+    control::ControlCommand,
+    streaming::SetStreaming,
+    transition::{AutoTransition, Cut},
+    Connection, Message,
+};
+use clap::{Parser, Subcommand};
+
+/// Time to wait for the switcher to confirm a change
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Parser)]
+struct Args {
+    address: Box<str>,
+    #[command(subcommand)]
+    command: Cmd,
+}
+
+#[derive(Debug, Subcommand)]
+enum Cmd {
+    /// Print every command received from the switcher
+    Monitor,
+    /// Print the inputs on program and preview every time the tally changes
+    Tally,
+    /// Put a source on program
+    Program { me: u8, source: u16 },
+    /// Cut preview to program
+    Cut {
+        #[arg(default_value_t = 0)]
+        me: u8,
+    },
+    /// Take preview to program using the selected transition
+    Auto {
+        #[arg(default_value_t = 0)]
+        me: u8,
+    },
+    /// Route a source to an aux output
+    Aux { aux: u8, source: u16 },
+    /// Control macros
+    Macro {
+        #[command(subcommand)]
+        command: MacroCmd,
+    },
+    /// Control the streaming encoder
+    Stream {
+        #[command(subcommand)]
+        command: StreamCmd,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum MacroCmd {
+    /// Run a macro and wait until it has finished
+    Run {
+        index: u16,
+        /// Seconds to wait for the macro to finish
+        #[arg(long, default_value_t = 60)]
+        timeout: u64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum StreamCmd {
+    Start,
+    Stop,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    tracing_subscriber::fmt::init();
+
+    let args = Args::parse();
+
+    let mut atem = Connection::open(&args.address).await?;
+
+    match args.command {
+        Cmd::Monitor => monitor(&mut atem).await,
+        Cmd::Tally => tally(&mut atem).await,
+        command => {
+            wait_for_state(&mut atem).await?;
+            control(&atem, command).await
+        }
+    }
+}
+
+async fn monitor(atem: &mut Connection) -> Result<()> {
+    loop {
+        let command = next_command(atem).await?;
+        println!("{}", command.display_with(&atem.system_info()));
+    }
+}
+
+async fn tally(atem: &mut Connection) -> Result<()> {
+    loop {
+        if let Command::TallySources(tally) = next_command(atem).await? {
+            let program: Vec<String> = tally
+                .iter()
+                .filter(|(_, state)| state.program())
+                .map(|(source, _)| source.to_string())
+                .collect();
+            let preview: Vec<String> = tally
+                .iter()
+                .filter(|(_, state)| state.preview())
+                .map(|(source, _)| source.to_string())
+                .collect();
+
+            println!(
+                "Program: {} Preview: {}",
+                program.join(", "),
+                preview.join(", ")
+            );
+        }
+    }
+}
+
+async fn control(atem: &Connection, command: Cmd) -> Result<()> {
+    let capabilities = atem.system_info().capabilities();
+
+    match command {
+        Cmd::Program { me, source } => {
+            check_index("mix effect block", me, capabilities.me_count())?;
+            atem.send_confirmed(
+                ControlCommand::ProgramInput(SetProgramInput::new(me, source)),
+                CONFIRM_TIMEOUT,
+            )
+            .await?;
+        }
+        Cmd::Cut { me } => {
+            check_index("mix effect block", me, capabilities.me_count())?;
+            atem.send_command(ControlCommand::Cut(Cut::new(me))).await?;
+        }
+        Cmd::Auto { me } => {
+            check_index("mix effect block", me, capabilities.me_count())?;
+            atem.send_command(ControlCommand::AutoTransition(AutoTransition::new(me)))
+                .await?;
+        }
+        Cmd::Aux { aux, source } => {
+            check_index("aux output", aux, capabilities.aux_count())?;
+            atem.send_confirmed(
+                ControlCommand::AuxSource(SetAuxSource::new(aux, source)),
+                CONFIRM_TIMEOUT,
+            )
+            .await?;
+        }
+        Cmd::Macro {
+            command: MacroCmd::Run { index, timeout },
+        } => {
+            atem.macros()
+                .run_to_completion(index, Duration::from_secs(timeout))
+                .await?
+        }
+        Cmd::Stream { command } => {
+            if !capabilities.has_streaming() {
+                bail!("{} has no streaming encoder", capabilities.model());
+            }
+            let start = matches!(command, StreamCmd::Start);
+            atem.send_command(ControlCommand::Streaming(SetStreaming::new(start)))
+                .await?;
+        }
+        Cmd::Monitor | Cmd::Tally => unreachable!(),
+    }
+
+    Ok(())
+}
+
+/// Receive commands until the switcher has described its topology, which it does right after
+/// the connection has been established
+async fn wait_for_state(atem: &mut Connection) -> Result<()> {
+    while !matches!(next_command(atem).await?, Command::Topology(_)) {}
+
+    Ok(())
+}
+
+async fn next_command(atem: &mut Connection) -> Result<Command> {
+    loop {
+        match atem.recv_message().await {
+            Some(Message::Connected) => {}
+            Some(Message::Disconnected(e)) => return Err(e.into()),
+            Some(Message::ParsingFailed(e)) => println!("{}", e),
+            Some(Message::ProtocolError(e)) => println!("{}", e),
+            Some(Message::Command(c)) => return Ok(c),
+            Some(Message::Warning(w)) => println!("Warning: {}", w),
+            None => bail!("Connection closed"),
+        }
+    }
+}
+
+fn check_index(name: &str, index: u8, count: u8) -> Result<()> {
+    if index >= count {
+        bail!("No {name} {index}, the switcher has {count}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+//! Record the initial state a switcher sends after connecting, as used by the fixtures in
+//! `tests/fixtures`
+
+use std::{collections::HashSet, time::Duration};
+
+use anyhow::{bail, Result};
+use atem_rs::packet::Packet;
+use bytes::{Buf, Bytes, BytesMut};
+use clap::Parser;
+use tokio::{fs, net::UdpSocket, time::timeout};
+
+/// The last command of the initial state
+const INIT_COMPLETE: &[u8; 4] = b"InCm";
+
+#[derive(Debug, Parser)]
+struct Args {
+    address: Box<str>,
+    /// File the command payloads are written to
+    output: Box<str>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let socket = UdpSocket::bind("0.0.0.0:0").await?;
+    socket.connect(format!("{}:9910", args.address)).await?;
+    socket.send(&Packet::new_hello_packet().serialize()).await?;
+
+    let mut capture = BytesMut::new();
+    let mut received = HashSet::new();
+    let mut packet_id: u16 = 0;
+    loop {
+        let mut buf = BytesMut::with_capacity(1500);
+        if timeout(Duration::from_secs(5), socket.recv_buf(&mut buf))
+            .await
+            .is_err()
+        {
+            bail!("Timed out before the initial state was complete");
+        }
+
+        let mut packets = buf.freeze();
+        while !packets.is_empty() {
+            let packet = Packet::deserialize(&mut packets)?;
+            if packet.is_hello() {
+                let ack = Packet::new_ack(packet.uid(), packet.id(), 0);
+                socket.send(&ack.serialize()).await?;
+                continue;
+            }
+
+            if packet.ack_request() {
+                packet_id = packet_id.wrapping_add(1);
+                let ack = Packet::new_ack(packet.uid(), packet.id(), packet_id);
+                socket.send(&ack.serialize()).await?;
+            }
+
+            // Packets sent again because an ack got lost are only recorded once
+            let Some(payload) = packet.payload() else {
+                continue;
+            };
+            if !received.insert(packet.id()) {
+                continue;
+            }
+
+            capture.extend_from_slice(&payload);
+            if contains_command(payload, INIT_COMPLETE) {
+                fs::write(args.output.as_ref(), &capture).await?;
+                println!("Wrote {} bytes to {}", capture.len(), args.output);
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn contains_command(mut payload: Bytes, name: &[u8; 4]) -> bool {
+    while payload.remaining() >= 8 {
+        let size = payload.get_u16() as usize;
+        payload.advance(2);
+        if &payload[..4] == name {
+            return true;
+        }
+        payload.advance(size.saturating_sub(4).clamp(4, payload.remaining()));
+    }
+
+    false
+}
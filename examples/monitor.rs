@@ -0,0 +1,272 @@
+use std::{collections::BTreeMap, time::Duration};
+
+use anyhow::{bail, Result};
+use atem_rs::{
+    audio::{AudioLevels, FairlightSourceLevels},
+    command::Command,
+    fairlight::{AudioSourceId, ChannelSplit},
+    systeminfo::SystemInfo,
+    Connection, Message,
+};
+use clap::Parser;
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Gauge, Paragraph},
+    DefaultTerminal, Frame,
+};
+
+/// Interval between redraws, also how often key presses are checked
+const FRAME_INTERVAL: Duration = Duration::from_millis(50);
+/// Level shown as an empty audio meter, quieter signals are clipped to it
+const METER_FLOOR: f32 = -60.0;
+
+#[derive(Debug, Parser)]
+struct Args {
+    address: Box<str>,
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let atem = Connection::open(&args.address).await?;
+
+    let terminal = ratatui::init();
+    let result = run(terminal, atem).await;
+    ratatui::restore();
+
+    result
+}
+
+async fn run(mut terminal: DefaultTerminal, mut atem: Connection) -> Result<()> {
+    let mut frame_interval = tokio::time::interval(FRAME_INTERVAL);
+    let mut status = String::from("Connecting");
+    let mut meters = Meters::default();
+
+    loop {
+        tokio::select! {
+            // The connection keeps the state up to date, only the audio levels aren't stored
+            message = atem.recv_message() => match message {
+                Some(Message::Disconnected(e)) => return Err(e.into()),
+                Some(Message::ParsingFailed(e)) => status = e.to_string(),
+                Some(Message::ProtocolError(e)) => status = e.to_string(),
+                Some(Message::Warning(w)) => status = format!("Warning: {w}"),
+                Some(Message::Command(command)) => meters.update(&atem, command)?,
+                Some(Message::Connected) => {}
+                None => bail!("Connection closed"),
+            },
+            _ = frame_interval.tick() => {
+                if quit_requested()? {
+                    return Ok(());
+                }
+                terminal.draw(|frame| draw(frame, &atem.system_info(), &meters, &status))?;
+            }
+        }
+    }
+}
+
+/// Latest audio levels, the switcher sends them periodically once enabled
+#[derive(Default)]
+struct Meters {
+    classic: Option<AudioLevels>,
+    fairlight: BTreeMap<AudioSourceId, FairlightSourceLevels>,
+}
+
+impl Meters {
+    fn update(&mut self, atem: &Connection, command: Command) -> Result<()> {
+        match command {
+            // Which levels to enable depends on the audio mixer, known once it is described
+            Command::AudioMixerConfig(_) | Command::FairlightMixerConfig(_) => {
+                atem.enable_audio_levels(true)?
+            }
+            Command::AudioLevels(levels) => self.classic = Some(levels),
+            Command::FairlightSourceLevels(levels) => {
+                self.fairlight.insert(levels.source(), levels);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Name, left and right level in dBFS of every meter. Only the classic mixer reports the
+    /// levels of the master bus, the Fairlight mixer only those of its sources.
+    fn rows(&self, system_info: &SystemInfo) -> Vec<(String, f32, f32)> {
+        let mut rows = Vec::new();
+
+        if let Some(levels) = &self.classic {
+            let master = levels.master();
+            rows.push(("Master".into(), master.left(), master.right()));
+            for (id, source) in levels.sources() {
+                rows.push((source_name(system_info, id), source.left(), source.right()));
+            }
+        }
+
+        for (source, levels) in &self.fairlight {
+            let mut name = source_name(system_info, source.input());
+            match source.split() {
+                ChannelSplit::Left => name.push_str(" L"),
+                ChannelSplit::Right => name.push_str(" R"),
+                _ => {}
+            }
+            rows.push((name, levels.levels().left(), levels.levels().right()));
+        }
+
+        rows
+    }
+}
+
+fn quit_requested() -> Result<bool> {
+    while event::poll(Duration::ZERO)? {
+        if let Event::Key(key) = event::read()? {
+            if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc) {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+fn draw(frame: &mut Frame, system_info: &SystemInfo, meters: &Meters, status: &str) {
+    let capabilities = system_info.capabilities();
+    let me_count = capabilities.me_count();
+    let meter_rows = meters.rows(system_info);
+
+    let [header, mix_effects, tally, audio, footer] = Layout::vertical([
+        Constraint::Length(1),
+        Constraint::Length(5 * me_count as u16),
+        Constraint::Length(3),
+        Constraint::Min(meter_rows.len() as u16 + 2),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+
+    frame.render_widget(
+        Line::from(format!(
+            "{} ({}) protocol {}",
+            system_info.product(),
+            capabilities.model(),
+            system_info.version()
+        ))
+        .bold(),
+        header,
+    );
+
+    let rows = Layout::vertical(vec![Constraint::Length(5); me_count as usize]).split(mix_effects);
+    for (me, area) in rows.iter().enumerate() {
+        draw_mix_effect(frame, *area, system_info, me as u8);
+    }
+
+    draw_tally(frame, tally, system_info);
+    draw_meters(frame, audio, &meter_rows);
+
+    frame.render_widget(
+        Line::from(format!("{status}  (q to quit)")).dark_gray(),
+        footer,
+    );
+}
+
+fn draw_mix_effect(frame: &mut Frame, area: Rect, system_info: &SystemInfo, me: u8) {
+    let block = Block::bordered().title(format!(" ME {} ", me + 1));
+    let [sources, transition] =
+        Layout::vertical([Constraint::Length(1), Constraint::Length(2)]).areas(block.inner(area));
+    frame.render_widget(block, area);
+
+    let source = |id: Option<u16>| match id {
+        Some(id) => source_name(system_info, id),
+        None => "-".into(),
+    };
+    frame.render_widget(
+        Line::from(vec![
+            Span::raw("Program: "),
+            Span::styled(source(system_info.program_input(me)), Color::Red),
+            Span::raw("  Preview: "),
+            Span::styled(source(system_info.preview_input(me)), Color::Green),
+        ]),
+        sources,
+    );
+
+    let (ratio, label) = match system_info.transition_position(me) {
+        Some(position) if position.position() > 0 => (
+            position.position() as f64 / 10000.0,
+            format!("{} frames left", position.frame_count()),
+        ),
+        _ => (0.0, "Idle".into()),
+    };
+    frame.render_widget(
+        Gauge::default()
+            .block(Block::new().title("Transition"))
+            .gauge_style(Style::new().fg(Color::Yellow))
+            .ratio(ratio)
+            .label(label),
+        transition,
+    );
+}
+
+fn draw_tally(frame: &mut Frame, area: Rect, system_info: &SystemInfo) {
+    let mut spans = Vec::new();
+    if let Some(tally) = system_info.tally_sources() {
+        for (id, state) in tally.iter() {
+            let color = match (state.program(), state.preview()) {
+                (true, _) => Color::Red,
+                (false, true) => Color::Green,
+                (false, false) => continue,
+            };
+            spans.push(
+                Span::styled(format!(" {} ", source_name(system_info, id)), color).reversed(),
+            );
+            spans.push(Span::raw(" "));
+        }
+    }
+
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)).block(Block::bordered().title(" Tally ")),
+        area,
+    );
+}
+
+fn draw_meters(frame: &mut Frame, area: Rect, rows: &[(String, f32, f32)]) {
+    let block = Block::bordered().title(" Audio (dBFS) ");
+    let areas = Layout::vertical(vec![Constraint::Length(1); rows.len()]).split(block.inner(area));
+    frame.render_widget(block, area);
+
+    for ((name, left, right), area) in rows.iter().zip(areas.iter()) {
+        let [label, meter] =
+            Layout::horizontal([Constraint::Length(10), Constraint::Min(10)]).areas(*area);
+        let level = left.max(*right);
+        let color = match level {
+            l if l > -6.0 => Color::Red,
+            l if l > -18.0 => Color::Yellow,
+            _ => Color::Green,
+        };
+
+        frame.render_widget(Line::from(name.as_str()), label);
+        frame.render_widget(
+            Gauge::default()
+                .gauge_style(Style::new().fg(color))
+                .ratio((1.0 - level.clamp(METER_FLOOR, 0.0) / METER_FLOOR) as f64)
+                .label(format!("{} / {}", decibels(*left), decibels(*right))),
+            meter,
+        );
+    }
+}
+
+fn decibels(level: f32) -> String {
+    if level <= METER_FLOOR {
+        "-inf".into()
+    } else {
+        format!("{level:.1}")
+    }
+}
+
+fn source_name(system_info: &SystemInfo, id: u16) -> String {
+    system_info
+        .source(id)
+        .and_then(|source| source.short_name())
+        .map(String::from)
+        .unwrap_or_else(|| id.to_string())
+}
@@ -0,0 +1,17 @@
+#![no_main]
+
+use atem_rs::{command::Command, systeminfo::SystemInfo};
+use bytes::Bytes;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut payload = Bytes::copy_from_slice(data);
+    let mut system_info = SystemInfo::default();
+    while !payload.is_empty() {
+        if let Ok(command) = Command::parse(&mut payload) {
+            let _ = command.to_string();
+            let _ = command.display_with(&system_info).to_string();
+            system_info.update(&command);
+        }
+    }
+});
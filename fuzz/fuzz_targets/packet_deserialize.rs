@@ -0,0 +1,14 @@
+#![no_main]
+
+use atem_rs::packet::Packet;
+use bytes::Bytes;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut packets = Bytes::copy_from_slice(data);
+    while !packets.is_empty() {
+        if Packet::deserialize(&mut packets).is_err() {
+            break;
+        }
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use atem_rs::packet::Packet;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|packet: Packet| {
+    let mut data = packet.serialize();
+    let parsed = Packet::deserialize(&mut data).unwrap();
+
+    assert_eq!(parsed, packet);
+    assert!(data.is_empty());
+});
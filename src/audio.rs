@@ -0,0 +1,1190 @@
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use bitflags::bitflags;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::fairlight::AudioSourceId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum MixMinusMode {
+    ProgramOut,
+    MixMinus,
+    Unknown(u8),
+}
+
+impl From<u8> for MixMinusMode {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => MixMinusMode::ProgramOut,
+            1 => MixMinusMode::MixMinus,
+            u => MixMinusMode::Unknown(u),
+        }
+    }
+}
+
+impl From<MixMinusMode> for u8 {
+    fn from(value: MixMinusMode) -> Self {
+        match value {
+            MixMinusMode::ProgramOut => 0,
+            MixMinusMode::MixMinus => 1,
+            MixMinusMode::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for MixMinusMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MixMinusMode::ProgramOut => write!(f, "Program out"),
+            MixMinusMode::MixMinus => write!(f, "Mix minus"),
+            MixMinusMode::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct MixMinusModeFlags: u8 {
+        const PROGRAM_OUT = 0x01;
+        const MIX_MINUS = 0x02;
+    }
+}
+
+/// Audio sent back on a mix minus output, e.g. to a remote camera position
+#[derive(Debug, Clone)]
+pub struct MixMinusOutput {
+    output: u16,
+    available_modes: MixMinusModeFlags,
+    mode: MixMinusMode,
+    audio_input: Option<u16>,
+}
+
+impl MixMinusOutput {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let output = data.get_u16();
+        let available_modes = MixMinusModeFlags::from_bits_retain(data.get_u8());
+        let mode = data.get_u8().into();
+        let has_audio_input = data.get_u8() == 1;
+        data.get_u8(); // Padding
+        let audio_input = data.get_u16();
+
+        MixMinusOutput {
+            output,
+            available_modes,
+            mode,
+            audio_input: has_audio_input.then_some(audio_input),
+        }
+    }
+
+    pub fn output(&self) -> u16 {
+        self.output
+    }
+
+    pub fn available_modes(&self) -> MixMinusModeFlags {
+        self.available_modes
+    }
+
+    pub fn mode(&self) -> MixMinusMode {
+        self.mode
+    }
+
+    /// Audio input removed from the mix when in mix minus mode
+    pub fn audio_input(&self) -> Option<u16> {
+        self.audio_input
+    }
+}
+
+impl Display for MixMinusOutput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Output: {} Mode: {}", self.output, self.mode)?;
+
+        if let Some(input) = self.audio_input {
+            write!(f, " Audio input: {input}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Change the mode of a mix minus output (`CMMP`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetMixMinusOutput {
+    output: u8,
+    mode: MixMinusMode,
+}
+
+impl SetMixMinusOutput {
+    pub fn new(output: u8, mode: MixMinusMode) -> Self {
+        SetMixMinusOutput { output, mode }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(0x01); // Mask, mode is the only property
+        data.put_u8(self.output);
+        data.put_u8(self.mode.into());
+        data.put_u8(0); // Padding
+    }
+}
+
+impl Display for SetMixMinusOutput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Output: {} Mode: {}", self.output, self.mode)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum TalkbackChannel {
+    Production,
+    Engineering,
+    Unknown(u8),
+}
+
+impl From<u8> for TalkbackChannel {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => TalkbackChannel::Production,
+            1 => TalkbackChannel::Engineering,
+            u => TalkbackChannel::Unknown(u),
+        }
+    }
+}
+
+impl From<TalkbackChannel> for u8 {
+    fn from(value: TalkbackChannel) -> Self {
+        match value {
+            TalkbackChannel::Production => 0,
+            TalkbackChannel::Engineering => 1,
+            TalkbackChannel::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for TalkbackChannel {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TalkbackChannel::Production => write!(f, "Production"),
+            TalkbackChannel::Engineering => write!(f, "Engineering"),
+            TalkbackChannel::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TalkbackProperties {
+    channel: TalkbackChannel,
+    mute_sdi: bool,
+}
+
+impl TalkbackProperties {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let channel = data.get_u8().into();
+        let mute_sdi = data.get_u8() == 1;
+
+        TalkbackProperties { channel, mute_sdi }
+    }
+
+    pub fn channel(&self) -> TalkbackChannel {
+        self.channel
+    }
+
+    /// True when talkback is muted on all SDI outputs of the channel
+    pub fn mute_sdi(&self) -> bool {
+        self.mute_sdi
+    }
+}
+
+impl Display for TalkbackProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Channel: {} Mute SDI: {}", self.channel, self.mute_sdi)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TalkbackInputProperties {
+    channel: TalkbackChannel,
+    input: u16,
+    can_mute_sdi: bool,
+    mute_sdi: bool,
+}
+
+impl TalkbackInputProperties {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let channel = data.get_u8().into();
+        data.get_u8(); // Padding
+        let input = data.get_u16();
+        let can_mute_sdi = data.get_u8() == 1; // Not verified
+        let mute_sdi = data.get_u8() == 1;
+
+        TalkbackInputProperties {
+            channel,
+            input,
+            can_mute_sdi,
+            mute_sdi,
+        }
+    }
+
+    pub fn channel(&self) -> TalkbackChannel {
+        self.channel
+    }
+
+    pub fn input(&self) -> u16 {
+        self.input
+    }
+
+    pub fn can_mute_sdi(&self) -> bool {
+        self.can_mute_sdi
+    }
+
+    pub fn mute_sdi(&self) -> bool {
+        self.mute_sdi
+    }
+}
+
+impl Display for TalkbackInputProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Channel: {} Input: {} Can mute SDI: {} Mute SDI: {}",
+            self.channel, self.input, self.can_mute_sdi, self.mute_sdi
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+/// Raw classic audio level of full scale, 0 dBFS
+const CLASSIC_FULL_SCALE: f32 = 8_388_608.0; // 2^23
+
+/// Meter levels of a stereo signal of the classic audio mixer. The switcher reports linear
+/// amplitudes where 2^23 is full scale, the level accessors convert them to dBFS with silence
+/// at negative infinity.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StereoLevels {
+    left: u32,
+    right: u32,
+    peak_left: u32,
+    peak_right: u32,
+}
+
+impl StereoLevels {
+    fn parse(data: &mut Bytes) -> Self {
+        StereoLevels {
+            left: data.get_u32(),
+            right: data.get_u32(),
+            peak_left: data.get_u32(),
+            peak_right: data.get_u32(),
+        }
+    }
+
+    /// Raw left, right, left peak and right peak levels as sent by the switcher
+    pub fn raw(&self) -> [u32; 4] {
+        [self.left, self.right, self.peak_left, self.peak_right]
+    }
+
+    #[cfg(feature = "std")]
+    pub fn left(&self) -> f32 {
+        classic_level_to_dbfs(self.left)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn right(&self) -> f32 {
+        classic_level_to_dbfs(self.right)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn peak_left(&self) -> f32 {
+        classic_level_to_dbfs(self.peak_left)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn peak_right(&self) -> f32 {
+        classic_level_to_dbfs(self.peak_right)
+    }
+}
+
+impl Display for StereoLevels {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Left: {} Right: {} Peak left: {} Peak right: {}",
+            self.left, self.right, self.peak_left, self.peak_right
+        )
+    }
+}
+
+/// Convert a raw classic audio meter level to dBFS
+#[cfg(feature = "std")]
+pub fn classic_level_to_dbfs(level: u32) -> f32 {
+    if level == 0 {
+        return f32::NEG_INFINITY;
+    }
+
+    20.0 * (level as f32 / CLASSIC_FULL_SCALE).log10()
+}
+
+/// Meter levels of the classic audio mixer (`AMLv`), only sent while enabled
+#[derive(Debug, Clone)]
+pub struct AudioLevels {
+    master: StereoLevels,
+    monitor: StereoLevels,
+    sources: Vec<(u16, StereoLevels)>,
+}
+
+impl AudioLevels {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let declared = data.get_u16() as usize;
+        data.get_u16(); // Padding
+        let master = StereoLevels::parse(data);
+        let monitor = StereoLevels::parse(data);
+
+        // Source ids padded to 4 bytes followed by the levels of each source, only keep the
+        // sources whose levels were received
+        let ids_size = (declared * 2).next_multiple_of(4);
+        let mut sources = Vec::new();
+        if data.remaining() >= ids_size {
+            let mut ids = data.split_to(ids_size);
+            let count = declared.min(data.remaining() / 16);
+            sources = (0..count)
+                .map(|_| (ids.get_u16(), StereoLevels::parse(data)))
+                .collect();
+        }
+
+        AudioLevels {
+            master,
+            monitor,
+            sources,
+        }
+    }
+
+    pub fn master(&self) -> &StereoLevels {
+        &self.master
+    }
+
+    pub fn monitor(&self) -> &StereoLevels {
+        &self.monitor
+    }
+
+    /// Levels of a source, e.g. a camera input or media player
+    pub fn source(&self, source_id: u16) -> Option<&StereoLevels> {
+        self.sources
+            .iter()
+            .find(|(id, _)| *id == source_id)
+            .map(|(_, levels)| levels)
+    }
+
+    /// Iterate over source ids and their levels
+    pub fn sources(&self) -> impl Iterator<Item = (u16, &StereoLevels)> {
+        self.sources.iter().map(|(id, levels)| (*id, levels))
+    }
+}
+
+impl Display for AudioLevels {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Master: {} Monitor: {} Sources: {}",
+            self.master,
+            self.monitor,
+            self.sources.len()
+        )
+    }
+}
+
+/// Meter levels of a stereo signal of the Fairlight mixer. The switcher reports them in
+/// hundredths of a dB relative to full scale, the level accessors convert them to dBFS.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct FairlightLevels {
+    left: i16,
+    right: i16,
+    peak_left: i16,
+    peak_right: i16,
+}
+
+impl FairlightLevels {
+    fn parse(data: &mut Bytes) -> Self {
+        FairlightLevels {
+            left: data.get_i16(),
+            right: data.get_i16(),
+            peak_left: data.get_i16(),
+            peak_right: data.get_i16(),
+        }
+    }
+
+    /// Raw left, right, left peak and right peak levels as sent by the switcher
+    pub fn raw(&self) -> [i16; 4] {
+        [self.left, self.right, self.peak_left, self.peak_right]
+    }
+
+    pub fn left(&self) -> f32 {
+        fairlight_level_to_dbfs(self.left)
+    }
+
+    pub fn right(&self) -> f32 {
+        fairlight_level_to_dbfs(self.right)
+    }
+
+    pub fn peak_left(&self) -> f32 {
+        fairlight_level_to_dbfs(self.peak_left)
+    }
+
+    pub fn peak_right(&self) -> f32 {
+        fairlight_level_to_dbfs(self.peak_right)
+    }
+}
+
+impl Display for FairlightLevels {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Left: {:.2} Right: {:.2} Peak left: {:.2} Peak right: {:.2}",
+            self.left(),
+            self.right(),
+            self.peak_left(),
+            self.peak_right()
+        )
+    }
+}
+
+/// Convert a raw Fairlight meter level or gain reduction to dB
+pub fn fairlight_level_to_dbfs(level: i16) -> f32 {
+    level as f32 / 100.0
+}
+
+/// Meter levels of a Fairlight mixer source (`FMLv`), only sent while enabled
+#[derive(Debug, Clone)]
+pub struct FairlightSourceLevels {
+    source: AudioSourceId,
+    input_levels: FairlightLevels,
+    expander_gain_reduction: i16,
+    compressor_gain_reduction: i16,
+    limiter_gain_reduction: i16,
+    output_levels: FairlightLevels,
+    levels: FairlightLevels,
+}
+
+impl FairlightSourceLevels {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let source = AudioSourceId::parse(data);
+        let input_levels = FairlightLevels::parse(data);
+        let expander_gain_reduction = data.get_i16();
+        let compressor_gain_reduction = data.get_i16();
+        let limiter_gain_reduction = data.get_i16();
+        let output_levels = FairlightLevels::parse(data);
+        let levels = FairlightLevels::parse(data);
+
+        FairlightSourceLevels {
+            source,
+            input_levels,
+            expander_gain_reduction,
+            compressor_gain_reduction,
+            limiter_gain_reduction,
+            output_levels,
+            levels,
+        }
+    }
+
+    pub fn source(&self) -> AudioSourceId {
+        self.source
+    }
+
+    /// Levels before the dynamics processing
+    pub fn input_levels(&self) -> &FairlightLevels {
+        &self.input_levels
+    }
+
+    /// Levels after the dynamics processing, before the fader
+    pub fn output_levels(&self) -> &FairlightLevels {
+        &self.output_levels
+    }
+
+    /// Levels after the fader
+    pub fn levels(&self) -> &FairlightLevels {
+        &self.levels
+    }
+
+    /// Gain reduction of the expander, compressor and limiter in dB
+    pub fn gain_reduction(&self) -> [f32; 3] {
+        [
+            fairlight_level_to_dbfs(self.expander_gain_reduction),
+            fairlight_level_to_dbfs(self.compressor_gain_reduction),
+            fairlight_level_to_dbfs(self.limiter_gain_reduction),
+        ]
+    }
+}
+
+impl Display for FairlightSourceLevels {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} Levels: {}", self.source, self.levels)
+    }
+}
+
+/// Mute talkback on all SDI outputs of a channel (`CTkM`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetTalkbackProperties {
+    channel: TalkbackChannel,
+    mute_sdi: bool,
+}
+
+impl SetTalkbackProperties {
+    pub fn new(channel: TalkbackChannel, mute_sdi: bool) -> Self {
+        SetTalkbackProperties { channel, mute_sdi }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(0x01); // Mask, mute SDI is the only property
+        data.put_u8(self.channel.into());
+        data.put_u8(self.mute_sdi as u8);
+        data.put_u8(0); // Padding
+    }
+}
+
+impl Display for SetTalkbackProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Channel: {} Mute SDI: {}", self.channel, self.mute_sdi)
+    }
+}
+
+/// Mute talkback on the SDI output feeding a single input (`CTkI`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetTalkbackInputProperties {
+    channel: TalkbackChannel,
+    input: u16,
+    mute_sdi: bool,
+}
+
+impl SetTalkbackInputProperties {
+    pub fn new(channel: TalkbackChannel, input: u16, mute_sdi: bool) -> Self {
+        SetTalkbackInputProperties {
+            channel,
+            input,
+            mute_sdi,
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(0x01); // Mask, mute SDI is the only property
+        data.put_u8(self.channel.into());
+        data.put_u16(self.input);
+        data.put_u8(self.mute_sdi as u8);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetTalkbackInputProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Channel: {} Input: {} Mute SDI: {}",
+            self.channel, self.input, self.mute_sdi
+        )
+    }
+}
+
+/// Start or stop sending classic audio meter levels (`SALN`), the switcher doesn't send them
+/// by default
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetAudioLevels {
+    enabled: bool,
+}
+
+impl SetAudioLevels {
+    pub fn new(enabled: bool) -> Self {
+        SetAudioLevels { enabled }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.enabled as u8);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetAudioLevels {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Enabled: {}", self.enabled)
+    }
+}
+
+/// Start or stop sending Fairlight meter levels (`SFLN`), the switcher doesn't send them by
+/// default
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetFairlightLevels {
+    enabled: bool,
+}
+
+impl SetFairlightLevels {
+    pub fn new(enabled: bool) -> Self {
+        SetFairlightLevels { enabled }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.enabled as u8);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetFairlightLevels {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Enabled: {}", self.enabled)
+    }
+}
+
+#[cfg(feature = "std")]
+/// Raw classic audio gain of 0 dB
+const CLASSIC_UNITY_GAIN: f32 = 32768.0;
+
+/// Convert a raw classic audio gain to dB
+#[cfg(feature = "std")]
+pub fn classic_gain_to_db(gain: u16) -> f32 {
+    if gain == 0 {
+        return f32::NEG_INFINITY;
+    }
+
+    20.0 * (gain as f32 / CLASSIC_UNITY_GAIN).log10()
+}
+
+/// Convert a gain in dB to the raw classic audio gain, clamped to the range the switcher accepts
+#[cfg(feature = "std")]
+pub fn db_to_classic_gain(db: f32) -> u16 {
+    (CLASSIC_UNITY_GAIN * 10f32.powf(db / 20.0))
+        .round()
+        .clamp(0.0, u16::MAX as f32) as u16
+}
+
+/// Monitor output of the classic audio mixer (`AMmO`)
+#[derive(Debug, Clone)]
+pub struct AudioMonitor {
+    enabled: bool,
+    gain: u16,
+    mute: bool,
+    solo: bool,
+    solo_source: u16,
+    dim: bool,
+}
+
+impl AudioMonitor {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let enabled = data.get_u8() == 1;
+        data.get_u8(); // Padding
+        let gain = data.get_u16();
+        let mute = data.get_u8() == 1;
+        let solo = data.get_u8() == 1;
+        let solo_source = data.get_u16();
+        let dim = data.get_u8() == 1;
+
+        AudioMonitor {
+            enabled,
+            gain,
+            mute,
+            solo,
+            solo_source,
+            dim,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Raw gain where 32768 is 0 dB, see [`classic_gain_to_db`]
+    pub fn gain(&self) -> u16 {
+        self.gain
+    }
+
+    pub fn mute(&self) -> bool {
+        self.mute
+    }
+
+    pub fn solo(&self) -> bool {
+        self.solo
+    }
+
+    /// Audio source listened to while solo is on
+    pub fn solo_source(&self) -> u16 {
+        self.solo_source
+    }
+
+    pub fn dim(&self) -> bool {
+        self.dim
+    }
+}
+
+impl Display for AudioMonitor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Enabled: {} Gain: {} Mute: {} Solo: {} Solo source: {} Dim: {}",
+            self.enabled, self.gain, self.mute, self.solo, self.solo_source, self.dim
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct AudioMonitorMask: u8 {
+        const ENABLED = 0x01;
+        const GAIN = 0x02;
+        const MUTE = 0x04;
+        const SOLO = 0x08;
+        const SOLO_SOURCE = 0x10;
+        const DIM = 0x20;
+    }
+}
+
+/// Change the monitor output of the classic audio mixer (`CAMm`), only the properties set are
+/// changed
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetAudioMonitor {
+    mask: AudioMonitorMask,
+    enabled: bool,
+    gain: u16,
+    mute: bool,
+    solo: bool,
+    solo_source: u16,
+    dim: bool,
+}
+
+impl SetAudioMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.mask |= AudioMonitorMask::ENABLED;
+        self.enabled = enabled;
+        self
+    }
+
+    /// Raw gain where 32768 is 0 dB, see [`db_to_classic_gain`]
+    pub fn gain(mut self, gain: u16) -> Self {
+        self.mask |= AudioMonitorMask::GAIN;
+        self.gain = gain;
+        self
+    }
+
+    pub fn mute(mut self, mute: bool) -> Self {
+        self.mask |= AudioMonitorMask::MUTE;
+        self.mute = mute;
+        self
+    }
+
+    /// Listen to a single audio source instead of the program mix, or back to program
+    pub fn solo(mut self, solo: Option<u16>) -> Self {
+        self.mask |= AudioMonitorMask::SOLO;
+        self.solo = solo.is_some();
+        if let Some(source) = solo {
+            self.mask |= AudioMonitorMask::SOLO_SOURCE;
+            self.solo_source = source;
+        }
+        self
+    }
+
+    pub fn dim(mut self, dim: bool) -> Self {
+        self.mask |= AudioMonitorMask::DIM;
+        self.dim = dim;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(self.enabled as u8);
+        data.put_u16(self.gain);
+        data.put_u8(self.mute as u8);
+        data.put_u8(self.solo as u8);
+        data.put_u16(self.solo_source);
+        data.put_u8(self.dim as u8);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetAudioMonitor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Enabled: {} Gain: {} Mute: {} Solo: {} Solo source: {} Dim: {}",
+            self.enabled, self.gain, self.mute, self.solo, self.solo_source, self.dim
+        )
+    }
+}
+
+/// Whether an input of the classic audio mixer is heard in the program mix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum AudioMixOption {
+    Off,
+    On,
+    /// Heard only while the video of the input is on program
+    AudioFollowVideo,
+    Unknown(u8),
+}
+
+impl From<u8> for AudioMixOption {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => AudioMixOption::Off,
+            1 => AudioMixOption::On,
+            2 => AudioMixOption::AudioFollowVideo,
+            u => AudioMixOption::Unknown(u),
+        }
+    }
+}
+
+impl From<AudioMixOption> for u8 {
+    fn from(value: AudioMixOption) -> Self {
+        match value {
+            AudioMixOption::Off => 0,
+            AudioMixOption::On => 1,
+            AudioMixOption::AudioFollowVideo => 2,
+            AudioMixOption::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for AudioMixOption {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AudioMixOption::Off => write!(f, "Off"),
+            AudioMixOption::On => write!(f, "On"),
+            AudioMixOption::AudioFollowVideo => write!(f, "Audio follow video"),
+            AudioMixOption::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+/// Fader of an input of the classic audio mixer (`AMIP`)
+#[derive(Debug, Clone)]
+pub struct AudioMixerInput {
+    input: u16,
+    mix_option: AudioMixOption,
+    gain: u16,
+    balance: i16,
+}
+
+impl AudioMixerInput {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let input = data.get_u16();
+        data.advance(6); // Type, from media player, padding and port type
+        let mix_option = data.get_u8().into();
+        data.get_u8(); // Padding
+        let gain = data.get_u16();
+        let balance = data.get_i16();
+
+        AudioMixerInput {
+            input,
+            mix_option,
+            gain,
+            balance,
+        }
+    }
+
+    pub fn input(&self) -> u16 {
+        self.input
+    }
+
+    pub fn mix_option(&self) -> AudioMixOption {
+        self.mix_option
+    }
+
+    /// Fader gain where 32768 is 0 dB, see [`classic_gain_to_db`]
+    pub fn gain(&self) -> u16 {
+        self.gain
+    }
+
+    /// Balance from -10000, left, to 10000, right
+    pub fn balance(&self) -> i16 {
+        self.balance
+    }
+}
+
+impl Display for AudioMixerInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Input: {} Mix option: {} Gain: {} Balance: {}",
+            self.input, self.mix_option, self.gain, self.balance
+        )
+    }
+}
+
+/// Master fader of the classic audio mixer (`AMMO`)
+#[derive(Debug, Clone)]
+pub struct AudioMixerMaster {
+    gain: u16,
+    balance: i16,
+    follow_fade_to_black: bool,
+}
+
+impl AudioMixerMaster {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let gain = data.get_u16();
+        let balance = data.get_i16();
+        let follow_fade_to_black = data.get_u8() == 1;
+
+        AudioMixerMaster {
+            gain,
+            balance,
+            follow_fade_to_black,
+        }
+    }
+
+    /// Fader gain where 32768 is 0 dB, see [`classic_gain_to_db`]
+    pub fn gain(&self) -> u16 {
+        self.gain
+    }
+
+    /// Balance from -10000, left, to 10000, right
+    pub fn balance(&self) -> i16 {
+        self.balance
+    }
+
+    pub fn follow_fade_to_black(&self) -> bool {
+        self.follow_fade_to_black
+    }
+}
+
+impl Display for AudioMixerMaster {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Gain: {} Balance: {} Follow fade to black: {}",
+            self.gain, self.balance, self.follow_fade_to_black
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct AudioMixerInputMask: u8 {
+        const MIX_OPTION = 0x01;
+        const GAIN = 0x02;
+        const BALANCE = 0x04;
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct AudioMixerMasterMask: u8 {
+        const GAIN = 0x01;
+        const BALANCE = 0x02;
+        const FOLLOW_FADE_TO_BLACK = 0x04;
+    }
+}
+
+/// Move the fader of an input of the classic audio mixer (`CAMI`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetAudioMixerInput {
+    mask: AudioMixerInputMask,
+    input: u16,
+    mix_option: AudioMixOption,
+    gain: u16,
+    balance: i16,
+}
+
+impl SetAudioMixerInput {
+    pub fn new(input: u16) -> Self {
+        SetAudioMixerInput {
+            mask: AudioMixerInputMask::empty(),
+            input,
+            mix_option: AudioMixOption::Off,
+            gain: 0,
+            balance: 0,
+        }
+    }
+
+    pub fn input(&self) -> u16 {
+        self.input
+    }
+
+    pub fn mix_option(mut self, mix_option: AudioMixOption) -> Self {
+        self.mask |= AudioMixerInputMask::MIX_OPTION;
+        self.mix_option = mix_option;
+        self
+    }
+
+    /// Fader gain where 32768 is 0 dB, see [`db_to_classic_gain`]
+    pub fn gain(mut self, gain: u16) -> Self {
+        self.mask |= AudioMixerInputMask::GAIN;
+        self.gain = gain;
+        self
+    }
+
+    /// Balance from -10000, left, to 10000, right
+    pub fn balance(mut self, balance: i16) -> Self {
+        self.mask |= AudioMixerInputMask::BALANCE;
+        self.balance = balance;
+        self
+    }
+
+    /// Properties changed by the command
+    pub fn mask(&self) -> AudioMixerInputMask {
+        self.mask
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(0); // Padding
+        data.put_u16(self.input);
+        data.put_u8(self.mix_option.into());
+        data.put_u8(0); // Padding
+        data.put_u16(self.gain);
+        data.put_i16(self.balance);
+        data.put_bytes(0, 2); // Padding
+    }
+}
+
+impl Display for SetAudioMixerInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Input: {} Mix option: {} Gain: {} Balance: {}",
+            self.input, self.mix_option, self.gain, self.balance
+        )
+    }
+}
+
+/// Move the master fader of the classic audio mixer (`CAMM`)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetAudioMixerMaster {
+    mask: AudioMixerMasterMask,
+    gain: u16,
+    balance: i16,
+}
+
+impl SetAudioMixerMaster {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fader gain where 32768 is 0 dB, see [`db_to_classic_gain`]
+    pub fn gain(mut self, gain: u16) -> Self {
+        self.mask |= AudioMixerMasterMask::GAIN;
+        self.gain = gain;
+        self
+    }
+
+    /// Balance from -10000, left, to 10000, right
+    pub fn balance(mut self, balance: i16) -> Self {
+        self.mask |= AudioMixerMasterMask::BALANCE;
+        self.balance = balance;
+        self
+    }
+
+    /// Properties changed by the command
+    pub fn mask(&self) -> AudioMixerMasterMask {
+        self.mask
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(0); // Padding
+        data.put_u16(self.gain);
+        data.put_i16(self.balance);
+        data.put_bytes(0, 2); // Follow fade to black and padding
+    }
+}
+
+impl Display for SetAudioMixerMaster {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Gain: {} Balance: {}", self.gain, self.balance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audio_levels_ok() {
+        let mut data = Bytes::from_static(&[
+            0x00, 0x01, 0x00, 0x00, // One source
+            0x00, 0x80, 0x00, 0x00, 0x00, 0x40, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x80,
+            0x00, 0x00, // Master
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, // Monitor
+            0x00, 0x01, 0x00, 0x00, // Source id and padding
+            0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x03, 0x00, 0x00,
+            0x00, 0x04,
+        ]);
+        let levels = AudioLevels::parse(&mut data);
+
+        assert_eq!(levels.master().raw(), [0x800000, 0x400000, 0, 0x800000]);
+        assert_eq!(levels.source(1).map(StereoLevels::raw), Some([1, 2, 3, 4]));
+        assert!(levels.source(2).is_none());
+    }
+
+    #[test]
+    fn audio_levels_truncated() {
+        let mut data = Bytes::from(
+            [
+                &[0x00, 0x03, 0x00, 0x00][..],
+                &[0; 32],
+                &[0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x00],
+                &[0; 20],
+            ]
+            .concat(),
+        );
+        let levels = AudioLevels::parse(&mut data);
+
+        // Levels of only the first source were received
+        assert_eq!(levels.sources().map(|(id, _)| id).collect::<Vec<_>>(), [1]);
+
+        let mut data = Bytes::from([&[0x00, 0x03, 0x00, 0x00][..], &[0; 36]].concat());
+        assert!(AudioLevels::parse(&mut data).sources().next().is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn classic_level_to_dbfs_ok() {
+        assert_eq!(classic_level_to_dbfs(0x800000), 0.0);
+        assert!((classic_level_to_dbfs(0x400000) + 6.0206).abs() < 0.001);
+        assert_eq!(classic_level_to_dbfs(0), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn audio_monitor_ok() {
+        let mut data = Bytes::from_static(&[
+            0x01, 0x00, 0x80, 0x00, 0x00, 0x01, 0x00, 0x05, 0x01, 0x00, 0x00, 0x00,
+        ]);
+        let monitor = AudioMonitor::parse(&mut data);
+
+        assert!(monitor.enabled() && monitor.solo() && monitor.dim() && !monitor.mute());
+        assert_eq!(monitor.gain(), 0x8000);
+        assert_eq!(monitor.solo_source(), 5);
+
+        let mut data = BytesMut::new();
+        SetAudioMonitor::new()
+            .solo(Some(5))
+            .dim(true)
+            .serialize(&mut data);
+        assert_eq!(
+            data.as_ref(),
+            [0x38, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x05, 0x01, 0x00, 0x00, 0x00]
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn classic_gain_to_db_ok() {
+        assert_eq!(classic_gain_to_db(0x8000), 0.0);
+        assert_eq!(db_to_classic_gain(0.0), 0x8000);
+        assert_eq!(db_to_classic_gain(100.0), u16::MAX);
+    }
+
+    #[test]
+    fn fairlight_level_to_dbfs_ok() {
+        assert_eq!(fairlight_level_to_dbfs(0), 0.0);
+        assert_eq!(fairlight_level_to_dbfs(-1850), -18.5);
+    }
+}
@@ -0,0 +1,50 @@
+use tokio::sync::mpsc;
+
+use crate::{
+    command::{Command, SetAuxSource},
+    control::ControlCommand,
+    Connection, Error,
+};
+
+/// Aux output of a connection, see [`Connection::aux`]
+pub struct AuxOutput<'a> {
+    connection: &'a Connection,
+    aux: u8,
+}
+
+impl<'a> AuxOutput<'a> {
+    pub(crate) fn new(connection: &'a Connection, aux: u8) -> Self {
+        AuxOutput { connection, aux }
+    }
+
+    pub fn index(&self) -> u8 {
+        self.aux
+    }
+
+    pub fn set_source(&self, source: u16) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::AuxSource(SetAuxSource::new(
+                self.aux, source,
+            )))
+    }
+
+    /// Source currently routed to the aux output as reported by the switcher
+    pub fn source(&self) -> Option<u16> {
+        self.connection.system_info().aux_source(self.aux)
+    }
+
+    /// Receive the new source every time the switcher reports a change of this aux output.
+    /// Drop the receiver to stop listening.
+    pub fn subscribe(&self) -> mpsc::UnboundedReceiver<u16> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let aux = self.aux;
+        self.connection.listen(move |command| match command {
+            Command::AuxSource(source) if source.aux() == aux => {
+                tx.send(source.source_id()).is_ok()
+            }
+            _ => !tx.is_closed(),
+        });
+
+        rx
+    }
+}
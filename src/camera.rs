@@ -0,0 +1,864 @@
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::{command, parser::parse_str};
+
+/// Scale of the signed 5.11 fixed point format used by the camera control protocol
+const FIXED16_SCALE: f32 = 2048.0;
+
+const CATEGORY_LENS: u8 = 0;
+const CATEGORY_VIDEO: u8 = 1;
+const CATEGORY_COLOR_CORRECTION: u8 = 8;
+const CATEGORY_PTZ: u8 = 11;
+
+const DATA_TYPE_BOOL: u8 = 0;
+const DATA_TYPE_INT8: u8 = 1;
+const DATA_TYPE_INT16: u8 = 2;
+const DATA_TYPE_INT32: u8 = 3;
+const DATA_TYPE_INT64: u8 = 4;
+const DATA_TYPE_STRING: u8 = 5;
+const DATA_TYPE_FIXED16: u8 = 128;
+
+pub fn fixed16_to_f32(value: i16) -> f32 {
+    value as f32 / FIXED16_SCALE
+}
+
+pub fn f32_to_fixed16(value: f32) -> i16 {
+    // Round half away from zero, the cast saturates at the limits of i16
+    let value = value * FIXED16_SCALE;
+    if value < 0.0 {
+        (value - 0.5) as i16
+    } else {
+        (value + 0.5) as i16
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum CameraControlValue {
+    Void,
+    Bool(Vec<bool>),
+    Int8(Vec<i8>),
+    Int16(Vec<i16>),
+    Int32(Vec<i32>),
+    Int64(Vec<i64>),
+    String(String),
+    Fixed16(Vec<f32>),
+    Unknown(u8),
+}
+
+impl CameraControlValue {
+    fn parse(
+        data_type: u8,
+        counts: &CameraControlCounts,
+        data: &mut Bytes,
+    ) -> Result<Self, command::ParseError> {
+        if data.remaining() < counts.data_size(data_type) {
+            return Err(command::ParseError::InvalidSize);
+        }
+
+        let value = match data_type {
+            DATA_TYPE_BOOL if counts.int8 == 0 => CameraControlValue::Void,
+            DATA_TYPE_BOOL => {
+                CameraControlValue::Bool((0..counts.int8).map(|_| data.get_u8() > 0).collect())
+            }
+            DATA_TYPE_INT8 => {
+                CameraControlValue::Int8((0..counts.int8).map(|_| data.get_i8()).collect())
+            }
+            DATA_TYPE_INT16 => {
+                CameraControlValue::Int16((0..counts.int16).map(|_| data.get_i16()).collect())
+            }
+            DATA_TYPE_INT32 => {
+                CameraControlValue::Int32((0..counts.int32).map(|_| data.get_i32()).collect())
+            }
+            DATA_TYPE_INT64 => {
+                CameraControlValue::Int64((0..counts.int64).map(|_| data.get_i64()).collect())
+            }
+            DATA_TYPE_STRING => CameraControlValue::String(
+                parse_str(&mut data.split_to(counts.string as usize))?.unwrap_or_default(),
+            ),
+            DATA_TYPE_FIXED16 => CameraControlValue::Fixed16(
+                (0..counts.int16)
+                    .map(|_| fixed16_to_f32(data.get_i16()))
+                    .collect(),
+            ),
+            u => CameraControlValue::Unknown(u),
+        };
+
+        Ok(value)
+    }
+
+    fn data_type(&self) -> u8 {
+        match self {
+            CameraControlValue::Void | CameraControlValue::Bool(_) => DATA_TYPE_BOOL,
+            CameraControlValue::Int8(_) => DATA_TYPE_INT8,
+            CameraControlValue::Int16(_) => DATA_TYPE_INT16,
+            CameraControlValue::Int32(_) => DATA_TYPE_INT32,
+            CameraControlValue::Int64(_) => DATA_TYPE_INT64,
+            CameraControlValue::String(_) => DATA_TYPE_STRING,
+            CameraControlValue::Fixed16(_) => DATA_TYPE_FIXED16,
+            CameraControlValue::Unknown(u) => *u,
+        }
+    }
+
+    fn counts(&self) -> CameraControlCounts {
+        let mut counts = CameraControlCounts::default();
+
+        match self {
+            CameraControlValue::Void | CameraControlValue::Unknown(_) => {}
+            CameraControlValue::Bool(values) => counts.int8 = values.len() as u16,
+            CameraControlValue::Int8(values) => counts.int8 = values.len() as u16,
+            CameraControlValue::Int16(values) => counts.int16 = values.len() as u16,
+            CameraControlValue::Int32(values) => counts.int32 = values.len() as u16,
+            CameraControlValue::Int64(values) => counts.int64 = values.len() as u16,
+            CameraControlValue::String(value) => counts.string = value.len() as u16,
+            CameraControlValue::Fixed16(values) => counts.int16 = values.len() as u16,
+        }
+
+        counts
+    }
+
+    fn serialize(&self, data: &mut BytesMut) {
+        match self {
+            CameraControlValue::Void | CameraControlValue::Unknown(_) => {}
+            CameraControlValue::Bool(values) => values.iter().for_each(|v| data.put_u8(*v as u8)),
+            CameraControlValue::Int8(values) => values.iter().for_each(|v| data.put_i8(*v)),
+            CameraControlValue::Int16(values) => values.iter().for_each(|v| data.put_i16(*v)),
+            CameraControlValue::Int32(values) => values.iter().for_each(|v| data.put_i32(*v)),
+            CameraControlValue::Int64(values) => values.iter().for_each(|v| data.put_i64(*v)),
+            CameraControlValue::String(value) => data.put_slice(value.as_bytes()),
+            CameraControlValue::Fixed16(values) => {
+                values.iter().for_each(|v| data.put_i16(f32_to_fixed16(*v)))
+            }
+        }
+    }
+}
+
+impl Display for CameraControlValue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fn join<T: Display>(values: &[T]) -> String {
+            values
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<String>>()
+                .join(", ")
+        }
+
+        match self {
+            CameraControlValue::Void => write!(f, "Void"),
+            CameraControlValue::Bool(values) => write!(f, "[{}]", join(values)),
+            CameraControlValue::Int8(values) => write!(f, "[{}]", join(values)),
+            CameraControlValue::Int16(values) => write!(f, "[{}]", join(values)),
+            CameraControlValue::Int32(values) => write!(f, "[{}]", join(values)),
+            CameraControlValue::Int64(values) => write!(f, "[{}]", join(values)),
+            CameraControlValue::String(value) => write!(f, "\"{value}\""),
+            CameraControlValue::Fixed16(values) => write!(f, "[{}]", join(values)),
+            CameraControlValue::Unknown(u) => write!(f, "Unknown data type ({u})"),
+        }
+    }
+}
+
+/// Element counts per data type as sent in the camera control header
+#[derive(Default)]
+struct CameraControlCounts {
+    int8: u16,
+    int16: u16,
+    int32: u16,
+    int64: u16,
+    string: u16,
+}
+
+impl CameraControlCounts {
+    fn parse(data: &mut Bytes) -> Self {
+        let int8 = data.get_u16();
+        let int16 = data.get_u16();
+        let int32 = data.get_u16();
+        let int64 = data.get_u16();
+        let string = data.get_u16();
+
+        CameraControlCounts {
+            int8,
+            int16,
+            int32,
+            int64,
+            string,
+        }
+    }
+
+    /// Size of the values of a data type
+    fn data_size(&self, data_type: u8) -> usize {
+        match data_type {
+            DATA_TYPE_BOOL | DATA_TYPE_INT8 => self.int8 as usize,
+            DATA_TYPE_INT16 | DATA_TYPE_FIXED16 => self.int16 as usize * 2,
+            DATA_TYPE_INT32 => self.int32 as usize * 4,
+            DATA_TYPE_INT64 => self.int64 as usize * 8,
+            DATA_TYPE_STRING => self.string as usize,
+            _ => 0,
+        }
+    }
+
+    fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.int8);
+        data.put_u16(self.int16);
+        data.put_u16(self.int32);
+        data.put_u16(self.int64);
+        data.put_u16(self.string);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ColorValues {
+    pub red: f32,
+    pub green: f32,
+    pub blue: f32,
+    pub luma: f32,
+}
+
+impl Display for ColorValues {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "R: {} G: {} B: {} Y: {}",
+            self.red, self.green, self.blue, self.luma
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum LensParameter {
+    Focus(f32),
+    AutoFocus,
+    ApertureFStop(f32),
+    Iris(f32),
+    ApertureOrdinal(i16),
+    AutoAperture,
+    OpticalImageStabilisation(bool),
+    Zoom(i16),
+    ZoomNormalised(f32),
+    ZoomSpeed(f32),
+}
+
+impl LensParameter {
+    fn from_value(parameter: u8, value: &CameraControlValue) -> Option<Self> {
+        let parameter = match (parameter, value) {
+            (0, CameraControlValue::Fixed16(v)) => LensParameter::Focus(*v.first()?),
+            (1, CameraControlValue::Void) => LensParameter::AutoFocus,
+            (2, CameraControlValue::Fixed16(v)) => LensParameter::ApertureFStop(*v.first()?),
+            (3, CameraControlValue::Fixed16(v)) => LensParameter::Iris(*v.first()?),
+            (4, CameraControlValue::Int16(v)) => LensParameter::ApertureOrdinal(*v.first()?),
+            (5, CameraControlValue::Void) => LensParameter::AutoAperture,
+            (6, CameraControlValue::Bool(v)) => {
+                LensParameter::OpticalImageStabilisation(*v.first()?)
+            }
+            (7, CameraControlValue::Int16(v)) => LensParameter::Zoom(*v.first()?),
+            (8, CameraControlValue::Fixed16(v)) => LensParameter::ZoomNormalised(*v.first()?),
+            (9, CameraControlValue::Fixed16(v)) => LensParameter::ZoomSpeed(*v.first()?),
+            _ => return None,
+        };
+
+        Some(parameter)
+    }
+
+    fn id(&self) -> u8 {
+        match self {
+            LensParameter::Focus(_) => 0,
+            LensParameter::AutoFocus => 1,
+            LensParameter::ApertureFStop(_) => 2,
+            LensParameter::Iris(_) => 3,
+            LensParameter::ApertureOrdinal(_) => 4,
+            LensParameter::AutoAperture => 5,
+            LensParameter::OpticalImageStabilisation(_) => 6,
+            LensParameter::Zoom(_) => 7,
+            LensParameter::ZoomNormalised(_) => 8,
+            LensParameter::ZoomSpeed(_) => 9,
+        }
+    }
+
+    fn value(&self) -> CameraControlValue {
+        match self {
+            LensParameter::Focus(v)
+            | LensParameter::ApertureFStop(v)
+            | LensParameter::Iris(v)
+            | LensParameter::ZoomNormalised(v)
+            | LensParameter::ZoomSpeed(v) => CameraControlValue::Fixed16(vec![*v]),
+            LensParameter::AutoFocus | LensParameter::AutoAperture => CameraControlValue::Void,
+            LensParameter::ApertureOrdinal(v) | LensParameter::Zoom(v) => {
+                CameraControlValue::Int16(vec![*v])
+            }
+            LensParameter::OpticalImageStabilisation(v) => CameraControlValue::Bool(vec![*v]),
+        }
+    }
+}
+
+impl Display for LensParameter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            LensParameter::Focus(focus) => write!(f, "Focus: {focus}"),
+            LensParameter::AutoFocus => write!(f, "Auto focus"),
+            LensParameter::ApertureFStop(fstop) => write!(f, "Aperture f-stop: {fstop}"),
+            LensParameter::Iris(iris) => write!(f, "Iris: {iris}"),
+            LensParameter::ApertureOrdinal(ordinal) => write!(f, "Aperture ordinal: {ordinal}"),
+            LensParameter::AutoAperture => write!(f, "Auto aperture"),
+            LensParameter::OpticalImageStabilisation(enabled) => {
+                write!(f, "Optical image stabilisation: {enabled}")
+            }
+            LensParameter::Zoom(zoom) => write!(f, "Zoom: {zoom}mm"),
+            LensParameter::ZoomNormalised(zoom) => write!(f, "Zoom: {zoom}"),
+            LensParameter::ZoomSpeed(speed) => write!(f, "Zoom speed: {speed}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum VideoParameter {
+    SensorGain(i8),
+    ManualWhiteBalance { temperature: i16, tint: i16 },
+    AutoWhiteBalance,
+    RestoreAutoWhiteBalance,
+    Exposure(i32),
+    ExposureOrdinal(i16),
+    DynamicRange(i8),
+    Sharpening(i8),
+    AutoExposureMode(i8),
+    ShutterAngle(i32),
+    ShutterSpeed(i32),
+    Gain(i8),
+    Iso(i32),
+}
+
+impl VideoParameter {
+    fn from_value(parameter: u8, value: &CameraControlValue) -> Option<Self> {
+        let parameter = match (parameter, value) {
+            (1, CameraControlValue::Int8(v)) => VideoParameter::SensorGain(*v.first()?),
+            (2, CameraControlValue::Int16(v)) => VideoParameter::ManualWhiteBalance {
+                temperature: *v.first()?,
+                tint: v.get(1).copied().unwrap_or_default(),
+            },
+            (3, CameraControlValue::Void) => VideoParameter::AutoWhiteBalance,
+            (4, CameraControlValue::Void) => VideoParameter::RestoreAutoWhiteBalance,
+            (5, CameraControlValue::Int32(v)) => VideoParameter::Exposure(*v.first()?),
+            (6, CameraControlValue::Int16(v)) => VideoParameter::ExposureOrdinal(*v.first()?),
+            (7, CameraControlValue::Int8(v)) => VideoParameter::DynamicRange(*v.first()?),
+            (8, CameraControlValue::Int8(v)) => VideoParameter::Sharpening(*v.first()?),
+            (10, CameraControlValue::Int8(v)) => VideoParameter::AutoExposureMode(*v.first()?),
+            (11, CameraControlValue::Int32(v)) => VideoParameter::ShutterAngle(*v.first()?),
+            (12, CameraControlValue::Int32(v)) => VideoParameter::ShutterSpeed(*v.first()?),
+            (13, CameraControlValue::Int8(v)) => VideoParameter::Gain(*v.first()?),
+            (14, CameraControlValue::Int32(v)) => VideoParameter::Iso(*v.first()?),
+            _ => return None,
+        };
+
+        Some(parameter)
+    }
+
+    fn id(&self) -> u8 {
+        match self {
+            VideoParameter::SensorGain(_) => 1,
+            VideoParameter::ManualWhiteBalance { .. } => 2,
+            VideoParameter::AutoWhiteBalance => 3,
+            VideoParameter::RestoreAutoWhiteBalance => 4,
+            VideoParameter::Exposure(_) => 5,
+            VideoParameter::ExposureOrdinal(_) => 6,
+            VideoParameter::DynamicRange(_) => 7,
+            VideoParameter::Sharpening(_) => 8,
+            VideoParameter::AutoExposureMode(_) => 10,
+            VideoParameter::ShutterAngle(_) => 11,
+            VideoParameter::ShutterSpeed(_) => 12,
+            VideoParameter::Gain(_) => 13,
+            VideoParameter::Iso(_) => 14,
+        }
+    }
+
+    fn value(&self) -> CameraControlValue {
+        match self {
+            VideoParameter::ExposureOrdinal(v) => CameraControlValue::Int16(vec![*v]),
+            VideoParameter::ManualWhiteBalance { temperature, tint } => {
+                CameraControlValue::Int16(vec![*temperature, *tint])
+            }
+            VideoParameter::AutoWhiteBalance | VideoParameter::RestoreAutoWhiteBalance => {
+                CameraControlValue::Void
+            }
+            VideoParameter::Exposure(v)
+            | VideoParameter::ShutterAngle(v)
+            | VideoParameter::ShutterSpeed(v)
+            | VideoParameter::Iso(v) => CameraControlValue::Int32(vec![*v]),
+            VideoParameter::SensorGain(v)
+            | VideoParameter::DynamicRange(v)
+            | VideoParameter::Sharpening(v)
+            | VideoParameter::AutoExposureMode(v)
+            | VideoParameter::Gain(v) => CameraControlValue::Int8(vec![*v]),
+        }
+    }
+}
+
+impl Display for VideoParameter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            VideoParameter::SensorGain(gain) => write!(f, "Sensor gain: {gain}"),
+            VideoParameter::ManualWhiteBalance { temperature, tint } => {
+                write!(f, "White balance: {temperature}K Tint: {tint}")
+            }
+            VideoParameter::AutoWhiteBalance => write!(f, "Auto white balance"),
+            VideoParameter::RestoreAutoWhiteBalance => write!(f, "Restore auto white balance"),
+            VideoParameter::Exposure(exposure) => write!(f, "Exposure: {exposure}us"),
+            VideoParameter::ExposureOrdinal(ordinal) => write!(f, "Exposure ordinal: {ordinal}"),
+            VideoParameter::DynamicRange(mode) => write!(f, "Dynamic range: {mode}"),
+            VideoParameter::Sharpening(level) => write!(f, "Sharpening: {level}"),
+            VideoParameter::AutoExposureMode(mode) => write!(f, "Auto exposure mode: {mode}"),
+            VideoParameter::ShutterAngle(angle) => {
+                write!(f, "Shutter angle: {}", *angle as f32 / 100.0)
+            }
+            VideoParameter::ShutterSpeed(speed) => write!(f, "Shutter speed: 1/{speed}"),
+            VideoParameter::Gain(gain) => write!(f, "Gain: {gain}dB"),
+            VideoParameter::Iso(iso) => write!(f, "ISO: {iso}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ColorCorrectionParameter {
+    Lift(ColorValues),
+    Gamma(ColorValues),
+    Gain(ColorValues),
+    Offset(ColorValues),
+    Contrast { pivot: f32, adjust: f32 },
+    LumaMix(f32),
+    ColorAdjust { hue: f32, saturation: f32 },
+    Reset,
+}
+
+impl ColorCorrectionParameter {
+    fn from_value(parameter: u8, value: &CameraControlValue) -> Option<Self> {
+        fn color_values(v: &[f32]) -> Option<ColorValues> {
+            Some(ColorValues {
+                red: *v.first()?,
+                green: *v.get(1)?,
+                blue: *v.get(2)?,
+                luma: *v.get(3)?,
+            })
+        }
+
+        let parameter = match (parameter, value) {
+            (0, CameraControlValue::Fixed16(v)) => ColorCorrectionParameter::Lift(color_values(v)?),
+            (1, CameraControlValue::Fixed16(v)) => {
+                ColorCorrectionParameter::Gamma(color_values(v)?)
+            }
+            (2, CameraControlValue::Fixed16(v)) => ColorCorrectionParameter::Gain(color_values(v)?),
+            (3, CameraControlValue::Fixed16(v)) => {
+                ColorCorrectionParameter::Offset(color_values(v)?)
+            }
+            (4, CameraControlValue::Fixed16(v)) => ColorCorrectionParameter::Contrast {
+                pivot: *v.first()?,
+                adjust: *v.get(1)?,
+            },
+            (5, CameraControlValue::Fixed16(v)) => ColorCorrectionParameter::LumaMix(*v.first()?),
+            (6, CameraControlValue::Fixed16(v)) => ColorCorrectionParameter::ColorAdjust {
+                hue: *v.first()?,
+                saturation: *v.get(1)?,
+            },
+            (7, CameraControlValue::Void) => ColorCorrectionParameter::Reset,
+            _ => return None,
+        };
+
+        Some(parameter)
+    }
+
+    fn id(&self) -> u8 {
+        match self {
+            ColorCorrectionParameter::Lift(_) => 0,
+            ColorCorrectionParameter::Gamma(_) => 1,
+            ColorCorrectionParameter::Gain(_) => 2,
+            ColorCorrectionParameter::Offset(_) => 3,
+            ColorCorrectionParameter::Contrast { .. } => 4,
+            ColorCorrectionParameter::LumaMix(_) => 5,
+            ColorCorrectionParameter::ColorAdjust { .. } => 6,
+            ColorCorrectionParameter::Reset => 7,
+        }
+    }
+
+    fn value(&self) -> CameraControlValue {
+        match self {
+            ColorCorrectionParameter::Lift(v)
+            | ColorCorrectionParameter::Gamma(v)
+            | ColorCorrectionParameter::Gain(v)
+            | ColorCorrectionParameter::Offset(v) => {
+                CameraControlValue::Fixed16(vec![v.red, v.green, v.blue, v.luma])
+            }
+            ColorCorrectionParameter::Contrast { pivot, adjust } => {
+                CameraControlValue::Fixed16(vec![*pivot, *adjust])
+            }
+            ColorCorrectionParameter::LumaMix(v) => CameraControlValue::Fixed16(vec![*v]),
+            ColorCorrectionParameter::ColorAdjust { hue, saturation } => {
+                CameraControlValue::Fixed16(vec![*hue, *saturation])
+            }
+            ColorCorrectionParameter::Reset => CameraControlValue::Void,
+        }
+    }
+}
+
+impl Display for ColorCorrectionParameter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ColorCorrectionParameter::Lift(values) => write!(f, "Lift: {values}"),
+            ColorCorrectionParameter::Gamma(values) => write!(f, "Gamma: {values}"),
+            ColorCorrectionParameter::Gain(values) => write!(f, "Gain: {values}"),
+            ColorCorrectionParameter::Offset(values) => write!(f, "Offset: {values}"),
+            ColorCorrectionParameter::Contrast { pivot, adjust } => {
+                write!(f, "Contrast pivot: {pivot} Adjust: {adjust}")
+            }
+            ColorCorrectionParameter::LumaMix(mix) => write!(f, "Luma mix: {mix}"),
+            ColorCorrectionParameter::ColorAdjust { hue, saturation } => {
+                write!(f, "Hue: {hue} Saturation: {saturation}")
+            }
+            ColorCorrectionParameter::Reset => write!(f, "Reset to defaults"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum PtzParameter {
+    PanTiltVelocity { pan: f32, tilt: f32 },
+    MemoryPreset { command: i8, slot: i8 },
+}
+
+impl PtzParameter {
+    fn from_value(parameter: u8, value: &CameraControlValue) -> Option<Self> {
+        let parameter = match (parameter, value) {
+            (0, CameraControlValue::Fixed16(v)) => PtzParameter::PanTiltVelocity {
+                pan: *v.first()?,
+                tilt: *v.get(1)?,
+            },
+            (1, CameraControlValue::Int8(v)) => PtzParameter::MemoryPreset {
+                command: *v.first()?,
+                slot: *v.get(1)?,
+            },
+            _ => return None,
+        };
+
+        Some(parameter)
+    }
+
+    fn id(&self) -> u8 {
+        match self {
+            PtzParameter::PanTiltVelocity { .. } => 0,
+            PtzParameter::MemoryPreset { .. } => 1,
+        }
+    }
+
+    fn value(&self) -> CameraControlValue {
+        match self {
+            PtzParameter::PanTiltVelocity { pan, tilt } => {
+                CameraControlValue::Fixed16(vec![*pan, *tilt])
+            }
+            PtzParameter::MemoryPreset { command, slot } => {
+                CameraControlValue::Int8(vec![*command, *slot])
+            }
+        }
+    }
+}
+
+impl Display for PtzParameter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PtzParameter::PanTiltVelocity { pan, tilt } => {
+                write!(f, "Pan velocity: {pan} Tilt velocity: {tilt}")
+            }
+            PtzParameter::MemoryPreset { command, slot } => {
+                write!(f, "Memory preset command: {command} Slot: {slot}")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum CameraControlParameter {
+    Lens(LensParameter),
+    Video(VideoParameter),
+    ColorCorrection(ColorCorrectionParameter),
+    Ptz(PtzParameter),
+    /// Parameter without a typed representation, or with an unexpected data type
+    Other {
+        category: u8,
+        parameter: u8,
+        value: CameraControlValue,
+    },
+}
+
+impl CameraControlParameter {
+    fn from_value(category: u8, parameter: u8, value: CameraControlValue) -> Self {
+        let typed = match category {
+            CATEGORY_LENS => {
+                LensParameter::from_value(parameter, &value).map(CameraControlParameter::Lens)
+            }
+            CATEGORY_VIDEO => {
+                VideoParameter::from_value(parameter, &value).map(CameraControlParameter::Video)
+            }
+            CATEGORY_COLOR_CORRECTION => ColorCorrectionParameter::from_value(parameter, &value)
+                .map(CameraControlParameter::ColorCorrection),
+            CATEGORY_PTZ => {
+                PtzParameter::from_value(parameter, &value).map(CameraControlParameter::Ptz)
+            }
+            _ => None,
+        };
+
+        typed.unwrap_or(CameraControlParameter::Other {
+            category,
+            parameter,
+            value,
+        })
+    }
+
+    pub fn category(&self) -> u8 {
+        match self {
+            CameraControlParameter::Lens(_) => CATEGORY_LENS,
+            CameraControlParameter::Video(_) => CATEGORY_VIDEO,
+            CameraControlParameter::ColorCorrection(_) => CATEGORY_COLOR_CORRECTION,
+            CameraControlParameter::Ptz(_) => CATEGORY_PTZ,
+            CameraControlParameter::Other { category, .. } => *category,
+        }
+    }
+
+    pub fn id(&self) -> u8 {
+        match self {
+            CameraControlParameter::Lens(lens) => lens.id(),
+            CameraControlParameter::Video(video) => video.id(),
+            CameraControlParameter::ColorCorrection(color) => color.id(),
+            CameraControlParameter::Ptz(ptz) => ptz.id(),
+            CameraControlParameter::Other { parameter, .. } => *parameter,
+        }
+    }
+
+    pub fn value(&self) -> CameraControlValue {
+        match self {
+            CameraControlParameter::Lens(lens) => lens.value(),
+            CameraControlParameter::Video(video) => video.value(),
+            CameraControlParameter::ColorCorrection(color) => color.value(),
+            CameraControlParameter::Ptz(ptz) => ptz.value(),
+            CameraControlParameter::Other { value, .. } => value.clone(),
+        }
+    }
+}
+
+impl Display for CameraControlParameter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            CameraControlParameter::Lens(lens) => write!(f, "Lens {lens}"),
+            CameraControlParameter::Video(video) => write!(f, "Video {video}"),
+            CameraControlParameter::ColorCorrection(color) => {
+                write!(f, "Color correction {color}")
+            }
+            CameraControlParameter::Ptz(ptz) => write!(f, "PTZ {ptz}"),
+            CameraControlParameter::Other {
+                category,
+                parameter,
+                value,
+            } => write!(
+                f,
+                "Category: {category} Parameter: {parameter} Value: {value}"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CameraControl {
+    input: u8,
+    parameter: CameraControlParameter,
+}
+
+impl CameraControl {
+    pub fn parse(data: &mut Bytes) -> Result<Self, command::ParseError> {
+        let input = data.get_u8();
+        let category = data.get_u8();
+        let parameter = data.get_u8();
+        let data_type = data.get_u8();
+        let counts = CameraControlCounts::parse(data);
+        data.get_u16(); // Padding
+        let value = CameraControlValue::parse(data_type, &counts, data)?;
+
+        Ok(CameraControl {
+            input,
+            parameter: CameraControlParameter::from_value(category, parameter, value),
+        })
+    }
+
+    pub fn input(&self) -> u8 {
+        self.input
+    }
+
+    pub fn parameter(&self) -> &CameraControlParameter {
+        &self.parameter
+    }
+}
+
+impl Display for CameraControl {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Input: {} {}", self.input, self.parameter)
+    }
+}
+
+/// Camera control change sent to a camera attached to the switcher (`CCmd`)
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetCameraControl {
+    input: u8,
+    parameter: CameraControlParameter,
+    relative: bool,
+}
+
+impl SetCameraControl {
+    pub fn new(input: u8, parameter: CameraControlParameter) -> Self {
+        SetCameraControl {
+            input,
+            parameter,
+            relative: false,
+        }
+    }
+
+    /// Offset the current camera value by the parameter value instead of replacing it
+    pub fn new_relative(input: u8, parameter: CameraControlParameter) -> Self {
+        SetCameraControl {
+            input,
+            parameter,
+            relative: true,
+        }
+    }
+
+    pub fn input(&self) -> u8 {
+        self.input
+    }
+
+    pub fn parameter(&self) -> &CameraControlParameter {
+        &self.parameter
+    }
+
+    pub fn relative(&self) -> bool {
+        self.relative
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        let value = self.parameter.value();
+        let start = data.len();
+
+        data.put_u8(self.input);
+        data.put_u8(self.parameter.category());
+        data.put_u8(self.parameter.id());
+        data.put_u8(self.relative as u8);
+        data.put_u8(value.data_type());
+        data.put_u8(0); // Padding
+        value.counts().serialize(data);
+        value.serialize(data);
+
+        // Pad the values to a 32-bit boundary, at least 8 bytes
+        let len = (data.len() - start).max(24).next_multiple_of(4);
+        data.resize(start + len, 0);
+    }
+}
+
+impl Display for SetCameraControl {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Input: {} {} Relative: {}",
+            self.input, self.parameter, self.relative
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed16_conversion_ok() {
+        assert_eq!(fixed16_to_f32(0x0800), 1.0);
+        assert_eq!(fixed16_to_f32(0x0400), 0.5);
+        assert_eq!(fixed16_to_f32(-0x0800), -1.0);
+        assert_eq!(f32_to_fixed16(0.5), 0x0400);
+        assert_eq!(f32_to_fixed16(-1.0), -0x0800);
+    }
+
+    #[test]
+    fn camera_control_parse_iris_ok() {
+        let data: [u8; 24] = [
+            0x01, 0x00, 0x03, 0x80, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+        let mut data = Bytes::copy_from_slice(&data);
+
+        let camera_control = CameraControl::parse(&mut data).unwrap();
+
+        assert_eq!(camera_control.input(), 1);
+        assert_eq!(
+            camera_control.parameter(),
+            &CameraControlParameter::Lens(LensParameter::Iris(0.5))
+        );
+    }
+
+    #[test]
+    fn camera_control_parse_lift_ok() {
+        let data: [u8; 24] = [
+            0x02, 0x08, 0x00, 0x80, 0x00, 0x00, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0xfc, 0x00, 0x02, 0x00, 0x08, 0x00,
+        ];
+        let mut data = Bytes::copy_from_slice(&data);
+
+        let camera_control = CameraControl::parse(&mut data).unwrap();
+
+        assert_eq!(
+            camera_control.parameter(),
+            &CameraControlParameter::ColorCorrection(ColorCorrectionParameter::Lift(ColorValues {
+                red: 0.0,
+                green: -0.5,
+                blue: 0.25,
+                luma: 1.0,
+            }))
+        );
+    }
+
+    #[test]
+    fn set_camera_control_serialize_ok() {
+        let command = SetCameraControl::new(
+            1,
+            CameraControlParameter::Video(VideoParameter::ManualWhiteBalance {
+                temperature: 5600,
+                tint: -10,
+            }),
+        );
+        let expected: [u8; 24] = [
+            0x01, 0x01, 0x02, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x15, 0xe0, 0xff, 0xf6, 0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let mut data = BytesMut::new();
+        command.serialize(&mut data);
+
+        assert_eq!(data.to_vec(), expected);
+    }
+
+    #[test]
+    fn set_camera_control_sensor_gain_int8() {
+        let command = SetCameraControl::new(
+            1,
+            CameraControlParameter::Video(VideoParameter::SensorGain(-6)),
+        );
+
+        let mut data = BytesMut::new();
+        command.serialize(&mut data);
+
+        assert_eq!(data[..5], [0x01, 0x01, 0x01, 0x00, DATA_TYPE_INT8]);
+        assert_eq!(data[16], 0xfa);
+    }
+}
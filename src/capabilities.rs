@@ -0,0 +1,280 @@
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use crate::{control::ControlCommand, systeminfo::SystemInfo};
+
+/// Model of a switcher, detected from the product name it reports (`_pin`)
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DeviceModel {
+    Mini,
+    MiniPro,
+    MiniProIso,
+    MiniExtreme,
+    MiniExtremeIso,
+    TelevisionStudioHd,
+    Constellation,
+    #[default]
+    Unknown,
+}
+
+impl DeviceModel {
+    pub fn from_product(product: &str) -> Self {
+        match product {
+            "ATEM Mini" => DeviceModel::Mini,
+            "ATEM Mini Pro" => DeviceModel::MiniPro,
+            "ATEM Mini Pro ISO" => DeviceModel::MiniProIso,
+            "ATEM Mini Extreme" => DeviceModel::MiniExtreme,
+            "ATEM Mini Extreme ISO" => DeviceModel::MiniExtremeIso,
+            "ATEM Television Studio HD" => DeviceModel::TelevisionStudioHd,
+            // Sold in several sizes and resolutions sharing the protocol
+            p if p.contains("Constellation") => DeviceModel::Constellation,
+            _ => DeviceModel::Unknown,
+        }
+    }
+}
+
+impl Display for DeviceModel {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DeviceModel::Mini => write!(f, "ATEM Mini"),
+            DeviceModel::MiniPro => write!(f, "ATEM Mini Pro"),
+            DeviceModel::MiniProIso => write!(f, "ATEM Mini Pro ISO"),
+            DeviceModel::MiniExtreme => write!(f, "ATEM Mini Extreme"),
+            DeviceModel::MiniExtremeIso => write!(f, "ATEM Mini Extreme ISO"),
+            DeviceModel::TelevisionStudioHd => write!(f, "ATEM Television Studio HD"),
+            DeviceModel::Constellation => write!(f, "ATEM Constellation"),
+            DeviceModel::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// What a switcher supports, derived from the product name, topology and configuration it sends
+/// while connecting, see [`SystemInfo::capabilities`]
+#[derive(Debug, Default, Clone)]
+pub struct Capabilities {
+    model: DeviceModel,
+    me_count: u8,
+    keyer_counts: Vec<u8>,
+    dsk_count: u8,
+    aux_count: u8,
+    dve_count: u8,
+    stinger_count: u8,
+    supersource_count: u8,
+    media_player_count: u8,
+    multiviewer_count: u8,
+    hyperdeck_count: u8,
+    mixminus_output_count: u8,
+    talkback_count: u8,
+    fairlight: bool,
+    streaming: bool,
+    recording: bool,
+}
+
+impl From<&SystemInfo> for Capabilities {
+    fn from(info: &SystemInfo) -> Self {
+        let topology = info.topology();
+
+        Capabilities {
+            model: DeviceModel::from_product(info.product()),
+            me_count: topology.me_count(),
+            keyer_counts: (0..topology.me_count())
+                .map(|me| info.me_config(me).map_or(0, |config| config.key_count()))
+                .collect(),
+            dsk_count: topology.dsk_count(),
+            aux_count: topology.aux_count(),
+            dve_count: topology.dve_count(),
+            stinger_count: topology.stinger_count(),
+            supersource_count: topology.supersource_count(),
+            media_player_count: topology.mediaplayer_count(),
+            multiviewer_count: topology.multiviewer_count(),
+            hyperdeck_count: topology.hyperdeck_count(),
+            mixminus_output_count: topology.mixminus_output_count(),
+            talkback_count: topology.talkback_count(),
+            fairlight: info.fairlight_mixer_config().is_some(),
+            #[cfg(feature = "streaming")]
+            streaming: info.streaming_service().is_some(),
+            #[cfg(not(feature = "streaming"))]
+            streaming: false,
+            #[cfg(feature = "recording")]
+            recording: info.recording_settings().is_some(),
+            #[cfg(not(feature = "recording"))]
+            recording: false,
+        }
+    }
+}
+
+impl Capabilities {
+    pub fn model(&self) -> DeviceModel {
+        self.model
+    }
+
+    pub fn me_count(&self) -> u8 {
+        self.me_count
+    }
+
+    /// Number of upstream keyers of a mix effect block
+    pub fn keyer_count(&self, me: u8) -> u8 {
+        self.keyer_counts.get(me as usize).copied().unwrap_or(0)
+    }
+
+    pub fn dsk_count(&self) -> u8 {
+        self.dsk_count
+    }
+
+    pub fn aux_count(&self) -> u8 {
+        self.aux_count
+    }
+
+    pub fn supersource_count(&self) -> u8 {
+        self.supersource_count
+    }
+
+    pub fn media_player_count(&self) -> u8 {
+        self.media_player_count
+    }
+
+    pub fn multiviewer_count(&self) -> u8 {
+        self.multiviewer_count
+    }
+
+    pub fn hyperdeck_count(&self) -> u8 {
+        self.hyperdeck_count
+    }
+
+    pub fn mixminus_output_count(&self) -> u8 {
+        self.mixminus_output_count
+    }
+
+    pub fn talkback_count(&self) -> u8 {
+        self.talkback_count
+    }
+
+    /// DVE keyers and DVE transitions
+    pub fn has_dve(&self) -> bool {
+        self.dve_count > 0
+    }
+
+    pub fn has_stinger(&self) -> bool {
+        self.stinger_count > 0
+    }
+
+    /// Fairlight audio mixer instead of the classic audio mixer
+    pub fn has_fairlight(&self) -> bool {
+        self.fairlight
+    }
+
+    /// Always false without the `streaming` feature
+    pub fn has_streaming(&self) -> bool {
+        self.streaming
+    }
+
+    /// Always false without the `recording` feature
+    pub fn has_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Whether the switcher has the feature a command controls. The index of the mix effect
+    /// block, keyer or output the command addresses is not checked.
+    pub fn supports(&self, command: &ControlCommand) -> bool {
+        match command {
+            #[cfg(feature = "streaming")]
+            ControlCommand::Streaming(_)
+            | ControlCommand::StreamingService(_)
+            | ControlCommand::StreamingAudioBitrates(_)
+            | ControlCommand::StreamingAuthentication(_)
+            | ControlCommand::StreamingLowLatency(_) => self.streaming,
+            #[cfg(feature = "recording")]
+            ControlCommand::Recording(_)
+            | ControlCommand::RecordingSettings(_)
+            | ControlCommand::SwitchRecordingDisk(_) => self.recording,
+            #[cfg(feature = "audio")]
+            ControlCommand::MixMinusOutput(_) => self.mixminus_output_count > 0,
+            #[cfg(feature = "audio")]
+            ControlCommand::TalkbackProperties(_) | ControlCommand::TalkbackInputProperties(_) => {
+                self.talkback_count > 0
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioLevels(_)
+            | ControlCommand::AudioMonitor(_)
+            | ControlCommand::AudioMixerInput(_)
+            | ControlCommand::AudioMixerMaster(_) => !self.fairlight,
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightLevels(_)
+            | ControlCommand::FairlightInputProperties(_)
+            | ControlCommand::FairlightSourceProperties(_)
+            | ControlCommand::FairlightMasterProperties(_)
+            | ControlCommand::FairlightCompressor(_)
+            | ControlCommand::FairlightLimiter(_)
+            | ControlCommand::FairlightExpander(_)
+            | ControlCommand::FairlightEqBand(_) => self.fairlight,
+            ControlCommand::HyperDeckPlayer(_) | ControlCommand::HyperDeckClip(_) => {
+                self.hyperdeck_count > 0
+            }
+            ControlCommand::AuxSource(_) => self.aux_count > 0,
+            ControlCommand::MultiViewInput(_)
+            | ControlCommand::MultiViewLayout(_)
+            | ControlCommand::MultiViewVU(_)
+            | ControlCommand::MultiViewSafeArea(_) => self.multiviewer_count > 0,
+            ControlCommand::MediaPlayerSource(_) | ControlCommand::MediaPlayerStatus(_) => {
+                self.media_player_count > 0
+            }
+            ControlCommand::KeyerDVE(_)
+            | ControlCommand::KeyerKeyframe(_)
+            | ControlCommand::TransitionDVERate(_) => self.has_dve(),
+            ControlCommand::TransitionStingerRate(_) => self.has_stinger(),
+            ControlCommand::DownstreamKeyerFillSource(_)
+            | ControlCommand::DownstreamKeyerCutSource(_)
+            | ControlCommand::DownstreamKeyerOnAir(_) => self.dsk_count > 0,
+            ControlCommand::SuperSourceBox(_) => self.supersource_count > 0,
+            _ => true,
+        }
+    }
+}
+
+impl Display for Capabilities {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Model: {} M/Es: {} DSKs: {} Aux: {} DVE: {} Fairlight: {} Streaming: {} Recording: {}",
+            self.model,
+            self.me_count,
+            self.dsk_count,
+            self.aux_count,
+            self.has_dve(),
+            self.fairlight,
+            self.streaming,
+            self.recording
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::{
+        command::{Command, SetAuxSource},
+        systeminfo::{MeConfig, Topology},
+    };
+
+    #[test]
+    fn capabilities_from_system_info() {
+        let mut system_info = SystemInfo::default();
+        system_info.set_product("ATEM Mini Extreme ISO");
+        // One M/E, no aux outputs and one DVE
+        let mut data = Bytes::from_static(&[1, 14, 2, 0, 0, 2, 1, 1, 4, 1, 0, 1, 0, 0, 0, 0]);
+        system_info.update(&Command::Topology(Topology::parse(&mut data)));
+        let mut data = Bytes::from_static(&[0, 4]);
+        system_info.update(&Command::MeConfig(MeConfig::parse(&mut data)));
+
+        let capabilities = system_info.capabilities();
+
+        assert_eq!(capabilities.model(), DeviceModel::MiniExtremeIso);
+        assert_eq!(capabilities.me_count(), 1);
+        assert_eq!(capabilities.keyer_count(0), 4);
+        assert_eq!(capabilities.keyer_count(1), 0);
+        assert!(capabilities.has_dve());
+        assert!(!capabilities.supports(&ControlCommand::AuxSource(SetAuxSource::new(0, 1))));
+    }
+}
@@ -0,0 +1,1151 @@
+use std::{
+    any::Any,
+    collections::{HashMap, VecDeque},
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::{Bytes, BytesMut};
+use tokio::{
+    net::UdpSocket,
+    sync::{mpsc, oneshot, watch},
+    time::{sleep_until, Instant},
+};
+
+#[cfg(feature = "history")]
+use std::path::PathBuf;
+#[cfg(feature = "media-pool")]
+use std::sync::atomic::{AtomicU16, Ordering};
+
+use crate::auxoutput::AuxOutput;
+use crate::color::{HslColor, SetColorGenerator};
+use crate::command::{
+    Command, CommandCategories, CustomParsers, SetAuxSource, SetPreviewInput, SetProgramInput,
+    SetTransitionPosition, Time, Warning,
+};
+use crate::control::{coalesce_batches, take_payload, AckSender, CommandBatch, ControlCommand};
+use crate::desiredstate::DesiredState;
+use crate::displayclock::{DisplayClockState, SetDisplayClockState, SetDisplayClockTime};
+#[cfg(feature = "history")]
+use crate::history::History;
+use crate::macros::Macros;
+use crate::mediaplayer::MediaPlayer;
+#[cfg(feature = "media-pool")]
+use crate::mediapool::{MediaPool, SetMediaLock};
+use crate::mixeffect::MixEffect;
+use crate::multiview::{
+    MultiViewLayoutStyle, SetMultiViewInput, SetMultiViewLayout, SetMultiViewSafeArea,
+    SetMultiViewVU,
+};
+use crate::packet::{Packet, MAX_PAYLOAD_SIZE};
+#[cfg(feature = "recording")]
+use crate::recording::{SetRecording, SetRecordingSettings, SwitchRecordingDisk};
+use crate::scheduler::{self, ScheduleAt, ScheduledOperation};
+use crate::source::{Input, SetInputProperties};
+#[cfg(feature = "streaming")]
+use crate::streaming::{
+    SetStreaming, SetStreamingAudioBitrates, SetStreamingAuthentication, SetStreamingLowLatency,
+    SetStreamingService,
+};
+use crate::systeminfo::{
+    ClearStartupState, DownConvertMode, RequestTime, SaveStartupState, SetDownConvertMode,
+    SetTimeCodeMode, SetVideoMode, SystemInfo, TimeCodeType, TransitionEdge, VideoMode,
+};
+use crate::tally::OnAirTally;
+use crate::trace::{debug, info};
+#[cfg(feature = "audio")]
+use crate::{
+    audio::{
+        SetAudioLevels, SetAudioMixerInput, SetAudioMixerMaster, SetAudioMonitor,
+        SetFairlightLevels,
+    },
+    fairlight::{
+        InputLevels, SetFairlightInputProperties, SetFairlightMasterProperties,
+        SetFairlightSourceProperties,
+    },
+};
+use crate::{Error, ParseError, ProtocolError, TransportError};
+
+pub enum Message {
+    Connected,
+    Disconnected(TransportError),
+    ParsingFailed(ParseError),
+    /// A request was not handled by the switcher as the protocol expects, the connection is
+    /// still usable
+    ProtocolError(ProtocolError),
+    Command(Command),
+    Warning(Warning),
+}
+
+/// Time to wait for the switcher to acknowledge a packet before sending it again
+const ACK_TIMEOUT: Duration = Duration::from_millis(500);
+/// Time to wait for the switcher to grant a media pool lock, e.g. while another client holds it
+#[cfg(feature = "media-pool")]
+const MEDIA_LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+/// Number of times a packet is sent again before giving up
+const MAX_RETRANSMITS: u8 = 5;
+/// Space made available in the receive buffer before every datagram, fits a full ethernet frame
+const RECV_BUFFER_SIZE: usize = 1500;
+
+/// Resolves once the switcher has acknowledged the packet carrying a command, or fails when the
+/// packet has been sent again too many times or the connection is closed
+pub struct Ack {
+    rx: oneshot::Receiver<Result<(), Error>>,
+}
+
+impl Future for Ack {
+    type Output = Result<(), Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.rx)
+            .poll(cx)
+            .map(|result| result.unwrap_or(Err(TransportError::ConnectionClosed.into())))
+    }
+}
+
+/// Command packet sent to the switcher but not acknowledged yet
+struct InFlight {
+    id: u16,
+    payload: Bytes,
+    sent: Instant,
+    retransmits: u8,
+    acks: Vec<AckSender>,
+}
+
+/// Limits for the traffic sent to the switcher, which only has a modest UDP receive capacity
+#[derive(Debug, Clone)]
+pub struct ConnectionOptions {
+    max_in_flight: usize,
+    packet_interval: Duration,
+    parsers: CustomParsers,
+    commands: CommandCategories,
+    #[cfg(feature = "history")]
+    history: Option<PathBuf>,
+}
+
+impl ConnectionOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maximum number of packets sent but not yet acknowledged by the switcher
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight.max(1);
+
+        self
+    }
+
+    /// Minimum time between two packets sent to the switcher
+    pub fn packet_interval(mut self, packet_interval: Duration) -> Self {
+        self.packet_interval = packet_interval;
+
+        self
+    }
+
+    /// Parse the command with the four character name, if not handled by this crate, and
+    /// deliver the result as [`Command::Custom`]
+    pub fn parser<T, F>(mut self, name: &[u8; 4], parse: F) -> Self
+    where
+        T: Any + Send,
+        F: Fn(&mut Bytes) -> Result<T, ParseError> + Send + Sync + 'static,
+    {
+        self.parsers.register(name, parse);
+
+        self
+    }
+
+    /// Only deliver commands of these categories as [`Message::Command`], e.g. to skip the
+    /// time code sent every frame. The state is still updated from every command. All
+    /// categories are delivered by default.
+    pub fn commands(mut self, categories: CommandCategories) -> Self {
+        self.commands = categories;
+
+        self
+    }
+
+    /// Don't deliver the time code sent every frame as messages, only keep the latest value
+    /// available as [`SystemInfo::time`]
+    pub fn latest_time_only(mut self) -> Self {
+        self.commands.remove(CommandCategories::TIME);
+
+        self
+    }
+
+    /// Append every command received from the switcher to a JSON Lines file, with the time it
+    /// was received and its raw bytes
+    #[cfg(feature = "history")]
+    pub fn history(mut self, path: impl Into<PathBuf>) -> Self {
+        self.history = Some(path.into());
+
+        self
+    }
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        ConnectionOptions {
+            max_in_flight: 16,
+            packet_interval: Duration::from_millis(1),
+            parsers: CustomParsers::default(),
+            commands: CommandCategories::all(),
+            #[cfg(feature = "history")]
+            history: None,
+        }
+    }
+}
+
+/// Called with every command received from the switcher and the start or end of a transition
+/// it caused, returns false once it is no longer interested in further commands
+type Listener = Box<dyn FnMut(&Command, Option<TransitionEdge>) -> bool + Send>;
+type Listeners = Arc<Mutex<Vec<Listener>>>;
+type Transfers = Arc<Mutex<HashMap<u16, mpsc::UnboundedSender<Command>>>>;
+
+pub struct Connection {
+    rx: mpsc::UnboundedReceiver<Message>,
+    command_tx: mpsc::UnboundedSender<CommandBatch>,
+    system_info: Arc<Mutex<SystemInfo>>,
+    listeners: Listeners,
+    #[cfg(feature = "media-pool")]
+    transfers: Transfers,
+    #[cfg(feature = "media-pool")]
+    next_transfer_id: AtomicU16,
+}
+
+impl Connection {
+    /// Open a connection to a Blackmagic ATEM switcher at address, on the standard port 9910
+    /// unless the address includes a port
+    pub async fn open(address: &str) -> Result<Self, Error> {
+        Self::open_with_options(address, ConnectionOptions::default()).await
+    }
+
+    /// Open a connection to a Blackmagic ATEM switcher at address with custom traffic limits
+    pub async fn open_with_options(
+        address: &str,
+        options: ConnectionOptions,
+    ) -> Result<Self, Error> {
+        let remote_addr: SocketAddr = match address.parse() {
+            Ok(address) => address,
+            Err(_) => format!("{}:9910", address).parse()?,
+        };
+        let local_addr: SocketAddr = "0.0.0.0:0".parse()?;
+
+        let socket = UdpSocket::bind(local_addr).await?;
+        socket.connect(remote_addr).await?;
+
+        #[cfg(feature = "history")]
+        let history = options.history.as_deref().map(History::open).transpose()?;
+
+        info!(
+            local = socket.local_addr()?,
+            remote = remote_addr;
+            "Connecting to ATEM switcher"
+        );
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let (command_tx, command_rx) = mpsc::unbounded_channel();
+        let system_info = Arc::new(Mutex::new(SystemInfo::default()));
+        let state = system_info.clone();
+        let listeners = Listeners::default();
+        let waiters = listeners.clone();
+        let transfers = Transfers::default();
+        let active_transfers = transfers.clone();
+        let connection = async move {
+            run(
+                socket,
+                options,
+                #[cfg(feature = "history")]
+                history,
+                tx,
+                command_rx,
+                state,
+                waiters.clone(),
+                active_transfers.clone(),
+            )
+            .await;
+            // Wake up anyone still waiting for a command or transfer
+            waiters.lock().unwrap().clear();
+            active_transfers.lock().unwrap().clear();
+        };
+        #[cfg(feature = "tracing")]
+        let connection = tracing::Instrument::instrument(
+            connection,
+            tracing::info_span!("atem", remote = %remote_addr),
+        );
+        tokio::task::spawn(connection);
+
+        Ok(Connection {
+            rx,
+            command_tx,
+            system_info,
+            listeners,
+            #[cfg(feature = "media-pool")]
+            transfers,
+            #[cfg(feature = "media-pool")]
+            next_transfer_id: AtomicU16::new(0),
+        })
+    }
+
+    pub async fn recv_message(&mut self) -> Option<Message> {
+        self.rx.recv().await
+    }
+
+    /// Stop queueing messages for [`Connection::recv_message`], for users that only rely on the
+    /// state and commands
+    #[cfg(feature = "rest")]
+    pub(crate) fn close_messages(&mut self) {
+        self.rx.close();
+    }
+
+    /// State of the switcher as reported by the commands received so far
+    pub fn system_info(&self) -> MutexGuard<'_, SystemInfo> {
+        self.system_info.lock().unwrap()
+    }
+
+    /// Sources actually on program and preview, updated whenever a change of the mix effect
+    /// blocks changes them, see [`OnAirTally`]. Drop the receiver to stop updating it.
+    pub fn watch_on_air_tally(&self) -> watch::Receiver<OnAirTally> {
+        let (changed_tx, mut changed_rx) = watch::channel(());
+        self.listen(move |command| match command {
+            Command::ProgramInput(_)
+            | Command::PreviewInput(_)
+            | Command::TransitionPosition(_)
+            | Command::TransitionStyleSelection(_)
+            | Command::KeyerBaseProperties(_)
+            | Command::KeyerOnAir(_)
+            | Command::DownstreamKeyerSources(_)
+            | Command::DownstreamKeyerState(_)
+            | Command::FadeToBlackState(_)
+            | Command::SuperSourceBox(_) => changed_tx.send(()).is_ok(),
+            _ => !changed_tx.is_closed(),
+        });
+
+        // Listen before taking the initial tally so no change can be missed in between
+        let (tx, rx) = watch::channel(self.system_info().on_air_tally());
+
+        // Listeners are called with the listeners locked, so the state can't be locked there
+        let system_info = self.system_info.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    changed = changed_rx.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                    }
+                    _ = tx.closed() => break,
+                }
+                let tally = system_info.lock().unwrap().on_air_tally();
+                tx.send_if_modified(|current| {
+                    let modified = *current != tally;
+                    *current = tally;
+                    modified
+                });
+            }
+        });
+
+        rx
+    }
+
+    /// Queue a command to be sent to the switcher. Commands queued in quick succession are
+    /// combined into a single packet. For continuous controls like the transition position or
+    /// camera control only the latest queued value is sent.
+    ///
+    /// The returned [`Ack`] resolves once the switcher has acknowledged the packet carrying the
+    /// command, the command is sent whether it is awaited or not.
+    pub fn send_command(&self, command: ControlCommand) -> Ack {
+        self.send_batch(command.into())
+    }
+
+    /// Queue a batch of commands that is guaranteed to be sent to the switcher in one packet
+    pub fn send_batch(&self, mut batch: CommandBatch) -> Ack {
+        let (ack_tx, ack_rx) = oneshot::channel();
+        let supported = batch
+            .commands()
+            .iter()
+            .try_for_each(|command| self.check_supported(command));
+        if let Err(e) = supported {
+            let _ = ack_tx.send(Err(e));
+        } else if batch.is_empty() {
+            let _ = ack_tx.send(Ok(()));
+        } else {
+            // If the connection is closed the batch is dropped along with the sender
+            batch.add_ack(ack_tx);
+            let _ = self.command_tx.send(batch);
+        }
+
+        Ack { rx: ack_rx }
+    }
+
+    /// Send a command and wait until the switcher reports state showing that it has taken
+    /// effect. The switcher silently ignores invalid commands, so an acknowledgement alone
+    /// doesn't mean anything changed. Only commands where
+    /// [`ControlCommand::is_confirmed_by`] knows the state counterpart can be confirmed.
+    pub async fn send_confirmed(
+        &self,
+        command: ControlCommand,
+        timeout: Duration,
+    ) -> Result<(), Error> {
+        let expected = command.clone();
+        self.send_and_observe(
+            command,
+            move |state| expected.is_confirmed_by(state),
+            timeout,
+        )
+        .await
+    }
+
+    /// Send a command and wait until a command received from the switcher matches
+    pub async fn send_and_observe<F>(
+        &self,
+        command: ControlCommand,
+        matches: F,
+        timeout: Duration,
+    ) -> Result<(), Error>
+    where
+        F: Fn(&Command) -> bool + Send + 'static,
+    {
+        // Start observing before sending so the state change can't be missed
+        let observed_rx = self.observe(matches);
+        let ack = self.send_command(command);
+
+        tokio::time::timeout(timeout, async {
+            ack.await?;
+            observed_rx
+                .await
+                .map_err(|_| TransportError::ConnectionClosed.into())
+        })
+        .await
+        .unwrap_or(Err(ProtocolError::NotConfirmed.into()))
+    }
+
+    pub(crate) fn observe<F>(&self, mut matches: F) -> oneshot::Receiver<()>
+    where
+        F: FnMut(&Command) -> bool + Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let mut tx = Some(tx);
+        self.listen(move |command| match tx.take() {
+            Some(tx) if matches(command) => {
+                let _ = tx.send(());
+                false
+            }
+            // Keep waiting unless the receiver has given up, e.g. after a timeout
+            Some(sender) if !sender.is_closed() => {
+                tx = Some(sender);
+                true
+            }
+            _ => false,
+        });
+
+        rx
+    }
+
+    /// Register a listener called with every command received from the switcher until it
+    /// returns false
+    pub(crate) fn listen<F>(&self, mut listener: F)
+    where
+        F: FnMut(&Command) -> bool + Send + 'static,
+    {
+        self.listen_with_transitions(move |command, _| listener(command));
+    }
+
+    /// Register a listener like [`Connection::listen`] that is also told when a transition
+    /// position it is called with starts or completes a transition
+    pub(crate) fn listen_with_transitions<F>(&self, listener: F)
+    where
+        F: FnMut(&Command, Option<TransitionEdge>) -> bool + Send + 'static,
+    {
+        self.listeners.lock().unwrap().push(Box::new(listener));
+    }
+
+    /// Queue a command without waiting for it to be acknowledged
+    pub(crate) fn queue_command(&self, command: ControlCommand) -> Result<(), Error> {
+        self.check_supported(&command)?;
+        self.command_tx
+            .send(command.into())
+            .map_err(|_| TransportError::ConnectionClosed.into())
+    }
+
+    /// The switcher silently ignores commands its firmware or model doesn't understand, reject
+    /// them instead. Nothing is rejected before the switcher has reported its protocol version
+    /// and topology.
+    fn check_supported(&self, command: &ControlCommand) -> Result<(), Error> {
+        let system_info = self.system_info();
+        let version = *system_info.version();
+        if let Some(min_version) = command.min_version() {
+            if version.is_known() && version < min_version {
+                debug!(command = command; "Command not supported by protocol version {}", version);
+                return Err(Error::UnsupportedCommand {
+                    name: String::from_utf8_lossy(command.name()).into_owned(),
+                    version,
+                });
+            }
+        }
+
+        let capabilities = system_info.capabilities();
+        if system_info.topology().is_known() && !capabilities.supports(command) {
+            debug!(command = command; "Command not supported by the {}", capabilities.model());
+            return Err(Error::UnsupportedByModel {
+                name: String::from_utf8_lossy(command.name()).into_owned(),
+                model: capabilities.model(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Send the commands once the time code of the switcher reaches the scheduled time, e.g.
+    /// to stop recording after two hours. Needs the time code and video mode reported by the
+    /// switcher, which is asked for its time code every second until the operation has run.
+    pub fn schedule(
+        &self,
+        at: ScheduleAt,
+        batch: impl Into<CommandBatch>,
+    ) -> Result<ScheduledOperation, Error> {
+        let batch = batch.into();
+        batch
+            .commands()
+            .iter()
+            .try_for_each(|command| self.check_supported(command))?;
+
+        let (start, mode) = {
+            let system_info = self.system_info();
+            system_info
+                .time()
+                .zip(system_info.video_mode())
+                .ok_or(Error::UnknownTime)?
+        };
+
+        scheduler::schedule(
+            start,
+            at,
+            mode,
+            batch,
+            self.command_tx.clone(),
+            |listener| self.listen(listener),
+        )
+    }
+
+    /// Ask the switcher to send the current time code
+    pub fn request_time(&self) -> Result<(), Error> {
+        self.queue_command(ControlCommand::RequestTime(RequestTime))
+    }
+
+    pub fn set_timecode_mode(&self, timecode_type: TimeCodeType) -> Result<(), Error> {
+        self.queue_command(ControlCommand::TimeCodeMode(SetTimeCodeMode::new(
+            timecode_type,
+        )))
+    }
+
+    pub fn start_display_clock(&self, clock: u8) -> Result<(), Error> {
+        self.set_display_clock_state(clock, DisplayClockState::Running)
+    }
+
+    pub fn stop_display_clock(&self, clock: u8) -> Result<(), Error> {
+        self.set_display_clock_state(clock, DisplayClockState::Stopped)
+    }
+
+    /// Reset the display clock to its start time
+    pub fn reset_display_clock(&self, clock: u8) -> Result<(), Error> {
+        self.set_display_clock_state(clock, DisplayClockState::Reset)
+    }
+
+    fn set_display_clock_state(&self, clock: u8, state: DisplayClockState) -> Result<(), Error> {
+        self.queue_command(ControlCommand::DisplayClockState(
+            SetDisplayClockState::new(clock, state),
+        ))
+    }
+
+    pub fn set_display_clock_time(&self, clock: u8, time: Time) -> Result<(), Error> {
+        self.queue_command(ControlCommand::DisplayClockTime(SetDisplayClockTime::new(
+            clock, time,
+        )))
+    }
+
+    /// Save the current configuration as the power on state of the switcher
+    pub fn save_startup_state(&self) -> Result<(), Error> {
+        self.queue_command(ControlCommand::SaveStartupState(SaveStartupState))
+    }
+
+    /// Clear the saved power on state, the switcher starts with its default configuration
+    pub fn clear_startup_state(&self) -> Result<(), Error> {
+        self.queue_command(ControlCommand::ClearStartupState(ClearStartupState))
+    }
+
+    pub fn set_video_mode(&self, mode: VideoMode) -> Result<(), Error> {
+        self.queue_command(ControlCommand::VideoMode(SetVideoMode::new(mode)))
+    }
+
+    /// Set how HD video is converted on the SD outputs
+    pub fn set_down_convert_mode(&self, mode: DownConvertMode) -> Result<(), Error> {
+        self.queue_command(ControlCommand::DownConvertMode(SetDownConvertMode::new(
+            mode,
+        )))
+    }
+
+    #[cfg(feature = "streaming")]
+    pub fn start_streaming(&self) -> Result<(), Error> {
+        self.queue_command(ControlCommand::Streaming(SetStreaming::new(true)))
+    }
+
+    #[cfg(feature = "streaming")]
+    pub fn stop_streaming(&self) -> Result<(), Error> {
+        self.queue_command(ControlCommand::Streaming(SetStreaming::new(false)))
+    }
+
+    #[cfg(feature = "streaming")]
+    pub fn set_streaming_service(&self, name: &str, url: &str, key: &str) -> Result<(), Error> {
+        self.queue_command(ControlCommand::StreamingService(
+            SetStreamingService::new().name(name).url(url).key(key),
+        ))
+    }
+
+    #[cfg(feature = "streaming")]
+    /// Low and high video bitrates of the streaming encoder in bits per second
+    pub fn set_streaming_video_bitrates(&self, low: u32, high: u32) -> Result<(), Error> {
+        self.queue_command(ControlCommand::StreamingService(
+            SetStreamingService::new().video_bitrates(low, high),
+        ))
+    }
+
+    #[cfg(feature = "streaming")]
+    /// Low and high audio bitrates of the streaming encoder in bits per second
+    pub fn set_streaming_audio_bitrates(&self, low: u32, high: u32) -> Result<(), Error> {
+        self.queue_command(ControlCommand::StreamingAudioBitrates(
+            SetStreamingAudioBitrates::new(low, high),
+        ))
+    }
+
+    #[cfg(feature = "streaming")]
+    /// Credentials for streaming services that require authentication
+    pub fn set_streaming_authentication(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> Result<(), Error> {
+        self.queue_command(ControlCommand::StreamingAuthentication(
+            SetStreamingAuthentication::new(username, password),
+        ))
+    }
+
+    #[cfg(feature = "streaming")]
+    pub fn set_streaming_low_latency(&self, enabled: bool) -> Result<(), Error> {
+        self.queue_command(ControlCommand::StreamingLowLatency(
+            SetStreamingLowLatency::new(enabled),
+        ))
+    }
+
+    #[cfg(feature = "recording")]
+    pub fn start_recording(&self) -> Result<(), Error> {
+        self.queue_command(ControlCommand::Recording(SetRecording::new(true)))
+    }
+
+    #[cfg(feature = "recording")]
+    pub fn stop_recording(&self) -> Result<(), Error> {
+        self.queue_command(ControlCommand::Recording(SetRecording::new(false)))
+    }
+
+    #[cfg(feature = "recording")]
+    pub fn set_recording_filename(&self, filename: &str) -> Result<(), Error> {
+        self.queue_command(ControlCommand::RecordingSettings(
+            SetRecordingSettings::new().filename(filename),
+        ))
+    }
+
+    #[cfg(feature = "recording")]
+    /// Enable or disable recording every input as a separate ISO file
+    pub fn set_iso_recording(&self, enabled: bool) -> Result<(), Error> {
+        self.queue_command(ControlCommand::RecordingSettings(
+            SetRecordingSettings::new().record_all_inputs(enabled),
+        ))
+    }
+
+    #[cfg(feature = "recording")]
+    /// Continue the ongoing recording on the next disk
+    pub fn switch_recording_disk(&self) -> Result<(), Error> {
+        self.queue_command(ControlCommand::SwitchRecordingDisk(SwitchRecordingDisk))
+    }
+
+    #[cfg(feature = "audio")]
+    /// Start or stop receiving audio meter levels, the switcher doesn't send them by default.
+    /// They arrive as [`Command::AudioLevels`] or [`Command::FairlightSourceLevels`] depending
+    /// on the audio mixer of the switcher.
+    pub fn enable_audio_levels(&self, enabled: bool) -> Result<(), Error> {
+        let command = if self.system_info().capabilities().has_fairlight() {
+            ControlCommand::FairlightLevels(SetFairlightLevels::new(enabled))
+        } else {
+            ControlCommand::AudioLevels(SetAudioLevels::new(enabled))
+        };
+        self.queue_command(command)
+    }
+
+    #[cfg(feature = "audio")]
+    /// Change the monitor output of the classic audio mixer, the current state is available
+    /// from [`SystemInfo::audio_monitor`]
+    pub fn set_audio_monitor(&self, monitor: SetAudioMonitor) -> Result<(), Error> {
+        self.queue_command(ControlCommand::AudioMonitor(monitor))
+    }
+
+    #[cfg(feature = "audio")]
+    /// Move the fader of an input of the classic audio mixer. Fader moves queued in quick
+    /// succession are combined, only the latest position is sent.
+    pub fn set_audio_mixer_input(&self, fader: SetAudioMixerInput) -> Result<(), Error> {
+        self.queue_command(ControlCommand::AudioMixerInput(fader))
+    }
+
+    #[cfg(feature = "audio")]
+    /// Move the master fader of the classic audio mixer
+    pub fn set_audio_mixer_master(&self, fader: SetAudioMixerMaster) -> Result<(), Error> {
+        self.queue_command(ControlCommand::AudioMixerMaster(fader))
+    }
+
+    #[cfg(feature = "audio")]
+    /// Move the fader of a Fairlight source. Fader moves queued in quick succession are
+    /// combined, only the latest position is sent.
+    pub fn set_fairlight_source(&self, fader: SetFairlightSourceProperties) -> Result<(), Error> {
+        self.queue_command(ControlCommand::FairlightSourceProperties(fader))
+    }
+
+    #[cfg(feature = "audio")]
+    /// Move the master fader of the Fairlight mixer
+    pub fn set_fairlight_master(&self, fader: SetFairlightMasterProperties) -> Result<(), Error> {
+        self.queue_command(ControlCommand::FairlightMasterProperties(fader))
+    }
+
+    #[cfg(feature = "audio")]
+    /// Select mic or line level of an analog Fairlight input, the levels an input supports are
+    /// listed by [`SystemInfo::fairlight_input`]
+    pub fn set_fairlight_input_level(&self, input: u16, level: InputLevels) -> Result<(), Error> {
+        self.queue_command(ControlCommand::FairlightInputProperties(
+            SetFairlightInputProperties::new(input).level(level),
+        ))
+    }
+
+    /// Rename an input, the long name is at most 20 bytes and the short name at most 4 bytes
+    pub fn set_input_name(
+        &self,
+        input: u16,
+        long_name: &str,
+        short_name: &str,
+    ) -> Result<(), Error> {
+        self.queue_command(ControlCommand::InputProperties(
+            SetInputProperties::new(input)
+                .long_name(long_name)?
+                .short_name(short_name)?,
+        ))
+    }
+
+    /// Select the external port of an input, e.g. SDI or HDMI
+    pub fn set_input_port(&self, input: u16, port: Input) -> Result<(), Error> {
+        self.queue_command(ControlCommand::InputProperties(
+            SetInputProperties::new(input).external_port_type(port),
+        ))
+    }
+
+    #[cfg(feature = "media-pool")]
+    /// Lock a media pool store and wait until the switcher has granted the lock.
+    /// The stills store is 0, clip stores start at 1. Fails with
+    /// [`ProtocolError::MediaLockTimeout`] when the lock isn't granted within 5 seconds, the
+    /// request is withdrawn then.
+    pub async fn acquire_media_lock(&self, store: u16) -> Result<(), Error> {
+        let lock_rx = self.observe(
+            move |command| matches!(command, Command::MediaLockObtained(lock) if lock.store() == store),
+        );
+
+        self.queue_command(ControlCommand::MediaLock(SetMediaLock::new(store, true)))?;
+
+        match tokio::time::timeout(MEDIA_LOCK_TIMEOUT, lock_rx).await {
+            Ok(obtained) => obtained.map_err(|_| TransportError::ConnectionClosed.into()),
+            Err(_) => {
+                // Otherwise the switcher may grant the lock later with no one to release it
+                self.release_media_lock(store)?;
+                Err(ProtocolError::MediaLockTimeout(store).into())
+            }
+        }
+    }
+
+    #[cfg(feature = "media-pool")]
+    pub fn release_media_lock(&self, store: u16) -> Result<(), Error> {
+        self.queue_command(ControlCommand::MediaLock(SetMediaLock::new(store, false)))
+    }
+
+    /// Control an aux output, the first one is 0
+    pub fn aux(&self, aux: u8) -> AuxOutput<'_> {
+        AuxOutput::new(self, aux)
+    }
+
+    /// Control a mix effect block, the first one is 0
+    pub fn mix_effect(&self, me: u8) -> MixEffect<'_> {
+        MixEffect::new(self, me)
+    }
+
+    /// Run, record and list the macros of the switcher
+    pub fn macros(&self) -> Macros<'_> {
+        Macros::new(self)
+    }
+
+    /// Control a media player, the first one is 0
+    pub fn media_player(&self, player: u8) -> MediaPlayer<'_> {
+        MediaPlayer::new(self, player)
+    }
+
+    #[cfg(feature = "media-pool")]
+    /// Manage the stills, clips and macros stored on the switcher
+    pub fn media_pool(&self) -> MediaPool<'_> {
+        MediaPool::new(self)
+    }
+
+    #[cfg(feature = "media-pool")]
+    /// Register a new data transfer, the commands belonging to it are sent to the receiver
+    pub(crate) fn start_transfer(&self) -> (u16, mpsc::UnboundedReceiver<Command>) {
+        let transfer_id = self.next_transfer_id.fetch_add(1, Ordering::Relaxed);
+        let (transfer_tx, transfer_rx) = mpsc::unbounded_channel();
+        self.transfers
+            .lock()
+            .unwrap()
+            .insert(transfer_id, transfer_tx);
+
+        (transfer_id, transfer_rx)
+    }
+
+    #[cfg(feature = "media-pool")]
+    pub(crate) fn finish_transfer(&self, transfer_id: u16) {
+        self.transfers.lock().unwrap().remove(&transfer_id);
+    }
+
+    /// Select the program source of the mix effect block
+    pub fn set_program_input(&self, me: u8, source: u16) -> Result<(), Error> {
+        self.queue_command(ControlCommand::ProgramInput(SetProgramInput::new(
+            me, source,
+        )))
+    }
+
+    /// Select the preview source of the mix effect block
+    pub fn set_preview_input(&self, me: u8, source: u16) -> Result<(), Error> {
+        self.queue_command(ControlCommand::PreviewInput(SetPreviewInput::new(
+            me, source,
+        )))
+    }
+
+    /// Move the transition of the mix effect block like the T-bar, position ranges from 0 to 10000
+    pub fn set_transition_position(&self, me: u8, position: u16) -> Result<(), Error> {
+        self.queue_command(ControlCommand::TransitionPosition(
+            SetTransitionPosition::new(me, position),
+        ))
+    }
+
+    /// Change the color of a color generator, the current color is available from
+    /// [`SystemInfo::color_generator`]
+    pub fn set_color_generator(&self, index: u8, color: HslColor) -> Result<(), Error> {
+        self.queue_command(ControlCommand::ColorGenerator(
+            SetColorGenerator::new(index).color(color),
+        ))
+    }
+
+    /// Bring the switcher to the desired state. Only the commands for the parts that differ
+    /// from the current state are sent, in the order of [`DesiredState::commands`], and this
+    /// waits until the switcher has acknowledged all of them.
+    pub async fn apply_state(&self, state: &DesiredState) -> Result<(), Error> {
+        let commands = state.commands(&self.system_info());
+        let acks: Vec<Ack> = commands
+            .into_iter()
+            .map(|command| self.send_command(command))
+            .collect();
+        for ack in acks {
+            ack.await?;
+        }
+
+        Ok(())
+    }
+
+    /// Route source to the aux output
+    pub fn set_aux_source(&self, aux: u8, source: u16) -> Result<(), Error> {
+        self.queue_command(ControlCommand::AuxSource(SetAuxSource::new(aux, source)))
+    }
+
+    /// Route source to a window of the multiview
+    pub fn set_multiview_input(&self, multiview: u8, window: u8, source: u16) -> Result<(), Error> {
+        self.queue_command(ControlCommand::MultiViewInput(SetMultiViewInput::new(
+            multiview, window, source,
+        )))
+    }
+
+    pub fn set_multiview_layout(
+        &self,
+        multiview: u8,
+        layout: MultiViewLayoutStyle,
+    ) -> Result<(), Error> {
+        self.queue_command(ControlCommand::MultiViewLayout(
+            SetMultiViewLayout::new(multiview).layout(layout),
+        ))
+    }
+
+    /// Show or hide the VU meter in a window of the multiview
+    pub fn set_multiview_vu(&self, multiview: u8, window: u8, enabled: bool) -> Result<(), Error> {
+        self.queue_command(ControlCommand::MultiViewVU(SetMultiViewVU::new(
+            multiview, window, enabled,
+        )))
+    }
+
+    /// Show or hide the safe area markers in a window of the multiview
+    pub fn set_multiview_safe_area(
+        &self,
+        multiview: u8,
+        window: u8,
+        enabled: bool,
+    ) -> Result<(), Error> {
+        self.queue_command(ControlCommand::MultiViewSafeArea(
+            SetMultiViewSafeArea::new(multiview, window, enabled),
+        ))
+    }
+}
+
+async fn send_hello_packet(socket: &UdpSocket) -> Result<(), TransportError> {
+    let packet = Packet::new_hello_packet();
+    socket.send(&packet.serialize()).await?;
+
+    Ok(())
+}
+
+#[cfg_attr(feature = "history", allow(clippy::too_many_arguments))]
+async fn run(
+    socket: UdpSocket,
+    options: ConnectionOptions,
+    #[cfg(feature = "history")] history: Option<History>,
+    tx: mpsc::UnboundedSender<Message>,
+    mut command_rx: mpsc::UnboundedReceiver<CommandBatch>,
+    system_info: Arc<Mutex<SystemInfo>>,
+    listeners: Listeners,
+    transfers: Transfers,
+) {
+    let mut packet_id: u16 = 0;
+    let mut command_packet_id: u16 = 0;
+    let mut session_id = 0;
+    // Commands sent before the switcher has assigned the session are dropped by it
+    let mut established = false;
+    let mut pending: VecDeque<CommandBatch> = VecDeque::new();
+    let mut in_flight: VecDeque<InFlight> = VecDeque::new();
+    let mut next_send = Instant::now();
+
+    if let Err(e) = send_hello_packet(&socket).await {
+        let _ = tx.send(Message::Disconnected(e));
+        return;
+    }
+
+    let mut buf = BytesMut::with_capacity(RECV_BUFFER_SIZE);
+    loop {
+        // Reclaims the allocation once everything parsed from the previous datagram is dropped
+        buf.reserve(RECV_BUFFER_SIZE);
+        let len = tokio::select! {
+            result = socket.recv_buf(&mut buf) => match result {
+                Ok(len) => len,
+                Err(e) => {
+                    let _ = tx.send(Message::Disconnected(e.into()));
+                    return;
+                }
+            },
+            batch = command_rx.recv() => {
+                // The connection has been dropped, end the session
+                let Some(batch) = batch else {
+                    return;
+                };
+                debug!(commands = batch; "Queue commands");
+                pending.push_back(batch);
+                while let Ok(batch) = command_rx.try_recv() {
+                    debug!(commands = batch; "Queue commands");
+                    pending.push_back(batch);
+                }
+                // Drop values of continuous controls that have been superseded while waiting
+                pending = coalesce_batches(pending.into()).into();
+                continue;
+            }
+            _ = sleep_until(next_send),
+                if established && !pending.is_empty() && in_flight.len() < options.max_in_flight => {
+                // Combine as many queued commands as possible into one packet
+                let (payload, acks) = take_payload(&mut pending, MAX_PAYLOAD_SIZE);
+                let payload = payload.freeze();
+                command_packet_id = command_packet_id.wrapping_add(1);
+                let packet = Packet::new_command(session_id, command_packet_id, payload.clone());
+                if let Err(e) = send_commands(&socket, &packet).await {
+                    let _ = tx.send(Message::Disconnected(e));
+                    return;
+                }
+
+                let now = Instant::now();
+                in_flight.push_back(InFlight {
+                    id: command_packet_id,
+                    payload,
+                    sent: now,
+                    retransmits: 0,
+                    acks,
+                });
+                next_send = now + options.packet_interval;
+                continue;
+            }
+            _ = sleep_until(in_flight.iter().map(|packet| packet.sent + ACK_TIMEOUT).min().unwrap_or(next_send)),
+                if !in_flight.is_empty() => {
+                let now = Instant::now();
+                let mut waiting = VecDeque::with_capacity(in_flight.len());
+                for mut packet in in_flight.drain(..) {
+                    if packet.sent + ACK_TIMEOUT > now {
+                        waiting.push_back(packet);
+                    } else if packet.retransmits < MAX_RETRANSMITS {
+                        debug!(
+                            packet_id = packet.id,
+                            session_id = session_id,
+                            retransmits = packet.retransmits;
+                            "No ack, sending packet again"
+                        );
+                        let resend = Packet::new_command_resend(session_id, packet.id, packet.payload.clone());
+                        if let Err(e) = send_commands(&socket, &resend).await {
+                            let _ = tx.send(Message::Disconnected(e));
+                            return;
+                        }
+                        packet.sent = now;
+                        packet.retransmits += 1;
+                        waiting.push_back(packet);
+                    } else {
+                        debug!(packet_id = packet.id, session_id = session_id; "No ack, giving up on packet");
+                        for ack in packet.acks {
+                            let _ = ack.send(Err(ProtocolError::NotAcknowledged.into()));
+                        }
+                        let _ = tx.send(Message::ProtocolError(ProtocolError::NotAcknowledged));
+                    }
+                }
+                in_flight = waiting;
+                continue;
+            }
+        };
+
+        if len > 0 {
+            let mut packets = buf.split().freeze();
+
+            while !packets.is_empty() {
+                let packet = match Packet::deserialize(&mut packets) {
+                    Ok(packet) => packet,
+                    Err(e) => {
+                        debug!(session_id = session_id; "Dropping malformed datagram: {}", e);
+                        let _ = tx.send(Message::ParsingFailed(e));
+                        break;
+                    }
+                };
+                session_id = packet.uid();
+
+                if packet.is_ack() {
+                    // Acks are cumulative, covering every packet up to and including ack_id
+                    let ack_id = packet.ack_id();
+                    in_flight.retain_mut(|sent| {
+                        if ack_id.wrapping_sub(sent.id) >= 0x8000 {
+                            return true;
+                        }
+
+                        debug!(
+                            packet_id = sent.id,
+                            session_id = session_id;
+                            "Packet acknowledged"
+                        );
+                        for ack in sent.acks.drain(..) {
+                            let _ = ack.send(Ok(()));
+                        }
+                        false
+                    });
+                }
+
+                if packet.is_hello() {
+                    debug!(
+                        packet_id = packet.id(),
+                        session_id = session_id;
+                        "Received hello packet"
+                    );
+
+                    if let Err(e) = send_ack(&socket, packet.uid(), 0x0, packet.id()).await {
+                        let _ = tx.send(Message::Disconnected(e));
+                        return;
+                    }
+                    continue;
+                }
+
+                // The first packet after the hello carries the session assigned by the switcher
+                established = true;
+                if packet.ack_request() {
+                    packet_id = packet_id.wrapping_add(1);
+                    if let Err(e) = send_ack(&socket, packet.uid(), packet_id, packet.id()).await {
+                        let _ = tx.send(Message::Disconnected(e));
+                        return;
+                    }
+                }
+
+                if let Some(mut payload) = packet.payload() {
+                    while !payload.is_empty() {
+                        #[cfg(feature = "history")]
+                        let raw = payload.clone();
+                        let result = Command::parse_with(&mut payload, &options.parsers);
+                        #[cfg(feature = "history")]
+                        if let (Some(history), Ok(command)) = (&history, &result) {
+                            history.record(raw.slice(..raw.len() - payload.len()), command);
+                        }
+
+                        match result {
+                            Ok(Command::Warning(warning)) => {
+                                let _ = tx.send(Message::Warning(warning));
+                            }
+                            Ok(command) => {
+                                let transfer = command
+                                    .transfer_id()
+                                    .and_then(|id| transfers.lock().unwrap().get(&id).cloned());
+                                if let Some(transfer) = transfer {
+                                    let _ = transfer.send(command);
+                                    continue;
+                                }
+
+                                let edge = system_info.lock().unwrap().update(&command);
+                                listeners
+                                    .lock()
+                                    .unwrap()
+                                    .retain_mut(|listener| listener(&command, edge));
+                                if options.commands.intersects(command.category()) {
+                                    let _ = tx.send(Message::Command(command));
+                                }
+                            }
+                            Err(e) => {
+                                debug!(
+                                    packet_id = packet.id(),
+                                    session_id = session_id;
+                                    "Parsing failed: {}", e
+                                );
+                                let _ = tx.send(Message::ParsingFailed(e));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+async fn send_ack(
+    socket: &UdpSocket,
+    uid: u16,
+    packet_id: u16,
+    ack_id: u16,
+) -> Result<(), TransportError> {
+    let packet = Packet::new_ack(uid, ack_id, packet_id);
+
+    debug!(packet_id = ack_id, session_id = uid; "Send ack");
+
+    socket.send(&packet.serialize()).await?;
+
+    Ok(())
+}
+
+async fn send_commands(socket: &UdpSocket, packet: &Packet) -> Result<(), TransportError> {
+    debug!(packet_id = packet.id(), session_id = packet.uid(); "Send packet");
+
+    socket.send(&packet.serialize()).await?;
+
+    Ok(())
+}
@@ -0,0 +1,279 @@
+use core::fmt::Display;
+
+use bitflags::bitflags;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+/// Largest hue in 1/10 degree, 360 degrees wraps around to 0
+const MAX_HUE: u16 = 3599;
+/// Largest saturation and luma in 1/10 percent
+const MAX_PERCENT: u16 = 1000;
+
+/// Color as the switcher represents it, hue in 1/10 degree and saturation and luma in 1/10
+/// percent
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct HslColor {
+    hue: u16,
+    saturation: u16,
+    luma: u16,
+}
+
+impl HslColor {
+    /// Hue wraps around at 360 degrees, saturation and luma are clamped to 100 percent
+    pub fn new(hue: u16, saturation: u16, luma: u16) -> Self {
+        HslColor {
+            hue: hue % (MAX_HUE + 1),
+            saturation: saturation.min(MAX_PERCENT),
+            luma: luma.min(MAX_PERCENT),
+        }
+    }
+
+    pub fn from_rgb(red: u8, green: u8, blue: u8) -> Self {
+        let [r, g, b] = [red, green, blue].map(|c| c as f32 / 255.0);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let delta = max - min;
+        let luma = (max + min) / 2.0;
+
+        if delta == 0.0 {
+            return HslColor::new(0, 0, to_tenths(luma * 100.0));
+        }
+
+        let saturation = delta / (1.0 - abs(2.0 * luma - 1.0));
+        let sector = if max == r {
+            (g - b) / delta
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        };
+        let hue = (sector * 60.0 + 360.0) % 360.0;
+
+        HslColor::new(
+            to_tenths(hue),
+            to_tenths(saturation * 100.0),
+            to_tenths(luma * 100.0),
+        )
+    }
+
+    /// Convert to 8 bit sRGB components, red, green and blue
+    pub fn to_rgb(&self) -> [u8; 3] {
+        let hue = self.hue as f32 / 600.0;
+        let saturation = self.saturation as f32 / MAX_PERCENT as f32;
+        let luma = self.luma as f32 / MAX_PERCENT as f32;
+
+        let chroma = (1.0 - abs(2.0 * luma - 1.0)) * saturation;
+        let x = chroma * (1.0 - abs(hue % 2.0 - 1.0));
+        let (r, g, b) = match hue as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = luma - chroma / 2.0;
+
+        [r, g, b].map(|c| ((c + m) * 255.0 + 0.5).clamp(0.0, 255.0) as u8)
+    }
+
+    pub fn parse(data: &mut Bytes) -> Self {
+        let hue = data.get_u16();
+        let saturation = data.get_u16();
+        let luma = data.get_u16();
+
+        HslColor::new(hue, saturation, luma)
+    }
+
+    /// Hue in 1/10 degree
+    pub fn hue(&self) -> u16 {
+        self.hue
+    }
+
+    /// Saturation in 1/10 percent
+    pub fn saturation(&self) -> u16 {
+        self.saturation
+    }
+
+    /// Luma in 1/10 percent
+    pub fn luma(&self) -> u16 {
+        self.luma
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.hue);
+        data.put_u16(self.saturation);
+        data.put_u16(self.luma);
+    }
+}
+
+impl Display for HslColor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Hue: {:.1} Saturation: {:.1}% Luma: {:.1}%",
+            self.hue as f32 / 10.0,
+            self.saturation as f32 / 10.0,
+            self.luma as f32 / 10.0
+        )
+    }
+}
+
+/// Round a non-negative value to 1/10 units
+fn to_tenths(value: f32) -> u16 {
+    (value * 10.0 + 0.5) as u16
+}
+
+fn abs(value: f32) -> f32 {
+    if value < 0.0 {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Color of a color generator (`ColV`)
+#[derive(Debug, Clone)]
+pub struct ColorGenerator {
+    index: u8,
+    color: HslColor,
+}
+
+impl ColorGenerator {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let index = data.get_u8();
+        data.get_u8(); // Padding
+        let color = HslColor::parse(data);
+
+        ColorGenerator { index, color }
+    }
+
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    pub fn color(&self) -> HslColor {
+        self.color
+    }
+}
+
+impl Display for ColorGenerator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Index: {} {}", self.index, self.color)
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct ColorGeneratorMask: u8 {
+        const HUE = 0x01;
+        const SATURATION = 0x02;
+        const LUMA = 0x04;
+    }
+}
+
+/// Change the color of a color generator (`CClV`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetColorGenerator {
+    mask: ColorGeneratorMask,
+    index: u8,
+    color: HslColor,
+}
+
+impl SetColorGenerator {
+    pub fn new(index: u8) -> Self {
+        SetColorGenerator {
+            mask: ColorGeneratorMask::empty(),
+            index,
+            color: HslColor::default(),
+        }
+    }
+
+    pub fn color(mut self, color: HslColor) -> Self {
+        self.mask = ColorGeneratorMask::all();
+        self.color = color;
+        self
+    }
+
+    /// Hue in 1/10 degree, wraps around at 360 degrees
+    pub fn hue(mut self, hue: u16) -> Self {
+        self.mask |= ColorGeneratorMask::HUE;
+        self.color = HslColor::new(hue, self.color.saturation, self.color.luma);
+        self
+    }
+
+    /// Saturation in 1/10 percent, clamped to 100 percent
+    pub fn saturation(mut self, saturation: u16) -> Self {
+        self.mask |= ColorGeneratorMask::SATURATION;
+        self.color = HslColor::new(self.color.hue, saturation, self.color.luma);
+        self
+    }
+
+    /// Luma in 1/10 percent, clamped to 100 percent
+    pub fn luma(mut self, luma: u16) -> Self {
+        self.mask |= ColorGeneratorMask::LUMA;
+        self.color = HslColor::new(self.color.hue, self.color.saturation, luma);
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(self.index);
+        self.color.serialize(data);
+    }
+}
+
+impl Display for SetColorGenerator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Index: {} {}", self.index, self.color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hsl_color_rgb_conversion() {
+        assert_eq!(HslColor::from_rgb(255, 0, 0), HslColor::new(0, 1000, 500));
+        assert_eq!(
+            HslColor::from_rgb(0, 0, 255),
+            HslColor::new(2400, 1000, 500)
+        );
+        assert_eq!(HslColor::from_rgb(128, 128, 128), HslColor::new(0, 0, 502));
+        assert_eq!(HslColor::new(1200, 1000, 250).to_rgb(), [0, 128, 0]);
+
+        for rgb in [[255, 128, 0], [12, 200, 99], [255, 255, 255], [0, 0, 0]] {
+            assert_eq!(HslColor::from_rgb(rgb[0], rgb[1], rgb[2]).to_rgb(), rgb);
+        }
+    }
+
+    #[test]
+    fn hsl_color_clamped() {
+        let color = HslColor::new(3700, 1200, 1000);
+
+        assert_eq!(color.hue(), 100);
+        assert_eq!(color.saturation(), 1000);
+        assert_eq!(color.luma(), 1000);
+    }
+
+    #[test]
+    fn color_generator_ok() {
+        let mut data = Bytes::from_static(&[0x01, 0x00, 0x04, 0xb0, 0x03, 0xe8, 0x01, 0xf4]);
+        let generator = ColorGenerator::parse(&mut data);
+
+        assert_eq!(generator.index(), 1);
+        assert_eq!(generator.color(), HslColor::new(1200, 1000, 500));
+
+        let mut data = BytesMut::new();
+        SetColorGenerator::new(1)
+            .color(generator.color())
+            .serialize(&mut data);
+        assert_eq!(
+            data.as_ref(),
+            [0x07, 0x01, 0x04, 0xb0, 0x03, 0xe8, 0x01, 0xf4]
+        );
+    }
+}
@@ -1,30 +1,255 @@
-use std::fmt::Display;
+use core::{any::Any, fmt::Display, time::Duration};
 
-use bytes::{Buf, Bytes};
+use alloc::{boxed::Box, string::String, sync::Arc, vec::Vec};
+
+use bitflags::bitflags;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 use thiserror::Error;
-use tracing::debug;
 
 use crate::{
-    multiview::{MultiViewInput, MultiViewLayout, MultiViewSafeArea, MultiViewVU},
+    color::ColorGenerator,
+    displayclock::{DisplayClockProperties, DisplayClockTime},
+    hyperdeck::{
+        HyperDeckClip, HyperDeckClipCount, HyperDeckPlayer, HyperDeckSettings, HyperDeckStorage,
+    },
+    keyer::{
+        DownstreamKeyerSources, DownstreamKeyerState, KeyerBaseProperties, KeyerKeyframe,
+        KeyerOnAir,
+    },
+    macros::{MacroProperties, MacroRecordStatus, MacroRunStatus},
+    mediaplayer::{MediaPlayerSource, MediaPlayerStatus},
+    multiview::{
+        MultiViewInput, MultiViewLayout, MultiViewSafeArea, MultiViewVU, MultiViewVideoMode,
+    },
     parser::parse_str,
     source::Source,
+    supersource::SuperSourceBox,
     systeminfo::{
-        MeConfig, MediaPlayerConfig, PowerState, TimeCodeState, Topology, Version, VideoMode,
-        VideoModeConfig,
+        AudioMixerConfig, DownConvertMode, FairlightChannelConfig, FairlightMixerConfig,
+        MacroPoolConfig, MeConfig, MediaPlayerConfig, PowerState, SuperSourceConfig, SystemInfo,
+        TimeCodeState, Topology, Version, VideoMode, VideoModeConfig,
     },
-    tally::{TallyInputs, TallySources},
+    tally::{TallyConfig, TallyInputs, TallySources},
+    trace::debug,
     transition::{
-        TransitionDVE, TransitionDip, TransitionMix, TransitionPreview, TransitionStinger,
-        TransitionStyleSelection, TransitionWipe,
+        FadeToBlackState, TransitionDVE, TransitionDip, TransitionMix, TransitionPreview,
+        TransitionStinger, TransitionStyleSelection, TransitionWipe,
     },
 };
 
+#[cfg(feature = "audio")]
+use crate::audio::{
+    AudioLevels, AudioMixerInput, AudioMixerMaster, AudioMonitor, FairlightSourceLevels,
+    MixMinusOutput, TalkbackInputProperties, TalkbackProperties,
+};
+#[cfg(feature = "camera")]
+use crate::camera::CameraControl;
+#[cfg(feature = "audio")]
+use crate::fairlight::{
+    FairlightCompressor, FairlightEqBand, FairlightExpander, FairlightInputProperties,
+    FairlightLimiter, FairlightMasterProperties, FairlightSourceProperties,
+};
+#[cfg(feature = "media-pool")]
+use crate::mediapool::{
+    MediaClip, MediaLockObtained, MediaLockState, MediaStill, TransferComplete, TransferContinue,
+    TransferData, TransferError,
+};
+#[cfg(feature = "recording")]
+use crate::recording::{RecordingDisk, RecordingSettings, RecordingStatus, RecordingTimeCode};
+#[cfg(feature = "streaming")]
+use crate::streaming::{
+    StreamingAudioBitrates, StreamingAuthentication, StreamingLowLatency, StreamingService,
+    StreamingStats, StreamingStatus, StreamingTimeCode,
+};
+
+/// Number of payload bytes included in the hex dump of [`ParseDiagnostics`]
+const MAX_DUMP_SIZE: usize = 64;
+
 #[derive(Debug, Error)]
-pub enum Error {
+pub enum ParseError {
     #[error("String parsing failed")]
-    Utf8Error(#[from] std::string::FromUtf8Error),
+    Utf8Error(#[from] alloc::string::FromUtf8Error),
     #[error("Unknown command ({0})")]
     UnknownCommand(String),
+    #[error("Command size doesn't fit in the packet")]
+    InvalidSize,
+    /// Reported by custom parsers, see [`CustomParsers`]
+    #[error("Invalid data: {0}")]
+    InvalidData(String),
+    #[error("{source} ({diagnostics})")]
+    Command {
+        diagnostics: ParseDiagnostics,
+        #[source]
+        source: Box<ParseError>,
+    },
+}
+
+impl ParseError {
+    /// Where in the packet parsing failed, if known
+    pub fn diagnostics(&self) -> Option<&ParseDiagnostics> {
+        match self {
+            ParseError::Command { diagnostics, .. } => Some(diagnostics),
+            _ => None,
+        }
+    }
+}
+
+/// Details of a command that failed to parse, for reporting protocol issues
+#[derive(Debug, Clone)]
+pub struct ParseDiagnostics {
+    name: String,
+    size: u16,
+    offset: usize,
+    data: Bytes,
+}
+
+impl ParseDiagnostics {
+    /// Four character name of the command
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Size of the command as declared in its header, including the header
+    pub fn size(&self) -> u16 {
+        self.size
+    }
+
+    /// Number of payload bytes consumed when parsing failed
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Payload of the command, without the header
+    pub fn data(&self) -> &Bytes {
+        &self.data
+    }
+}
+
+impl Display for ParseDiagnostics {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Command: {} Size: {} Offset: {} Data: [",
+            self.name, self.size, self.offset
+        )?;
+        for (i, byte) in self.data.iter().take(MAX_DUMP_SIZE).enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{byte:02X}")?;
+        }
+        if self.data.len() > MAX_DUMP_SIZE {
+            write!(f, " ... {} more bytes", self.data.len() - MAX_DUMP_SIZE)?;
+        }
+        write!(f, "]")
+    }
+}
+
+/// Bytes of a command payload that parsing skipped because their meaning is unknown, with
+/// their offset in the payload. They are only kept with the `research` feature, so unknown
+/// fields can be correlated with the behavior of the switcher, otherwise this is always empty.
+#[derive(Debug, Default, Clone)]
+pub struct UnknownBytes {
+    #[cfg(feature = "research")]
+    size: usize,
+    #[cfg(feature = "research")]
+    ranges: Vec<(usize, Bytes)>,
+}
+
+impl UnknownBytes {
+    /// Start tracking skipped bytes of a payload before any of it is parsed
+    #[cfg_attr(not(feature = "research"), allow(unused_variables))]
+    pub(crate) fn new(data: &Bytes) -> Self {
+        UnknownBytes {
+            #[cfg(feature = "research")]
+            size: data.len(),
+            #[cfg(feature = "research")]
+            ranges: Vec::new(),
+        }
+    }
+
+    pub(crate) fn skip(&mut self, data: &mut Bytes, len: usize) {
+        #[cfg(feature = "research")]
+        {
+            let offset = self.size - data.len();
+            self.ranges.push((offset, data.split_to(len)));
+        }
+        #[cfg(not(feature = "research"))]
+        data.advance(len);
+    }
+
+    /// Skip whatever is left after the known fields, if anything
+    pub(crate) fn skip_rest(&mut self, data: &mut Bytes) {
+        if data.has_remaining() {
+            self.skip(data, data.len());
+        }
+    }
+
+    /// Offset in the payload and the bytes of each skipped range
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &Bytes)> {
+        #[cfg(feature = "research")]
+        let ranges = self.ranges.iter().map(|(offset, bytes)| (*offset, bytes));
+        #[cfg(not(feature = "research"))]
+        let ranges = core::iter::empty();
+
+        ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+}
+
+impl Display for UnknownBytes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (i, (offset, bytes)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{offset}: [")?;
+            for (j, byte) in bytes.iter().enumerate() {
+                if j > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{byte:02X}")?;
+            }
+            write!(f, "]")?;
+        }
+
+        Ok(())
+    }
+}
+
+bitflags! {
+    /// Groups of related commands received from the switcher, see [`Command::category`]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct CommandCategories: u32 {
+        /// Product, version, topology, configuration and video mode
+        const SYSTEM = 0x0001;
+        /// Input properties
+        const SOURCES = 0x0002;
+        /// Program and preview inputs and transition settings
+        const MIX_EFFECT = 0x0004;
+        /// Transition position, sent every frame while a transition runs
+        const TRANSITION_POSITION = 0x0008;
+        /// Time code, sent every frame
+        const TIME = 0x0010;
+        const TALLY = 0x0020;
+        const AUX = 0x0040;
+        const MULTIVIEW = 0x0080;
+        const CAMERA = 0x0100;
+        const STREAMING = 0x0200;
+        const RECORDING = 0x0400;
+        const HYPERDECK = 0x0800;
+        /// Mix minus outputs and talkback
+        const AUDIO = 0x1000;
+        /// Media players, the media pool and data transfers
+        const MEDIA = 0x2000;
+        const MACROS = 0x4000;
+        const DISPLAY_CLOCK = 0x8000;
+        /// Commands parsed by custom parsers
+        const CUSTOM = 0x10000;
+    }
 }
 
 #[allow(dead_code)]
@@ -33,6 +258,7 @@ pub enum Command {
     Product(String),
     Topology(Topology),
     Source(Source),
+    ColorGenerator(ColorGenerator),
     ProgramInput(SourceSelection),
     PreviewInput(SourceSelection),
     TransitionPosition(TransitionPosition),
@@ -41,7 +267,7 @@ pub enum Command {
     TallySources(TallySources),
     PowerState(PowerState),
     TransitionStyleSelection(TransitionStyleSelection),
-    AuxSource(SourceSelection),
+    AuxSource(AuxSource),
     MultiViewInput(MultiViewInput),
     TimeCodeState(TimeCodeState),
     VideoMode(VideoMode),
@@ -57,152 +283,756 @@ pub enum Command {
     TransitionWipe(TransitionWipe),
     TransitionDVE(TransitionDVE),
     TransitionStinger(TransitionStinger),
+    KeyerBaseProperties(KeyerBaseProperties),
+    KeyerOnAir(KeyerOnAir),
+    KeyerKeyframe(KeyerKeyframe),
+    DownstreamKeyerSources(DownstreamKeyerSources),
+    DownstreamKeyerState(DownstreamKeyerState),
+    FadeToBlackState(FadeToBlackState),
+    SuperSourceBox(SuperSourceBox),
+    #[cfg(feature = "camera")]
+    CameraControl(CameraControl),
+    #[cfg(feature = "streaming")]
+    StreamingStatus(StreamingStatus),
+    #[cfg(feature = "streaming")]
+    StreamingService(StreamingService),
+    #[cfg(feature = "streaming")]
+    StreamingTimeCode(StreamingTimeCode),
+    #[cfg(feature = "streaming")]
+    StreamingStats(StreamingStats),
+    #[cfg(feature = "recording")]
+    RecordingStatus(RecordingStatus),
+    #[cfg(feature = "recording")]
+    RecordingTimeCode(RecordingTimeCode),
+    #[cfg(feature = "recording")]
+    RecordingDisk(RecordingDisk),
+    #[cfg(feature = "recording")]
+    RecordingSettings(RecordingSettings),
+    HyperDeckSettings(HyperDeckSettings),
+    HyperDeckStorage(HyperDeckStorage),
+    HyperDeckPlayer(HyperDeckPlayer),
+    HyperDeckClipCount(HyperDeckClipCount),
+    HyperDeckClip(HyperDeckClip),
+    TallyConfig(TallyConfig),
+    SuperSourceConfig(SuperSourceConfig),
+    AudioMixerConfig(AudioMixerConfig),
+    FairlightMixerConfig(FairlightMixerConfig),
+    FairlightChannelConfig(FairlightChannelConfig),
+    MacroPoolConfig(MacroPoolConfig),
+    Warning(Warning),
+    #[cfg(feature = "audio")]
+    MixMinusOutput(MixMinusOutput),
+    #[cfg(feature = "audio")]
+    AudioLevels(AudioLevels),
+    #[cfg(feature = "audio")]
+    AudioMonitor(AudioMonitor),
+    #[cfg(feature = "audio")]
+    AudioMixerInput(AudioMixerInput),
+    #[cfg(feature = "audio")]
+    AudioMixerMaster(AudioMixerMaster),
+    #[cfg(feature = "audio")]
+    FairlightSourceLevels(FairlightSourceLevels),
+    #[cfg(feature = "audio")]
+    FairlightInputProperties(FairlightInputProperties),
+    #[cfg(feature = "audio")]
+    FairlightSourceProperties(FairlightSourceProperties),
+    #[cfg(feature = "audio")]
+    FairlightMasterProperties(FairlightMasterProperties),
+    #[cfg(feature = "audio")]
+    FairlightCompressor(FairlightCompressor),
+    #[cfg(feature = "audio")]
+    FairlightLimiter(FairlightLimiter),
+    #[cfg(feature = "audio")]
+    FairlightExpander(FairlightExpander),
+    #[cfg(feature = "audio")]
+    FairlightEqBand(FairlightEqBand),
+    #[cfg(feature = "audio")]
+    TalkbackProperties(TalkbackProperties),
+    #[cfg(feature = "audio")]
+    TalkbackInputProperties(TalkbackInputProperties),
+    DownConvertMode(DownConvertMode),
+    MultiViewVideoMode(MultiViewVideoMode),
+    #[cfg(feature = "media-pool")]
+    MediaLockState(MediaLockState),
+    #[cfg(feature = "media-pool")]
+    MediaLockObtained(MediaLockObtained),
+    #[cfg(feature = "media-pool")]
+    TransferData(TransferData),
+    #[cfg(feature = "media-pool")]
+    TransferComplete(TransferComplete),
+    #[cfg(feature = "media-pool")]
+    TransferError(TransferError),
+    #[cfg(feature = "media-pool")]
+    TransferContinue(TransferContinue),
+    #[cfg(feature = "media-pool")]
+    MediaStill(MediaStill),
+    #[cfg(feature = "media-pool")]
+    MediaClip(MediaClip),
+    DisplayClockProperties(DisplayClockProperties),
+    DisplayClockTime(DisplayClockTime),
+    #[cfg(feature = "streaming")]
+    StreamingAudioBitrates(StreamingAudioBitrates),
+    #[cfg(feature = "streaming")]
+    StreamingAuthentication(StreamingAuthentication),
+    #[cfg(feature = "streaming")]
+    StreamingLowLatency(StreamingLowLatency),
+    MediaPlayerSource(MediaPlayerSource),
+    MediaPlayerStatus(MediaPlayerStatus),
+    MacroProperties(MacroProperties),
+    MacroRunStatus(MacroRunStatus),
+    MacroRecordStatus(MacroRecordStatus),
+    Custom(CustomCommand),
 }
 
 impl Command {
-    pub fn parse(payload: &mut Bytes) -> Result<Command, Error> {
-        let size = payload.get_u16();
-        payload.get_u16(); // skip two bytes, unknow function.
-        let cmd = payload.split_to(4);
-        let data_size = size as usize - 8;
-        let mut data = payload.split_to(data_size);
-        debug!("Command {:?} Size: {}", cmd, size);
-
-        match &cmd[..] {
-            b"_ver" => {
-                let version = Version::parse(&mut data);
-                Ok(Command::Version(version))
-            }
-            b"_pin" => {
-                let product = parse_str(&mut data)?.unwrap();
-                Ok(Command::Product(product))
-            }
-            b"_top" => {
-                let topology = Topology::parse(&mut data);
-                Ok(Command::Topology(topology))
-            }
-            b"InPr" => {
-                let source = Source::parse(&mut data)?;
-                Ok(Command::Source(source))
-            }
-            b"PrgI" => {
-                let source_selection = SourceSelection::parse(&mut data);
-                Ok(Command::ProgramInput(source_selection))
-            }
-            b"PrvI" => {
-                let source_selection = SourceSelection::parse(&mut data);
-                Ok(Command::PreviewInput(source_selection))
-            }
-            b"TrPs" => {
-                let transition_position = TransitionPosition::parse(&mut data);
-                Ok(Command::TransitionPosition(transition_position))
-            }
-            b"Time" => {
-                let time = Time::parse(&mut data);
-                Ok(Command::Time(time))
-            }
-            b"TlIn" => {
-                let tally_inputs = TallyInputs::parse(&mut data);
-                Ok(Command::TallyInputs(tally_inputs))
-            }
-            b"TlSr" => {
-                let tally_sources = TallySources::parse(&mut data);
-                Ok(Command::TallySources(tally_sources))
-            }
-            b"Powr" => {
-                let power_state = PowerState::parse(&mut data);
-                Ok(Command::PowerState(power_state))
-            }
-            b"TrSS" => {
-                let transition_style_selection = TransitionStyleSelection::parse(&mut data);
-                Ok(Command::TransitionStyleSelection(
-                    transition_style_selection,
-                ))
-            }
-            b"AuxS" => {
-                let source_selection = SourceSelection::parse(&mut data);
-                Ok(Command::AuxSource(source_selection))
-            }
-            b"MvIn" => {
-                let multiview_input = MultiViewInput::parse(&mut data);
-                Ok(Command::MultiViewInput(multiview_input))
-            }
-            b"TCCc" => {
-                let timecode_state = TimeCodeState::parse(&mut data);
-                Ok(Command::TimeCodeState(timecode_state))
-            }
-            b"VidM" => {
-                let videomode = VideoMode::parse(&mut data);
-                Ok(Command::VideoMode(videomode))
-            }
-            b"_MeC" => {
-                let me_config = MeConfig::parse(&mut data);
-                Ok(Command::MeConfig(me_config))
-            }
-            b"_mpl" => {
-                let media_player_config = MediaPlayerConfig::parse(&mut data);
-                Ok(Command::MediaPlayerConfig(media_player_config))
-            }
-            b"_VMC" => {
-                let videomode_config = VideoModeConfig::parse(&mut data);
-                Ok(Command::VideoModeConfig(videomode_config))
-            }
-            b"VuMC" => {
-                let multiview_vu = MultiViewVU::parse(&mut data);
-                Ok(Command::MultiViewVU(multiview_vu))
-            }
-            b"SaMw" => {
-                let multiview_safe_area = MultiViewSafeArea::parse(&mut data);
-                Ok(Command::MultiViewSafeArea(multiview_safe_area))
-            }
-            b"MvPr" => {
-                let multiview_layout = MultiViewLayout::parse(&mut data);
-                Ok(Command::MultiViewLayout(multiview_layout))
-            }
-            b"TrPr" => {
-                let transition_preview = TransitionPreview::parse(&mut data);
-                Ok(Command::TransitionPreview(transition_preview))
-            }
-            b"TMxP" => {
-                let transition_mix = TransitionMix::parse(&mut data);
-                Ok(Command::TransitionMix(transition_mix))
-            }
-            b"TDpP" => {
-                let transition_dip = TransitionDip::parse(&mut data);
-                Ok(Command::TransitionDip(transition_dip))
-            }
-            b"TWpP" => {
-                let transition_wipe = TransitionWipe::parse(&mut data);
-                Ok(Command::TransitionWipe(transition_wipe))
+    /// Id of the data transfer the command belongs to
+    pub fn transfer_id(&self) -> Option<u16> {
+        match self {
+            #[cfg(feature = "media-pool")]
+            Command::TransferData(data) => Some(data.transfer_id()),
+            #[cfg(feature = "media-pool")]
+            Command::TransferComplete(complete) => Some(complete.transfer_id()),
+            #[cfg(feature = "media-pool")]
+            Command::TransferError(error) => Some(error.transfer_id()),
+            #[cfg(feature = "media-pool")]
+            Command::TransferContinue(next) => Some(next.transfer_id()),
+            _ => None,
+        }
+    }
+
+    /// Category the command belongs to, for filtering commands
+    pub fn category(&self) -> CommandCategories {
+        match self {
+            Command::Version(_)
+            | Command::Product(_)
+            | Command::Topology(_)
+            | Command::PowerState(_)
+            | Command::VideoMode(_)
+            | Command::MeConfig(_)
+            | Command::MediaPlayerConfig(_)
+            | Command::VideoModeConfig(_)
+            | Command::SuperSourceConfig(_)
+            | Command::AudioMixerConfig(_)
+            | Command::FairlightMixerConfig(_)
+            | Command::FairlightChannelConfig(_)
+            | Command::MacroPoolConfig(_)
+            | Command::DownConvertMode(_)
+            | Command::Warning(_) => CommandCategories::SYSTEM,
+            Command::Source(_) | Command::ColorGenerator(_) => CommandCategories::SOURCES,
+            Command::ProgramInput(_)
+            | Command::PreviewInput(_)
+            | Command::TransitionStyleSelection(_)
+            | Command::TransitionPreview(_)
+            | Command::TransitionMix(_)
+            | Command::TransitionDip(_)
+            | Command::TransitionWipe(_)
+            | Command::TransitionDVE(_)
+            | Command::TransitionStinger(_)
+            | Command::KeyerBaseProperties(_)
+            | Command::KeyerOnAir(_)
+            | Command::KeyerKeyframe(_)
+            | Command::DownstreamKeyerSources(_)
+            | Command::DownstreamKeyerState(_)
+            | Command::FadeToBlackState(_)
+            | Command::SuperSourceBox(_) => CommandCategories::MIX_EFFECT,
+            Command::TransitionPosition(_) => CommandCategories::TRANSITION_POSITION,
+            Command::Time(_) | Command::TimeCodeState(_) => CommandCategories::TIME,
+            Command::TallyInputs(_) | Command::TallySources(_) | Command::TallyConfig(_) => {
+                CommandCategories::TALLY
             }
-            b"TDvP" => {
-                let transtion_dve = TransitionDVE::parse(&mut data);
-                Ok(Command::TransitionDVE(transtion_dve))
+            Command::AuxSource(_) => CommandCategories::AUX,
+            Command::MultiViewInput(_)
+            | Command::MultiViewVU(_)
+            | Command::MultiViewSafeArea(_)
+            | Command::MultiViewLayout(_)
+            | Command::MultiViewVideoMode(_) => CommandCategories::MULTIVIEW,
+            #[cfg(feature = "camera")]
+            Command::CameraControl(_) => CommandCategories::CAMERA,
+            #[cfg(feature = "streaming")]
+            Command::StreamingStatus(_)
+            | Command::StreamingService(_)
+            | Command::StreamingTimeCode(_)
+            | Command::StreamingStats(_)
+            | Command::StreamingAudioBitrates(_)
+            | Command::StreamingAuthentication(_)
+            | Command::StreamingLowLatency(_) => CommandCategories::STREAMING,
+            #[cfg(feature = "recording")]
+            Command::RecordingStatus(_)
+            | Command::RecordingTimeCode(_)
+            | Command::RecordingDisk(_)
+            | Command::RecordingSettings(_) => CommandCategories::RECORDING,
+            Command::HyperDeckSettings(_)
+            | Command::HyperDeckStorage(_)
+            | Command::HyperDeckPlayer(_)
+            | Command::HyperDeckClipCount(_)
+            | Command::HyperDeckClip(_) => CommandCategories::HYPERDECK,
+            #[cfg(feature = "audio")]
+            Command::MixMinusOutput(_)
+            | Command::AudioLevels(_)
+            | Command::AudioMonitor(_)
+            | Command::AudioMixerInput(_)
+            | Command::AudioMixerMaster(_)
+            | Command::FairlightSourceLevels(_)
+            | Command::FairlightInputProperties(_)
+            | Command::FairlightSourceProperties(_)
+            | Command::FairlightMasterProperties(_)
+            | Command::FairlightCompressor(_)
+            | Command::FairlightLimiter(_)
+            | Command::FairlightExpander(_)
+            | Command::FairlightEqBand(_)
+            | Command::TalkbackProperties(_)
+            | Command::TalkbackInputProperties(_) => CommandCategories::AUDIO,
+            #[cfg(feature = "media-pool")]
+            Command::MediaLockState(_)
+            | Command::MediaLockObtained(_)
+            | Command::TransferData(_)
+            | Command::TransferComplete(_)
+            | Command::TransferError(_)
+            | Command::TransferContinue(_)
+            | Command::MediaStill(_)
+            | Command::MediaClip(_) => CommandCategories::MEDIA,
+            Command::MediaPlayerSource(_) | Command::MediaPlayerStatus(_) => {
+                CommandCategories::MEDIA
             }
-            b"TStP" => {
-                let transition_stinger = TransitionStinger::parse(&mut data);
-                Ok(Command::TransitionStinger(transition_stinger))
+            Command::MacroProperties(_)
+            | Command::MacroRunStatus(_)
+            | Command::MacroRecordStatus(_) => CommandCategories::MACROS,
+            Command::DisplayClockProperties(_) | Command::DisplayClockTime(_) => {
+                CommandCategories::DISPLAY_CLOCK
             }
-            _ => {
-                debug!(
-                    "Unknown command: {} Data: {:02X?} [{}]",
-                    String::from_utf8(cmd.to_vec())?,
-                    &data[..],
-                    data_size
-                );
-                Err(Error::UnknownCommand(String::from_utf8(cmd.to_vec())?))
+            Command::Custom(_) => CommandCategories::CUSTOM,
+        }
+    }
+
+    pub fn parse(payload: &mut Bytes) -> Result<Command, ParseError> {
+        Self::parse_with(payload, &CustomParsers::default())
+    }
+
+    /// Parse a command, falling back to the custom parsers for commands not handled by this
+    /// crate
+    pub fn parse_with(
+        payload: &mut Bytes,
+        custom_parsers: &CustomParsers,
+    ) -> Result<Command, ParseError> {
+        if payload.len() < 8 {
+            let data = payload.split_off(0);
+            return Err(ParseError::Command {
+                diagnostics: ParseDiagnostics {
+                    name: String::new(),
+                    size: 0,
+                    offset: 0,
+                    data,
+                },
+                source: Box::new(ParseError::InvalidSize),
+            });
+        }
+
+        let size = payload.get_u16();
+        payload.get_u16(); // skip two bytes, unknow function.
+        let cmd = payload.split_to(4);
+        let name = String::from_utf8_lossy(&cmd).into_owned();
+        debug!(command = name, size = size; "Parse command");
+
+        let data_size = (size as usize).wrapping_sub(8);
+        if size < 8 || data_size > payload.len() {
+            // The rest of the packet can't be trusted, drop it
+            let data = payload.split_off(0);
+            return Err(ParseError::Command {
+                diagnostics: ParseDiagnostics {
+                    name,
+                    size,
+                    offset: 0,
+                    data,
+                },
+                source: Box::new(ParseError::InvalidSize),
+            });
+        }
+
+        let data = payload.split_to(data_size);
+        let mut remaining = data.clone();
+        Self::parse_data(&cmd, &mut remaining, custom_parsers).map_err(|e| ParseError::Command {
+            diagnostics: ParseDiagnostics {
+                name,
+                size,
+                offset: data_size - remaining.len(),
+                data,
+            },
+            source: Box::new(e),
+        })
+    }
+
+    fn parse_data(
+        cmd: &[u8],
+        data: &mut Bytes,
+        custom_parsers: &CustomParsers,
+    ) -> Result<Command, ParseError> {
+        if let Ok(index) = PARSERS.binary_search_by_key(&cmd, |(name, _, _)| &name[..]) {
+            let (_, min_size, parse) = PARSERS[index];
+            if data.len() < min_size {
+                return Err(ParseError::InvalidSize);
             }
+            return parse(data);
+        }
+
+        match custom_parsers.get(cmd) {
+            Some((name, parse)) => Ok(Command::Custom(CustomCommand {
+                name: *name,
+                payload: parse(data)?,
+            })),
+            None => Err(ParseError::UnknownCommand(String::from_utf8(cmd.to_vec())?)),
         }
     }
 }
 
+type CustomParseFn =
+    Arc<dyn Fn(&mut Bytes) -> Result<Box<dyn Any + Send>, ParseError> + Send + Sync>;
+
+/// User supplied parsers for commands not handled by this crate, e.g. undocumented commands of
+/// new firmware
+#[derive(Clone, Default)]
+pub struct CustomParsers {
+    parsers: Vec<([u8; 4], CustomParseFn)>,
+}
+
+impl CustomParsers {
+    /// Register a parser for the command with the four character name. The parsed value is
+    /// delivered as [`Command::Custom`]. Commands already handled by this crate can't be
+    /// replaced, a parser registered again for the same name replaces the previous one.
+    pub fn register<T, F>(&mut self, name: &[u8; 4], parse: F)
+    where
+        T: Any + Send,
+        F: Fn(&mut Bytes) -> Result<T, ParseError> + Send + Sync + 'static,
+    {
+        let parse: CustomParseFn = Arc::new(move |data: &mut Bytes| {
+            parse(data).map(|payload| Box::new(payload) as Box<dyn Any + Send>)
+        });
+
+        match self.parsers.iter_mut().find(|(n, _)| n == name) {
+            Some(entry) => entry.1 = parse,
+            None => self.parsers.push((*name, parse)),
+        }
+    }
+
+    fn get(&self, name: &[u8]) -> Option<&([u8; 4], CustomParseFn)> {
+        self.parsers.iter().find(|(n, _)| &n[..] == name)
+    }
+}
+
+impl core::fmt::Debug for CustomParsers {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_list()
+            .entries(
+                self.parsers
+                    .iter()
+                    .map(|(name, _)| String::from_utf8_lossy(name)),
+            )
+            .finish()
+    }
+}
+
+/// Command parsed by a parser registered in [`CustomParsers`]
+pub struct CustomCommand {
+    name: [u8; 4],
+    payload: Box<dyn Any + Send>,
+}
+
+impl CustomCommand {
+    pub fn name(&self) -> String {
+        String::from_utf8_lossy(&self.name).into_owned()
+    }
+
+    /// Value returned by the custom parser, None if it is of another type
+    pub fn payload<T: Any>(&self) -> Option<&T> {
+        self.payload.downcast_ref()
+    }
+}
+
+impl Display for CustomCommand {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Name: {}", self.name())
+    }
+}
+
+/// Parse the payload of a command, without the header
+type ParseFn = fn(&mut Bytes) -> Result<Command, ParseError>;
+
+/// Parsers for the supported commands, sorted by name, with the smallest payload each accepts.
+/// Shorter payloads are rejected before parsing so the parsers can read fixed fields without
+/// checking.
+const PARSERS: &[(&[u8; 4], usize, ParseFn)] = &[
+    #[cfg(feature = "audio")]
+    (b"AEBP", 34, |data| {
+        Ok(Command::FairlightEqBand(FairlightEqBand::parse_source(
+            data,
+        )))
+    }),
+    #[cfg(feature = "audio")]
+    (b"AICP", 40, |data| {
+        Ok(Command::FairlightCompressor(
+            FairlightCompressor::parse_source(data),
+        ))
+    }),
+    #[cfg(feature = "audio")]
+    (b"AILP", 36, |data| {
+        Ok(Command::FairlightLimiter(FairlightLimiter::parse_source(
+            data,
+        )))
+    }),
+    #[cfg(feature = "audio")]
+    (b"AIXP", 40, |data| {
+        Ok(Command::FairlightExpander(FairlightExpander::parse(data)))
+    }),
+    #[cfg(feature = "audio")]
+    (b"AMBP", 18, |data| {
+        Ok(Command::FairlightEqBand(FairlightEqBand::parse_master(
+            data,
+        )))
+    }),
+    #[cfg(feature = "audio")]
+    (b"AMCP", 24, |data| {
+        Ok(Command::FairlightCompressor(
+            FairlightCompressor::parse_master(data),
+        ))
+    }),
+    #[cfg(feature = "audio")]
+    (b"AMIP", 14, |data| {
+        Ok(Command::AudioMixerInput(AudioMixerInput::parse(data)))
+    }),
+    #[cfg(feature = "audio")]
+    (b"AMLP", 20, |data| {
+        Ok(Command::FairlightLimiter(FairlightLimiter::parse_master(
+            data,
+        )))
+    }),
+    #[cfg(feature = "audio")]
+    (b"AMLv", 36, |data| {
+        Ok(Command::AudioLevels(AudioLevels::parse(data)))
+    }),
+    #[cfg(feature = "audio")]
+    (b"AMMO", 5, |data| {
+        Ok(Command::AudioMixerMaster(AudioMixerMaster::parse(data)))
+    }),
+    #[cfg(feature = "audio")]
+    (b"AMmO", 9, |data| {
+        Ok(Command::AudioMonitor(AudioMonitor::parse(data)))
+    }),
+    (b"AuxS", 4, |data| {
+        Ok(Command::AuxSource(AuxSource::parse(data)))
+    }),
+    #[cfg(feature = "camera")]
+    (b"CCdP", 16, |data| {
+        Ok(Command::CameraControl(CameraControl::parse(data)?))
+    }),
+    (b"ColV", 8, |data| {
+        Ok(Command::ColorGenerator(ColorGenerator::parse(data)))
+    }),
+    (b"DCPV", 17, |data| {
+        Ok(Command::DisplayClockProperties(
+            DisplayClockProperties::parse(data),
+        ))
+    }),
+    (b"DSTV", 5, |data| {
+        Ok(Command::DisplayClockTime(DisplayClockTime::parse(data)))
+    }),
+    (b"DcOt", 1, |data| {
+        Ok(Command::DownConvertMode(DownConvertMode::parse(data)))
+    }),
+    (b"DskB", 6, |data| {
+        Ok(Command::DownstreamKeyerSources(
+            DownstreamKeyerSources::parse(data),
+        ))
+    }),
+    (b"DskS", 5, |data| {
+        Ok(Command::DownstreamKeyerState(DownstreamKeyerState::parse(
+            data,
+        )))
+    }),
+    #[cfg(feature = "audio")]
+    (b"FAIP", 13, |data| {
+        Ok(Command::FairlightInputProperties(
+            FairlightInputProperties::parse(data),
+        ))
+    }),
+    #[cfg(feature = "audio")]
+    (b"FAMP", 17, |data| {
+        Ok(Command::FairlightMasterProperties(
+            FairlightMasterProperties::parse(data),
+        ))
+    }),
+    #[cfg(feature = "audio")]
+    (b"FASP", 50, |data| {
+        Ok(Command::FairlightSourceProperties(
+            FairlightSourceProperties::parse(data),
+        ))
+    }),
+    #[cfg(feature = "audio")]
+    (b"FMLv", 46, |data| {
+        Ok(Command::FairlightSourceLevels(
+            FairlightSourceLevels::parse(data),
+        ))
+    }),
+    #[cfg(feature = "media-pool")]
+    (b"FTCD", 10, |data| {
+        Ok(Command::TransferContinue(TransferContinue::parse(data)))
+    }),
+    #[cfg(feature = "media-pool")]
+    (b"FTDC", 2, |data| {
+        Ok(Command::TransferComplete(TransferComplete::parse(data)))
+    }),
+    #[cfg(feature = "media-pool")]
+    (b"FTDE", 3, |data| {
+        Ok(Command::TransferError(TransferError::parse(data)))
+    }),
+    #[cfg(feature = "media-pool")]
+    (b"FTDa", 4, |data| {
+        Ok(Command::TransferData(TransferData::parse(data)))
+    }),
+    (b"FtbS", 4, |data| {
+        Ok(Command::FadeToBlackState(FadeToBlackState::parse(data)))
+    }),
+    (b"InPr", 36, |data| {
+        Ok(Command::Source(Source::parse(data)?))
+    }),
+    (b"KKFP", 52, |data| {
+        Ok(Command::KeyerKeyframe(KeyerKeyframe::parse(data)))
+    }),
+    (b"KeBP", 22, |data| {
+        Ok(Command::KeyerBaseProperties(KeyerBaseProperties::parse(
+            data,
+        )))
+    }),
+    (b"KeOn", 3, |data| {
+        Ok(Command::KeyerOnAir(KeyerOnAir::parse(data)))
+    }),
+    #[cfg(feature = "media-pool")]
+    (b"LKOB", 2, |data| {
+        Ok(Command::MediaLockObtained(MediaLockObtained::parse(data)))
+    }),
+    #[cfg(feature = "media-pool")]
+    (b"LKST", 3, |data| {
+        Ok(Command::MediaLockState(MediaLockState::parse(data)))
+    }),
+    #[cfg(feature = "audio")]
+    (b"MMOP", 8, |data| {
+        Ok(Command::MixMinusOutput(MixMinusOutput::parse(data)))
+    }),
+    (b"MPCE", 4, |data| {
+        Ok(Command::MediaPlayerSource(MediaPlayerSource::parse(data)))
+    }),
+    #[cfg(feature = "media-pool")]
+    (b"MPCS", 68, |data| {
+        Ok(Command::MediaClip(MediaClip::parse(data)?))
+    }),
+    #[cfg(feature = "media-pool")]
+    (b"MPfe", 24, |data| {
+        Ok(Command::MediaStill(MediaStill::parse(data)?))
+    }),
+    (b"MPrp", 8, |data| {
+        Ok(Command::MacroProperties(MacroProperties::parse(data)?))
+    }),
+    (b"MRPr", 4, |data| {
+        Ok(Command::MacroRunStatus(MacroRunStatus::parse(data)))
+    }),
+    (b"MRcS", 4, |data| {
+        Ok(Command::MacroRecordStatus(MacroRecordStatus::parse(data)))
+    }),
+    (b"MvIn", 4, |data| {
+        Ok(Command::MultiViewInput(MultiViewInput::parse(data)))
+    }),
+    (b"MvPr", 3, |data| {
+        Ok(Command::MultiViewLayout(MultiViewLayout::parse(data)))
+    }),
+    (b"MvVM", 2, |data| {
+        Ok(Command::MultiViewVideoMode(MultiViewVideoMode::parse(data)))
+    }),
+    (b"Powr", 1, |data| {
+        Ok(Command::PowerState(PowerState::parse(data)))
+    }),
+    (b"PrgI", 4, |data| {
+        Ok(Command::ProgramInput(SourceSelection::parse(data)))
+    }),
+    (b"PrvI", 4, |data| {
+        Ok(Command::PreviewInput(SourceSelection::parse(data)))
+    }),
+    (b"RCPS", 6, |data| {
+        Ok(Command::MediaPlayerStatus(MediaPlayerStatus::parse(data)))
+    }),
+    #[cfg(feature = "recording")]
+    (b"RMSu", 137, |data| {
+        Ok(Command::RecordingSettings(RecordingSettings::parse(data)?))
+    }),
+    #[cfg(feature = "recording")]
+    (b"RTMD", 74, |data| {
+        Ok(Command::RecordingDisk(RecordingDisk::parse(data)?))
+    }),
+    #[cfg(feature = "recording")]
+    (b"RTMR", 5, |data| {
+        Ok(Command::RecordingTimeCode(RecordingTimeCode::parse(data)))
+    }),
+    #[cfg(feature = "recording")]
+    (b"RTMS", 8, |data| {
+        Ok(Command::RecordingStatus(RecordingStatus::parse(data)))
+    }),
+    (b"RXCC", 4, |data| {
+        Ok(Command::HyperDeckClipCount(HyperDeckClipCount::parse(data)))
+    }),
+    (b"RXCI", 76, |data| {
+        Ok(Command::HyperDeckClip(HyperDeckClip::parse(data)?))
+    }),
+    (b"RXCP", 16, |data| {
+        Ok(Command::HyperDeckPlayer(HyperDeckPlayer::parse(data)))
+    }),
+    (b"RXMS", 14, |data| {
+        Ok(Command::HyperDeckSettings(HyperDeckSettings::parse(data)))
+    }),
+    (b"RXSS", 12, |data| {
+        Ok(Command::HyperDeckStorage(HyperDeckStorage::parse(data)))
+    }),
+    #[cfg(feature = "streaming")]
+    (b"SAth", 128, |data| {
+        Ok(Command::StreamingAuthentication(
+            StreamingAuthentication::parse(data)?,
+        ))
+    }),
+    #[cfg(feature = "streaming")]
+    (b"SLow", 1, |data| {
+        Ok(Command::StreamingLowLatency(StreamingLowLatency::parse(
+            data,
+        )))
+    }),
+    #[cfg(feature = "streaming")]
+    (b"SRSS", 6, |data| {
+        Ok(Command::StreamingStats(StreamingStats::parse(data)))
+    }),
+    #[cfg(feature = "streaming")]
+    (b"SRST", 5, |data| {
+        Ok(Command::StreamingTimeCode(StreamingTimeCode::parse(data)))
+    }),
+    #[cfg(feature = "streaming")]
+    (b"SRSU", 1096, |data| {
+        Ok(Command::StreamingService(StreamingService::parse(data)?))
+    }),
+    (b"SSBP", 24, |data| {
+        Ok(Command::SuperSourceBox(SuperSourceBox::parse(data)))
+    }),
+    #[cfg(feature = "streaming")]
+    (b"STAB", 8, |data| {
+        Ok(Command::StreamingAudioBitrates(
+            StreamingAudioBitrates::parse(data),
+        ))
+    }),
+    (b"SaMw", 3, |data| {
+        Ok(Command::MultiViewSafeArea(MultiViewSafeArea::parse(data)))
+    }),
+    #[cfg(feature = "streaming")]
+    (b"StRS", 4, |data| {
+        Ok(Command::StreamingStatus(StreamingStatus::parse(data)))
+    }),
+    (b"TCCc", 1, |data| {
+        Ok(Command::TimeCodeState(TimeCodeState::parse(data)))
+    }),
+    (b"TDpP", 4, |data| {
+        Ok(Command::TransitionDip(TransitionDip::parse(data)))
+    }),
+    (b"TDvP", 17, |data| {
+        Ok(Command::TransitionDVE(TransitionDVE::parse(data)))
+    }),
+    (b"TMxP", 2, |data| {
+        Ok(Command::TransitionMix(TransitionMix::parse(data)))
+    }),
+    (b"TStP", 18, |data| {
+        Ok(Command::TransitionStinger(TransitionStinger::parse(data)))
+    }),
+    (b"TWpP", 18, |data| {
+        Ok(Command::TransitionWipe(TransitionWipe::parse(data)))
+    }),
+    (b"Time", 4, |data| Ok(Command::Time(Time::parse(data)))),
+    #[cfg(feature = "audio")]
+    (b"TkIP", 6, |data| {
+        Ok(Command::TalkbackInputProperties(
+            TalkbackInputProperties::parse(data),
+        ))
+    }),
+    #[cfg(feature = "audio")]
+    (b"TkMP", 2, |data| {
+        Ok(Command::TalkbackProperties(TalkbackProperties::parse(data)))
+    }),
+    (b"TlIn", 2, |data| {
+        Ok(Command::TallyInputs(TallyInputs::parse(data)))
+    }),
+    (b"TlSr", 2, |data| {
+        Ok(Command::TallySources(TallySources::parse(data)))
+    }),
+    (b"TrPr", 2, |data| {
+        Ok(Command::TransitionPreview(TransitionPreview::parse(data)))
+    }),
+    (b"TrPs", 6, |data| {
+        Ok(Command::TransitionPosition(TransitionPosition::parse(data)))
+    }),
+    (b"TrSS", 5, |data| {
+        Ok(Command::TransitionStyleSelection(
+            TransitionStyleSelection::parse(data),
+        ))
+    }),
+    (b"VidM", 1, |data| {
+        Ok(Command::VideoMode(VideoMode::parse(data)))
+    }),
+    (b"VuMC", 3, |data| {
+        Ok(Command::MultiViewVU(MultiViewVU::parse(data)))
+    }),
+    (b"Warn", 44, |data| {
+        Ok(Command::Warning(Warning::parse(data)?))
+    }),
+    (b"_AMC", 3, |data| {
+        Ok(Command::AudioMixerConfig(AudioMixerConfig::parse(data)))
+    }),
+    (b"_FAC", 2, |data| {
+        Ok(Command::FairlightMixerConfig(FairlightMixerConfig::parse(
+            data,
+        )))
+    }),
+    (b"_FMC", 2, |data| {
+        Ok(Command::FairlightChannelConfig(
+            FairlightChannelConfig::parse(data),
+        ))
+    }),
+    (b"_MAC", 1, |data| {
+        Ok(Command::MacroPoolConfig(MacroPoolConfig::parse(data)))
+    }),
+    (b"_MeC", 2, |data| {
+        Ok(Command::MeConfig(MeConfig::parse(data)))
+    }),
+    (b"_SSC", 3, |data| {
+        Ok(Command::SuperSourceConfig(SuperSourceConfig::parse(data)))
+    }),
+    (b"_TlC", 5, |data| {
+        Ok(Command::TallyConfig(TallyConfig::parse(data)))
+    }),
+    (b"_VMC", 2, |data| {
+        Ok(Command::VideoModeConfig(VideoModeConfig::parse(data)))
+    }),
+    (b"_mpl", 2, |data| {
+        Ok(Command::MediaPlayerConfig(MediaPlayerConfig::parse(data)))
+    }),
+    (b"_pin", 0, |data| {
+        Ok(Command::Product(parse_str(data)?.unwrap_or_default()))
+    }),
+    (b"_top", 14, |data| {
+        Ok(Command::Topology(Topology::parse(data)))
+    }),
+    (b"_ver", 4, |data| {
+        Ok(Command::Version(Version::parse(data)))
+    }),
+];
+
 impl Display for Command {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             Command::Version(version) => write!(f, "Firmware version: {version}"),
             Command::Product(product) => write!(f, "Product: {product}"),
             Command::Topology(topology) => write!(f, "Topology: {topology}"),
             Command::Source(source) => write!(f, "{source}"),
+            Command::ColorGenerator(generator) => write!(f, "Color generator: {generator}"),
             Command::ProgramInput(selection) => write!(f, "Program input ME: {selection}"),
             Command::PreviewInput(selection) => write!(f, "Preview input ME: {selection}"),
             Command::TransitionPosition(position) => write!(f, "Transition position: {position}"),
@@ -213,7 +1043,7 @@ impl Display for Command {
             Command::TransitionStyleSelection(selection) => {
                 write!(f, "Transition style selection: {selection}")
             }
-            Command::AuxSource(selection) => write!(f, "Aux: {selection}"),
+            Command::AuxSource(aux) => write!(f, "Aux source: {aux}"),
             Command::MultiViewInput(input) => write!(f, "Multiview input: {input}"),
             Command::TimeCodeState(state) => write!(f, "Time code state: {state}"),
             Command::VideoMode(mode) => write!(f, "Video mode: {mode}"),
@@ -227,36 +1057,376 @@ impl Display for Command {
             Command::TransitionMix(mix) => write!(f, "Transition mix: {mix}"),
             Command::TransitionDip(dip) => write!(f, "Transition dip: {dip}"),
             Command::TransitionWipe(wipe) => write!(f, "Transition wipe: {wipe}"),
+            Command::KeyerBaseProperties(properties) => write!(f, "Keyer: {properties}"),
+            Command::KeyerOnAir(on_air) => write!(f, "Keyer on air: {on_air}"),
+            Command::KeyerKeyframe(keyframe) => write!(f, "Keyer keyframe: {keyframe}"),
+            Command::DownstreamKeyerSources(sources) => {
+                write!(f, "Downstream keyer sources: {sources}")
+            }
+            Command::DownstreamKeyerState(state) => write!(f, "Downstream keyer state: {state}"),
+            Command::FadeToBlackState(state) => write!(f, "Fade to black state: {state}"),
+            Command::SuperSourceBox(ssbox) => write!(f, "SuperSource box: {ssbox}"),
             Command::TransitionDVE(dve) => write!(f, "Transition DVE: {dve}"),
             Command::TransitionStinger(stinger) => write!(f, "Transition stinger: {stinger}"),
+            #[cfg(feature = "camera")]
+            Command::CameraControl(control) => write!(f, "Camera control: {control}"),
+            #[cfg(feature = "streaming")]
+            Command::StreamingStatus(status) => write!(f, "Streaming status: {status}"),
+            #[cfg(feature = "streaming")]
+            Command::StreamingService(service) => write!(f, "Streaming service: {service}"),
+            #[cfg(feature = "streaming")]
+            Command::StreamingTimeCode(timecode) => write!(f, "Streaming time code: {timecode}"),
+            #[cfg(feature = "streaming")]
+            Command::StreamingStats(stats) => write!(f, "Streaming stats: {stats}"),
+            #[cfg(feature = "recording")]
+            Command::RecordingStatus(status) => write!(f, "Recording status: {status}"),
+            #[cfg(feature = "recording")]
+            Command::RecordingTimeCode(timecode) => write!(f, "Recording time code: {timecode}"),
+            #[cfg(feature = "recording")]
+            Command::RecordingDisk(disk) => write!(f, "Recording disk: {disk}"),
+            #[cfg(feature = "recording")]
+            Command::RecordingSettings(settings) => write!(f, "Recording settings: {settings}"),
+            Command::HyperDeckSettings(settings) => write!(f, "HyperDeck settings: {settings}"),
+            Command::HyperDeckStorage(storage) => write!(f, "HyperDeck storage: {storage}"),
+            Command::HyperDeckPlayer(player) => write!(f, "HyperDeck player: {player}"),
+            Command::HyperDeckClipCount(count) => write!(f, "HyperDeck clip count: {count}"),
+            Command::HyperDeckClip(clip) => write!(f, "HyperDeck clip: {clip}"),
+            Command::TallyConfig(config) => write!(f, "Tally config: {config}"),
+            Command::SuperSourceConfig(config) => write!(f, "SuperSource config: {config}"),
+            Command::AudioMixerConfig(config) => write!(f, "Audio mixer config: {config}"),
+            Command::FairlightMixerConfig(config) => write!(f, "Fairlight mixer config: {config}"),
+            Command::FairlightChannelConfig(config) => {
+                write!(f, "Fairlight channel config: {config}")
+            }
+            Command::MacroPoolConfig(config) => write!(f, "Macro pool config: {config}"),
+            Command::Warning(warning) => write!(f, "Warning: {warning}"),
+            #[cfg(feature = "audio")]
+            Command::MixMinusOutput(output) => write!(f, "Mix minus output: {output}"),
+            #[cfg(feature = "audio")]
+            Command::AudioLevels(levels) => write!(f, "Audio levels: {levels}"),
+            #[cfg(feature = "audio")]
+            Command::AudioMonitor(monitor) => write!(f, "Audio monitor: {monitor}"),
+            #[cfg(feature = "audio")]
+            Command::AudioMixerInput(input) => write!(f, "Audio mixer input: {input}"),
+            #[cfg(feature = "audio")]
+            Command::AudioMixerMaster(master) => write!(f, "Audio mixer master: {master}"),
+            #[cfg(feature = "audio")]
+            Command::FairlightSourceLevels(levels) => {
+                write!(f, "Fairlight source levels: {levels}")
+            }
+            #[cfg(feature = "audio")]
+            Command::FairlightInputProperties(properties) => {
+                write!(f, "Fairlight input: {properties}")
+            }
+            #[cfg(feature = "audio")]
+            Command::FairlightSourceProperties(properties) => {
+                write!(f, "Fairlight source: {properties}")
+            }
+            #[cfg(feature = "audio")]
+            Command::FairlightMasterProperties(properties) => {
+                write!(f, "Fairlight master: {properties}")
+            }
+            #[cfg(feature = "audio")]
+            Command::FairlightCompressor(compressor) => {
+                write!(f, "Fairlight compressor: {compressor}")
+            }
+            #[cfg(feature = "audio")]
+            Command::FairlightLimiter(limiter) => write!(f, "Fairlight limiter: {limiter}"),
+            #[cfg(feature = "audio")]
+            Command::FairlightExpander(expander) => write!(f, "Fairlight expander: {expander}"),
+            #[cfg(feature = "audio")]
+            Command::FairlightEqBand(band) => write!(f, "Fairlight EQ band: {band}"),
+            #[cfg(feature = "audio")]
+            Command::TalkbackProperties(properties) => write!(f, "Talkback: {properties}"),
+            #[cfg(feature = "audio")]
+            Command::TalkbackInputProperties(properties) => {
+                write!(f, "Talkback input: {properties}")
+            }
+            Command::DownConvertMode(mode) => write!(f, "Down convert mode: {mode}"),
+            Command::MultiViewVideoMode(mode) => write!(f, "Multiview video mode: {mode}"),
+            #[cfg(feature = "media-pool")]
+            Command::MediaLockState(state) => write!(f, "Media lock state: {state}"),
+            #[cfg(feature = "media-pool")]
+            Command::MediaLockObtained(lock) => write!(f, "Media lock obtained: {lock}"),
+            #[cfg(feature = "media-pool")]
+            Command::TransferData(data) => write!(f, "Transfer data: {data}"),
+            #[cfg(feature = "media-pool")]
+            Command::TransferComplete(complete) => write!(f, "Transfer complete: {complete}"),
+            #[cfg(feature = "media-pool")]
+            Command::TransferError(error) => write!(f, "Transfer error: {error}"),
+            #[cfg(feature = "media-pool")]
+            Command::TransferContinue(next) => write!(f, "Transfer continue: {next}"),
+            #[cfg(feature = "media-pool")]
+            Command::MediaStill(still) => write!(f, "Media still: {still}"),
+            #[cfg(feature = "media-pool")]
+            Command::MediaClip(clip) => write!(f, "Media clip: {clip}"),
+            Command::DisplayClockProperties(properties) => {
+                write!(f, "Display clock: {properties}")
+            }
+            Command::DisplayClockTime(time) => write!(f, "Display clock time: {time}"),
+            #[cfg(feature = "streaming")]
+            Command::StreamingAudioBitrates(bitrates) => {
+                write!(f, "Streaming audio bitrates: {bitrates}")
+            }
+            #[cfg(feature = "streaming")]
+            Command::StreamingAuthentication(authentication) => {
+                write!(f, "Streaming authentication: {authentication}")
+            }
+            #[cfg(feature = "streaming")]
+            Command::StreamingLowLatency(low_latency) => {
+                write!(f, "Streaming low latency: {low_latency}")
+            }
+            Command::MediaPlayerSource(source) => write!(f, "Media player source: {source}"),
+            Command::MediaPlayerStatus(status) => write!(f, "Media player status: {status}"),
+            Command::MacroProperties(properties) => write!(f, "Macro: {properties}"),
+            Command::MacroRunStatus(status) => write!(f, "Macro run status: {status}"),
+            Command::MacroRecordStatus(status) => write!(f, "Macro record status: {status}"),
+            Command::Custom(command) => write!(f, "Custom command: {command}"),
+        }
+    }
+}
+
+impl Command {
+    /// Display the command with source ids resolved to the names known by the switcher state
+    pub fn display_with<'a>(&'a self, system_info: &'a SystemInfo) -> CommandDisplay<'a> {
+        CommandDisplay {
+            command: self,
+            system_info,
+        }
+    }
+}
+
+/// Human readable form of a command using names from the switcher state, see
+/// [`Command::display_with`]
+pub struct CommandDisplay<'a> {
+    command: &'a Command,
+    system_info: &'a SystemInfo,
+}
+
+impl CommandDisplay<'_> {
+    fn source_name(&self, source_id: u16) -> SourceName<'_> {
+        SourceName {
+            source_id,
+            name: self.system_info.source(source_id).and_then(Source::name),
+        }
+    }
+}
+
+impl Display for CommandDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.command {
+            Command::ProgramInput(selection) => write!(
+                f,
+                "Program ME{}: {}",
+                selection.me() as u16 + 1,
+                self.source_name(selection.source_id())
+            ),
+            Command::PreviewInput(selection) => write!(
+                f,
+                "Preview ME{}: {}",
+                selection.me() as u16 + 1,
+                self.source_name(selection.source_id())
+            ),
+            Command::AuxSource(aux) => write!(
+                f,
+                "Aux {}: {}",
+                aux.aux() as u16 + 1,
+                self.source_name(aux.source_id())
+            ),
+            Command::TallySources(tallys) => {
+                write!(f, "Tally sources:")?;
+                for (source_id, state) in tallys.iter() {
+                    if state.program() || state.preview() {
+                        write!(f, " {} ({})", self.source_name(source_id), state)?;
+                    }
+                }
+                Ok(())
+            }
+            command => write!(f, "{command}"),
+        }
+    }
+}
+
+/// Long name of a source, falling back to the id for sources not known yet
+struct SourceName<'a> {
+    source_id: u16,
+    name: Option<&'a str>,
+}
+
+impl Display for SourceName<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.name {
+            Some(name) => write!(f, "{name}"),
+            None => write!(f, "Source {}", self.source_id),
         }
     }
 }
 
+/// Program or preview source of a mix effect block (`PrgI`, `PrvI`)
 pub struct SourceSelection {
-    destination: u8,
+    me: u8,
     source_id: u16,
 }
 
 impl SourceSelection {
     pub fn parse(data: &mut Bytes) -> Self {
-        let destination = data.get_u8();
+        let me = data.get_u8();
         data.get_u8(); // Skip
         let source_id = data.get_u16();
 
-        SourceSelection {
-            destination,
-            source_id,
-        }
+        SourceSelection { me, source_id }
+    }
+
+    /// Mix effect block the source is selected for
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    pub fn source_id(&self) -> u16 {
+        self.source_id
     }
 }
 
 impl Display for SourceSelection {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} Source: {}", self.destination, self.source_id)
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ME: {} Source: {}", self.me, self.source_id)
     }
 }
 
+/// Source routed to an aux output (`AuxS`)
+pub struct AuxSource {
+    aux: u8,
+    source_id: u16,
+}
+
+impl AuxSource {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let aux = data.get_u8();
+        data.get_u8(); // Skip
+        let source_id = data.get_u16();
+
+        AuxSource { aux, source_id }
+    }
+
+    pub fn aux(&self) -> u8 {
+        self.aux
+    }
+
+    pub fn source_id(&self) -> u16 {
+        self.source_id
+    }
+}
+
+impl Display for AuxSource {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Aux: {} Source: {}", self.aux, self.source_id)
+    }
+}
+
+/// Select the program source of a mix effect block (`CPgI`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetProgramInput {
+    me: u8,
+    source_id: u16,
+}
+
+impl SetProgramInput {
+    pub fn new(me: u8, source_id: u16) -> Self {
+        SetProgramInput { me, source_id }
+    }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    pub fn source_id(&self) -> u16 {
+        self.source_id
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.me);
+        data.put_u8(0); // Padding
+        data.put_u16(self.source_id);
+    }
+}
+
+impl Display for SetProgramInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ME: {} Source: {}", self.me, self.source_id)
+    }
+}
+
+/// Select the preview source of a mix effect block (`CPvI`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetPreviewInput {
+    me: u8,
+    source_id: u16,
+}
+
+impl SetPreviewInput {
+    pub fn new(me: u8, source_id: u16) -> Self {
+        SetPreviewInput { me, source_id }
+    }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    pub fn source_id(&self) -> u16 {
+        self.source_id
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.me);
+        data.put_u8(0); // Padding
+        data.put_u16(self.source_id);
+    }
+}
+
+impl Display for SetPreviewInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ME: {} Source: {}", self.me, self.source_id)
+    }
+}
+
+/// Route a source to an aux output (`CAuS`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetAuxSource {
+    aux: u8,
+    source_id: u16,
+}
+
+impl SetAuxSource {
+    pub fn new(aux: u8, source_id: u16) -> Self {
+        SetAuxSource { aux, source_id }
+    }
+
+    pub fn aux(&self) -> u8 {
+        self.aux
+    }
+
+    pub fn source_id(&self) -> u16 {
+        self.source_id
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(0x01); // Mask, source is the only property
+        data.put_u8(self.aux);
+        data.put_u16(self.source_id);
+    }
+}
+
+impl Display for SetAuxSource {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} Source: {}", self.aux, self.source_id)
+    }
+}
+
+/// Progress of the transition of a mix effect block (`TrPs`)
+#[derive(Debug, Clone)]
 pub struct TransitionPosition {
     me: u8,
     frame_count: u8,
@@ -277,10 +1447,34 @@ impl TransitionPosition {
             position,
         }
     }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    /// Frames left of the running transition
+    pub fn frame_count(&self) -> u8 {
+        self.frame_count
+    }
+
+    /// Position from 0 to 10000, 0 when no transition is running
+    pub fn position(&self) -> u16 {
+        self.position
+    }
+
+    /// Position from 0.0 to 1.0
+    pub fn normalized(&self) -> f32 {
+        self.position as f32 / 10000.0
+    }
+
+    /// Whether a transition is running, the position is 0 when none is
+    pub fn is_running(&self) -> bool {
+        self.position > 0
+    }
 }
 
 impl Display for TransitionPosition {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "ME: {} Frame count: {} Position: {}",
@@ -289,6 +1483,44 @@ impl Display for TransitionPosition {
     }
 }
 
+/// Move the transition of a mix effect block to a position, like dragging the T-bar (`CTPs`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetTransitionPosition {
+    me: u8,
+    position: u16,
+}
+
+impl SetTransitionPosition {
+    /// Position ranges from 0 to 10000
+    pub fn new(me: u8, position: u16) -> Self {
+        SetTransitionPosition {
+            me,
+            position: position.min(10000),
+        }
+    }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.me);
+        data.put_u8(0); // Padding
+        data.put_u16(self.position);
+    }
+}
+
+impl Display for SetTransitionPosition {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ME: {} Position: {}", self.me, self.position)
+    }
+}
+
+/// Time code of the switcher, hours, minutes, seconds and frames. Conversions count every
+/// frame, drop frame time codes aren't supported.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub struct Time {
     hour: u8,
     minute: u8,
@@ -297,6 +1529,15 @@ pub struct Time {
 }
 
 impl Time {
+    pub fn new(hour: u8, minute: u8, second: u8, frame: u8) -> Self {
+        Time {
+            hour,
+            minute,
+            second,
+            frame,
+        }
+    }
+
     pub fn parse(data: &mut Bytes) -> Self {
         let hour = data.get_u8();
         let minute = data.get_u8();
@@ -309,10 +1550,92 @@ impl Time {
             frame,
         }
     }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.hour);
+        data.put_u8(self.minute);
+        data.put_u8(self.second);
+        data.put_u8(self.frame);
+    }
+
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    pub fn frame(&self) -> u8 {
+        self.frame
+    }
+
+    /// Time code of a number of frames since midnight, None if the video mode is unknown or
+    /// the frames are more than a day
+    pub fn from_frames(frames: u32, mode: VideoMode) -> Option<Self> {
+        let rate = mode.timecode_rate()?;
+        let seconds = frames / rate;
+        if seconds >= SECONDS_PER_DAY {
+            return None;
+        }
+
+        Some(Time::new(
+            (seconds / 3600) as u8,
+            (seconds / 60 % 60) as u8,
+            (seconds % 60) as u8,
+            (frames % rate) as u8,
+        ))
+    }
+
+    /// Number of frames since midnight, None if the video mode is unknown
+    pub fn to_frames(&self, mode: VideoMode) -> Option<u32> {
+        let rate = mode.timecode_rate()?;
+        let seconds = (self.hour as u32 * 60 + self.minute as u32) * 60 + self.second as u32;
+
+        Some(seconds * rate + self.frame as u32)
+    }
+
+    /// Time code of the frame shown at a time since midnight, rounded down to whole frames
+    pub fn from_duration(duration: Duration, mode: VideoMode) -> Option<Self> {
+        let (numerator, denominator) = mode.frame_rate()?;
+        let frames = duration.as_nanos() * numerator as u128 / (denominator as u128 * NANOS);
+
+        Self::from_frames(frames.try_into().ok()?, mode)
+    }
+
+    /// Time since midnight at the start of the frame, at the real frame rate of the video mode
+    pub fn to_duration(&self, mode: VideoMode) -> Option<Duration> {
+        let (numerator, denominator) = mode.frame_rate()?;
+        let frames = self.to_frames(mode)? as u128;
+        let nanos = frames * denominator as u128 * NANOS / numerator as u128;
+
+        Some(Duration::from_nanos(nanos as u64))
+    }
+
+    /// Move the time code a number of frames forward, or back if negative. None if it passes
+    /// midnight.
+    pub fn add_frames(&self, frames: i64, mode: VideoMode) -> Option<Self> {
+        let total = (self.to_frames(mode)? as i64).checked_add(frames)?;
+
+        Self::from_frames(total.try_into().ok()?, mode)
+    }
+
+    /// Time elapsed since an earlier time code, None if earlier is later than this time code
+    pub fn duration_since(&self, earlier: Time, mode: VideoMode) -> Option<Duration> {
+        self.to_duration(mode)?
+            .checked_sub(earlier.to_duration(mode)?)
+    }
 }
 
+const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+const NANOS: u128 = 1_000_000_000;
+
 impl Display for Time {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "{:02}:{:02}:{:02}:{:02}",
@@ -320,3 +1643,193 @@ impl Display for Time {
         )
     }
 }
+
+/// Human readable warning from the switcher, e.g. fan failure or over temperature
+#[derive(Debug, Clone)]
+pub struct Warning {
+    text: String,
+}
+
+impl Warning {
+    pub fn parse(data: &mut Bytes) -> Result<Self, ParseError> {
+        let text = parse_str(&mut data.split_to(44))?.unwrap_or_default();
+
+        Ok(Warning { text })
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+
+impl Display for Warning {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+/// Parse a command from its name and payload, the header is added, for tests feeding state
+/// commands to [`SystemInfo`](crate::systeminfo::SystemInfo)
+#[cfg(test)]
+pub(crate) fn test_command(name: &[u8; 4], payload: &[u8]) -> Command {
+    let mut data = BytesMut::new();
+    data.put_u16(payload.len() as u16 + 8);
+    data.put_u16(0);
+    data.put_slice(name);
+    data.put_slice(payload);
+    Command::parse(&mut data.freeze()).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::put_str;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parsers_sorted() {
+        assert!(PARSERS.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    }
+
+    #[test]
+    fn parse_unknown_command_diagnostics() {
+        let mut payload = Bytes::from_static(&[
+            0x00, 0x0c, 0x00, 0x00, b'X', b'y', b'z', b'w', 0x01, 0x02, 0x03, 0x04,
+        ]);
+
+        let error = Command::parse(&mut payload).err().unwrap();
+        let diagnostics = error.diagnostics().unwrap();
+
+        assert!(payload.is_empty());
+        assert_eq!(diagnostics.name(), "Xyzw");
+        assert_eq!(diagnostics.size(), 12);
+        assert_eq!(diagnostics.offset(), 0);
+        assert_eq!(
+            diagnostics.to_string(),
+            "Command: Xyzw Size: 12 Offset: 0 Data: [01 02 03 04]"
+        );
+    }
+
+    #[test]
+    fn parse_invalid_size() {
+        let mut payload = Bytes::from_static(&[0x00, 0x10, 0x00, 0x00, b'T', b'i', b'm', b'e']);
+
+        let error = Command::parse(&mut payload).err().unwrap();
+
+        assert!(payload.is_empty());
+        assert!(matches!(error, ParseError::Command { ref source, .. }
+            if matches!(**source, ParseError::InvalidSize)));
+    }
+
+    #[test]
+    fn parse_truncated_command() {
+        let mut payload =
+            Bytes::from_static(&[0x00, 0x0a, 0x00, 0x00, b'P', b'r', b'g', b'I', 0x00, 0x00]);
+
+        let error = Command::parse(&mut payload).err().unwrap();
+
+        assert!(payload.is_empty());
+        assert!(matches!(error, ParseError::Command { ref source, .. }
+            if matches!(**source, ParseError::InvalidSize)));
+    }
+
+    #[test]
+    fn display_with_source_names() {
+        let mut data = BytesMut::new();
+        data.put_u16(3);
+        put_str(&mut data, "CAM 3", 20);
+        put_str(&mut data, "CAM3", 4);
+        data.put_bytes(0, 12);
+        let mut system_info = SystemInfo::default();
+        system_info.set_source(Source::parse(&mut data.freeze()).unwrap());
+
+        let mut data = Bytes::from_static(&[0x00, 0x00, 0x00, 0x03]);
+        let program = Command::ProgramInput(SourceSelection::parse(&mut data));
+        let mut data = Bytes::from_static(&[0x00, 0x00, 0x00, 0x04]);
+        let preview = Command::PreviewInput(SourceSelection::parse(&mut data));
+
+        assert_eq!(
+            program.display_with(&system_info).to_string(),
+            "Program ME1: CAM 3"
+        );
+        assert_eq!(
+            preview.display_with(&system_info).to_string(),
+            "Preview ME1: Source 4"
+        );
+    }
+
+    #[test]
+    fn parse_custom_command() {
+        let mut parsers = CustomParsers::default();
+        parsers.register(b"Xyzw", |data: &mut Bytes| Ok(data.get_u32()));
+        let mut payload = Bytes::from_static(&[
+            0x00, 0x0c, 0x00, 0x00, b'X', b'y', b'z', b'w', 0x01, 0x02, 0x03, 0x04,
+        ]);
+
+        let Ok(Command::Custom(command)) = Command::parse_with(&mut payload, &parsers) else {
+            panic!("Custom command not parsed");
+        };
+
+        assert_eq!(command.name(), "Xyzw");
+        assert_eq!(command.payload::<u32>(), Some(&0x01020304));
+        assert!(command.payload::<u16>().is_none());
+    }
+
+    #[test]
+    fn time_conversions() {
+        let time = Time::new(1, 2, 3, 4);
+
+        assert_eq!(time.to_frames(VideoMode::Res1080p25), Some(93079));
+        assert_eq!(Time::from_frames(93079, VideoMode::Res1080p25), Some(time));
+        assert_eq!(
+            time.to_duration(VideoMode::Res1080p25),
+            Some(Duration::from_millis(3_723_160))
+        );
+        assert_eq!(
+            Time::from_duration(Duration::from_millis(3_723_170), VideoMode::Res1080p25),
+            Some(time)
+        );
+        assert_eq!(
+            Time::new(0, 0, 1, 0).to_duration(VideoMode::Res1080p29_97),
+            Some(Duration::from_nanos(1_001_000_000))
+        );
+        assert_eq!(
+            time.add_frames(-5, VideoMode::Res1080p25),
+            Some(Time::new(1, 2, 2, 24))
+        );
+        assert_eq!(
+            Time::new(23, 59, 59, 24).add_frames(1, VideoMode::Res1080p25),
+            None
+        );
+        assert!(Time::new(0, 59, 59, 24) < Time::new(1, 0, 0, 0));
+        assert_eq!(time.to_frames(VideoMode::Unknown(99)), None);
+    }
+
+    #[test]
+    fn command_category() {
+        let program = Command::parse(&mut Bytes::from_static(&[
+            0x00, 0x0c, 0x00, 0x00, b'P', b'r', b'g', b'I', 0x00, 0x00, 0x00, 0x01,
+        ]))
+        .unwrap();
+        let time = Command::parse(&mut Bytes::from_static(&[
+            0x00, 0x0c, 0x00, 0x00, b'T', b'i', b'm', b'e', 0x01, 0x02, 0x03, 0x04,
+        ]))
+        .unwrap();
+
+        assert_eq!(program.category(), CommandCategories::MIX_EFFECT);
+        assert_eq!(time.category(), CommandCategories::TIME);
+        assert!(!(CommandCategories::all() - CommandCategories::TIME).intersects(time.category()));
+    }
+
+    proptest! {
+        #[test]
+        fn time_round_trip(hour: u8, minute: u8, second: u8, frame: u8) {
+            let time = Time::new(hour, minute, second, frame);
+
+            let mut data = BytesMut::new();
+            time.serialize(&mut data);
+
+            prop_assert_eq!(Time::parse(&mut data.freeze()), time);
+        }
+    }
+}
@@ -0,0 +1,2088 @@
+#[cfg(feature = "client")]
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::{vec, vec::Vec};
+use core::fmt::Display;
+
+use bytes::{BufMut, Bytes, BytesMut};
+#[cfg(feature = "client")]
+use tokio::sync::oneshot;
+
+#[cfg(feature = "client")]
+use crate::Error;
+use crate::{
+    color::SetColorGenerator,
+    command::{Command, SetAuxSource, SetPreviewInput, SetProgramInput, SetTransitionPosition},
+    displayclock::{SetDisplayClockProperties, SetDisplayClockState, SetDisplayClockTime},
+    hyperdeck::{SetHyperDeckClip, SetHyperDeckPlayer},
+    keyer::{
+        SetDownstreamKeyerCutSource, SetDownstreamKeyerFillSource, SetDownstreamKeyerOnAir,
+        SetKeyerChroma, SetKeyerCutSource, SetKeyerDVE, SetKeyerFillSource, SetKeyerKeyframe,
+        SetKeyerLuma, SetKeyerMask, SetKeyerOnAir, SetKeyerPattern, SetKeyerType,
+    },
+    macros::{SetMacroAction, StartMacroRecording},
+    mediaplayer::{SetMediaPlayerSource, SetMediaPlayerStatus},
+    multiview::{SetMultiViewInput, SetMultiViewLayout, SetMultiViewSafeArea, SetMultiViewVU},
+    source::SetInputProperties,
+    supersource::SetSuperSourceBox,
+    systeminfo::{
+        ClearStartupState, RequestTime, SaveStartupState, SetDownConvertMode, SetTimeCodeMode,
+        SetVideoMode, Version,
+    },
+    transition::{
+        AutoTransition, Cut, FadeToBlack, SetTransitionDVERate, SetTransitionDip, SetTransitionMix,
+        SetTransitionStingerRate, SetTransitionStyle, SetTransitionWipeRate,
+    },
+};
+
+#[cfg(feature = "audio")]
+use crate::audio::{
+    SetAudioLevels, SetAudioMixerInput, SetAudioMixerMaster, SetAudioMonitor, SetFairlightLevels,
+    SetMixMinusOutput, SetTalkbackInputProperties, SetTalkbackProperties,
+};
+#[cfg(feature = "camera")]
+use crate::camera::SetCameraControl;
+#[cfg(feature = "audio")]
+use crate::fairlight::{
+    SetFairlightCompressor, SetFairlightEqBand, SetFairlightExpander, SetFairlightInputProperties,
+    SetFairlightLimiter, SetFairlightMasterProperties, SetFairlightSourceProperties,
+};
+#[cfg(feature = "media-pool")]
+use crate::mediapool::{
+    ClearMediaClip, ClearMediaStill, RequestDownload, RequestUpload, SetMediaClip, SetMediaLock,
+    SetMediaStillName, TransferAck, TransferData, TransferFileDescription,
+};
+#[cfg(feature = "recording")]
+use crate::recording::{SetRecording, SetRecordingSettings, SwitchRecordingDisk};
+#[cfg(feature = "streaming")]
+use crate::streaming::{
+    SetStreaming, SetStreamingAudioBitrates, SetStreamingAuthentication, SetStreamingLowLatency,
+    SetStreamingService,
+};
+
+const COMMAND_HEADER_SIZE: u16 = 0x08;
+
+/// Command sent from the client to the switcher
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ControlCommand {
+    #[cfg(feature = "camera")]
+    CameraControl(SetCameraControl),
+    #[cfg(feature = "streaming")]
+    Streaming(SetStreaming),
+    #[cfg(feature = "streaming")]
+    StreamingService(SetStreamingService),
+    #[cfg(feature = "recording")]
+    Recording(SetRecording),
+    #[cfg(feature = "recording")]
+    RecordingSettings(SetRecordingSettings),
+    #[cfg(feature = "recording")]
+    SwitchRecordingDisk(SwitchRecordingDisk),
+    HyperDeckPlayer(SetHyperDeckPlayer),
+    HyperDeckClip(SetHyperDeckClip),
+    RequestTime(RequestTime),
+    TimeCodeMode(SetTimeCodeMode),
+    #[cfg(feature = "audio")]
+    MixMinusOutput(SetMixMinusOutput),
+    #[cfg(feature = "audio")]
+    TalkbackProperties(SetTalkbackProperties),
+    #[cfg(feature = "audio")]
+    TalkbackInputProperties(SetTalkbackInputProperties),
+    #[cfg(feature = "audio")]
+    AudioLevels(SetAudioLevels),
+    #[cfg(feature = "audio")]
+    AudioMonitor(SetAudioMonitor),
+    #[cfg(feature = "audio")]
+    AudioMixerInput(SetAudioMixerInput),
+    #[cfg(feature = "audio")]
+    AudioMixerMaster(SetAudioMixerMaster),
+    #[cfg(feature = "audio")]
+    FairlightLevels(SetFairlightLevels),
+    #[cfg(feature = "audio")]
+    FairlightInputProperties(SetFairlightInputProperties),
+    #[cfg(feature = "audio")]
+    FairlightSourceProperties(SetFairlightSourceProperties),
+    #[cfg(feature = "audio")]
+    FairlightMasterProperties(SetFairlightMasterProperties),
+    #[cfg(feature = "audio")]
+    FairlightCompressor(SetFairlightCompressor),
+    #[cfg(feature = "audio")]
+    FairlightLimiter(SetFairlightLimiter),
+    #[cfg(feature = "audio")]
+    FairlightExpander(SetFairlightExpander),
+    #[cfg(feature = "audio")]
+    FairlightEqBand(SetFairlightEqBand),
+    AuxSource(SetAuxSource),
+    MultiViewInput(SetMultiViewInput),
+    MultiViewLayout(SetMultiViewLayout),
+    MultiViewVU(SetMultiViewVU),
+    MultiViewSafeArea(SetMultiViewSafeArea),
+    VideoMode(SetVideoMode),
+    DownConvertMode(SetDownConvertMode),
+    InputProperties(SetInputProperties),
+    #[cfg(feature = "media-pool")]
+    MediaLock(SetMediaLock),
+    #[cfg(feature = "media-pool")]
+    RequestDownload(RequestDownload),
+    #[cfg(feature = "media-pool")]
+    TransferAck(TransferAck),
+    #[cfg(feature = "media-pool")]
+    RequestUpload(RequestUpload),
+    #[cfg(feature = "media-pool")]
+    TransferData(TransferData),
+    #[cfg(feature = "media-pool")]
+    TransferFileDescription(TransferFileDescription),
+    #[cfg(feature = "media-pool")]
+    ClearMediaStill(ClearMediaStill),
+    #[cfg(feature = "media-pool")]
+    ClearMediaClip(ClearMediaClip),
+    #[cfg(feature = "media-pool")]
+    MediaStillName(SetMediaStillName),
+    #[cfg(feature = "media-pool")]
+    MediaClip(SetMediaClip),
+    SaveStartupState(SaveStartupState),
+    ClearStartupState(ClearStartupState),
+    DisplayClockProperties(SetDisplayClockProperties),
+    DisplayClockState(SetDisplayClockState),
+    DisplayClockTime(SetDisplayClockTime),
+    #[cfg(feature = "streaming")]
+    StreamingAudioBitrates(SetStreamingAudioBitrates),
+    #[cfg(feature = "streaming")]
+    StreamingAuthentication(SetStreamingAuthentication),
+    #[cfg(feature = "streaming")]
+    StreamingLowLatency(SetStreamingLowLatency),
+    TransitionPosition(SetTransitionPosition),
+    ColorGenerator(SetColorGenerator),
+    ProgramInput(SetProgramInput),
+    PreviewInput(SetPreviewInput),
+    TransitionStyle(SetTransitionStyle),
+    Cut(Cut),
+    AutoTransition(AutoTransition),
+    FadeToBlack(FadeToBlack),
+    KeyerOnAir(SetKeyerOnAir),
+    KeyerFillSource(SetKeyerFillSource),
+    KeyerCutSource(SetKeyerCutSource),
+    KeyerType(SetKeyerType),
+    KeyerMask(SetKeyerMask),
+    KeyerLuma(SetKeyerLuma),
+    KeyerChroma(SetKeyerChroma),
+    KeyerPattern(SetKeyerPattern),
+    KeyerDVE(SetKeyerDVE),
+    KeyerKeyframe(SetKeyerKeyframe),
+    DownstreamKeyerFillSource(SetDownstreamKeyerFillSource),
+    DownstreamKeyerCutSource(SetDownstreamKeyerCutSource),
+    DownstreamKeyerOnAir(SetDownstreamKeyerOnAir),
+    SuperSourceBox(SetSuperSourceBox),
+    MediaPlayerSource(SetMediaPlayerSource),
+    MediaPlayerStatus(SetMediaPlayerStatus),
+    MacroAction(SetMacroAction),
+    StartMacroRecording(StartMacroRecording),
+    TransitionMix(SetTransitionMix),
+    TransitionDip(SetTransitionDip),
+    TransitionWipeRate(SetTransitionWipeRate),
+    TransitionDVERate(SetTransitionDVERate),
+    TransitionStingerRate(SetTransitionStingerRate),
+}
+
+impl ControlCommand {
+    /// Four character name identifying the command on the wire
+    pub fn name(&self) -> &'static [u8; 4] {
+        match self {
+            #[cfg(feature = "camera")]
+            ControlCommand::CameraControl(_) => b"CCmd",
+            #[cfg(feature = "streaming")]
+            ControlCommand::Streaming(_) => b"StrR",
+            #[cfg(feature = "streaming")]
+            ControlCommand::StreamingService(_) => b"CRSS",
+            #[cfg(feature = "recording")]
+            ControlCommand::Recording(_) => b"RcTM",
+            #[cfg(feature = "recording")]
+            ControlCommand::RecordingSettings(_) => b"CRMS",
+            #[cfg(feature = "recording")]
+            ControlCommand::SwitchRecordingDisk(_) => b"RMSp",
+            ControlCommand::HyperDeckPlayer(_) => b"CXCP",
+            ControlCommand::HyperDeckClip(_) => b"CXCS",
+            ControlCommand::RequestTime(_) => b"TiRq",
+            ControlCommand::TimeCodeMode(_) => b"CTCC",
+            #[cfg(feature = "audio")]
+            ControlCommand::MixMinusOutput(_) => b"CMMP",
+            #[cfg(feature = "audio")]
+            ControlCommand::TalkbackProperties(_) => b"CTkM",
+            #[cfg(feature = "audio")]
+            ControlCommand::TalkbackInputProperties(_) => b"CTkI",
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioLevels(_) => b"SALN",
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioMonitor(_) => b"CAMm",
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioMixerInput(_) => b"CAMI",
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioMixerMaster(_) => b"CAMM",
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightLevels(_) => b"SFLN",
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightInputProperties(_) => b"CFIP",
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightSourceProperties(_) => b"CFSP",
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightMasterProperties(_) => b"CFMP",
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightCompressor(compressor) => compressor.name(),
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightLimiter(limiter) => limiter.name(),
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightExpander(_) => b"CIXP",
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightEqBand(band) => band.name(),
+            ControlCommand::AuxSource(_) => b"CAuS",
+            ControlCommand::MultiViewInput(_) => b"CMvI",
+            ControlCommand::MultiViewLayout(_) => b"CMvP",
+            ControlCommand::MultiViewVU(_) => b"VuMC",
+            ControlCommand::MultiViewSafeArea(_) => b"SaMw",
+            ControlCommand::VideoMode(_) => b"CVdM",
+            ControlCommand::DownConvertMode(_) => b"CDcO",
+            ControlCommand::InputProperties(_) => b"CInL",
+            #[cfg(feature = "media-pool")]
+            ControlCommand::MediaLock(_) => b"LOCK",
+            #[cfg(feature = "media-pool")]
+            ControlCommand::RequestDownload(_) => b"FTSU",
+            #[cfg(feature = "media-pool")]
+            ControlCommand::TransferAck(_) => b"FTUA",
+            #[cfg(feature = "media-pool")]
+            ControlCommand::RequestUpload(_) => b"FTSD",
+            #[cfg(feature = "media-pool")]
+            ControlCommand::TransferData(_) => b"FTDa",
+            #[cfg(feature = "media-pool")]
+            ControlCommand::TransferFileDescription(_) => b"FTFD",
+            #[cfg(feature = "media-pool")]
+            ControlCommand::ClearMediaStill(_) => b"CSTL",
+            #[cfg(feature = "media-pool")]
+            ControlCommand::ClearMediaClip(_) => b"CMPC",
+            #[cfg(feature = "media-pool")]
+            ControlCommand::MediaStillName(_) => b"SMPS",
+            #[cfg(feature = "media-pool")]
+            ControlCommand::MediaClip(_) => b"SMPC",
+            ControlCommand::SaveStartupState(_) => b"SRsv",
+            ControlCommand::ClearStartupState(_) => b"SRcl",
+            ControlCommand::DisplayClockProperties(_) => b"DCPC",
+            ControlCommand::DisplayClockState(_) => b"DCSC",
+            ControlCommand::DisplayClockTime(_) => b"DCTS",
+            #[cfg(feature = "streaming")]
+            ControlCommand::StreamingAudioBitrates(_) => b"CSAB",
+            #[cfg(feature = "streaming")]
+            ControlCommand::StreamingAuthentication(_) => b"CAth",
+            #[cfg(feature = "streaming")]
+            ControlCommand::StreamingLowLatency(_) => b"CSLw",
+            ControlCommand::TransitionPosition(_) => b"CTPs",
+            ControlCommand::ColorGenerator(_) => b"CClV",
+            ControlCommand::ProgramInput(_) => b"CPgI",
+            ControlCommand::PreviewInput(_) => b"CPvI",
+            ControlCommand::TransitionStyle(_) => b"CTTp",
+            ControlCommand::Cut(_) => b"DCut",
+            ControlCommand::AutoTransition(_) => b"DAut",
+            ControlCommand::FadeToBlack(_) => b"FtbA",
+            ControlCommand::KeyerOnAir(_) => b"CKOn",
+            ControlCommand::KeyerFillSource(_) => b"CKeF",
+            ControlCommand::KeyerCutSource(_) => b"CKeC",
+            ControlCommand::KeyerType(_) => b"CKTp",
+            ControlCommand::KeyerMask(_) => b"CKMs",
+            ControlCommand::KeyerLuma(_) => b"CKLm",
+            ControlCommand::KeyerChroma(_) => b"CKCk",
+            ControlCommand::KeyerPattern(_) => b"CKPt",
+            ControlCommand::KeyerDVE(_) => b"CKDV",
+            ControlCommand::KeyerKeyframe(_) => b"CKFP",
+            ControlCommand::DownstreamKeyerFillSource(_) => b"CDsF",
+            ControlCommand::DownstreamKeyerCutSource(_) => b"CDsC",
+            ControlCommand::DownstreamKeyerOnAir(_) => b"CDsL",
+            ControlCommand::SuperSourceBox(_) => b"CSBP",
+            ControlCommand::MediaPlayerSource(_) => b"MPSS",
+            ControlCommand::MediaPlayerStatus(_) => b"SCPS",
+            ControlCommand::MacroAction(_) => b"MAct",
+            ControlCommand::StartMacroRecording(_) => b"MSRc",
+            ControlCommand::TransitionMix(_) => b"CTMx",
+            ControlCommand::TransitionDip(_) => b"CTDp",
+            ControlCommand::TransitionWipeRate(_) => b"CTWp",
+            ControlCommand::TransitionDVERate(_) => b"CTDv",
+            ControlCommand::TransitionStingerRate(_) => b"CTSt",
+        }
+    }
+
+    /// Oldest protocol version that understands the command, None if every version does
+    pub fn min_version(&self) -> Option<Version> {
+        match self {
+            #[cfg(feature = "streaming")]
+            ControlCommand::Streaming(_)
+            | ControlCommand::StreamingService(_)
+            | ControlCommand::StreamingAudioBitrates(_)
+            | ControlCommand::StreamingAuthentication(_)
+            | ControlCommand::StreamingLowLatency(_) => Some(Version::V8_1_1),
+            #[cfg(feature = "recording")]
+            ControlCommand::Recording(_)
+            | ControlCommand::RecordingSettings(_)
+            | ControlCommand::SwitchRecordingDisk(_) => Some(Version::V8_1_1),
+            _ => None,
+        }
+    }
+
+    /// Whether the state command received from the switcher shows that this command has taken
+    /// effect. Only commands with a state counterpart can be confirmed, for any other command
+    /// this is always false.
+    pub fn is_confirmed_by(&self, command: &Command) -> bool {
+        match (self, command) {
+            (ControlCommand::ProgramInput(input), Command::ProgramInput(selection)) => {
+                selection.me() == input.me() && selection.source_id() == input.source_id()
+            }
+            (ControlCommand::PreviewInput(input), Command::PreviewInput(selection)) => {
+                selection.me() == input.me() && selection.source_id() == input.source_id()
+            }
+            (ControlCommand::AuxSource(aux), Command::AuxSource(source)) => {
+                source.aux() == aux.aux() && source.source_id() == aux.source_id()
+            }
+            _ => false,
+        }
+    }
+
+    /// Identifies the target of a continuously changing value and the properties the command
+    /// sets on it, as a mask. A queued command is superseded by a later one for the same target
+    /// that sets at least the same properties, only the latest values need to be sent. Commands
+    /// without a mask set every property.
+    pub fn coalesce_key(&self) -> Option<(&'static [u8; 4], u128, u64)> {
+        match self {
+            ControlCommand::TransitionPosition(position) => {
+                Some((self.name(), position.me() as u128, u64::MAX))
+            }
+            ControlCommand::KeyerDVE(dve) => Some((
+                self.name(),
+                (dve.me() as u128) << 8 | dve.keyer() as u128,
+                dve.mask().bits() as u64,
+            )),
+            #[cfg(feature = "camera")]
+            ControlCommand::CameraControl(control) if !control.relative() => {
+                let parameter = control.parameter();
+                Some((
+                    self.name(),
+                    u32::from_be_bytes([0, control.input(), parameter.category(), parameter.id()])
+                        as u128,
+                    u64::MAX,
+                ))
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioMixerInput(fader) => Some((
+                self.name(),
+                fader.input() as u128,
+                fader.mask().bits() as u64,
+            )),
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioMixerMaster(fader) => {
+                Some((self.name(), 0, fader.mask().bits() as u64))
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightSourceProperties(fader) => {
+                let source = fader.source();
+                Some((
+                    self.name(),
+                    (source.input() as u128) << 64 | source.source() as u64 as u128,
+                    fader.mask().bits() as u64,
+                ))
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightMasterProperties(fader) => {
+                Some((self.name(), 0, fader.mask().bits() as u64))
+            }
+            _ => None,
+        }
+    }
+
+    /// Serialize the command including the command header
+    pub fn serialize(&self) -> Bytes {
+        let mut data = BytesMut::new();
+
+        match self {
+            #[cfg(feature = "camera")]
+            ControlCommand::CameraControl(camera_control) => camera_control.serialize(&mut data),
+            #[cfg(feature = "streaming")]
+            ControlCommand::Streaming(streaming) => streaming.serialize(&mut data),
+            #[cfg(feature = "streaming")]
+            ControlCommand::StreamingService(streaming_service) => {
+                streaming_service.serialize(&mut data)
+            }
+            #[cfg(feature = "recording")]
+            ControlCommand::Recording(recording) => recording.serialize(&mut data),
+            #[cfg(feature = "recording")]
+            ControlCommand::RecordingSettings(recording_settings) => {
+                recording_settings.serialize(&mut data)
+            }
+            #[cfg(feature = "recording")]
+            ControlCommand::SwitchRecordingDisk(switch_recording_disk) => {
+                switch_recording_disk.serialize(&mut data)
+            }
+            ControlCommand::HyperDeckPlayer(hyper_deck_player) => {
+                hyper_deck_player.serialize(&mut data)
+            }
+            ControlCommand::HyperDeckClip(hyper_deck_clip) => hyper_deck_clip.serialize(&mut data),
+            ControlCommand::RequestTime(request_time) => request_time.serialize(&mut data),
+            ControlCommand::TimeCodeMode(time_code_mode) => time_code_mode.serialize(&mut data),
+            #[cfg(feature = "audio")]
+            ControlCommand::MixMinusOutput(mix_minus_output) => {
+                mix_minus_output.serialize(&mut data)
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::TalkbackProperties(talkback_properties) => {
+                talkback_properties.serialize(&mut data)
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::TalkbackInputProperties(talkback_input_properties) => {
+                talkback_input_properties.serialize(&mut data)
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioLevels(audio_levels) => audio_levels.serialize(&mut data),
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioMonitor(audio_monitor) => audio_monitor.serialize(&mut data),
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioMixerInput(input) => input.serialize(&mut data),
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioMixerMaster(master) => master.serialize(&mut data),
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightLevels(fairlight_levels) => {
+                fairlight_levels.serialize(&mut data)
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightInputProperties(properties) => properties.serialize(&mut data),
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightSourceProperties(properties) => {
+                properties.serialize(&mut data)
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightMasterProperties(properties) => {
+                properties.serialize(&mut data)
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightCompressor(compressor) => compressor.serialize(&mut data),
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightLimiter(limiter) => limiter.serialize(&mut data),
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightExpander(expander) => expander.serialize(&mut data),
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightEqBand(band) => band.serialize(&mut data),
+            ControlCommand::AuxSource(aux_source) => aux_source.serialize(&mut data),
+            ControlCommand::MultiViewInput(multiview_input) => multiview_input.serialize(&mut data),
+            ControlCommand::MultiViewLayout(multiview_layout) => {
+                multiview_layout.serialize(&mut data)
+            }
+            ControlCommand::MultiViewVU(multiview_vu) => multiview_vu.serialize(&mut data),
+            ControlCommand::MultiViewSafeArea(multiview_safe_area) => {
+                multiview_safe_area.serialize(&mut data)
+            }
+            ControlCommand::VideoMode(video_mode) => video_mode.serialize(&mut data),
+            ControlCommand::DownConvertMode(down_convert_mode) => {
+                down_convert_mode.serialize(&mut data)
+            }
+            ControlCommand::InputProperties(input_properties) => {
+                input_properties.serialize(&mut data)
+            }
+            #[cfg(feature = "media-pool")]
+            ControlCommand::MediaLock(media_lock) => media_lock.serialize(&mut data),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::RequestDownload(request_download) => {
+                request_download.serialize(&mut data)
+            }
+            #[cfg(feature = "media-pool")]
+            ControlCommand::TransferAck(transfer_ack) => transfer_ack.serialize(&mut data),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::RequestUpload(request_upload) => request_upload.serialize(&mut data),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::TransferData(transfer_data) => transfer_data.serialize(&mut data),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::TransferFileDescription(file_description) => {
+                file_description.serialize(&mut data)
+            }
+            #[cfg(feature = "media-pool")]
+            ControlCommand::ClearMediaStill(clear_still) => clear_still.serialize(&mut data),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::ClearMediaClip(clear_clip) => clear_clip.serialize(&mut data),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::MediaStillName(still_name) => still_name.serialize(&mut data),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::MediaClip(media_clip) => media_clip.serialize(&mut data),
+            ControlCommand::SaveStartupState(save_startup_state) => {
+                save_startup_state.serialize(&mut data)
+            }
+            ControlCommand::ClearStartupState(clear_startup_state) => {
+                clear_startup_state.serialize(&mut data)
+            }
+            ControlCommand::DisplayClockProperties(display_clock_properties) => {
+                display_clock_properties.serialize(&mut data)
+            }
+            ControlCommand::DisplayClockState(display_clock_state) => {
+                display_clock_state.serialize(&mut data)
+            }
+            ControlCommand::DisplayClockTime(display_clock_time) => {
+                display_clock_time.serialize(&mut data)
+            }
+            #[cfg(feature = "streaming")]
+            ControlCommand::StreamingAudioBitrates(streaming_audio_bitrates) => {
+                streaming_audio_bitrates.serialize(&mut data)
+            }
+            #[cfg(feature = "streaming")]
+            ControlCommand::StreamingAuthentication(streaming_authentication) => {
+                streaming_authentication.serialize(&mut data)
+            }
+            #[cfg(feature = "streaming")]
+            ControlCommand::StreamingLowLatency(streaming_low_latency) => {
+                streaming_low_latency.serialize(&mut data)
+            }
+            ControlCommand::TransitionPosition(transition_position) => {
+                transition_position.serialize(&mut data)
+            }
+            ControlCommand::ColorGenerator(color_generator) => color_generator.serialize(&mut data),
+            ControlCommand::ProgramInput(program_input) => program_input.serialize(&mut data),
+            ControlCommand::PreviewInput(preview_input) => preview_input.serialize(&mut data),
+            ControlCommand::TransitionStyle(transition_style) => {
+                transition_style.serialize(&mut data)
+            }
+            ControlCommand::Cut(cut) => cut.serialize(&mut data),
+            ControlCommand::AutoTransition(auto_transition) => auto_transition.serialize(&mut data),
+            ControlCommand::FadeToBlack(fade_to_black) => fade_to_black.serialize(&mut data),
+            ControlCommand::KeyerOnAir(keyer_on_air) => keyer_on_air.serialize(&mut data),
+            ControlCommand::KeyerFillSource(keyer_fill_source) => {
+                keyer_fill_source.serialize(&mut data)
+            }
+            ControlCommand::KeyerCutSource(keyer_cut_source) => {
+                keyer_cut_source.serialize(&mut data)
+            }
+            ControlCommand::KeyerType(keyer_type) => keyer_type.serialize(&mut data),
+            ControlCommand::KeyerMask(keyer_mask) => keyer_mask.serialize(&mut data),
+            ControlCommand::KeyerLuma(keyer_luma) => keyer_luma.serialize(&mut data),
+            ControlCommand::KeyerChroma(keyer_chroma) => keyer_chroma.serialize(&mut data),
+            ControlCommand::KeyerPattern(keyer_pattern) => keyer_pattern.serialize(&mut data),
+            ControlCommand::KeyerDVE(keyer_dve) => keyer_dve.serialize(&mut data),
+            ControlCommand::KeyerKeyframe(keyframe) => keyframe.serialize(&mut data),
+            ControlCommand::DownstreamKeyerFillSource(source) => source.serialize(&mut data),
+            ControlCommand::DownstreamKeyerCutSource(source) => source.serialize(&mut data),
+            ControlCommand::DownstreamKeyerOnAir(on_air) => on_air.serialize(&mut data),
+            ControlCommand::SuperSourceBox(ssbox) => ssbox.serialize(&mut data),
+            ControlCommand::MediaPlayerSource(media_player_source) => {
+                media_player_source.serialize(&mut data)
+            }
+            ControlCommand::MediaPlayerStatus(media_player_status) => {
+                media_player_status.serialize(&mut data)
+            }
+            ControlCommand::MacroAction(macro_action) => macro_action.serialize(&mut data),
+            ControlCommand::StartMacroRecording(start_recording) => {
+                start_recording.serialize(&mut data)
+            }
+            ControlCommand::TransitionMix(transition_mix) => transition_mix.serialize(&mut data),
+            ControlCommand::TransitionDip(transition_dip) => transition_dip.serialize(&mut data),
+            ControlCommand::TransitionWipeRate(transition_wipe) => {
+                transition_wipe.serialize(&mut data)
+            }
+            ControlCommand::TransitionDVERate(transition_dve) => {
+                transition_dve.serialize(&mut data)
+            }
+            ControlCommand::TransitionStingerRate(transition_stinger) => {
+                transition_stinger.serialize(&mut data)
+            }
+        }
+
+        let mut bytes = BytesMut::with_capacity(data.len() + COMMAND_HEADER_SIZE as usize);
+        bytes.put_u16(data.len() as u16 + COMMAND_HEADER_SIZE);
+        bytes.put_u16(0x00);
+        bytes.put_slice(self.name());
+        bytes.extend_from_slice(&data);
+
+        bytes.freeze()
+    }
+}
+
+impl Display for ControlCommand {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            #[cfg(feature = "camera")]
+            ControlCommand::CameraControl(control) => write!(f, "Camera control: {control}"),
+            #[cfg(feature = "streaming")]
+            ControlCommand::Streaming(streaming) => write!(f, "Streaming: {streaming}"),
+            #[cfg(feature = "streaming")]
+            ControlCommand::StreamingService(service) => write!(f, "Streaming service: {service}"),
+            #[cfg(feature = "recording")]
+            ControlCommand::Recording(recording) => write!(f, "Recording: {recording}"),
+            #[cfg(feature = "recording")]
+            ControlCommand::RecordingSettings(settings) => {
+                write!(f, "Recording settings: {settings}")
+            }
+            #[cfg(feature = "recording")]
+            ControlCommand::SwitchRecordingDisk(disk) => write!(f, "Recording: {disk}"),
+            ControlCommand::HyperDeckPlayer(player) => write!(f, "HyperDeck player: {player}"),
+            ControlCommand::HyperDeckClip(clip) => write!(f, "HyperDeck clip: {clip}"),
+            ControlCommand::RequestTime(time) => write!(f, "Time: {time}"),
+            ControlCommand::TimeCodeMode(mode) => write!(f, "Time code mode: {mode}"),
+            #[cfg(feature = "audio")]
+            ControlCommand::MixMinusOutput(output) => write!(f, "Mix minus output: {output}"),
+            #[cfg(feature = "audio")]
+            ControlCommand::TalkbackProperties(properties) => write!(f, "Talkback: {properties}"),
+            #[cfg(feature = "audio")]
+            ControlCommand::TalkbackInputProperties(properties) => {
+                write!(f, "Talkback input: {properties}")
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioLevels(levels) => write!(f, "Audio levels: {levels}"),
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioMonitor(monitor) => write!(f, "Audio monitor: {monitor}"),
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioMixerInput(input) => write!(f, "Audio mixer input: {input}"),
+            #[cfg(feature = "audio")]
+            ControlCommand::AudioMixerMaster(master) => write!(f, "Audio mixer master: {master}"),
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightLevels(levels) => write!(f, "Fairlight levels: {levels}"),
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightInputProperties(properties) => {
+                write!(f, "Fairlight input: {properties}")
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightSourceProperties(properties) => {
+                write!(f, "Fairlight source: {properties}")
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightMasterProperties(properties) => {
+                write!(f, "Fairlight master: {properties}")
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightCompressor(compressor) => {
+                write!(f, "Fairlight compressor: {compressor}")
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightLimiter(limiter) => write!(f, "Fairlight limiter: {limiter}"),
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightExpander(expander) => {
+                write!(f, "Fairlight expander: {expander}")
+            }
+            #[cfg(feature = "audio")]
+            ControlCommand::FairlightEqBand(band) => write!(f, "Fairlight EQ band: {band}"),
+            ControlCommand::AuxSource(selection) => write!(f, "Aux: {selection}"),
+            ControlCommand::MultiViewInput(input) => write!(f, "Multiview input: {input}"),
+            ControlCommand::MultiViewLayout(layout) => write!(f, "Multiview layout: {layout}"),
+            ControlCommand::MultiViewVU(vu) => write!(f, "Multiview VU: {vu}"),
+            ControlCommand::MultiViewSafeArea(safe_area) => {
+                write!(f, "Multiview safe area: {safe_area}")
+            }
+            ControlCommand::VideoMode(mode) => write!(f, "Video mode: {mode}"),
+            ControlCommand::DownConvertMode(mode) => write!(f, "Down convert mode: {mode}"),
+            ControlCommand::InputProperties(properties) => {
+                write!(f, "Input properties: {properties}")
+            }
+            #[cfg(feature = "media-pool")]
+            ControlCommand::MediaLock(lock) => write!(f, "Media lock: {lock}"),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::RequestDownload(request) => write!(f, "Request download: {request}"),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::TransferAck(ack) => write!(f, "Transfer ack: {ack}"),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::RequestUpload(request) => write!(f, "Request upload: {request}"),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::TransferData(data) => write!(f, "Transfer data: {data}"),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::TransferFileDescription(description) => {
+                write!(f, "Transfer file description: {description}")
+            }
+            #[cfg(feature = "media-pool")]
+            ControlCommand::ClearMediaStill(clear) => write!(f, "Clear media still: {clear}"),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::ClearMediaClip(clear) => write!(f, "Clear media clip: {clear}"),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::MediaStillName(name) => write!(f, "Media still name: {name}"),
+            #[cfg(feature = "media-pool")]
+            ControlCommand::MediaClip(clip) => write!(f, "Media clip: {clip}"),
+            ControlCommand::SaveStartupState(save) => write!(f, "Startup state: {save}"),
+            ControlCommand::ClearStartupState(clear) => write!(f, "Startup state: {clear}"),
+            ControlCommand::DisplayClockProperties(properties) => {
+                write!(f, "Display clock: {properties}")
+            }
+            ControlCommand::DisplayClockState(state) => write!(f, "Display clock state: {state}"),
+            ControlCommand::DisplayClockTime(time) => write!(f, "Display clock time: {time}"),
+            #[cfg(feature = "streaming")]
+            ControlCommand::StreamingAudioBitrates(bitrates) => {
+                write!(f, "Streaming audio bitrates: {bitrates}")
+            }
+            #[cfg(feature = "streaming")]
+            ControlCommand::StreamingAuthentication(authentication) => {
+                write!(f, "Streaming authentication: {authentication}")
+            }
+            #[cfg(feature = "streaming")]
+            ControlCommand::StreamingLowLatency(low_latency) => {
+                write!(f, "Streaming low latency: {low_latency}")
+            }
+            ControlCommand::TransitionPosition(position) => {
+                write!(f, "Transition position: {position}")
+            }
+            ControlCommand::ColorGenerator(generator) => write!(f, "Color generator: {generator}"),
+            ControlCommand::ProgramInput(input) => write!(f, "Program input: {input}"),
+            ControlCommand::PreviewInput(input) => write!(f, "Preview input: {input}"),
+            ControlCommand::TransitionStyle(style) => write!(f, "Transition style: {style}"),
+            ControlCommand::Cut(cut) => write!(f, "Cut: {cut}"),
+            ControlCommand::AutoTransition(auto) => write!(f, "Auto transition: {auto}"),
+            ControlCommand::FadeToBlack(fade) => write!(f, "Fade to black: {fade}"),
+            ControlCommand::KeyerOnAir(on_air) => write!(f, "Keyer on air: {on_air}"),
+            ControlCommand::KeyerFillSource(source) => write!(f, "Keyer fill source: {source}"),
+            ControlCommand::KeyerCutSource(source) => write!(f, "Keyer cut source: {source}"),
+            ControlCommand::KeyerType(key_type) => write!(f, "Keyer type: {key_type}"),
+            ControlCommand::KeyerMask(mask) => write!(f, "Keyer mask: {mask}"),
+            ControlCommand::KeyerLuma(luma) => write!(f, "Luma key: {luma}"),
+            ControlCommand::KeyerChroma(chroma) => write!(f, "Chroma key: {chroma}"),
+            ControlCommand::KeyerPattern(pattern) => write!(f, "Pattern key: {pattern}"),
+            ControlCommand::KeyerDVE(dve) => write!(f, "DVE key: {dve}"),
+            ControlCommand::KeyerKeyframe(keyframe) => write!(f, "Keyer keyframe: {keyframe}"),
+            ControlCommand::DownstreamKeyerFillSource(source) => {
+                write!(f, "Downstream keyer fill source: {source}")
+            }
+            ControlCommand::DownstreamKeyerCutSource(source) => {
+                write!(f, "Downstream keyer cut source: {source}")
+            }
+            ControlCommand::DownstreamKeyerOnAir(on_air) => {
+                write!(f, "Downstream keyer on air: {on_air}")
+            }
+            ControlCommand::SuperSourceBox(ssbox) => write!(f, "SuperSource box: {ssbox}"),
+            ControlCommand::MediaPlayerSource(source) => write!(f, "Media player source: {source}"),
+            ControlCommand::MediaPlayerStatus(status) => write!(f, "Media player status: {status}"),
+            ControlCommand::MacroAction(action) => write!(f, "Macro: {action}"),
+            ControlCommand::StartMacroRecording(recording) => {
+                write!(f, "Start macro recording: {recording}")
+            }
+            ControlCommand::TransitionMix(mix) => write!(f, "Transition mix: {mix}"),
+            ControlCommand::TransitionDip(dip) => write!(f, "Transition dip: {dip}"),
+            ControlCommand::TransitionWipeRate(wipe) => write!(f, "Transition wipe: {wipe}"),
+            ControlCommand::TransitionDVERate(dve) => write!(f, "Transition DVE: {dve}"),
+            ControlCommand::TransitionStingerRate(stinger) => {
+                write!(f, "Transition stinger: {stinger}")
+            }
+        }
+    }
+}
+
+/// Notified once the packet carrying a batch has been acknowledged by the switcher
+#[cfg(feature = "client")]
+pub(crate) type AckSender = oneshot::Sender<Result<(), Error>>;
+
+/// Group of commands that is always sent to the switcher in the same packet
+#[derive(Default)]
+pub struct CommandBatch {
+    commands: Vec<ControlCommand>,
+    #[cfg(feature = "client")]
+    acks: Vec<AckSender>,
+}
+
+impl CommandBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn command(mut self, command: ControlCommand) -> Self {
+        self.commands.push(command);
+
+        self
+    }
+
+    pub fn commands(&self) -> &[ControlCommand] {
+        &self.commands
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    #[cfg(feature = "client")]
+    pub(crate) fn add_ack(&mut self, ack: AckSender) {
+        self.acks.push(ack);
+    }
+
+    #[cfg(feature = "client")]
+    fn coalesce_key(&self) -> Option<(&'static [u8; 4], u128, u64)> {
+        match self.commands.as_slice() {
+            [command] => command.coalesce_key(),
+            _ => None,
+        }
+    }
+
+    pub fn serialize(&self) -> Bytes {
+        let mut bytes = BytesMut::new();
+        for command in &self.commands {
+            bytes.extend_from_slice(&command.serialize());
+        }
+
+        bytes.freeze()
+    }
+}
+
+impl From<ControlCommand> for CommandBatch {
+    fn from(command: ControlCommand) -> Self {
+        CommandBatch {
+            commands: vec![command],
+            #[cfg(feature = "client")]
+            acks: Vec::new(),
+        }
+    }
+}
+
+impl Display for CommandBatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (i, command) in self.commands.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{command}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Replace queued single commands with later values for the same control. A value only
+/// replaces an earlier one when it sets at least the properties of the earlier one and
+/// neither another command nor a command setting any of the same properties was queued in
+/// between, so the values the switcher ends up with never change. Explicit batches of several
+/// commands are never changed.
+#[cfg(feature = "client")]
+pub(crate) fn coalesce_batches(batches: Vec<CommandBatch>) -> Vec<CommandBatch> {
+    let mut coalesced: Vec<CommandBatch> = Vec::with_capacity(batches.len());
+    // Position and properties of the queued commands for each target
+    let mut queued: BTreeMap<_, Vec<(usize, u64)>> = BTreeMap::new();
+    for mut batch in batches {
+        match batch.coalesce_key() {
+            Some((name, target, mask)) => {
+                let slots = queued.entry((name, target)).or_default();
+                let superseded = slots
+                    .iter_mut()
+                    .rev()
+                    .find(|(_, queued_mask)| mask & queued_mask != 0)
+                    .filter(|(_, queued_mask)| mask & *queued_mask == *queued_mask);
+                match superseded {
+                    Some((index, queued_mask)) => {
+                        // Whoever waits for the superseded value is served by the latest one
+                        let superseded: &mut CommandBatch = &mut coalesced[*index];
+                        batch.acks.append(&mut superseded.acks);
+                        *superseded = batch;
+                        *queued_mask = mask;
+                    }
+                    None => {
+                        slots.push((coalesced.len(), mask));
+                        coalesced.push(batch);
+                    }
+                }
+            }
+            None => {
+                // Later values must not overtake a command that may depend on them
+                queued.clear();
+                coalesced.push(batch);
+            }
+        }
+    }
+
+    coalesced
+}
+
+/// Take batches from the front of the queue, as many as fit in one packet payload without
+/// splitting any batch. A batch larger than the payload size is taken on its own. Returns the
+/// payload and everyone waiting for it to be acknowledged.
+#[cfg(feature = "client")]
+pub(crate) fn take_payload(
+    batches: &mut VecDeque<CommandBatch>,
+    max_size: usize,
+) -> (BytesMut, Vec<AckSender>) {
+    let mut payload = BytesMut::new();
+    let mut acks = Vec::new();
+    while let Some(batch) = batches.front() {
+        let data = batch.serialize();
+        if !payload.is_empty() && payload.len() + data.len() > max_size {
+            break;
+        }
+
+        payload.extend_from_slice(&data);
+        if let Some(mut batch) = batches.pop_front() {
+            acks.append(&mut batch.acks);
+        }
+    }
+
+    (payload, acks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "camera")]
+    use crate::camera::{self, CameraControlParameter, CameraControlValue};
+    #[cfg(feature = "audio")]
+    use crate::fairlight::{AudioSourceId, ChannelSplit, EqFrequencyRanges, EqShapes};
+    #[cfg(feature = "audio")]
+    use crate::{
+        audio::{AudioMixOption, MixMinusMode, TalkbackChannel},
+        fairlight::{FairlightMixOption, InputConfigurations, InputLevels},
+    };
+    use crate::{
+        color::HslColor,
+        command::Time,
+        displayclock::{DisplayClockMode, DisplayClockState},
+        hyperdeck::HyperDeckState,
+        keyer::{KeyType, KeyerMask, Keyframe},
+        mediaplayer::MediaPlayerSourceType,
+        multiview::MultiViewLayoutStyle,
+        source::Input,
+        systeminfo::{DownConvertMode, TimeCodeType, VideoMode},
+        transition::{TransitionLayers, TransitionStyle},
+    };
+    #[cfg(feature = "arbitrary")]
+    use arbitrary::{Arbitrary, Unstructured};
+    use proptest::prelude::*;
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn take_payload_ok() {
+        let batches = || {
+            VecDeque::from([
+                CommandBatch::from(ControlCommand::SaveStartupState(SaveStartupState)),
+                CommandBatch::new()
+                    .command(ControlCommand::ClearStartupState(ClearStartupState))
+                    .command(ControlCommand::SaveStartupState(SaveStartupState)),
+            ])
+        };
+
+        // Every command is 12 bytes, so the second batch doesn't fit with the first
+        let mut queue = batches();
+        assert_eq!(take_payload(&mut queue, 30).0.len(), 12);
+        assert_eq!(take_payload(&mut queue, 30).0.len(), 24);
+        assert!(queue.is_empty());
+
+        let mut queue = batches();
+        let (payload, _) = take_payload(&mut queue, 36);
+        assert!(queue.is_empty());
+        assert_eq!(&payload[4..8], b"SRsv");
+        assert_eq!(&payload[16..20], b"SRcl");
+    }
+
+    #[test]
+    fn is_confirmed_by_ok() {
+        let command = ControlCommand::ProgramInput(SetProgramInput::new(1, 3));
+        let program = |data: [u8; 12]| Command::parse(&mut Bytes::copy_from_slice(&data)).unwrap();
+
+        assert!(command.is_confirmed_by(&program(*b"\x00\x0c\x00\x00PrgI\x01\x00\x00\x03")));
+        assert!(!command.is_confirmed_by(&program(*b"\x00\x0c\x00\x00PrgI\x00\x00\x00\x03")));
+        assert!(!command.is_confirmed_by(&program(*b"\x00\x0c\x00\x00PrvI\x01\x00\x00\x03")));
+    }
+
+    #[cfg(feature = "recording")]
+    #[test]
+    fn min_version_ok() {
+        let recording = ControlCommand::Recording(crate::recording::SetRecording::new(true));
+        let cut = ControlCommand::Cut(Cut::new(0));
+
+        assert_eq!(recording.min_version(), Some(Version::V8_1_1));
+        assert_eq!(cut.min_version(), None);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn coalesce_batches_ok() {
+        let position = |me, position| {
+            CommandBatch::from(ControlCommand::TransitionPosition(
+                SetTransitionPosition::new(me, position),
+            ))
+        };
+        let batches = vec![
+            position(0, 1000),
+            position(1, 1000),
+            CommandBatch::from(ControlCommand::SaveStartupState(SaveStartupState)),
+            position(0, 2000),
+            position(0, 3000),
+        ];
+
+        let coalesced = coalesce_batches(batches);
+        assert_eq!(coalesced.len(), 4);
+        assert_eq!(coalesced[0].serialize()[8..], [0x00, 0x00, 0x03, 0xe8]);
+        assert_eq!(coalesced[1].serialize()[8..], [0x01, 0x00, 0x03, 0xe8]);
+        assert_eq!(coalesced[3].serialize()[8..], [0x00, 0x00, 0x0b, 0xb8]);
+
+        let batches = vec![
+            position(0, 5000),
+            CommandBatch::from(ControlCommand::Cut(Cut::new(0))),
+            position(0, 9000),
+        ];
+
+        let coalesced = coalesce_batches(batches);
+        assert_eq!(coalesced.len(), 3);
+        assert_eq!(coalesced[0].serialize()[8..], [0x00, 0x00, 0x13, 0x88]);
+        assert_eq!(&coalesced[1].serialize()[4..8], b"DCut");
+        assert_eq!(coalesced[2].serialize()[8..], [0x00, 0x00, 0x23, 0x28]);
+    }
+
+    #[cfg(feature = "client")]
+    #[test]
+    fn coalesce_batches_dve() {
+        let dve = |dve| CommandBatch::from(ControlCommand::KeyerDVE(dve));
+        let batches = vec![
+            dve(SetKeyerDVE::new(0, 0).position(100, 100)),
+            dve(SetKeyerDVE::new(0, 0).size(500, 500)),
+            dve(SetKeyerDVE::new(0, 1).position(100, 100)),
+            dve(SetKeyerDVE::new(0, 0).position(200, 200)),
+        ];
+
+        let coalesced = coalesce_batches(batches);
+        assert_eq!(coalesced.len(), 3);
+        assert_eq!(coalesced[0].serialize()[24..28], 200i32.to_be_bytes());
+        assert_eq!(coalesced[2].serialize()[13], 1);
+    }
+
+    #[cfg(all(feature = "client", feature = "audio"))]
+    #[test]
+    fn coalesce_batches_faders() {
+        let input = |input, gain| {
+            CommandBatch::from(ControlCommand::AudioMixerInput(
+                SetAudioMixerInput::new(input).gain(gain),
+            ))
+        };
+        let source = |input, gain| {
+            CommandBatch::from(ControlCommand::FairlightSourceProperties(
+                SetFairlightSourceProperties::new(AudioSourceId::new(input, ChannelSplit::Stereo))
+                    .fader_gain(gain),
+            ))
+        };
+        let batches = vec![
+            input(1, 1000),
+            input(2, 1000),
+            input(1, 2000),
+            source(1, -1000),
+            source(1, -500),
+        ];
+
+        let coalesced = coalesce_batches(batches);
+        assert_eq!(coalesced.len(), 3);
+        assert_eq!(coalesced[0].serialize()[14..16], 2000u16.to_be_bytes());
+        assert_eq!(coalesced[2].serialize()[48..52], (-500i32).to_be_bytes());
+    }
+
+    #[cfg(feature = "audio")]
+    #[test]
+    fn coalesce_batches_overlapping_masks() {
+        let fader =
+            |fader: SetAudioMixerInput| CommandBatch::from(ControlCommand::AudioMixerInput(fader));
+        let batches = vec![
+            fader(SetAudioMixerInput::new(1).gain(1000)),
+            fader(SetAudioMixerInput::new(1).gain(2000).balance(-500)),
+            fader(SetAudioMixerInput::new(1).gain(3000)),
+        ];
+
+        // The last gain must not be sent before the gain and balance command
+        let coalesced = coalesce_batches(batches);
+        assert_eq!(coalesced.len(), 2);
+        assert_eq!(coalesced[0].serialize()[14..18], [0x07, 0xd0, 0xfe, 0x0c]);
+        assert_eq!(coalesced[1].serialize()[14..16], 3000u16.to_be_bytes());
+    }
+
+    /// Build the state command matching a set-command from the fields of the serialized
+    /// set-command, each copied from its offset in the set-command to its offset in the state
+    /// command, and parse it
+    fn parse_as_state(
+        command: ControlCommand,
+        name: &[u8; 4],
+        size: usize,
+        fields: &[(usize, usize, usize)],
+    ) -> Command {
+        let serialized = command.serialize();
+        assert_eq!(
+            u16::from_be_bytes([serialized[0], serialized[1]]) as usize,
+            serialized.len()
+        );
+        assert_eq!(&serialized[4..8], &command.name()[..]);
+        let set = &serialized[8..];
+        let mut state = BytesMut::new();
+        state.put_u16(size as u16 + 8);
+        state.put_u16(0);
+        state.put_slice(name);
+        state.put_bytes(0, size);
+        for &(from, to, len) in fields {
+            state[8 + to..8 + to + len].copy_from_slice(&set[from..from + len]);
+        }
+
+        Command::parse(&mut state.freeze()).unwrap()
+    }
+
+    #[test]
+    fn aux_source_round_trip() {
+        let command = ControlCommand::AuxSource(SetAuxSource::new(3, 2001));
+
+        let Command::AuxSource(aux) = parse_as_state(command, b"AuxS", 4, &[(1, 0, 1), (2, 2, 2)])
+        else {
+            panic!("Not an aux source");
+        };
+        assert_eq!(aux.aux(), 3);
+        assert_eq!(aux.source_id(), 2001);
+    }
+
+    #[test]
+    fn color_generator_round_trip() {
+        let color = HslColor::new(1200, 750, 333);
+        let command = ControlCommand::ColorGenerator(SetColorGenerator::new(1).color(color));
+
+        let Command::ColorGenerator(generator) =
+            parse_as_state(command, b"ColV", 8, &[(1, 0, 1), (2, 2, 6)])
+        else {
+            panic!("Not a color generator");
+        };
+        assert_eq!(generator.index(), 1);
+        assert_eq!(generator.color(), color);
+    }
+
+    #[test]
+    fn multiview_round_trip() {
+        let command = ControlCommand::MultiViewInput(SetMultiViewInput::new(1, 7, 3010));
+        let Command::MultiViewInput(input) = parse_as_state(command, b"MvIn", 4, &[(0, 0, 4)])
+        else {
+            panic!("Not a multiview input");
+        };
+        assert_eq!(input.multiview(), 1);
+        assert_eq!(input.window(), 7);
+        assert_eq!(input.source(), 3010);
+
+        let command = ControlCommand::MultiViewLayout(
+            SetMultiViewLayout::new(1)
+                .layout(MultiViewLayoutStyle::ProgramLeft)
+                .flip_program(true),
+        );
+        let Command::MultiViewLayout(layout) = parse_as_state(command, b"MvPr", 3, &[(1, 0, 3)])
+        else {
+            panic!("Not a multiview layout");
+        };
+        assert_eq!(layout.multiview(), 1);
+        assert_eq!(layout.layout(), MultiViewLayoutStyle::ProgramLeft);
+        assert!(layout.flip_program());
+
+        let command = ControlCommand::MultiViewVU(SetMultiViewVU::new(1, 5, true));
+        let Command::MultiViewVU(vu) = parse_as_state(command, b"VuMC", 3, &[(0, 0, 3)]) else {
+            panic!("Not a multiview VU meter");
+        };
+        assert_eq!((vu.multiview(), vu.window(), vu.enabled()), (1, 5, true));
+
+        let command = ControlCommand::MultiViewSafeArea(SetMultiViewSafeArea::new(1, 0, true));
+        let Command::MultiViewSafeArea(safe_area) =
+            parse_as_state(command, b"SaMw", 3, &[(0, 0, 3)])
+        else {
+            panic!("Not a multiview safe area");
+        };
+        assert_eq!(
+            (
+                safe_area.multiview(),
+                safe_area.window(),
+                safe_area.enabled()
+            ),
+            (1, 0, true)
+        );
+    }
+
+    #[test]
+    fn supersource_box_round_trip() {
+        let command = ControlCommand::SuperSourceBox(
+            SetSuperSourceBox::new(0, 2)
+                .enabled(true)
+                .source(3)
+                .position(-800, 450)
+                .size(500)
+                .cropped(true)
+                .crop(100, 200, 300, 400),
+        );
+        let Command::SuperSourceBox(ssbox) =
+            parse_as_state(command, b"SSBP", 24, &[(2, 0, 4), (6, 4, 10), (16, 14, 8)])
+        else {
+            panic!("Not a SuperSource box");
+        };
+        assert_eq!((ssbox.supersource(), ssbox.index()), (0, 2));
+        assert!(ssbox.enabled() && ssbox.cropped());
+        assert_eq!(ssbox.source(), 3);
+        assert_eq!(ssbox.position(), (-800, 450));
+        assert_eq!(ssbox.size(), 500);
+        assert_eq!(ssbox.crop(), (100, 200, 300, 400));
+    }
+
+    #[cfg(feature = "audio")]
+    #[test]
+    fn fairlight_eq_band_round_trip() {
+        let source = AudioSourceId::new(1301, ChannelSplit::Left);
+        let band = |band: SetFairlightEqBand| {
+            ControlCommand::FairlightEqBand(
+                band.enabled(true)
+                    .shape(EqShapes::BELL)
+                    .frequency_range(EqFrequencyRanges::MID_HIGH)
+                    .frequency(4000)
+                    .gain(-350)
+                    .q_factor(140),
+            )
+        };
+        let check = |parsed: Command, band: u8| {
+            let Command::FairlightEqBand(parsed) = parsed else {
+                panic!("Not an EQ band");
+            };
+            assert_eq!(parsed.band(), band);
+            assert!(parsed.enabled());
+            assert_eq!(parsed.shape(), EqShapes::BELL);
+            assert_eq!(parsed.frequency_range(), EqFrequencyRanges::MID_HIGH);
+            assert_eq!(parsed.frequency(), 4000);
+            assert_eq!(parsed.gain(), -350);
+            assert_eq!(parsed.q_factor(), 140);
+            parsed.source()
+        };
+
+        let parsed = parse_as_state(
+            band(SetFairlightEqBand::source(source, 2)),
+            b"AEBP",
+            34,
+            &[
+                (2, 0, 2),
+                (8, 8, 8),
+                (16, 16, 2),
+                (18, 19, 1),
+                (19, 21, 1),
+                (20, 24, 10),
+            ],
+        );
+        assert_eq!(check(parsed, 2), Some(source));
+
+        let parsed = parse_as_state(
+            band(SetFairlightEqBand::master(4)),
+            b"AMBP",
+            18,
+            &[(1, 0, 2), (3, 3, 1), (4, 5, 1), (8, 8, 10)],
+        );
+        assert_eq!(check(parsed, 4), None);
+    }
+
+    #[cfg(feature = "audio")]
+    #[test]
+    fn fairlight_dynamics_round_trip() {
+        let source = AudioSourceId::new(1301, ChannelSplit::Right);
+        let compressor = |compressor: SetFairlightCompressor| {
+            ControlCommand::FairlightCompressor(
+                compressor
+                    .enabled(true)
+                    .threshold(-1200)
+                    .ratio(400)
+                    .attack(150)
+                    .hold(20)
+                    .release(9000),
+            )
+        };
+        let check_compressor = |parsed: Command| {
+            let Command::FairlightCompressor(parsed) = parsed else {
+                panic!("Not a compressor");
+            };
+            assert!(parsed.enabled());
+            assert_eq!(parsed.threshold(), -1200);
+            assert_eq!(parsed.ratio(), 400);
+            assert_eq!(
+                (parsed.attack(), parsed.hold(), parsed.release()),
+                (150, 20, 9000)
+            );
+            parsed.source()
+        };
+
+        let parsed = parse_as_state(
+            compressor(SetFairlightCompressor::source(source)),
+            b"AICP",
+            40,
+            &[(2, 0, 2), (8, 8, 32)],
+        );
+        assert_eq!(check_compressor(parsed), Some(source));
+        let parsed = parse_as_state(
+            compressor(SetFairlightCompressor::master()),
+            b"AMCP",
+            24,
+            &[(1, 0, 1), (4, 4, 20)],
+        );
+        assert_eq!(check_compressor(parsed), None);
+
+        let limiter = |limiter: SetFairlightLimiter| {
+            ControlCommand::FairlightLimiter(
+                limiter
+                    .enabled(true)
+                    .threshold(-300)
+                    .attack(70)
+                    .hold(10)
+                    .release(5000),
+            )
+        };
+        let check_limiter = |parsed: Command| {
+            let Command::FairlightLimiter(parsed) = parsed else {
+                panic!("Not a limiter");
+            };
+            assert!(parsed.enabled());
+            assert_eq!(parsed.threshold(), -300);
+            assert_eq!(
+                (parsed.attack(), parsed.hold(), parsed.release()),
+                (70, 10, 5000)
+            );
+            parsed.source()
+        };
+
+        let parsed = parse_as_state(
+            limiter(SetFairlightLimiter::source(source)),
+            b"AILP",
+            36,
+            &[(2, 0, 2), (8, 8, 28)],
+        );
+        assert_eq!(check_limiter(parsed), Some(source));
+        let parsed = parse_as_state(
+            limiter(SetFairlightLimiter::master()),
+            b"AMLP",
+            20,
+            &[(1, 0, 1), (4, 4, 16)],
+        );
+        assert_eq!(check_limiter(parsed), None);
+
+        let command = ControlCommand::FairlightExpander(
+            SetFairlightExpander::new(source)
+                .enabled(true)
+                .gate(true)
+                .threshold(-4500)
+                .range(1800)
+                .ratio(200)
+                .attack(140)
+                .hold(30)
+                .release(9300),
+        );
+        let Command::FairlightExpander(expander) =
+            parse_as_state(command, b"AIXP", 40, &[(2, 0, 2), (8, 8, 32)])
+        else {
+            panic!("Not an expander");
+        };
+        assert_eq!(expander.source(), source);
+        assert!(expander.enabled());
+        assert!(expander.gate());
+        assert_eq!(
+            (expander.threshold(), expander.range(), expander.ratio()),
+            (-4500, 1800, 200)
+        );
+        assert_eq!(
+            (expander.attack(), expander.hold(), expander.release()),
+            (140, 30, 9300)
+        );
+    }
+
+    /// Zoom, focus and other values the camera control protocol sends as fixed point
+    #[cfg(feature = "camera")]
+    fn fixed16() -> impl Strategy<Value = f32> {
+        any::<i16>().prop_map(camera::fixed16_to_f32)
+    }
+
+    #[cfg(feature = "camera")]
+    fn camera_parameter() -> impl Strategy<Value = CameraControlParameter> {
+        use crate::camera::{
+            ColorCorrectionParameter as Color, ColorValues, LensParameter as Lens,
+            PtzParameter as Ptz, VideoParameter as Video,
+        };
+        use CameraControlParameter::*;
+
+        prop_oneof![
+            fixed16().prop_map(|focus| Lens(Lens::Focus(focus))),
+            Just(Lens(Lens::AutoFocus)),
+            any::<bool>().prop_map(|enabled| Lens(Lens::OpticalImageStabilisation(enabled))),
+            any::<i16>().prop_map(|zoom| Lens(Lens::Zoom(zoom))),
+            any::<i8>().prop_map(|gain| Video(Video::SensorGain(gain))),
+            (any::<i16>(), any::<i16>()).prop_map(|(temperature, tint)| {
+                Video(Video::ManualWhiteBalance { temperature, tint })
+            }),
+            any::<i32>().prop_map(|exposure| Video(Video::Exposure(exposure))),
+            (fixed16(), fixed16(), fixed16(), fixed16()).prop_map(|(red, green, blue, luma)| {
+                ColorCorrection(Color::Lift(ColorValues {
+                    red,
+                    green,
+                    blue,
+                    luma,
+                }))
+            }),
+            (fixed16(), fixed16())
+                .prop_map(|(pivot, adjust)| ColorCorrection(Color::Contrast { pivot, adjust })),
+            Just(ColorCorrection(Color::Reset)),
+            (fixed16(), fixed16()).prop_map(|(pan, tilt)| Ptz(Ptz::PanTiltVelocity { pan, tilt })),
+            (any::<i8>(), any::<i8>())
+                .prop_map(|(command, slot)| Ptz(Ptz::MemoryPreset { command, slot })),
+            (
+                200u8..,
+                any::<u8>(),
+                proptest::collection::vec(any::<i64>(), 1..4)
+            )
+                .prop_map(|(category, parameter, values)| Other {
+                    category,
+                    parameter,
+                    value: CameraControlValue::Int64(values),
+                }),
+            (200u8.., any::<u8>(), "[ -~]{0,32}").prop_map(|(category, parameter, value)| {
+                Other {
+                    category,
+                    parameter,
+                    value: CameraControlValue::String(value),
+                }
+            }),
+        ]
+    }
+
+    fn keyer_mask() -> impl Strategy<Value = KeyerMask> {
+        (
+            -9000i16..=9000,
+            -9000i16..=9000,
+            -16000i16..=16000,
+            -16000i16..=16000,
+        )
+            .prop_map(|(top, bottom, left, right)| {
+                KeyerMask::new(top, bottom, left, right).unwrap()
+            })
+    }
+
+    fn time() -> impl Strategy<Value = Time> {
+        any::<[u8; 4]>()
+            .prop_map(|[hour, minute, second, frame]| Time::new(hour, minute, second, frame))
+    }
+
+    proptest! {
+        #[test]
+        fn mix_effect_round_trip(
+            me in any::<u8>(),
+            source in any::<u16>(),
+            position in 0u16..=10000,
+            style in any::<u8>().prop_map(TransitionStyle::from),
+            layers in any::<u8>().prop_map(TransitionLayers::from_bits_retain),
+        ) {
+            let command = ControlCommand::ProgramInput(SetProgramInput::new(me, source));
+            let Command::ProgramInput(program) = parse_as_state(command, b"PrgI", 4, &[(0, 0, 4)])
+            else {
+                panic!("Not a program input");
+            };
+            prop_assert_eq!((program.me(), program.source_id()), (me, source));
+
+            let command = ControlCommand::PreviewInput(SetPreviewInput::new(me, source));
+            let Command::PreviewInput(preview) = parse_as_state(command, b"PrvI", 4, &[(0, 0, 4)])
+            else {
+                panic!("Not a preview input");
+            };
+            prop_assert_eq!((preview.me(), preview.source_id()), (me, source));
+
+            let command =
+                ControlCommand::TransitionPosition(SetTransitionPosition::new(me, position));
+            let Command::TransitionPosition(transition) =
+                parse_as_state(command, b"TrPs", 8, &[(0, 0, 1), (2, 4, 2)])
+            else {
+                panic!("Not a transition position");
+            };
+            prop_assert_eq!((transition.me(), transition.position()), (me, position));
+
+            let command = ControlCommand::TransitionStyle(
+                SetTransitionStyle::new(me).style(style).next_transition(layers),
+            );
+            let Command::TransitionStyleSelection(selection) =
+                parse_as_state(command, b"TrSS", 8, &[(1, 0, 1), (2, 3, 1), (3, 4, 1)])
+            else {
+                panic!("Not a transition style");
+            };
+            prop_assert_eq!(selection.me(), me);
+            prop_assert_eq!(selection.next_style(), style);
+            prop_assert_eq!(selection.next_layers(), layers);
+        }
+
+        #[test]
+        fn transition_round_trip(
+            me in any::<u8>(),
+            rate in any::<u8>(),
+            source in any::<u16>(),
+            stinger_rate in any::<u16>(),
+        ) {
+            let command = ControlCommand::TransitionMix(SetTransitionMix::new(me, rate));
+            let Command::TransitionMix(mix) = parse_as_state(command, b"TMxP", 4, &[(0, 0, 2)])
+            else {
+                panic!("Not a mix transition");
+            };
+            prop_assert_eq!((mix.me(), mix.rate()), (me, rate));
+
+            let command =
+                ControlCommand::TransitionDip(SetTransitionDip::new(me).rate(rate).source(source));
+            let Command::TransitionDip(dip) =
+                parse_as_state(command, b"TDpP", 4, &[(1, 0, 2), (4, 2, 2)])
+            else {
+                panic!("Not a dip transition");
+            };
+            prop_assert_eq!((dip.me(), dip.rate(), dip.source()), (me, rate, source));
+
+            let command = ControlCommand::TransitionWipeRate(SetTransitionWipeRate::new(me, rate));
+            let Command::TransitionWipe(wipe) = parse_as_state(command, b"TWpP", 20, &[(2, 0, 2)])
+            else {
+                panic!("Not a wipe transition");
+            };
+            prop_assert_eq!((wipe.me(), wipe.rate()), (me, rate));
+
+            let command = ControlCommand::TransitionDVERate(SetTransitionDVERate::new(me, rate));
+            let Command::TransitionDVE(dve) = parse_as_state(command, b"TDvP", 20, &[(2, 0, 2)])
+            else {
+                panic!("Not a DVE transition");
+            };
+            prop_assert_eq!((dve.me(), dve.rate()), (me, rate));
+
+            let command = ControlCommand::TransitionStingerRate(SetTransitionStingerRate::new(
+                me,
+                stinger_rate,
+            ));
+            let Command::TransitionStinger(stinger) =
+                parse_as_state(command, b"TStP", 20, &[(2, 0, 1), (18, 16, 2)])
+            else {
+                panic!("Not a stinger transition");
+            };
+            prop_assert_eq!((stinger.me(), stinger.rate()), (me, stinger_rate));
+        }
+
+        #[test]
+        fn keyer_round_trip(
+            me in any::<u8>(),
+            keyer in any::<u8>(),
+            on_air in any::<bool>(),
+            fill in any::<u16>(),
+            cut in any::<u16>(),
+            key_type in any::<u8>().prop_map(KeyType::from),
+            flying_key in any::<bool>(),
+            mask_enabled in any::<bool>(),
+            mask in keyer_mask(),
+        ) {
+            let command = ControlCommand::KeyerOnAir(SetKeyerOnAir::new(me, keyer, on_air));
+            let Command::KeyerOnAir(state) = parse_as_state(command, b"KeOn", 4, &[(0, 0, 3)])
+            else {
+                panic!("Not a keyer on air state");
+            };
+            prop_assert_eq!((state.me(), state.keyer(), state.on_air()), (me, keyer, on_air));
+
+            let base = |command: ControlCommand, fields: &[(usize, usize, usize)]| {
+                let Command::KeyerBaseProperties(properties) =
+                    parse_as_state(command, b"KeBP", 24, fields)
+                else {
+                    panic!("Not keyer base properties");
+                };
+                assert_eq!((properties.me(), properties.keyer()), (me, keyer));
+                properties
+            };
+
+            let command = ControlCommand::KeyerFillSource(SetKeyerFillSource::new(me, keyer, fill));
+            prop_assert_eq!(base(command, &[(0, 0, 2), (2, 8, 2)]).fill_source(), fill);
+
+            let command = ControlCommand::KeyerCutSource(SetKeyerCutSource::new(me, keyer, cut));
+            prop_assert_eq!(base(command, &[(0, 0, 2), (2, 10, 2)]).cut_source(), cut);
+
+            let command = ControlCommand::KeyerType(
+                SetKeyerType::new(me, keyer).key_type(key_type).flying_key(flying_key),
+            );
+            let properties = base(command, &[(1, 0, 2), (3, 2, 1), (4, 6, 1)]);
+            prop_assert_eq!(properties.key_type(), key_type);
+            prop_assert_eq!(properties.fly_enabled(), flying_key);
+
+            let command = ControlCommand::KeyerMask(
+                SetKeyerMask::new(me, keyer).enabled(mask_enabled).edges(mask),
+            );
+            let properties = base(command, &[(1, 0, 2), (3, 12, 1), (4, 14, 8)]);
+            prop_assert_eq!(properties.mask_enabled(), mask_enabled);
+            prop_assert_eq!(properties.mask(), mask);
+        }
+
+        #[test]
+        fn keyer_keyframe_round_trip(
+            me in any::<u8>(),
+            keyer in any::<u8>(),
+            keyframe in any::<u8>().prop_map(Keyframe::from),
+            size in any::<(i32, i32)>(),
+            position in any::<(i32, i32)>(),
+            rotation in any::<i32>(),
+            border_width in any::<(u16, u16)>(),
+            border_softness in any::<(u8, u8)>(),
+            border_bevel in any::<(u8, u8)>(),
+            border_opacity in any::<u8>(),
+            border_color in any::<(u16, u16, u16)>(),
+            light_source in any::<(u16, u8)>(),
+            mask_enabled in any::<bool>(),
+            mask in keyer_mask(),
+        ) {
+            let border_color = HslColor::new(border_color.0, border_color.1, border_color.2);
+            let command = ControlCommand::KeyerKeyframe(
+                SetKeyerKeyframe::new(me, keyer, keyframe)
+                    .size(size.0, size.1)
+                    .position(position.0, position.1)
+                    .rotation(rotation)
+                    .border_width(border_width.0, border_width.1)
+                    .border_softness(border_softness.0, border_softness.1)
+                    .border_bevel(border_bevel.0, border_bevel.1)
+                    .border_opacity(border_opacity)
+                    .border_color(border_color)
+                    .light_source(light_source.0, light_source.1)
+                    .mask_enabled(mask_enabled)
+                    .mask_edges(mask),
+            );
+            let Command::KeyerKeyframe(parsed) = parse_as_state(command, b"KKFP", 52, &[(4, 0, 52)])
+            else {
+                panic!("Not a keyer keyframe");
+            };
+            prop_assert_eq!((parsed.me(), parsed.keyer()), (me, keyer));
+            prop_assert_eq!(parsed.keyframe(), keyframe);
+            prop_assert_eq!(parsed.size(), size);
+            prop_assert_eq!(parsed.position(), position);
+            prop_assert_eq!(parsed.rotation(), rotation);
+            prop_assert_eq!(parsed.border_width(), border_width);
+            prop_assert_eq!(parsed.border_softness(), border_softness);
+            prop_assert_eq!(parsed.border_bevel(), border_bevel);
+            prop_assert_eq!(parsed.border_opacity(), border_opacity);
+            prop_assert_eq!(parsed.border_color(), border_color);
+            prop_assert_eq!(parsed.light_source(), light_source);
+            prop_assert_eq!(parsed.mask_enabled(), mask_enabled);
+            prop_assert_eq!(parsed.mask(), mask);
+        }
+
+        #[test]
+        fn downstream_keyer_round_trip(
+            keyer in any::<u8>(),
+            fill in any::<u16>(),
+            cut in any::<u16>(),
+            on_air in any::<bool>(),
+        ) {
+            let command = ControlCommand::DownstreamKeyerFillSource(
+                SetDownstreamKeyerFillSource::new(keyer, fill),
+            );
+            let Command::DownstreamKeyerSources(sources) =
+                parse_as_state(command, b"DskB", 8, &[(0, 0, 1), (2, 2, 2)])
+            else {
+                panic!("Not downstream keyer sources");
+            };
+            prop_assert_eq!((sources.keyer(), sources.fill_source()), (keyer, fill));
+
+            let command = ControlCommand::DownstreamKeyerCutSource(
+                SetDownstreamKeyerCutSource::new(keyer, cut),
+            );
+            let Command::DownstreamKeyerSources(sources) =
+                parse_as_state(command, b"DskB", 8, &[(0, 0, 1), (2, 4, 2)])
+            else {
+                panic!("Not downstream keyer sources");
+            };
+            prop_assert_eq!((sources.keyer(), sources.cut_source()), (keyer, cut));
+
+            let command =
+                ControlCommand::DownstreamKeyerOnAir(SetDownstreamKeyerOnAir::new(keyer, on_air));
+            let Command::DownstreamKeyerState(state) =
+                parse_as_state(command, b"DskS", 8, &[(0, 0, 2)])
+            else {
+                panic!("Not a downstream keyer state");
+            };
+            prop_assert_eq!((state.keyer(), state.on_air()), (keyer, on_air));
+        }
+
+        #[cfg(feature = "camera")]
+        #[test]
+        fn camera_control_round_trip(
+            input in any::<u8>(),
+            parameter in camera_parameter(),
+            relative in any::<bool>(),
+        ) {
+            let command = if relative {
+                SetCameraControl::new_relative(input, parameter.clone())
+            } else {
+                SetCameraControl::new(input, parameter.clone())
+            };
+            let command = ControlCommand::CameraControl(command);
+            let size = command.serialize().len() - 8;
+            let Command::CameraControl(camera) = parse_as_state(
+                command,
+                b"CCdP",
+                size,
+                &[(0, 0, 3), (4, 3, 1), (6, 4, 10), (16, 16, size - 16)],
+            ) else {
+                panic!("Not a camera control");
+            };
+            prop_assert_eq!(camera.input(), input);
+            prop_assert_eq!(camera.parameter(), &parameter);
+        }
+
+        #[cfg(feature = "streaming")]
+        #[test]
+        fn streaming_round_trip(
+            name in "[ -~]{0,64}",
+            url in "[ -~]{0,512}",
+            key in "[ -~]{0,512}",
+            video_bitrates in any::<[u32; 2]>(),
+            audio_bitrates in any::<[u32; 2]>(),
+            username in "[ -~]{0,64}",
+            password in "[ -~]{0,64}",
+            low_latency in any::<bool>(),
+        ) {
+            let command = ControlCommand::StreamingService(
+                SetStreamingService::new()
+                    .name(&name)
+                    .url(&url)
+                    .key(&key)
+                    .video_bitrates(video_bitrates[0], video_bitrates[1]),
+            );
+            let Command::StreamingService(service) =
+                parse_as_state(command, b"SRSU", 1096, &[(1, 0, 1088), (1092, 1088, 8)])
+            else {
+                panic!("Not a streaming service");
+            };
+            prop_assert_eq!(service.name(), Some(name.as_str()));
+            prop_assert_eq!(service.url(), Some(url.as_str()));
+            prop_assert_eq!(service.key(), Some(key.as_str()));
+            prop_assert_eq!(service.video_bitrates(), video_bitrates);
+
+            let command = ControlCommand::StreamingAudioBitrates(SetStreamingAudioBitrates::new(
+                audio_bitrates[0],
+                audio_bitrates[1],
+            ));
+            let Command::StreamingAudioBitrates(bitrates) =
+                parse_as_state(command, b"STAB", 8, &[(0, 0, 8)])
+            else {
+                panic!("Not streaming audio bitrates");
+            };
+            prop_assert_eq!(bitrates.bitrates(), audio_bitrates);
+
+            let command = ControlCommand::StreamingAuthentication(
+                SetStreamingAuthentication::new(&username, &password),
+            );
+            let Command::StreamingAuthentication(authentication) =
+                parse_as_state(command, b"SAth", 128, &[(0, 0, 128)])
+            else {
+                panic!("Not streaming authentication");
+            };
+            prop_assert_eq!(authentication.username(), Some(username.as_str()));
+            prop_assert_eq!(authentication.password(), Some(password.as_str()));
+
+            let command =
+                ControlCommand::StreamingLowLatency(SetStreamingLowLatency::new(low_latency));
+            let Command::StreamingLowLatency(state) =
+                parse_as_state(command, b"SLow", 4, &[(0, 0, 1)])
+            else {
+                panic!("Not a streaming low latency state");
+            };
+            prop_assert_eq!(state.enabled(), low_latency);
+        }
+
+        #[cfg(feature = "recording")]
+        #[test]
+        fn recording_settings_round_trip(
+            filename in "[ -~]{0,128}",
+            working_set in any::<(u32, u32)>(),
+            record_all_inputs in any::<bool>(),
+        ) {
+            let command = ControlCommand::RecordingSettings(
+                SetRecordingSettings::new()
+                    .filename(&filename)
+                    .working_set(working_set.0, working_set.1)
+                    .record_all_inputs(record_all_inputs),
+            );
+            let Command::RecordingSettings(settings) =
+                parse_as_state(command, b"RMSu", 140, &[(1, 0, 128), (132, 128, 9)])
+            else {
+                panic!("Not recording settings");
+            };
+            prop_assert_eq!(settings.filename(), Some(filename.as_str()));
+            prop_assert_eq!(settings.working_set(), working_set);
+            prop_assert_eq!(settings.record_all_inputs(), record_all_inputs);
+        }
+
+        #[cfg(feature = "audio")]
+        #[test]
+        fn audio_round_trip(
+            output in any::<u8>(),
+            mode in any::<u8>().prop_map(MixMinusMode::from),
+            channel in any::<u8>().prop_map(TalkbackChannel::from),
+            input in any::<u16>(),
+            mute_sdi in any::<bool>(),
+            configuration in any::<u8>().prop_map(InputConfigurations::from_bits_retain),
+            level in any::<u8>().prop_map(InputLevels::from_bits_retain),
+            mix_option in any::<u8>(),
+            gain in any::<i32>(),
+            balance in any::<i16>(),
+            source in any::<i64>(),
+        ) {
+            let command = ControlCommand::MixMinusOutput(SetMixMinusOutput::new(output, mode));
+            let Command::MixMinusOutput(mix_minus) =
+                parse_as_state(command, b"MMOP", 8, &[(1, 1, 1), (2, 3, 1)])
+            else {
+                panic!("Not a mix minus output");
+            };
+            prop_assert_eq!(mix_minus.output(), output as u16);
+            prop_assert_eq!(mix_minus.mode(), mode);
+
+            let command =
+                ControlCommand::TalkbackProperties(SetTalkbackProperties::new(channel, mute_sdi));
+            let Command::TalkbackProperties(talkback) =
+                parse_as_state(command, b"TkMP", 4, &[(1, 0, 2)])
+            else {
+                panic!("Not talkback properties");
+            };
+            prop_assert_eq!((talkback.channel(), talkback.mute_sdi()), (channel, mute_sdi));
+
+            let command = ControlCommand::TalkbackInputProperties(
+                SetTalkbackInputProperties::new(channel, input, mute_sdi),
+            );
+            let Command::TalkbackInputProperties(talkback) =
+                parse_as_state(command, b"TkIP", 8, &[(1, 0, 1), (2, 2, 2), (4, 5, 1)])
+            else {
+                panic!("Not talkback input properties");
+            };
+            prop_assert_eq!(talkback.channel(), channel);
+            prop_assert_eq!(talkback.input(), input);
+            prop_assert_eq!(talkback.mute_sdi(), mute_sdi);
+
+            let command = ControlCommand::FairlightInputProperties(
+                SetFairlightInputProperties::new(input)
+                    .configuration(configuration)
+                    .level(level),
+            );
+            let Command::FairlightInputProperties(properties) =
+                parse_as_state(command, b"FAIP", 16, &[(2, 0, 2), (4, 10, 1), (5, 12, 1)])
+            else {
+                panic!("Not Fairlight input properties");
+            };
+            prop_assert_eq!(properties.input(), input);
+            prop_assert_eq!(properties.configuration(), configuration);
+            prop_assert_eq!(properties.level(), level);
+
+            let command = ControlCommand::AudioMixerInput(
+                SetAudioMixerInput::new(input)
+                    .mix_option(AudioMixOption::from(mix_option))
+                    .gain(gain as u16)
+                    .balance(balance),
+            );
+            let Command::AudioMixerInput(fader) =
+                parse_as_state(command, b"AMIP", 16, &[(2, 0, 2), (4, 8, 1), (6, 10, 4)])
+            else {
+                panic!("Not an audio mixer input");
+            };
+            prop_assert_eq!(fader.input(), input);
+            prop_assert_eq!(fader.mix_option(), AudioMixOption::from(mix_option));
+            prop_assert_eq!((fader.gain(), fader.balance()), (gain as u16, balance));
+
+            let command = ControlCommand::AudioMixerMaster(
+                SetAudioMixerMaster::new().gain(gain as u16).balance(balance),
+            );
+            let Command::AudioMixerMaster(fader) =
+                parse_as_state(command, b"AMMO", 8, &[(2, 0, 4)])
+            else {
+                panic!("Not an audio mixer master");
+            };
+            prop_assert_eq!((fader.gain(), fader.balance()), (gain as u16, balance));
+
+            let id = AudioSourceId::new(input, ChannelSplit::from(source));
+            let command = ControlCommand::FairlightSourceProperties(
+                SetFairlightSourceProperties::new(id)
+                    .gain(gain)
+                    .balance(balance)
+                    .fader_gain(gain)
+                    .mix_option(FairlightMixOption::from(mix_option)),
+            );
+            let Command::FairlightSourceProperties(fader) = parse_as_state(
+                command,
+                b"FASP",
+                52,
+                &[(2, 0, 2), (8, 8, 8), (20, 20, 4), (36, 40, 2), (40, 44, 4), (44, 49, 1)],
+            ) else {
+                panic!("Not Fairlight source properties");
+            };
+            prop_assert_eq!(fader.source(), id);
+            prop_assert_eq!((fader.gain(), fader.balance()), (gain, balance));
+            prop_assert_eq!(fader.fader_gain(), gain);
+            prop_assert_eq!(fader.mix_option(), FairlightMixOption::from(mix_option));
+
+            let command = ControlCommand::FairlightMasterProperties(
+                SetFairlightMasterProperties::new().fader_gain(gain),
+            );
+            let Command::FairlightMasterProperties(fader) =
+                parse_as_state(command, b"FAMP", 20, &[(12, 12, 4)])
+            else {
+                panic!("Not Fairlight master properties");
+            };
+            prop_assert_eq!(fader.fader_gain(), gain);
+        }
+
+        #[test]
+        fn display_clock_round_trip(
+            clock in any::<u8>(),
+            enabled in any::<bool>(),
+            size in any::<u8>(),
+            opacity in any::<u8>(),
+            position in any::<(i16, i16)>(),
+            auto_hide in any::<bool>(),
+            start_from in time(),
+            mode in any::<u8>().prop_map(DisplayClockMode::from),
+            state in any::<u8>().prop_map(DisplayClockState::from),
+            current in time(),
+        ) {
+            let command = ControlCommand::DisplayClockProperties(
+                SetDisplayClockProperties::new(clock)
+                    .enabled(enabled)
+                    .size(size)
+                    .opacity(opacity)
+                    .position(position.0, position.1)
+                    .auto_hide(auto_hide)
+                    .start_from(start_from)
+                    .mode(mode),
+            );
+            let Command::DisplayClockProperties(properties) = parse_as_state(
+                command,
+                b"DCPV",
+                20,
+                &[(2, 0, 2), (4, 3, 1), (5, 5, 1), (6, 6, 10)],
+            ) else {
+                panic!("Not display clock properties");
+            };
+            prop_assert_eq!(properties.clock(), clock);
+            prop_assert_eq!(properties.enabled(), enabled);
+            prop_assert_eq!(properties.size(), size);
+            prop_assert_eq!(properties.opacity(), opacity);
+            prop_assert_eq!(properties.position(), position);
+            prop_assert_eq!(properties.auto_hide(), auto_hide);
+            prop_assert_eq!(properties.start_from(), start_from);
+            prop_assert_eq!(properties.mode(), mode);
+
+            let command =
+                ControlCommand::DisplayClockState(SetDisplayClockState::new(clock, state));
+            let Command::DisplayClockProperties(properties) =
+                parse_as_state(command, b"DCPV", 20, &[(0, 0, 1), (1, 16, 1)])
+            else {
+                panic!("Not display clock properties");
+            };
+            prop_assert_eq!((properties.clock(), properties.state()), (clock, state));
+
+            let command =
+                ControlCommand::DisplayClockTime(SetDisplayClockTime::new(clock, current));
+            let Command::DisplayClockTime(time) = parse_as_state(command, b"DSTV", 8, &[(0, 0, 5)])
+            else {
+                panic!("Not a display clock time");
+            };
+            prop_assert_eq!((time.clock(), time.time()), (clock, current));
+        }
+
+        #[test]
+        fn system_round_trip(
+            video_mode in any::<u8>().prop_map(VideoMode::from),
+            down_convert_mode in any::<u8>().prop_map(DownConvertMode::from),
+            timecode_type in any::<u8>().prop_map(TimeCodeType::from),
+            id in any::<u16>(),
+            long_name in "[ -~]{0,20}",
+            short_name in "[ -~]{0,4}",
+            port in any::<u16>().prop_map(Input::from),
+        ) {
+            let command = ControlCommand::VideoMode(SetVideoMode::new(video_mode));
+            let Command::VideoMode(mode) = parse_as_state(command, b"VidM", 4, &[(0, 0, 1)]) else {
+                panic!("Not a video mode");
+            };
+            prop_assert_eq!(mode, video_mode);
+
+            let command =
+                ControlCommand::DownConvertMode(SetDownConvertMode::new(down_convert_mode));
+            let Command::DownConvertMode(mode) = parse_as_state(command, b"DcOt", 4, &[(0, 0, 1)])
+            else {
+                panic!("Not a down convert mode");
+            };
+            prop_assert_eq!(mode, down_convert_mode);
+
+            let command = ControlCommand::TimeCodeMode(SetTimeCodeMode::new(timecode_type));
+            let Command::TimeCodeState(state) = parse_as_state(command, b"TCCc", 4, &[(0, 0, 1)])
+            else {
+                panic!("Not a time code state");
+            };
+            prop_assert_eq!(state.timecode_type(), timecode_type);
+
+            let command = ControlCommand::InputProperties(
+                SetInputProperties::new(id)
+                    .long_name(&long_name)
+                    .unwrap()
+                    .short_name(&short_name)
+                    .unwrap()
+                    .external_port_type(port),
+            );
+            let Command::Source(source) =
+                parse_as_state(command, b"InPr", 36, &[(2, 0, 26), (28, 30, 2)])
+            else {
+                panic!("Not a source");
+            };
+            prop_assert_eq!(source.id(), id);
+            prop_assert_eq!(source.long_name().unwrap_or_default(), long_name);
+            prop_assert_eq!(source.short_name().unwrap_or_default(), short_name);
+            prop_assert_eq!(source.active_input(), port);
+        }
+
+        #[test]
+        fn player_round_trip(
+            player in any::<u8>(),
+            index in any::<u8>(),
+            clip in any::<bool>(),
+            playing in any::<bool>(),
+            looping in any::<bool>(),
+            frame in any::<u16>(),
+            id in any::<u16>(),
+            state in any::<u8>().prop_map(HyperDeckState::from),
+            single_clip in any::<bool>(),
+            speed in any::<i16>(),
+        ) {
+            let source = SetMediaPlayerSource::new(player);
+            let source = if clip { source.clip(index) } else { source.still(index) };
+            let command = ControlCommand::MediaPlayerSource(source);
+            let Command::MediaPlayerSource(source) =
+                parse_as_state(command, b"MPCE", 4, &[(1, 0, 4)])
+            else {
+                panic!("Not a media player source");
+            };
+            prop_assert_eq!(source.player(), player);
+            if clip {
+                prop_assert_eq!(source.source_type(), MediaPlayerSourceType::Clip);
+                prop_assert_eq!(source.clip_index(), index);
+            } else {
+                prop_assert_eq!(source.source_type(), MediaPlayerSourceType::Still);
+                prop_assert_eq!(source.still_index(), index);
+            }
+
+            let command = ControlCommand::MediaPlayerStatus(
+                SetMediaPlayerStatus::new(player)
+                    .playing(playing)
+                    .looping(looping)
+                    .clip_frame(frame),
+            );
+            let Command::MediaPlayerStatus(status) =
+                parse_as_state(command, b"RCPS", 8, &[(1, 0, 4), (6, 4, 2)])
+            else {
+                panic!("Not a media player status");
+            };
+            prop_assert_eq!(status.player(), player);
+            prop_assert_eq!((status.playing(), status.looping()), (playing, looping));
+            prop_assert!(!status.at_beginning());
+            prop_assert_eq!(status.clip_frame(), frame);
+
+            let command = ControlCommand::HyperDeckPlayer(
+                SetHyperDeckPlayer::new(id)
+                    .state(state)
+                    .single_clip(single_clip)
+                    .looping(looping)
+                    .speed(speed),
+            );
+            let Command::HyperDeckPlayer(hyperdeck) =
+                parse_as_state(command, b"RXCP", 16, &[(2, 0, 2), (4, 2, 3), (8, 6, 2)])
+            else {
+                panic!("Not a HyperDeck player");
+            };
+            prop_assert_eq!((hyperdeck.id(), hyperdeck.state()), (id, state));
+            prop_assert_eq!((hyperdeck.single_clip(), hyperdeck.looping()), (single_clip, looping));
+            prop_assert_eq!(hyperdeck.speed(), speed);
+        }
+    }
+
+    #[cfg(feature = "arbitrary")]
+    proptest! {
+        #[test]
+        fn control_command_framing(data in proptest::collection::vec(any::<u8>(), 0..1024)) {
+            let Ok(command) = ControlCommand::arbitrary(&mut Unstructured::new(&data)) else {
+                return Ok(());
+            };
+
+            let serialized = command.serialize();
+            let mut payload = serialized.clone();
+            let _ = Command::parse(&mut payload);
+
+            prop_assert_eq!(
+                u16::from_be_bytes([serialized[0], serialized[1]]) as usize,
+                serialized.len()
+            );
+            prop_assert_eq!(&serialized[4..8], &command.name()[..]);
+            prop_assert!(payload.is_empty());
+        }
+    }
+}
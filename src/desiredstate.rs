@@ -0,0 +1,559 @@
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec::Vec,
+};
+
+#[cfg(feature = "audio")]
+use crate::{
+    audio::{
+        AudioMixerInput, AudioMixerMaster, AudioMonitor, SetAudioMixerInput, SetAudioMixerMaster,
+        SetAudioMonitor,
+    },
+    fairlight::{
+        AudioSourceId, FairlightMasterProperties, FairlightSourceProperties, InputLevels,
+        SetFairlightInputProperties, SetFairlightMasterProperties, SetFairlightSourceProperties,
+    },
+};
+use crate::{
+    color::{HslColor, SetColorGenerator},
+    command::{SetAuxSource, SetPreviewInput, SetProgramInput},
+    control::ControlCommand,
+    keyer::{
+        SetDownstreamKeyerCutSource, SetDownstreamKeyerFillSource, SetDownstreamKeyerOnAir,
+        SetKeyerCutSource, SetKeyerFillSource, SetKeyerOnAir,
+    },
+    source::SetInputProperties,
+    supersource::{SetSuperSourceBox, SuperSourceBox},
+    systeminfo::SystemInfo,
+    transition::{SetTransitionStyle, TransitionLayers, TransitionStyle},
+    Error,
+};
+
+#[derive(Debug, Clone)]
+struct InputNames {
+    long_name: String,
+    short_name: String,
+    command: SetInputProperties,
+}
+
+/// State the switcher should be brought to, e.g. the configuration of a show. Only the parts
+/// that are set are compared with the current state, see [`DesiredState::commands`].
+#[derive(Debug, Clone, Default)]
+pub struct DesiredState {
+    input_names: BTreeMap<u16, InputNames>,
+    color_generators: BTreeMap<u8, HslColor>,
+    transition_styles: BTreeMap<u8, TransitionStyle>,
+    transition_layers: BTreeMap<u8, TransitionLayers>,
+    keyer_fill_sources: BTreeMap<(u8, u8), u16>,
+    keyer_cut_sources: BTreeMap<(u8, u8), u16>,
+    keyers_on_air: BTreeMap<(u8, u8), bool>,
+    downstream_keyer_fill_sources: BTreeMap<u8, u16>,
+    downstream_keyer_cut_sources: BTreeMap<u8, u16>,
+    downstream_keyers_on_air: BTreeMap<u8, bool>,
+    supersource_boxes: BTreeMap<(u8, u8), SuperSourceBox>,
+    #[cfg(feature = "audio")]
+    audio_monitor: Option<AudioMonitor>,
+    #[cfg(feature = "audio")]
+    audio_mixer_inputs: BTreeMap<u16, AudioMixerInput>,
+    #[cfg(feature = "audio")]
+    audio_mixer_master: Option<AudioMixerMaster>,
+    #[cfg(feature = "audio")]
+    fairlight_input_levels: BTreeMap<u16, InputLevels>,
+    #[cfg(feature = "audio")]
+    fairlight_sources: BTreeMap<AudioSourceId, FairlightSourceProperties>,
+    #[cfg(feature = "audio")]
+    fairlight_master: Option<FairlightMasterProperties>,
+    preview_inputs: BTreeMap<u8, u16>,
+    program_inputs: BTreeMap<u8, u16>,
+    aux_sources: BTreeMap<u8, u16>,
+}
+
+impl DesiredState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Names of an input, the long name is at most 20 bytes and the short name at most 4 bytes
+    pub fn input_names(
+        mut self,
+        input: u16,
+        long_name: &str,
+        short_name: &str,
+    ) -> Result<Self, Error> {
+        let command = SetInputProperties::new(input)
+            .long_name(long_name)?
+            .short_name(short_name)?;
+
+        self.input_names.insert(
+            input,
+            InputNames {
+                long_name: long_name.to_string(),
+                short_name: short_name.to_string(),
+                command,
+            },
+        );
+        Ok(self)
+    }
+
+    pub fn color_generator(mut self, index: u8, color: HslColor) -> Self {
+        self.color_generators.insert(index, color);
+        self
+    }
+
+    /// Style of the next transition of the mix effect block
+    pub fn transition_style(mut self, me: u8, style: TransitionStyle) -> Self {
+        self.transition_styles.insert(me, style);
+        self
+    }
+
+    /// Layers included in the next transition of the mix effect block
+    pub fn transition_layers(mut self, me: u8, layers: TransitionLayers) -> Self {
+        self.transition_layers.insert(me, layers);
+        self
+    }
+
+    pub fn keyer_fill_source(mut self, me: u8, keyer: u8, source: u16) -> Self {
+        self.keyer_fill_sources.insert((me, keyer), source);
+        self
+    }
+
+    pub fn keyer_cut_source(mut self, me: u8, keyer: u8, source: u16) -> Self {
+        self.keyer_cut_sources.insert((me, keyer), source);
+        self
+    }
+
+    /// Whether an upstream keyer is on air, switched without a transition
+    pub fn keyer_on_air(mut self, me: u8, keyer: u8, on_air: bool) -> Self {
+        self.keyers_on_air.insert((me, keyer), on_air);
+        self
+    }
+
+    pub fn downstream_keyer_fill_source(mut self, keyer: u8, source: u16) -> Self {
+        self.downstream_keyer_fill_sources.insert(keyer, source);
+        self
+    }
+
+    pub fn downstream_keyer_cut_source(mut self, keyer: u8, source: u16) -> Self {
+        self.downstream_keyer_cut_sources.insert(keyer, source);
+        self
+    }
+
+    /// Whether a downstream keyer is on air, switched without a transition
+    pub fn downstream_keyer_on_air(mut self, keyer: u8, on_air: bool) -> Self {
+        self.downstream_keyers_on_air.insert(keyer, on_air);
+        self
+    }
+
+    /// Source and layout of a box of a SuperSource, e.g. as reported by
+    /// [`SystemInfo::supersource_box`]
+    pub fn supersource_box(mut self, ssbox: SuperSourceBox) -> Self {
+        self.supersource_boxes
+            .insert((ssbox.supersource(), ssbox.index()), ssbox);
+        self
+    }
+
+    #[cfg(feature = "audio")]
+    /// Settings of the classic audio monitor output, e.g. as reported by
+    /// [`SystemInfo::audio_monitor`]
+    pub fn audio_monitor(mut self, monitor: AudioMonitor) -> Self {
+        self.audio_monitor = Some(monitor);
+        self
+    }
+
+    #[cfg(feature = "audio")]
+    /// Mix option, fader gain and balance of an input of the classic audio mixer, e.g. as
+    /// reported by [`SystemInfo::audio_mixer_input`]
+    pub fn audio_mixer_input(mut self, input: AudioMixerInput) -> Self {
+        self.audio_mixer_inputs.insert(input.input(), input);
+        self
+    }
+
+    #[cfg(feature = "audio")]
+    /// Fader gain and balance of the classic audio mixer master, e.g. as reported by
+    /// [`SystemInfo::audio_mixer_master`]
+    pub fn audio_mixer_master(mut self, master: AudioMixerMaster) -> Self {
+        self.audio_mixer_master = Some(master);
+        self
+    }
+
+    #[cfg(feature = "audio")]
+    /// Mic or line level of an analog Fairlight input
+    pub fn fairlight_input_level(mut self, input: u16, level: InputLevels) -> Self {
+        self.fairlight_input_levels.insert(input, level);
+        self
+    }
+
+    #[cfg(feature = "audio")]
+    /// Mix option, fader gain and balance of a Fairlight source, e.g. as reported by
+    /// [`SystemInfo::fairlight_source`]
+    pub fn fairlight_source(mut self, properties: FairlightSourceProperties) -> Self {
+        self.fairlight_sources
+            .insert(properties.source(), properties);
+        self
+    }
+
+    #[cfg(feature = "audio")]
+    /// Fader gain of the Fairlight master, e.g. as reported by [`SystemInfo::fairlight_master`]
+    pub fn fairlight_master(mut self, properties: FairlightMasterProperties) -> Self {
+        self.fairlight_master = Some(properties);
+        self
+    }
+
+    pub fn preview_input(mut self, me: u8, source: u16) -> Self {
+        self.preview_inputs.insert(me, source);
+        self
+    }
+
+    pub fn program_input(mut self, me: u8, source: u16) -> Self {
+        self.program_inputs.insert(me, source);
+        self
+    }
+
+    pub fn aux_source(mut self, aux: u8, source: u16) -> Self {
+        self.aux_sources.insert(aux, source);
+        self
+    }
+
+    /// Commands changing the parts of `system_info` that differ from the desired state, in the
+    /// order they should be sent. Parts the switcher hasn't reported yet are always set.
+    /// Inputs are named and keyers, SuperSources and transitions set up before any source is
+    /// routed, keyers are taken on or off air once the program is routed.
+    pub fn commands(&self, system_info: &SystemInfo) -> Vec<ControlCommand> {
+        let mut commands = Vec::new();
+
+        for (&input, names) in &self.input_names {
+            let source = system_info.source(input);
+            let current = source.map(|source| (source.long_name(), source.short_name()));
+            let desired = (
+                Some(names.long_name.as_str()).filter(|name| !name.is_empty()),
+                Some(names.short_name.as_str()).filter(|name| !name.is_empty()),
+            );
+            if current != Some(desired) {
+                commands.push(ControlCommand::InputProperties(names.command.clone()));
+            }
+        }
+
+        for (&index, &color) in &self.color_generators {
+            if system_info.color_generator(index) != Some(color) {
+                commands.push(ControlCommand::ColorGenerator(
+                    SetColorGenerator::new(index).color(color),
+                ));
+            }
+        }
+
+        let transition_mes = self
+            .transition_styles
+            .keys()
+            .chain(self.transition_layers.keys())
+            .copied()
+            .collect::<BTreeSet<u8>>();
+        for me in transition_mes {
+            let current = system_info.transition_style(me);
+            let mut style = SetTransitionStyle::new(me);
+            let mut changed = false;
+            if let Some(&next_style) = self.transition_styles.get(&me) {
+                if current.map(|current| current.next_style()) != Some(next_style) {
+                    style = style.style(next_style);
+                    changed = true;
+                }
+            }
+            if let Some(&layers) = self.transition_layers.get(&me) {
+                if current.map(|current| current.next_layers()) != Some(layers) {
+                    style = style.next_transition(layers);
+                    changed = true;
+                }
+            }
+            if changed {
+                commands.push(ControlCommand::TransitionStyle(style));
+            }
+        }
+
+        for (&(me, keyer), &source) in &self.keyer_fill_sources {
+            let current = system_info
+                .keyer(me, keyer)
+                .map(|keyer| keyer.fill_source());
+            if current != Some(source) {
+                commands.push(ControlCommand::KeyerFillSource(SetKeyerFillSource::new(
+                    me, keyer, source,
+                )));
+            }
+        }
+        for (&(me, keyer), &source) in &self.keyer_cut_sources {
+            let current = system_info.keyer(me, keyer).map(|keyer| keyer.cut_source());
+            if current != Some(source) {
+                commands.push(ControlCommand::KeyerCutSource(SetKeyerCutSource::new(
+                    me, keyer, source,
+                )));
+            }
+        }
+
+        for (&keyer, &source) in &self.downstream_keyer_fill_sources {
+            let current = system_info
+                .downstream_keyer_sources(keyer)
+                .map(|sources| sources.fill_source());
+            if current != Some(source) {
+                commands.push(ControlCommand::DownstreamKeyerFillSource(
+                    SetDownstreamKeyerFillSource::new(keyer, source),
+                ));
+            }
+        }
+        for (&keyer, &source) in &self.downstream_keyer_cut_sources {
+            let current = system_info
+                .downstream_keyer_sources(keyer)
+                .map(|sources| sources.cut_source());
+            if current != Some(source) {
+                commands.push(ControlCommand::DownstreamKeyerCutSource(
+                    SetDownstreamKeyerCutSource::new(keyer, source),
+                ));
+            }
+        }
+
+        for ssbox in self.supersource_boxes.values() {
+            if let Some(set) = supersource_box_changes(
+                system_info.supersource_box(ssbox.supersource(), ssbox.index()),
+                ssbox,
+            ) {
+                commands.push(ControlCommand::SuperSourceBox(set));
+            }
+        }
+
+        #[cfg(feature = "audio")]
+        self.audio_commands(system_info, &mut commands);
+
+        for (&me, &source) in &self.preview_inputs {
+            if system_info.preview_input(me) != Some(source) {
+                commands.push(ControlCommand::PreviewInput(SetPreviewInput::new(
+                    me, source,
+                )));
+            }
+        }
+        for (&me, &source) in &self.program_inputs {
+            if system_info.program_input(me) != Some(source) {
+                commands.push(ControlCommand::ProgramInput(SetProgramInput::new(
+                    me, source,
+                )));
+            }
+        }
+
+        for (&(me, keyer), &on_air) in &self.keyers_on_air {
+            if system_info.keyer_on_air(me, keyer) != Some(on_air) {
+                commands.push(ControlCommand::KeyerOnAir(SetKeyerOnAir::new(
+                    me, keyer, on_air,
+                )));
+            }
+        }
+        for (&keyer, &on_air) in &self.downstream_keyers_on_air {
+            let current = system_info
+                .downstream_keyer_state(keyer)
+                .map(|state| state.on_air());
+            if current != Some(on_air) {
+                commands.push(ControlCommand::DownstreamKeyerOnAir(
+                    SetDownstreamKeyerOnAir::new(keyer, on_air),
+                ));
+            }
+        }
+
+        for (&aux, &source) in &self.aux_sources {
+            if system_info.aux_source(aux) != Some(source) {
+                commands.push(ControlCommand::AuxSource(SetAuxSource::new(aux, source)));
+            }
+        }
+
+        commands
+    }
+
+    #[cfg(feature = "audio")]
+    fn audio_commands(&self, system_info: &SystemInfo, commands: &mut Vec<ControlCommand>) {
+        if let Some(monitor) = &self.audio_monitor {
+            let current = system_info.audio_monitor();
+            let solo = |monitor: &AudioMonitor| monitor.solo().then_some(monitor.solo_source());
+            let mut set = SetAudioMonitor::new();
+            let mut changed = false;
+            if current.map(AudioMonitor::enabled) != Some(monitor.enabled()) {
+                set = set.enabled(monitor.enabled());
+                changed = true;
+            }
+            if current.map(AudioMonitor::gain) != Some(monitor.gain()) {
+                set = set.gain(monitor.gain());
+                changed = true;
+            }
+            if current.map(AudioMonitor::mute) != Some(monitor.mute()) {
+                set = set.mute(monitor.mute());
+                changed = true;
+            }
+            if current.map(solo) != Some(solo(monitor)) {
+                set = set.solo(solo(monitor));
+                changed = true;
+            }
+            if current.map(AudioMonitor::dim) != Some(monitor.dim()) {
+                set = set.dim(monitor.dim());
+                changed = true;
+            }
+            if changed {
+                commands.push(ControlCommand::AudioMonitor(set));
+            }
+        }
+
+        for input in self.audio_mixer_inputs.values() {
+            let current = system_info.audio_mixer_input(input.input());
+            let mut set = SetAudioMixerInput::new(input.input());
+            let mut changed = false;
+            if current.map(AudioMixerInput::mix_option) != Some(input.mix_option()) {
+                set = set.mix_option(input.mix_option());
+                changed = true;
+            }
+            if current.map(AudioMixerInput::gain) != Some(input.gain()) {
+                set = set.gain(input.gain());
+                changed = true;
+            }
+            if current.map(AudioMixerInput::balance) != Some(input.balance()) {
+                set = set.balance(input.balance());
+                changed = true;
+            }
+            if changed {
+                commands.push(ControlCommand::AudioMixerInput(set));
+            }
+        }
+
+        if let Some(master) = &self.audio_mixer_master {
+            let current = system_info.audio_mixer_master();
+            let mut set = SetAudioMixerMaster::new();
+            let mut changed = false;
+            if current.map(AudioMixerMaster::gain) != Some(master.gain()) {
+                set = set.gain(master.gain());
+                changed = true;
+            }
+            if current.map(AudioMixerMaster::balance) != Some(master.balance()) {
+                set = set.balance(master.balance());
+                changed = true;
+            }
+            if changed {
+                commands.push(ControlCommand::AudioMixerMaster(set));
+            }
+        }
+
+        for (&input, &level) in &self.fairlight_input_levels {
+            let current = system_info
+                .fairlight_input(input)
+                .map(|input| input.level());
+            if current != Some(level) {
+                commands.push(ControlCommand::FairlightInputProperties(
+                    SetFairlightInputProperties::new(input).level(level),
+                ));
+            }
+        }
+
+        for source in self.fairlight_sources.values() {
+            let current = system_info.fairlight_source(source.source());
+            let mut set = SetFairlightSourceProperties::new(source.source());
+            let mut changed = false;
+            if current.map(FairlightSourceProperties::mix_option) != Some(source.mix_option()) {
+                set = set.mix_option(source.mix_option());
+                changed = true;
+            }
+            if current.map(FairlightSourceProperties::fader_gain) != Some(source.fader_gain()) {
+                set = set.fader_gain(source.fader_gain());
+                changed = true;
+            }
+            if current.map(FairlightSourceProperties::balance) != Some(source.balance()) {
+                set = set.balance(source.balance());
+                changed = true;
+            }
+            if changed {
+                commands.push(ControlCommand::FairlightSourceProperties(set));
+            }
+        }
+
+        if let Some(master) = &self.fairlight_master {
+            let current = system_info
+                .fairlight_master()
+                .map(FairlightMasterProperties::fader_gain);
+            if current != Some(master.fader_gain()) {
+                commands.push(ControlCommand::FairlightMasterProperties(
+                    SetFairlightMasterProperties::new().fader_gain(master.fader_gain()),
+                ));
+            }
+        }
+    }
+}
+
+/// Set-command for the properties of a SuperSource box that differ, all of them when the
+/// switcher hasn't reported the box yet
+fn supersource_box_changes(
+    current: Option<&SuperSourceBox>,
+    desired: &SuperSourceBox,
+) -> Option<SetSuperSourceBox> {
+    let mut set = SetSuperSourceBox::new(desired.supersource(), desired.index());
+    let mut changed = false;
+    if current.map(SuperSourceBox::enabled) != Some(desired.enabled()) {
+        set = set.enabled(desired.enabled());
+        changed = true;
+    }
+    if current.map(SuperSourceBox::source) != Some(desired.source()) {
+        set = set.source(desired.source());
+        changed = true;
+    }
+    if current.map(SuperSourceBox::position) != Some(desired.position()) {
+        let (x, y) = desired.position();
+        set = set.position(x, y);
+        changed = true;
+    }
+    if current.map(SuperSourceBox::size) != Some(desired.size()) {
+        set = set.size(desired.size());
+        changed = true;
+    }
+    if current.map(SuperSourceBox::cropped) != Some(desired.cropped()) {
+        set = set.cropped(desired.cropped());
+        changed = true;
+    }
+    if current.map(SuperSourceBox::crop) != Some(desired.crop()) {
+        let (top, bottom, left, right) = desired.crop();
+        set = set.crop(top, bottom, left, right);
+        changed = true;
+    }
+
+    changed.then_some(set)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands_only_for_differences() {
+        let mut system_info = SystemInfo::default();
+        system_info.set_program_input(0, 1);
+        system_info.set_preview_input(0, 2);
+        system_info.set_aux_source(0, 3);
+
+        let state = DesiredState::new()
+            .program_input(0, 1)
+            .preview_input(0, 4)
+            .aux_source(0, 3)
+            .aux_source(1, 1)
+            .color_generator(0, HslColor::new(0, 1000, 500));
+        let names: Vec<&[u8; 4]> = state
+            .commands(&system_info)
+            .iter()
+            .map(ControlCommand::name)
+            .collect();
+        assert_eq!(names, [b"CClV", b"CPvI", b"CAuS"]);
+
+        system_info.set_preview_input(0, 4);
+        system_info.set_aux_source(1, 1);
+        system_info.set_color_generator(0, HslColor::new(0, 1000, 500));
+        assert!(state.commands(&system_info).is_empty());
+    }
+
+    #[test]
+    fn input_names_too_long() {
+        assert!(DesiredState::new()
+            .input_names(1, "Camera 1", "CAM1")
+            .is_ok());
+        assert!(matches!(
+            DesiredState::new().input_names(1, "Camera 1", "CAM01"),
+            Err(Error::NameTooLong(4))
+        ));
+    }
+}
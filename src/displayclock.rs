@@ -0,0 +1,398 @@
+use core::fmt::Display;
+
+use bitflags::bitflags;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::command::Time;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum DisplayClockMode {
+    Countdown,
+    CountUp,
+    TimeOfDay,
+    Unknown(u8),
+}
+
+impl From<u8> for DisplayClockMode {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => DisplayClockMode::Countdown,
+            1 => DisplayClockMode::CountUp,
+            2 => DisplayClockMode::TimeOfDay,
+            u => DisplayClockMode::Unknown(u),
+        }
+    }
+}
+
+impl From<DisplayClockMode> for u8 {
+    fn from(value: DisplayClockMode) -> Self {
+        match value {
+            DisplayClockMode::Countdown => 0,
+            DisplayClockMode::CountUp => 1,
+            DisplayClockMode::TimeOfDay => 2,
+            DisplayClockMode::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for DisplayClockMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DisplayClockMode::Countdown => write!(f, "Countdown"),
+            DisplayClockMode::CountUp => write!(f, "Count up"),
+            DisplayClockMode::TimeOfDay => write!(f, "Time of day"),
+            DisplayClockMode::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum DisplayClockState {
+    Reset,
+    Stopped,
+    Running,
+    Unknown(u8),
+}
+
+impl From<u8> for DisplayClockState {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => DisplayClockState::Reset,
+            1 => DisplayClockState::Stopped,
+            2 => DisplayClockState::Running,
+            u => DisplayClockState::Unknown(u),
+        }
+    }
+}
+
+impl From<DisplayClockState> for u8 {
+    fn from(value: DisplayClockState) -> Self {
+        match value {
+            DisplayClockState::Reset => 0,
+            DisplayClockState::Stopped => 1,
+            DisplayClockState::Running => 2,
+            DisplayClockState::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for DisplayClockState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DisplayClockState::Reset => write!(f, "Reset"),
+            DisplayClockState::Stopped => write!(f, "Stopped"),
+            DisplayClockState::Running => write!(f, "Running"),
+            DisplayClockState::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+/// Appearance and mode of the display clock shown in the multiview (`DCPV`)
+#[derive(Debug, Clone)]
+pub struct DisplayClockProperties {
+    clock: u8,
+    enabled: bool,
+    size: u8,
+    opacity: u8,
+    position_x: i16,
+    position_y: i16,
+    auto_hide: bool,
+    start_from: Time,
+    mode: DisplayClockMode,
+    state: DisplayClockState,
+}
+
+impl DisplayClockProperties {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let clock = data.get_u8();
+        let enabled = data.get_u8() == 1;
+        data.get_u8(); // Padding
+        let size = data.get_u8();
+        data.get_u8(); // Padding
+        let opacity = data.get_u8();
+        let position_x = data.get_i16();
+        let position_y = data.get_i16();
+        let auto_hide = data.get_u8() == 1;
+        let start_from = Time::parse(data);
+        let mode = data.get_u8().into();
+        let state = data.get_u8().into();
+
+        DisplayClockProperties {
+            clock,
+            enabled,
+            size,
+            opacity,
+            position_x,
+            position_y,
+            auto_hide,
+            start_from,
+            mode,
+            state,
+        }
+    }
+
+    pub fn clock(&self) -> u8 {
+        self.clock
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn size(&self) -> u8 {
+        self.size
+    }
+
+    pub fn opacity(&self) -> u8 {
+        self.opacity
+    }
+
+    /// Position of the clock in 1/1000 of the screen
+    pub fn position(&self) -> (i16, i16) {
+        (self.position_x, self.position_y)
+    }
+
+    pub fn auto_hide(&self) -> bool {
+        self.auto_hide
+    }
+
+    /// Time the clock starts at when counting down or up
+    pub fn start_from(&self) -> Time {
+        self.start_from
+    }
+
+    pub fn mode(&self) -> DisplayClockMode {
+        self.mode
+    }
+
+    pub fn state(&self) -> DisplayClockState {
+        self.state
+    }
+}
+
+impl Display for DisplayClockProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Clock: {} Enabled: {} Size: {} Opacity: {} Position: {}, {} Auto hide: {} Start from: {} Mode: {} State: {}",
+            self.clock,
+            self.enabled,
+            self.size,
+            self.opacity,
+            self.position_x,
+            self.position_y,
+            self.auto_hide,
+            self.start_from,
+            self.mode,
+            self.state
+        )
+    }
+}
+
+/// Current time of the display clock (`DSTV`)
+#[derive(Debug, Clone)]
+pub struct DisplayClockTime {
+    clock: u8,
+    time: Time,
+}
+
+impl DisplayClockTime {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let clock = data.get_u8();
+        let time = Time::parse(data);
+
+        DisplayClockTime { clock, time }
+    }
+
+    pub fn clock(&self) -> u8 {
+        self.clock
+    }
+
+    pub fn time(&self) -> Time {
+        self.time
+    }
+}
+
+impl Display for DisplayClockTime {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Clock: {} Time: {}", self.clock, self.time)
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct DisplayClockPropertiesMask: u16 {
+        const ENABLED = 0x0001;
+        const SIZE = 0x0002;
+        const OPACITY = 0x0004;
+        const POSITION_X = 0x0008;
+        const POSITION_Y = 0x0010;
+        const AUTO_HIDE = 0x0020;
+        const START_FROM = 0x0040;
+        const MODE = 0x0080;
+    }
+}
+
+/// Change the appearance and mode of the display clock (`DCPC`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetDisplayClockProperties {
+    mask: DisplayClockPropertiesMask,
+    clock: u8,
+    enabled: bool,
+    size: u8,
+    opacity: u8,
+    position_x: i16,
+    position_y: i16,
+    auto_hide: bool,
+    start_from: Time,
+    mode: DisplayClockMode,
+}
+
+impl SetDisplayClockProperties {
+    pub fn new(clock: u8) -> Self {
+        SetDisplayClockProperties {
+            mask: DisplayClockPropertiesMask::empty(),
+            clock,
+            enabled: false,
+            size: 0,
+            opacity: 0,
+            position_x: 0,
+            position_y: 0,
+            auto_hide: false,
+            start_from: Time::default(),
+            mode: DisplayClockMode::Countdown,
+        }
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.mask |= DisplayClockPropertiesMask::ENABLED;
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn size(mut self, size: u8) -> Self {
+        self.mask |= DisplayClockPropertiesMask::SIZE;
+        self.size = size;
+        self
+    }
+
+    pub fn opacity(mut self, opacity: u8) -> Self {
+        self.mask |= DisplayClockPropertiesMask::OPACITY;
+        self.opacity = opacity;
+        self
+    }
+
+    /// Position of the clock in 1/1000 of the screen
+    pub fn position(mut self, x: i16, y: i16) -> Self {
+        self.mask |=
+            DisplayClockPropertiesMask::POSITION_X | DisplayClockPropertiesMask::POSITION_Y;
+        self.position_x = x;
+        self.position_y = y;
+        self
+    }
+
+    pub fn auto_hide(mut self, auto_hide: bool) -> Self {
+        self.mask |= DisplayClockPropertiesMask::AUTO_HIDE;
+        self.auto_hide = auto_hide;
+        self
+    }
+
+    pub fn start_from(mut self, time: Time) -> Self {
+        self.mask |= DisplayClockPropertiesMask::START_FROM;
+        self.start_from = time;
+        self
+    }
+
+    pub fn mode(mut self, mode: DisplayClockMode) -> Self {
+        self.mask |= DisplayClockPropertiesMask::MODE;
+        self.mode = mode;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.mask.bits());
+        data.put_u8(self.clock);
+        data.put_u8(self.enabled as u8);
+        data.put_u8(self.size);
+        data.put_u8(self.opacity);
+        data.put_i16(self.position_x);
+        data.put_i16(self.position_y);
+        data.put_u8(self.auto_hide as u8);
+        self.start_from.serialize(data);
+        data.put_u8(self.mode.into());
+    }
+}
+
+impl Display for SetDisplayClockProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Clock: {} Enabled: {} Size: {} Opacity: {} Position: {}, {} Auto hide: {} Start from: {} Mode: {}",
+            self.clock,
+            self.enabled,
+            self.size,
+            self.opacity,
+            self.position_x,
+            self.position_y,
+            self.auto_hide,
+            self.start_from,
+            self.mode
+        )
+    }
+}
+
+/// Start, stop or reset the display clock (`DCSC`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetDisplayClockState {
+    clock: u8,
+    state: DisplayClockState,
+}
+
+impl SetDisplayClockState {
+    pub fn new(clock: u8, state: DisplayClockState) -> Self {
+        SetDisplayClockState { clock, state }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.clock);
+        data.put_u8(self.state.into());
+        data.put_bytes(0, 2); // Padding
+    }
+}
+
+impl Display for SetDisplayClockState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Clock: {} State: {}", self.clock, self.state)
+    }
+}
+
+/// Set the current time of the display clock (`DCTS`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetDisplayClockTime {
+    clock: u8,
+    time: Time,
+}
+
+impl SetDisplayClockTime {
+    pub fn new(clock: u8, time: Time) -> Self {
+        SetDisplayClockTime { clock, time }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.clock);
+        self.time.serialize(data);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetDisplayClockTime {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Clock: {} Time: {}", self.clock, self.time)
+    }
+}
@@ -0,0 +1,1704 @@
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use bitflags::bitflags;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+/// Channel split component of a Fairlight source id
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum ChannelSplit {
+    /// Both channels of the input, or the only channel of a mono input
+    Stereo,
+    /// Left channel of an input split into two mono sources
+    Left,
+    /// Right channel of an input split into two mono sources
+    Right,
+    Unknown(i64),
+}
+
+impl From<i64> for ChannelSplit {
+    fn from(value: i64) -> Self {
+        match value {
+            -65280 => ChannelSplit::Stereo,
+            -256 => ChannelSplit::Left,
+            -255 => ChannelSplit::Right,
+            u => ChannelSplit::Unknown(u),
+        }
+    }
+}
+
+impl From<ChannelSplit> for i64 {
+    fn from(value: ChannelSplit) -> Self {
+        match value {
+            ChannelSplit::Stereo => -65280,
+            ChannelSplit::Left => -256,
+            ChannelSplit::Right => -255,
+            ChannelSplit::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for ChannelSplit {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ChannelSplit::Stereo => write!(f, "Stereo"),
+            ChannelSplit::Left => write!(f, "Left"),
+            ChannelSplit::Right => write!(f, "Right"),
+            ChannelSplit::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+/// Source of the Fairlight mixer, an input and the 64 bit source id that selects which of its
+/// channels are used. Fairlight inputs aren't addressed by video source id since a split stereo
+/// input has two sources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AudioSourceId {
+    input: u16,
+    source: i64,
+}
+
+impl AudioSourceId {
+    pub fn new(input: u16, split: ChannelSplit) -> Self {
+        AudioSourceId {
+            input,
+            source: split.into(),
+        }
+    }
+
+    /// Parse the input and source as laid out in the Fairlight commands, the input followed by
+    /// 6 bytes of padding and the source
+    pub fn parse(data: &mut Bytes) -> Self {
+        let input = data.get_u16();
+        data.advance(6); // Padding
+        let source = data.get_i64();
+
+        AudioSourceId { input, source }
+    }
+
+    pub fn input(&self) -> u16 {
+        self.input
+    }
+
+    /// Raw 64 bit source id
+    pub fn source(&self) -> i64 {
+        self.source
+    }
+
+    pub fn split(&self) -> ChannelSplit {
+        self.source.into()
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.input);
+        data.put_bytes(0, 6); // Padding
+        data.put_i64(self.source);
+    }
+}
+
+impl Display for AudioSourceId {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Input: {} Source: {}", self.input, self.split())
+    }
+}
+
+/// Where the audio of a Fairlight input comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum FairlightInputType {
+    EmbeddedWithVideo,
+    MediaPlayer,
+    AudioIn,
+    Madi,
+    Unknown(u8),
+}
+
+impl From<u8> for FairlightInputType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => FairlightInputType::EmbeddedWithVideo,
+            1 => FairlightInputType::MediaPlayer,
+            2 => FairlightInputType::AudioIn,
+            4 => FairlightInputType::Madi,
+            u => FairlightInputType::Unknown(u),
+        }
+    }
+}
+
+impl Display for FairlightInputType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FairlightInputType::EmbeddedWithVideo => write!(f, "Embedded with video"),
+            FairlightInputType::MediaPlayer => write!(f, "Media player"),
+            FairlightInputType::AudioIn => write!(f, "Audio in"),
+            FairlightInputType::Madi => write!(f, "MADI"),
+            FairlightInputType::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+bitflags! {
+    /// Physical connector of a Fairlight input
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct AudioPortType: u16 {
+        const SDI = 0x0001;
+        const HDMI = 0x0002;
+        const COMPONENT = 0x0004;
+        const COMPOSITE = 0x0008;
+        const SVIDEO = 0x0010;
+        const XLR = 0x0020;
+        const AES_EBU = 0x0040;
+        const RCA = 0x0080;
+        const INTERNAL = 0x0100;
+        const TS_JACK = 0x0200;
+        const MADI = 0x0400;
+        const TRS_JACK = 0x0800;
+        const RJ45 = 0x1000;
+    }
+}
+
+bitflags! {
+    /// Channel configurations of a Fairlight input, an input supports several and uses one of
+    /// them
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct InputConfigurations: u8 {
+        const MONO = 0x01;
+        const STEREO = 0x02;
+        const DUAL_MONO = 0x04;
+    }
+}
+
+bitflags! {
+    /// Signal levels of an analog Fairlight input, an input supports several and uses one of
+    /// them. Inputs without level selection support none.
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct InputLevels: u8 {
+        /// Consumer line level, -10 dBV
+        const CONSUMER_LINE = 0x01;
+        /// Professional line level, +4 dBu
+        const PRO_LINE = 0x02;
+        const MIC = 0x04;
+    }
+}
+
+impl Display for InputLevels {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut output = Vec::new();
+
+        if self.contains(InputLevels::CONSUMER_LINE) {
+            output.push("Consumer line");
+        }
+        if self.contains(InputLevels::PRO_LINE) {
+            output.push("Pro line");
+        }
+        if self.contains(InputLevels::MIC) {
+            output.push("Mic");
+        }
+
+        write!(f, "{}", output.join(", "))
+    }
+}
+
+/// Properties of a Fairlight input (`FAIP`)
+#[derive(Debug, Clone)]
+pub struct FairlightInputProperties {
+    input: u16,
+    input_type: FairlightInputType,
+    port_type: AudioPortType,
+    supported_configurations: InputConfigurations,
+    configuration: InputConfigurations,
+    supported_levels: InputLevels,
+    level: InputLevels,
+}
+
+impl FairlightInputProperties {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let input = data.get_u16();
+        let input_type = data.get_u8().into();
+        data.advance(3); // Padding
+        let port_type = AudioPortType::from_bits_retain(data.get_u16());
+        data.get_u8(); // Padding
+        let supported_configurations = InputConfigurations::from_bits_retain(data.get_u8());
+        let configuration = InputConfigurations::from_bits_retain(data.get_u8());
+        let supported_levels = InputLevels::from_bits_retain(data.get_u8());
+        let level = InputLevels::from_bits_retain(data.get_u8());
+
+        FairlightInputProperties {
+            input,
+            input_type,
+            port_type,
+            supported_configurations,
+            configuration,
+            supported_levels,
+            level,
+        }
+    }
+
+    pub fn input(&self) -> u16 {
+        self.input
+    }
+
+    pub fn input_type(&self) -> FairlightInputType {
+        self.input_type
+    }
+
+    pub fn port_type(&self) -> AudioPortType {
+        self.port_type
+    }
+
+    pub fn supported_configurations(&self) -> InputConfigurations {
+        self.supported_configurations
+    }
+
+    pub fn configuration(&self) -> InputConfigurations {
+        self.configuration
+    }
+
+    /// Empty for inputs without level selection, e.g. embedded audio
+    pub fn supported_levels(&self) -> InputLevels {
+        self.supported_levels
+    }
+
+    pub fn level(&self) -> InputLevels {
+        self.level
+    }
+}
+
+impl Display for FairlightInputProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Input: {} Type: {} Configuration: {:?} Level: {}",
+            self.input, self.input_type, self.configuration, self.level
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct FairlightInputPropertiesMask: u8 {
+        const CONFIGURATION = 0x01;
+        const LEVEL = 0x02;
+    }
+}
+
+/// Change the properties of a Fairlight input (`CFIP`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetFairlightInputProperties {
+    mask: FairlightInputPropertiesMask,
+    input: u16,
+    configuration: InputConfigurations,
+    level: InputLevels,
+}
+
+impl SetFairlightInputProperties {
+    pub fn new(input: u16) -> Self {
+        SetFairlightInputProperties {
+            mask: FairlightInputPropertiesMask::empty(),
+            input,
+            configuration: InputConfigurations::empty(),
+            level: InputLevels::empty(),
+        }
+    }
+
+    /// One of the configurations supported by the input
+    pub fn configuration(mut self, configuration: InputConfigurations) -> Self {
+        self.mask |= FairlightInputPropertiesMask::CONFIGURATION;
+        self.configuration = configuration;
+        self
+    }
+
+    /// One of the levels supported by the input
+    pub fn level(mut self, level: InputLevels) -> Self {
+        self.mask |= FairlightInputPropertiesMask::LEVEL;
+        self.level = level;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(0); // Padding
+        data.put_u16(self.input);
+        data.put_u8(self.configuration.bits());
+        data.put_u8(self.level.bits());
+        data.put_bytes(0, 2); // Padding
+    }
+}
+
+impl Display for SetFairlightInputProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Input: {} Configuration: {:?} Level: {}",
+            self.input, self.configuration, self.level
+        )
+    }
+}
+
+/// Compressor of a Fairlight source (`AICP`) or the master bus (`AMCP`). Thresholds are in
+/// hundredths of a dB, the ratio in hundredths and times in hundredths of a millisecond.
+#[derive(Debug, Clone)]
+pub struct FairlightCompressor {
+    source: Option<AudioSourceId>,
+    enabled: bool,
+    threshold: i32,
+    ratio: i16,
+    attack: i32,
+    hold: i32,
+    release: i32,
+}
+
+impl FairlightCompressor {
+    pub fn parse_source(data: &mut Bytes) -> Self {
+        let source = AudioSourceId::parse(data);
+        Self::parse(Some(source), data)
+    }
+
+    pub fn parse_master(data: &mut Bytes) -> Self {
+        Self::parse(None, data)
+    }
+
+    fn parse(source: Option<AudioSourceId>, data: &mut Bytes) -> Self {
+        let enabled = data.get_u8() > 0;
+        data.advance(3); // Padding
+        let threshold = data.get_i32();
+        let ratio = data.get_i16();
+        data.advance(2); // Padding
+        let attack = data.get_i32();
+        let hold = data.get_i32();
+        let release = data.get_i32();
+
+        FairlightCompressor {
+            source,
+            enabled,
+            threshold,
+            ratio,
+            attack,
+            hold,
+            release,
+        }
+    }
+
+    /// None for the master bus
+    pub fn source(&self) -> Option<AudioSourceId> {
+        self.source
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn threshold(&self) -> i32 {
+        self.threshold
+    }
+
+    pub fn ratio(&self) -> i16 {
+        self.ratio
+    }
+
+    pub fn attack(&self) -> i32 {
+        self.attack
+    }
+
+    pub fn hold(&self) -> i32 {
+        self.hold
+    }
+
+    pub fn release(&self) -> i32 {
+        self.release
+    }
+}
+
+impl Display for FairlightCompressor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.source {
+            Some(source) => write!(f, "{source}")?,
+            None => write!(f, "Master")?,
+        }
+        write!(
+            f,
+            " Enabled: {} Threshold: {} Ratio: {} Attack: {} Hold: {} Release: {}",
+            self.enabled, self.threshold, self.ratio, self.attack, self.hold, self.release
+        )
+    }
+}
+
+/// Limiter of a Fairlight source (`AILP`) or the master bus (`AMLP`). Thresholds are in
+/// hundredths of a dB and times in hundredths of a millisecond.
+#[derive(Debug, Clone)]
+pub struct FairlightLimiter {
+    source: Option<AudioSourceId>,
+    enabled: bool,
+    threshold: i32,
+    attack: i32,
+    hold: i32,
+    release: i32,
+}
+
+impl FairlightLimiter {
+    pub fn parse_source(data: &mut Bytes) -> Self {
+        let source = AudioSourceId::parse(data);
+        Self::parse(Some(source), data)
+    }
+
+    pub fn parse_master(data: &mut Bytes) -> Self {
+        Self::parse(None, data)
+    }
+
+    fn parse(source: Option<AudioSourceId>, data: &mut Bytes) -> Self {
+        let enabled = data.get_u8() > 0;
+        data.advance(3); // Padding
+        let threshold = data.get_i32();
+        let attack = data.get_i32();
+        let hold = data.get_i32();
+        let release = data.get_i32();
+
+        FairlightLimiter {
+            source,
+            enabled,
+            threshold,
+            attack,
+            hold,
+            release,
+        }
+    }
+
+    /// None for the master bus
+    pub fn source(&self) -> Option<AudioSourceId> {
+        self.source
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn threshold(&self) -> i32 {
+        self.threshold
+    }
+
+    pub fn attack(&self) -> i32 {
+        self.attack
+    }
+
+    pub fn hold(&self) -> i32 {
+        self.hold
+    }
+
+    pub fn release(&self) -> i32 {
+        self.release
+    }
+}
+
+impl Display for FairlightLimiter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.source {
+            Some(source) => write!(f, "{source}")?,
+            None => write!(f, "Master")?,
+        }
+        write!(
+            f,
+            " Enabled: {} Threshold: {} Attack: {} Hold: {} Release: {}",
+            self.enabled, self.threshold, self.attack, self.hold, self.release
+        )
+    }
+}
+
+/// Expander or gate of a Fairlight source (`AIXP`), the master bus has none. Thresholds and
+/// the range are in hundredths of a dB, the ratio in hundredths and times in hundredths of a
+/// millisecond.
+#[derive(Debug, Clone)]
+pub struct FairlightExpander {
+    source: AudioSourceId,
+    enabled: bool,
+    gate: bool,
+    threshold: i32,
+    range: i16,
+    ratio: i16,
+    attack: i32,
+    hold: i32,
+    release: i32,
+}
+
+impl FairlightExpander {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let source = AudioSourceId::parse(data);
+        let enabled = data.get_u8() > 0;
+        let gate = data.get_u8() > 0;
+        data.advance(2); // Padding
+        let threshold = data.get_i32();
+        let range = data.get_i16();
+        let ratio = data.get_i16();
+        let attack = data.get_i32();
+        let hold = data.get_i32();
+        let release = data.get_i32();
+
+        FairlightExpander {
+            source,
+            enabled,
+            gate,
+            threshold,
+            range,
+            ratio,
+            attack,
+            hold,
+            release,
+        }
+    }
+
+    pub fn source(&self) -> AudioSourceId {
+        self.source
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Works as a gate instead of an expander
+    pub fn gate(&self) -> bool {
+        self.gate
+    }
+
+    pub fn threshold(&self) -> i32 {
+        self.threshold
+    }
+
+    pub fn range(&self) -> i16 {
+        self.range
+    }
+
+    pub fn ratio(&self) -> i16 {
+        self.ratio
+    }
+
+    pub fn attack(&self) -> i32 {
+        self.attack
+    }
+
+    pub fn hold(&self) -> i32 {
+        self.hold
+    }
+
+    pub fn release(&self) -> i32 {
+        self.release
+    }
+}
+
+impl Display for FairlightExpander {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} Enabled: {} Gate: {} Threshold: {} Range: {} Ratio: {} Attack: {} Hold: {} Release: {}",
+            self.source,
+            self.enabled,
+            self.gate,
+            self.threshold,
+            self.range,
+            self.ratio,
+            self.attack,
+            self.hold,
+            self.release
+        )
+    }
+}
+
+/// Write the mask and the source the set-commands of the dynamics start with. The master bus
+/// variants have no source and the enabled flag directly follows the mask.
+fn put_dynamics_header(
+    data: &mut BytesMut,
+    mask: u8,
+    source: Option<AudioSourceId>,
+    enabled: bool,
+) {
+    data.put_u8(mask);
+    match source {
+        Some(source) => {
+            data.put_u8(0); // Padding
+            data.put_u16(source.input());
+            data.put_bytes(0, 4); // Padding
+            data.put_i64(source.source());
+            data.put_u8(enabled as u8);
+            data.put_bytes(0, 3); // Padding
+        }
+        None => {
+            data.put_u8(enabled as u8);
+            data.put_bytes(0, 2); // Padding
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct FairlightCompressorMask: u8 {
+        const ENABLED = 0x01;
+        const THRESHOLD = 0x02;
+        const RATIO = 0x04;
+        const ATTACK = 0x08;
+        const HOLD = 0x10;
+        const RELEASE = 0x20;
+    }
+}
+
+/// Change the compressor of a Fairlight source (`CICP`) or the master bus (`CMCP`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetFairlightCompressor {
+    mask: FairlightCompressorMask,
+    source: Option<AudioSourceId>,
+    enabled: bool,
+    threshold: i32,
+    ratio: i16,
+    attack: i32,
+    hold: i32,
+    release: i32,
+}
+
+impl SetFairlightCompressor {
+    pub fn source(source: AudioSourceId) -> Self {
+        Self::new(Some(source))
+    }
+
+    pub fn master() -> Self {
+        Self::new(None)
+    }
+
+    fn new(source: Option<AudioSourceId>) -> Self {
+        SetFairlightCompressor {
+            mask: FairlightCompressorMask::empty(),
+            source,
+            enabled: false,
+            threshold: 0,
+            ratio: 0,
+            attack: 0,
+            hold: 0,
+            release: 0,
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static [u8; 4] {
+        match self.source {
+            Some(_) => b"CICP",
+            None => b"CMCP",
+        }
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.mask |= FairlightCompressorMask::ENABLED;
+        self.enabled = enabled;
+        self
+    }
+
+    /// Threshold in hundredths of a dB
+    pub fn threshold(mut self, threshold: i32) -> Self {
+        self.mask |= FairlightCompressorMask::THRESHOLD;
+        self.threshold = threshold;
+        self
+    }
+
+    /// Ratio in hundredths, e.g. 400 for 4:1
+    pub fn ratio(mut self, ratio: i16) -> Self {
+        self.mask |= FairlightCompressorMask::RATIO;
+        self.ratio = ratio;
+        self
+    }
+
+    /// Attack in hundredths of a millisecond
+    pub fn attack(mut self, attack: i32) -> Self {
+        self.mask |= FairlightCompressorMask::ATTACK;
+        self.attack = attack;
+        self
+    }
+
+    /// Hold in hundredths of a millisecond
+    pub fn hold(mut self, hold: i32) -> Self {
+        self.mask |= FairlightCompressorMask::HOLD;
+        self.hold = hold;
+        self
+    }
+
+    /// Release in hundredths of a millisecond
+    pub fn release(mut self, release: i32) -> Self {
+        self.mask |= FairlightCompressorMask::RELEASE;
+        self.release = release;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        put_dynamics_header(data, self.mask.bits(), self.source, self.enabled);
+        data.put_i32(self.threshold);
+        data.put_i16(self.ratio);
+        data.put_bytes(0, 2); // Padding
+        data.put_i32(self.attack);
+        data.put_i32(self.hold);
+        data.put_i32(self.release);
+    }
+}
+
+impl Display for SetFairlightCompressor {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.source {
+            Some(source) => write!(f, "{source}")?,
+            None => write!(f, "Master")?,
+        }
+        write!(
+            f,
+            " Enabled: {} Threshold: {} Ratio: {} Attack: {} Hold: {} Release: {}",
+            self.enabled, self.threshold, self.ratio, self.attack, self.hold, self.release
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct FairlightLimiterMask: u8 {
+        const ENABLED = 0x01;
+        const THRESHOLD = 0x02;
+        const ATTACK = 0x04;
+        const HOLD = 0x08;
+        const RELEASE = 0x10;
+    }
+}
+
+/// Change the limiter of a Fairlight source (`CILP`) or the master bus (`CMLP`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetFairlightLimiter {
+    mask: FairlightLimiterMask,
+    source: Option<AudioSourceId>,
+    enabled: bool,
+    threshold: i32,
+    attack: i32,
+    hold: i32,
+    release: i32,
+}
+
+impl SetFairlightLimiter {
+    pub fn source(source: AudioSourceId) -> Self {
+        Self::new(Some(source))
+    }
+
+    pub fn master() -> Self {
+        Self::new(None)
+    }
+
+    fn new(source: Option<AudioSourceId>) -> Self {
+        SetFairlightLimiter {
+            mask: FairlightLimiterMask::empty(),
+            source,
+            enabled: false,
+            threshold: 0,
+            attack: 0,
+            hold: 0,
+            release: 0,
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static [u8; 4] {
+        match self.source {
+            Some(_) => b"CILP",
+            None => b"CMLP",
+        }
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.mask |= FairlightLimiterMask::ENABLED;
+        self.enabled = enabled;
+        self
+    }
+
+    /// Threshold in hundredths of a dB
+    pub fn threshold(mut self, threshold: i32) -> Self {
+        self.mask |= FairlightLimiterMask::THRESHOLD;
+        self.threshold = threshold;
+        self
+    }
+
+    /// Attack in hundredths of a millisecond
+    pub fn attack(mut self, attack: i32) -> Self {
+        self.mask |= FairlightLimiterMask::ATTACK;
+        self.attack = attack;
+        self
+    }
+
+    /// Hold in hundredths of a millisecond
+    pub fn hold(mut self, hold: i32) -> Self {
+        self.mask |= FairlightLimiterMask::HOLD;
+        self.hold = hold;
+        self
+    }
+
+    /// Release in hundredths of a millisecond
+    pub fn release(mut self, release: i32) -> Self {
+        self.mask |= FairlightLimiterMask::RELEASE;
+        self.release = release;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        put_dynamics_header(data, self.mask.bits(), self.source, self.enabled);
+        data.put_i32(self.threshold);
+        data.put_i32(self.attack);
+        data.put_i32(self.hold);
+        data.put_i32(self.release);
+    }
+}
+
+impl Display for SetFairlightLimiter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.source {
+            Some(source) => write!(f, "{source}")?,
+            None => write!(f, "Master")?,
+        }
+        write!(
+            f,
+            " Enabled: {} Threshold: {} Attack: {} Hold: {} Release: {}",
+            self.enabled, self.threshold, self.attack, self.hold, self.release
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct FairlightExpanderMask: u8 {
+        const ENABLED = 0x01;
+        const GATE = 0x02;
+        const THRESHOLD = 0x04;
+        const RANGE = 0x08;
+        const RATIO = 0x10;
+        const ATTACK = 0x20;
+        const HOLD = 0x40;
+        const RELEASE = 0x80;
+    }
+}
+
+/// Change the expander or gate of a Fairlight source (`CIXP`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetFairlightExpander {
+    mask: FairlightExpanderMask,
+    source: AudioSourceId,
+    enabled: bool,
+    gate: bool,
+    threshold: i32,
+    range: i16,
+    ratio: i16,
+    attack: i32,
+    hold: i32,
+    release: i32,
+}
+
+impl SetFairlightExpander {
+    pub fn new(source: AudioSourceId) -> Self {
+        SetFairlightExpander {
+            mask: FairlightExpanderMask::empty(),
+            source,
+            enabled: false,
+            gate: false,
+            threshold: 0,
+            range: 0,
+            ratio: 0,
+            attack: 0,
+            hold: 0,
+            release: 0,
+        }
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.mask |= FairlightExpanderMask::ENABLED;
+        self.enabled = enabled;
+        self
+    }
+
+    /// Work as a gate instead of an expander
+    pub fn gate(mut self, gate: bool) -> Self {
+        self.mask |= FairlightExpanderMask::GATE;
+        self.gate = gate;
+        self
+    }
+
+    /// Threshold in hundredths of a dB
+    pub fn threshold(mut self, threshold: i32) -> Self {
+        self.mask |= FairlightExpanderMask::THRESHOLD;
+        self.threshold = threshold;
+        self
+    }
+
+    /// Range in hundredths of a dB
+    pub fn range(mut self, range: i16) -> Self {
+        self.mask |= FairlightExpanderMask::RANGE;
+        self.range = range;
+        self
+    }
+
+    /// Ratio in hundredths, e.g. 200 for 1:2
+    pub fn ratio(mut self, ratio: i16) -> Self {
+        self.mask |= FairlightExpanderMask::RATIO;
+        self.ratio = ratio;
+        self
+    }
+
+    /// Attack in hundredths of a millisecond
+    pub fn attack(mut self, attack: i32) -> Self {
+        self.mask |= FairlightExpanderMask::ATTACK;
+        self.attack = attack;
+        self
+    }
+
+    /// Hold in hundredths of a millisecond
+    pub fn hold(mut self, hold: i32) -> Self {
+        self.mask |= FairlightExpanderMask::HOLD;
+        self.hold = hold;
+        self
+    }
+
+    /// Release in hundredths of a millisecond
+    pub fn release(mut self, release: i32) -> Self {
+        self.mask |= FairlightExpanderMask::RELEASE;
+        self.release = release;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(0); // Padding
+        data.put_u16(self.source.input());
+        data.put_bytes(0, 4); // Padding
+        data.put_i64(self.source.source());
+        data.put_u8(self.enabled as u8);
+        data.put_u8(self.gate as u8);
+        data.put_bytes(0, 2); // Padding
+        data.put_i32(self.threshold);
+        data.put_i16(self.range);
+        data.put_i16(self.ratio);
+        data.put_i32(self.attack);
+        data.put_i32(self.hold);
+        data.put_i32(self.release);
+    }
+}
+
+impl Display for SetFairlightExpander {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} Enabled: {} Gate: {} Threshold: {} Range: {} Ratio: {} Attack: {} Hold: {} Release: {}",
+            self.source,
+            self.enabled,
+            self.gate,
+            self.threshold,
+            self.range,
+            self.ratio,
+            self.attack,
+            self.hold,
+            self.release
+        )
+    }
+}
+
+bitflags! {
+    /// Filter shapes of an EQ band, a band supports several and uses one of them
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct EqShapes: u8 {
+        const LOW_SHELF = 0x01;
+        const LOW_PASS = 0x02;
+        const BELL = 0x04;
+        const NOTCH = 0x08;
+        const HIGH_PASS = 0x10;
+        const HIGH_SHELF = 0x20;
+    }
+}
+
+bitflags! {
+    /// Frequency ranges of an EQ band, a band supports several and uses one of them
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct EqFrequencyRanges: u8 {
+        const LOW = 0x01;
+        const MID_LOW = 0x02;
+        const MID_HIGH = 0x04;
+        const HIGH = 0x08;
+    }
+}
+
+/// Band of the EQ of a Fairlight source (`AEBP`) or the master bus (`AMBP`). The gain is in
+/// hundredths of a dB, the Q factor in hundredths and the frequency in Hz.
+#[derive(Debug, Clone)]
+pub struct FairlightEqBand {
+    source: Option<AudioSourceId>,
+    band: u8,
+    enabled: bool,
+    supported_shapes: EqShapes,
+    shape: EqShapes,
+    supported_frequency_ranges: EqFrequencyRanges,
+    frequency_range: EqFrequencyRanges,
+    frequency: u32,
+    gain: i32,
+    q_factor: i16,
+}
+
+impl FairlightEqBand {
+    pub fn parse_source(data: &mut Bytes) -> Self {
+        let source = AudioSourceId::parse(data);
+        Self::parse(Some(source), data)
+    }
+
+    pub fn parse_master(data: &mut Bytes) -> Self {
+        Self::parse(None, data)
+    }
+
+    fn parse(source: Option<AudioSourceId>, data: &mut Bytes) -> Self {
+        let band = data.get_u8();
+        let enabled = data.get_u8() > 0;
+        let supported_shapes = EqShapes::from_bits_retain(data.get_u8());
+        let shape = EqShapes::from_bits_retain(data.get_u8());
+        let supported_frequency_ranges = EqFrequencyRanges::from_bits_retain(data.get_u8());
+        let frequency_range = EqFrequencyRanges::from_bits_retain(data.get_u8());
+        data.advance(2); // Padding
+        let frequency = data.get_u32();
+        let gain = data.get_i32();
+        let q_factor = data.get_i16();
+
+        FairlightEqBand {
+            source,
+            band,
+            enabled,
+            supported_shapes,
+            shape,
+            supported_frequency_ranges,
+            frequency_range,
+            frequency,
+            gain,
+            q_factor,
+        }
+    }
+
+    /// None for the master bus
+    pub fn source(&self) -> Option<AudioSourceId> {
+        self.source
+    }
+
+    pub fn band(&self) -> u8 {
+        self.band
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn supported_shapes(&self) -> EqShapes {
+        self.supported_shapes
+    }
+
+    pub fn shape(&self) -> EqShapes {
+        self.shape
+    }
+
+    pub fn supported_frequency_ranges(&self) -> EqFrequencyRanges {
+        self.supported_frequency_ranges
+    }
+
+    pub fn frequency_range(&self) -> EqFrequencyRanges {
+        self.frequency_range
+    }
+
+    pub fn frequency(&self) -> u32 {
+        self.frequency
+    }
+
+    pub fn gain(&self) -> i32 {
+        self.gain
+    }
+
+    pub fn q_factor(&self) -> i16 {
+        self.q_factor
+    }
+}
+
+impl Display for FairlightEqBand {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.source {
+            Some(source) => write!(f, "{source}")?,
+            None => write!(f, "Master")?,
+        }
+        write!(
+            f,
+            " Band: {} Enabled: {} Shape: {:?} Range: {:?} Frequency: {} Gain: {} Q: {}",
+            self.band,
+            self.enabled,
+            self.shape,
+            self.frequency_range,
+            self.frequency,
+            self.gain,
+            self.q_factor
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct FairlightEqBandMask: u8 {
+        const ENABLED = 0x01;
+        const SHAPE = 0x02;
+        const FREQUENCY_RANGE = 0x04;
+        const FREQUENCY = 0x08;
+        const GAIN = 0x10;
+        const Q_FACTOR = 0x20;
+    }
+}
+
+/// Change a band of the EQ of a Fairlight source (`CEBP`) or the master bus (`CMBP`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetFairlightEqBand {
+    mask: FairlightEqBandMask,
+    source: Option<AudioSourceId>,
+    band: u8,
+    enabled: bool,
+    shape: EqShapes,
+    frequency_range: EqFrequencyRanges,
+    frequency: u32,
+    gain: i32,
+    q_factor: i16,
+}
+
+impl SetFairlightEqBand {
+    pub fn source(source: AudioSourceId, band: u8) -> Self {
+        Self::new(Some(source), band)
+    }
+
+    pub fn master(band: u8) -> Self {
+        Self::new(None, band)
+    }
+
+    fn new(source: Option<AudioSourceId>, band: u8) -> Self {
+        SetFairlightEqBand {
+            mask: FairlightEqBandMask::empty(),
+            source,
+            band,
+            enabled: false,
+            shape: EqShapes::empty(),
+            frequency_range: EqFrequencyRanges::empty(),
+            frequency: 0,
+            gain: 0,
+            q_factor: 0,
+        }
+    }
+
+    pub(crate) fn name(&self) -> &'static [u8; 4] {
+        match self.source {
+            Some(_) => b"CEBP",
+            None => b"CMBP",
+        }
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.mask |= FairlightEqBandMask::ENABLED;
+        self.enabled = enabled;
+        self
+    }
+
+    /// One of the shapes supported by the band
+    pub fn shape(mut self, shape: EqShapes) -> Self {
+        self.mask |= FairlightEqBandMask::SHAPE;
+        self.shape = shape;
+        self
+    }
+
+    /// One of the frequency ranges supported by the band
+    pub fn frequency_range(mut self, frequency_range: EqFrequencyRanges) -> Self {
+        self.mask |= FairlightEqBandMask::FREQUENCY_RANGE;
+        self.frequency_range = frequency_range;
+        self
+    }
+
+    /// Frequency in Hz
+    pub fn frequency(mut self, frequency: u32) -> Self {
+        self.mask |= FairlightEqBandMask::FREQUENCY;
+        self.frequency = frequency;
+        self
+    }
+
+    /// Gain in hundredths of a dB
+    pub fn gain(mut self, gain: i32) -> Self {
+        self.mask |= FairlightEqBandMask::GAIN;
+        self.gain = gain;
+        self
+    }
+
+    /// Q factor in hundredths
+    pub fn q_factor(mut self, q_factor: i16) -> Self {
+        self.mask |= FairlightEqBandMask::Q_FACTOR;
+        self.q_factor = q_factor;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        if let Some(source) = self.source {
+            data.put_u8(0); // Padding
+            data.put_u16(source.input());
+            data.put_bytes(0, 4); // Padding
+            data.put_i64(source.source());
+        }
+        data.put_u8(self.band);
+        data.put_u8(self.enabled as u8);
+        data.put_u8(self.shape.bits());
+        data.put_u8(self.frequency_range.bits());
+        if self.source.is_none() {
+            data.put_bytes(0, 3); // Padding
+        }
+        data.put_u32(self.frequency);
+        data.put_i32(self.gain);
+        data.put_i16(self.q_factor);
+        data.put_bytes(0, 2); // Padding
+    }
+}
+
+impl Display for SetFairlightEqBand {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.source {
+            Some(source) => write!(f, "{source}")?,
+            None => write!(f, "Master")?,
+        }
+        write!(
+            f,
+            " Band: {} Enabled: {} Shape: {:?} Range: {:?} Frequency: {} Gain: {} Q: {}",
+            self.band,
+            self.enabled,
+            self.shape,
+            self.frequency_range,
+            self.frequency,
+            self.gain,
+            self.q_factor
+        )
+    }
+}
+
+/// Whether a Fairlight source is heard in the program mix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum FairlightMixOption {
+    Off,
+    On,
+    /// Heard only while the video of the input is on program
+    AudioFollowVideo,
+    Unknown(u8),
+}
+
+impl From<u8> for FairlightMixOption {
+    fn from(value: u8) -> Self {
+        match value {
+            0x01 => FairlightMixOption::Off,
+            0x02 => FairlightMixOption::On,
+            0x04 => FairlightMixOption::AudioFollowVideo,
+            u => FairlightMixOption::Unknown(u),
+        }
+    }
+}
+
+impl From<FairlightMixOption> for u8 {
+    fn from(value: FairlightMixOption) -> Self {
+        match value {
+            FairlightMixOption::Off => 0x01,
+            FairlightMixOption::On => 0x02,
+            FairlightMixOption::AudioFollowVideo => 0x04,
+            FairlightMixOption::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for FairlightMixOption {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FairlightMixOption::Off => write!(f, "Off"),
+            FairlightMixOption::On => write!(f, "On"),
+            FairlightMixOption::AudioFollowVideo => write!(f, "Audio follow video"),
+            FairlightMixOption::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+/// Gain, balance and fader of a Fairlight source (`FASP`)
+#[derive(Debug, Clone)]
+pub struct FairlightSourceProperties {
+    source: AudioSourceId,
+    gain: i32,
+    balance: i16,
+    fader_gain: i32,
+    mix_option: FairlightMixOption,
+}
+
+impl FairlightSourceProperties {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let source = AudioSourceId::parse(data);
+        data.advance(4); // Type, frames delay and padding
+        let gain = data.get_i32();
+        data.advance(16); // Stereo simulation, EQ and make up gain
+        let balance = data.get_i16();
+        data.advance(2); // Padding
+        let fader_gain = data.get_i32();
+        data.get_u8(); // Supported mix options
+        let mix_option = data.get_u8().into();
+
+        FairlightSourceProperties {
+            source,
+            gain,
+            balance,
+            fader_gain,
+            mix_option,
+        }
+    }
+
+    pub fn source(&self) -> AudioSourceId {
+        self.source
+    }
+
+    /// Input gain in hundredths of a dB
+    pub fn gain(&self) -> i32 {
+        self.gain
+    }
+
+    /// Balance from -10000, left, to 10000, right
+    pub fn balance(&self) -> i16 {
+        self.balance
+    }
+
+    /// Fader gain in hundredths of a dB
+    pub fn fader_gain(&self) -> i32 {
+        self.fader_gain
+    }
+
+    pub fn mix_option(&self) -> FairlightMixOption {
+        self.mix_option
+    }
+}
+
+impl Display for FairlightSourceProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} Gain: {} Balance: {} Fader: {} Mix option: {}",
+            self.source, self.gain, self.balance, self.fader_gain, self.mix_option
+        )
+    }
+}
+
+/// Master fader of the Fairlight mixer (`FAMP`)
+#[derive(Debug, Clone)]
+pub struct FairlightMasterProperties {
+    fader_gain: i32,
+    follow_fade_to_black: bool,
+}
+
+impl FairlightMasterProperties {
+    pub fn parse(data: &mut Bytes) -> Self {
+        data.advance(12); // EQ bands, EQ enabled, padding, EQ and make up gain
+        let fader_gain = data.get_i32();
+        let follow_fade_to_black = data.get_u8() == 1;
+
+        FairlightMasterProperties {
+            fader_gain,
+            follow_fade_to_black,
+        }
+    }
+
+    /// Fader gain in hundredths of a dB
+    pub fn fader_gain(&self) -> i32 {
+        self.fader_gain
+    }
+
+    pub fn follow_fade_to_black(&self) -> bool {
+        self.follow_fade_to_black
+    }
+}
+
+impl Display for FairlightMasterProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Fader: {} Follow fade to black: {}",
+            self.fader_gain, self.follow_fade_to_black
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct FairlightSourcePropertiesMask: u16 {
+        const FRAMES_DELAY = 0x0001;
+        const GAIN = 0x0002;
+        const STEREO_SIMULATION = 0x0004;
+        const EQUALIZER_ENABLED = 0x0008;
+        const EQUALIZER_GAIN = 0x0010;
+        const MAKE_UP_GAIN = 0x0020;
+        const BALANCE = 0x0040;
+        const FADER_GAIN = 0x0080;
+        const MIX_OPTION = 0x0100;
+    }
+}
+
+/// Change the gain, balance and fader of a Fairlight source (`CFSP`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetFairlightSourceProperties {
+    mask: FairlightSourcePropertiesMask,
+    source: AudioSourceId,
+    gain: i32,
+    balance: i16,
+    fader_gain: i32,
+    mix_option: FairlightMixOption,
+}
+
+impl SetFairlightSourceProperties {
+    pub fn new(source: AudioSourceId) -> Self {
+        SetFairlightSourceProperties {
+            mask: FairlightSourcePropertiesMask::empty(),
+            source,
+            gain: 0,
+            balance: 0,
+            fader_gain: 0,
+            mix_option: FairlightMixOption::Off,
+        }
+    }
+
+    pub fn source(&self) -> AudioSourceId {
+        self.source
+    }
+
+    /// Input gain in hundredths of a dB
+    pub fn gain(mut self, gain: i32) -> Self {
+        self.mask |= FairlightSourcePropertiesMask::GAIN;
+        self.gain = gain;
+        self
+    }
+
+    /// Balance from -10000, left, to 10000, right
+    pub fn balance(mut self, balance: i16) -> Self {
+        self.mask |= FairlightSourcePropertiesMask::BALANCE;
+        self.balance = balance;
+        self
+    }
+
+    /// Fader gain in hundredths of a dB
+    pub fn fader_gain(mut self, gain: i32) -> Self {
+        self.mask |= FairlightSourcePropertiesMask::FADER_GAIN;
+        self.fader_gain = gain;
+        self
+    }
+
+    pub fn mix_option(mut self, mix_option: FairlightMixOption) -> Self {
+        self.mask |= FairlightSourcePropertiesMask::MIX_OPTION;
+        self.mix_option = mix_option;
+        self
+    }
+
+    /// Properties changed by the command
+    pub fn mask(&self) -> FairlightSourcePropertiesMask {
+        self.mask
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.mask.bits());
+        data.put_u16(self.source.input());
+        data.put_bytes(0, 4); // Padding
+        data.put_i64(self.source.source());
+        data.put_bytes(0, 4); // Frames delay and padding
+        data.put_i32(self.gain);
+        data.put_bytes(0, 12); // Stereo simulation, EQ and make up gain
+        data.put_i16(self.balance);
+        data.put_bytes(0, 2); // Padding
+        data.put_i32(self.fader_gain);
+        data.put_u8(self.mix_option.into());
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetFairlightSourceProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} Gain: {} Balance: {} Fader: {} Mix option: {}",
+            self.source, self.gain, self.balance, self.fader_gain, self.mix_option
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct FairlightMasterPropertiesMask: u8 {
+        const EQUALIZER_ENABLED = 0x01;
+        const EQUALIZER_GAIN = 0x02;
+        const MAKE_UP_GAIN = 0x04;
+        const FADER_GAIN = 0x08;
+        const FOLLOW_FADE_TO_BLACK = 0x10;
+    }
+}
+
+/// Change the master fader of the Fairlight mixer (`CFMP`)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetFairlightMasterProperties {
+    mask: FairlightMasterPropertiesMask,
+    fader_gain: i32,
+}
+
+impl SetFairlightMasterProperties {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fader gain in hundredths of a dB
+    pub fn fader_gain(mut self, gain: i32) -> Self {
+        self.mask |= FairlightMasterPropertiesMask::FADER_GAIN;
+        self.fader_gain = gain;
+        self
+    }
+
+    /// Properties changed by the command
+    pub fn mask(&self) -> FairlightMasterPropertiesMask {
+        self.mask
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_bytes(0, 11); // EQ enabled, padding, EQ and make up gain
+        data.put_i32(self.fader_gain);
+        data.put_bytes(0, 4); // Follow fade to black and padding
+    }
+}
+
+impl Display for SetFairlightMasterProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Fader: {}", self.fader_gain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn audio_source_id_ok() {
+        let raw = [
+            0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0x01,
+        ];
+        let id = AudioSourceId::parse(&mut Bytes::copy_from_slice(&raw));
+
+        assert_eq!(id, AudioSourceId::new(2, ChannelSplit::Right));
+        assert_eq!(id.source(), -255);
+
+        let mut data = BytesMut::new();
+        id.serialize(&mut data);
+        assert_eq!(data.as_ref(), raw);
+    }
+
+    #[test]
+    fn compressor_ok() {
+        let mut data = Bytes::from_static(&[
+            0x01, 0x00, 0x00, 0x00, 0xff, 0xff, 0xf8, 0x30, 0x01, 0x90, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x64, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x27, 0x10,
+        ]);
+        let compressor = FairlightCompressor::parse_master(&mut data);
+
+        assert!(compressor.source().is_none() && compressor.enabled());
+        assert_eq!(compressor.threshold(), -2000);
+        assert_eq!(compressor.ratio(), 400);
+        assert_eq!(compressor.attack(), 100);
+        assert_eq!(compressor.release(), 10000);
+
+        let mut data = BytesMut::new();
+        SetFairlightCompressor::master()
+            .enabled(true)
+            .ratio(400)
+            .serialize(&mut data);
+        assert_eq!(data.len(), 24);
+        assert_eq!(
+            &data[..10],
+            [0x05, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x90]
+        );
+
+        let mut data = BytesMut::new();
+        SetFairlightCompressor::source(AudioSourceId::new(1, ChannelSplit::Stereo))
+            .enabled(true)
+            .serialize(&mut data);
+        assert_eq!(data.len(), 40);
+        assert_eq!(&data[..4], [0x01, 0x00, 0x00, 0x01]);
+        assert_eq!(data[16], 0x01);
+    }
+
+    #[test]
+    fn eq_band_ok() {
+        let mut data = Bytes::from_static(&[
+            0x05, 0x01, 0x3f, 0x04, 0x0f, 0x04, 0x00, 0x00, 0x00, 0x00, 0x0b, 0xb8, 0xff, 0xff,
+            0xfe, 0x0c, 0x00, 0x46, 0x00, 0x00,
+        ]);
+        let band = FairlightEqBand::parse_master(&mut data);
+
+        assert!(band.source().is_none() && band.enabled());
+        assert_eq!(band.band(), 5);
+        assert_eq!(band.supported_shapes(), EqShapes::all());
+        assert_eq!(band.shape(), EqShapes::BELL);
+        assert_eq!(band.frequency_range(), EqFrequencyRanges::MID_HIGH);
+        assert_eq!(band.frequency(), 3000);
+        assert_eq!(band.gain(), -500);
+        assert_eq!(band.q_factor(), 70);
+
+        let mut data = BytesMut::new();
+        SetFairlightEqBand::master(5)
+            .shape(EqShapes::BELL)
+            .frequency(3000)
+            .serialize(&mut data);
+        assert_eq!(
+            data.as_ref(),
+            [
+                0x0a, 0x05, 0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0b, 0xb8, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00
+            ]
+        );
+
+        let mut data = BytesMut::new();
+        SetFairlightEqBand::source(AudioSourceId::new(1, ChannelSplit::Stereo), 0)
+            .serialize(&mut data);
+        assert_eq!(data.len(), 32);
+    }
+
+    #[test]
+    fn input_properties_ok() {
+        let mut data = Bytes::from_static(&[
+            0x04, 0xb1, 0x02, 0x00, 0x00, 0x00, 0x00, 0x20, 0x00, 0x03, 0x01, 0x06, 0x04, 0x00,
+            0x00, 0x00,
+        ]);
+        let properties = FairlightInputProperties::parse(&mut data);
+
+        assert_eq!(properties.input(), 1201);
+        assert_eq!(properties.input_type(), FairlightInputType::AudioIn);
+        assert_eq!(properties.port_type(), AudioPortType::XLR);
+        assert_eq!(properties.configuration(), InputConfigurations::MONO);
+        assert_eq!(
+            properties.supported_levels(),
+            InputLevels::PRO_LINE | InputLevels::MIC
+        );
+        assert_eq!(properties.level(), InputLevels::MIC);
+
+        let mut data = BytesMut::new();
+        SetFairlightInputProperties::new(1201)
+            .level(InputLevels::PRO_LINE)
+            .serialize(&mut data);
+        assert_eq!(
+            data.as_ref(),
+            [0x02, 0x00, 0x04, 0xb1, 0x00, 0x02, 0x00, 0x00]
+        );
+    }
+}
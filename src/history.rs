@@ -0,0 +1,114 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::Path,
+    sync::mpsc,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bytes::Bytes;
+use serde_json::json;
+
+use crate::{command::Command, trace::debug, Error};
+
+struct Record {
+    time: SystemTime,
+    raw: Bytes,
+    command: String,
+}
+
+/// Appends every command received from the switcher to a JSON Lines file, one object per
+/// command with the time it was received in milliseconds since the Unix epoch, the command
+/// name, the decoded command and the raw command bytes including the header as hex, e.g.
+/// `{"time_ms":1700000000000,"name":"PrgI","command":"Program input ME: 0 Source: 1","raw":"000c0000..."}`
+pub(crate) struct History {
+    tx: mpsc::Sender<Record>,
+}
+
+impl History {
+    /// Open the file for appending, it is created if missing. Lines are written from a
+    /// separate thread so a slow disk doesn't hold up the connection.
+    pub(crate) fn open(path: &Path) -> Result<Self, Error> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || write_records(file, rx));
+
+        Ok(History { tx })
+    }
+
+    pub(crate) fn record(&self, raw: Bytes, command: &Command) {
+        let _ = self.tx.send(Record {
+            time: SystemTime::now(),
+            raw,
+            command: command.to_string(),
+        });
+    }
+}
+
+fn write_records(file: File, rx: mpsc::Receiver<Record>) {
+    let mut writer = BufWriter::new(file);
+    // Flush once everything received so far has been written
+    while let Ok(record) = rx.recv() {
+        let result = std::iter::once(record)
+            .chain(rx.try_iter())
+            .try_for_each(|record| write_record(&mut writer, &record))
+            .and_then(|()| writer.flush());
+        if let Err(e) = result {
+            debug!("Writing command history failed: {}", e);
+            return;
+        }
+    }
+}
+
+fn write_record(writer: &mut impl Write, record: &Record) -> std::io::Result<()> {
+    let time_ms = record
+        .time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64;
+    let name = record
+        .raw
+        .get(4..8)
+        .map(String::from_utf8_lossy)
+        .unwrap_or_default();
+    let raw: String = record
+        .raw
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    let line = json!({
+        "time_ms": time_ms,
+        "name": name,
+        "command": record.command,
+        "raw": raw,
+    });
+    writeln!(writer, "{line}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_record_ok() {
+        let raw = Bytes::from_static(b"\x00\x0c\x00\x00PrgI\x00\x00\x00\x01");
+        let command = Command::parse(&mut raw.clone()).unwrap();
+        let record = Record {
+            time: UNIX_EPOCH + std::time::Duration::from_millis(1500),
+            raw,
+            command: command.to_string(),
+        };
+        let mut line = Vec::new();
+
+        write_record(&mut line, &record).unwrap();
+
+        let value: serde_json::Value = serde_json::from_slice(&line).unwrap();
+        assert_eq!(value["time_ms"], 1500);
+        assert_eq!(value["name"], "PrgI");
+        assert_eq!(value["command"], command.to_string());
+        assert_eq!(value["raw"], "000c00005072674900000001");
+        assert!(line.ends_with(b"\n"));
+    }
+}
@@ -0,0 +1,482 @@
+use alloc::string::String;
+use core::{fmt::Display, net::Ipv4Addr};
+
+use bitflags::bitflags;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::{command, parser::parse_str};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum HyperDeckState {
+    Idle,
+    Play,
+    Record,
+    Shuttle,
+    Unknown(u8),
+}
+
+impl From<u8> for HyperDeckState {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => HyperDeckState::Idle,
+            1 => HyperDeckState::Play,
+            2 => HyperDeckState::Record,
+            3 => HyperDeckState::Shuttle,
+            u => HyperDeckState::Unknown(u),
+        }
+    }
+}
+
+impl From<HyperDeckState> for u8 {
+    fn from(value: HyperDeckState) -> Self {
+        match value {
+            HyperDeckState::Idle => 0,
+            HyperDeckState::Play => 1,
+            HyperDeckState::Record => 2,
+            HyperDeckState::Shuttle => 3,
+            HyperDeckState::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for HyperDeckState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            HyperDeckState::Idle => write!(f, "Idle"),
+            HyperDeckState::Play => write!(f, "Play"),
+            HyperDeckState::Record => write!(f, "Record"),
+            HyperDeckState::Shuttle => write!(f, "Shuttle"),
+            HyperDeckState::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HyperDeckTime {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub frame: u8,
+}
+
+impl HyperDeckTime {
+    pub fn parse(data: &mut Bytes) -> Self {
+        HyperDeckTime {
+            hour: data.get_u8(),
+            minute: data.get_u8(),
+            second: data.get_u8(),
+            frame: data.get_u8(),
+        }
+    }
+}
+
+impl Display for HyperDeckTime {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}:{:02}:{:02}",
+            self.hour, self.minute, self.second, self.frame
+        )
+    }
+}
+
+/// Connection settings of a HyperDeck attached to the switcher
+#[derive(Debug, Clone)]
+pub struct HyperDeckSettings {
+    id: u16,
+    address: Ipv4Addr,
+    input: u16,
+    auto_roll: bool,
+    auto_roll_frame_delay: u16,
+}
+
+impl HyperDeckSettings {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let id = data.get_u16();
+        data.get_u16(); // Padding
+        let address = Ipv4Addr::from(data.get_u32());
+        let input = data.get_u16();
+        let auto_roll = data.get_u8() == 1;
+        data.get_u8(); // Padding
+        let auto_roll_frame_delay = data.get_u16();
+
+        HyperDeckSettings {
+            id,
+            address,
+            input,
+            auto_roll,
+            auto_roll_frame_delay,
+        }
+    }
+
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    pub fn address(&self) -> Ipv4Addr {
+        self.address
+    }
+
+    /// Switcher input the deck playback is connected to
+    pub fn input(&self) -> u16 {
+        self.input
+    }
+}
+
+impl Display for HyperDeckSettings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "HyperDeck {}: Address: {} Input: {} Auto roll: {} Frame delay: {}",
+            self.id, self.address, self.input, self.auto_roll, self.auto_roll_frame_delay
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HyperDeckStorage {
+    id: u16,
+    active_storage_media: i16,
+    current_clip: u16,
+    frame_rate: u16,
+    time_scale: u16,
+    interlaced: bool,
+    drop_frame: bool,
+}
+
+impl HyperDeckStorage {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let id = data.get_u16();
+        let active_storage_media = data.get_i16();
+        let current_clip = data.get_u16();
+        let frame_rate = data.get_u16();
+        let time_scale = data.get_u16();
+        let interlaced = data.get_u8() == 1;
+        let drop_frame = data.get_u8() == 1;
+
+        HyperDeckStorage {
+            id,
+            active_storage_media,
+            current_clip,
+            frame_rate,
+            time_scale,
+            interlaced,
+            drop_frame,
+        }
+    }
+
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    /// Active storage slot, negative when no media is inserted
+    pub fn active_storage_media(&self) -> i16 {
+        self.active_storage_media
+    }
+
+    pub fn current_clip(&self) -> u16 {
+        self.current_clip
+    }
+}
+
+impl Display for HyperDeckStorage {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "HyperDeck {}: Storage media: {} Clip: {} Frame rate: {}/{} Interlaced: {} Drop frame: {}",
+            self.id,
+            self.active_storage_media,
+            self.current_clip,
+            self.frame_rate,
+            self.time_scale,
+            self.interlaced,
+            self.drop_frame
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HyperDeckPlayer {
+    id: u16,
+    state: HyperDeckState,
+    single_clip: bool,
+    looping: bool,
+    speed: i16,
+    clip_time: HyperDeckTime,
+    timeline_time: HyperDeckTime,
+}
+
+impl HyperDeckPlayer {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let id = data.get_u16();
+        let state = data.get_u8().into();
+        let single_clip = data.get_u8() == 1;
+        let looping = data.get_u8() == 1;
+        data.get_u8(); // Padding
+        let speed = data.get_i16();
+        let clip_time = HyperDeckTime::parse(data);
+        let timeline_time = HyperDeckTime::parse(data);
+
+        HyperDeckPlayer {
+            id,
+            state,
+            single_clip,
+            looping,
+            speed,
+            clip_time,
+            timeline_time,
+        }
+    }
+
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    pub fn state(&self) -> HyperDeckState {
+        self.state
+    }
+
+    /// Playback stops at the end of the current clip
+    pub fn single_clip(&self) -> bool {
+        self.single_clip
+    }
+
+    pub fn looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Playback speed in percent
+    pub fn speed(&self) -> i16 {
+        self.speed
+    }
+
+    pub fn clip_time(&self) -> HyperDeckTime {
+        self.clip_time
+    }
+}
+
+impl Display for HyperDeckPlayer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "HyperDeck {}: State: {} Single clip: {} Loop: {} Speed: {}% Clip time: {} Timeline time: {}",
+            self.id,
+            self.state,
+            self.single_clip,
+            self.looping,
+            self.speed,
+            self.clip_time,
+            self.timeline_time
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HyperDeckClipCount {
+    id: u16,
+    count: u16,
+}
+
+impl HyperDeckClipCount {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let id = data.get_u16();
+        let count = data.get_u16();
+
+        HyperDeckClipCount { id, count }
+    }
+
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    pub fn count(&self) -> u16 {
+        self.count
+    }
+}
+
+impl Display for HyperDeckClipCount {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "HyperDeck {}: Clips: {}", self.id, self.count)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HyperDeckClip {
+    id: u16,
+    clip: u16,
+    name: Option<String>,
+    start: HyperDeckTime,
+    duration: HyperDeckTime,
+}
+
+impl HyperDeckClip {
+    pub fn parse(data: &mut Bytes) -> Result<Self, command::ParseError> {
+        let id = data.get_u16();
+        let clip = data.get_u16();
+        let name = parse_str(&mut data.split_to(64))?;
+        let start = HyperDeckTime::parse(data);
+        let duration = HyperDeckTime::parse(data);
+
+        Ok(HyperDeckClip {
+            id,
+            clip,
+            name,
+            start,
+            duration,
+        })
+    }
+
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    pub fn clip(&self) -> u16 {
+        self.clip
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn duration(&self) -> HyperDeckTime {
+        self.duration
+    }
+}
+
+impl Display for HyperDeckClip {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "HyperDeck {}: Clip {}: {} Start: {} Duration: {}",
+            self.id,
+            self.clip,
+            self.name.as_deref().unwrap_or(""),
+            self.start,
+            self.duration
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct HyperDeckPlayerMask: u8 {
+        const STATE = 0x01;
+        const SINGLE_CLIP = 0x02;
+        const LOOP = 0x04;
+        const SPEED = 0x08;
+    }
+}
+
+/// Change the transport state of a HyperDeck (`CXCP`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetHyperDeckPlayer {
+    mask: HyperDeckPlayerMask,
+    id: u16,
+    state: HyperDeckState,
+    single_clip: bool,
+    looping: bool,
+    speed: i16,
+}
+
+impl SetHyperDeckPlayer {
+    pub fn new(id: u16) -> Self {
+        SetHyperDeckPlayer {
+            mask: HyperDeckPlayerMask::empty(),
+            id,
+            state: HyperDeckState::Idle,
+            single_clip: false,
+            looping: false,
+            speed: 100,
+        }
+    }
+
+    /// Play at normal speed
+    pub fn play(id: u16) -> Self {
+        SetHyperDeckPlayer::new(id)
+            .state(HyperDeckState::Play)
+            .speed(100)
+    }
+
+    pub fn stop(id: u16) -> Self {
+        SetHyperDeckPlayer::new(id).state(HyperDeckState::Idle)
+    }
+
+    pub fn record(id: u16) -> Self {
+        SetHyperDeckPlayer::new(id).state(HyperDeckState::Record)
+    }
+
+    pub fn state(mut self, state: HyperDeckState) -> Self {
+        self.mask |= HyperDeckPlayerMask::STATE;
+        self.state = state;
+        self
+    }
+
+    pub fn single_clip(mut self, single_clip: bool) -> Self {
+        self.mask |= HyperDeckPlayerMask::SINGLE_CLIP;
+        self.single_clip = single_clip;
+        self
+    }
+
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.mask |= HyperDeckPlayerMask::LOOP;
+        self.looping = looping;
+        self
+    }
+
+    /// Playback speed in percent
+    pub fn speed(mut self, speed: i16) -> Self {
+        self.mask |= HyperDeckPlayerMask::SPEED;
+        self.speed = speed;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(0); // Padding
+        data.put_u16(self.id);
+        data.put_u8(self.state.into());
+        data.put_u8(self.single_clip as u8);
+        data.put_u8(self.looping as u8);
+        data.put_u8(0); // Padding
+        data.put_i16(self.speed);
+        data.put_u16(0); // Padding
+    }
+}
+
+impl Display for SetHyperDeckPlayer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "HyperDeck {}: State: {} Single clip: {} Loop: {} Speed: {}%",
+            self.id, self.state, self.single_clip, self.looping, self.speed
+        )
+    }
+}
+
+/// Cue a clip on a HyperDeck (`CXCS`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetHyperDeckClip {
+    id: u16,
+    clip: u16,
+}
+
+impl SetHyperDeckClip {
+    pub fn new(id: u16, clip: u16) -> Self {
+        SetHyperDeckClip { id, clip }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.id);
+        data.put_u16(self.clip);
+    }
+}
+
+impl Display for SetHyperDeckClip {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "HyperDeck {}: Clip: {}", self.id, self.clip)
+    }
+}
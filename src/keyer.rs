@@ -0,0 +1,1692 @@
+use core::fmt::Display;
+
+use bitflags::bitflags;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::{color::HslColor, command::UnknownBytes, transition::WipePattern, Error};
+
+/// Largest distance of the top and bottom mask edges from the center
+const MAX_MASK_VERTICAL: i16 = 9000;
+/// Largest distance of the left and right mask edges from the center
+const MAX_MASK_HORIZONTAL: i16 = 16000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum KeyType {
+    Luma,
+    Chroma,
+    Pattern,
+    Dve,
+    Unknown(u8),
+}
+
+impl From<u8> for KeyType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => KeyType::Luma,
+            1 => KeyType::Chroma,
+            2 => KeyType::Pattern,
+            3 => KeyType::Dve,
+            u => KeyType::Unknown(u),
+        }
+    }
+}
+
+impl From<KeyType> for u8 {
+    fn from(value: KeyType) -> Self {
+        match value {
+            KeyType::Luma => 0,
+            KeyType::Chroma => 1,
+            KeyType::Pattern => 2,
+            KeyType::Dve => 3,
+            KeyType::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for KeyType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            KeyType::Luma => write!(f, "Luma"),
+            KeyType::Chroma => write!(f, "Chroma"),
+            KeyType::Pattern => write!(f, "Pattern"),
+            KeyType::Dve => write!(f, "DVE"),
+            KeyType::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+/// Edges of a rectangular key mask in 1/1000 units from the center of the screen, top and
+/// bottom from -9000 to 9000 and left and right from -16000 to 16000
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct KeyerMask {
+    top: i16,
+    bottom: i16,
+    left: i16,
+    right: i16,
+}
+
+impl KeyerMask {
+    pub fn new(top: i16, bottom: i16, left: i16, right: i16) -> Result<Self, Error> {
+        if let Some(edge) = [top, bottom]
+            .into_iter()
+            .find(|edge| !(-MAX_MASK_VERTICAL..=MAX_MASK_VERTICAL).contains(edge))
+            .or_else(|| {
+                [left, right]
+                    .into_iter()
+                    .find(|edge| !(-MAX_MASK_HORIZONTAL..=MAX_MASK_HORIZONTAL).contains(edge))
+            })
+        {
+            return Err(Error::MaskOutOfRange(edge));
+        }
+
+        Ok(KeyerMask {
+            top,
+            bottom,
+            left,
+            right,
+        })
+    }
+
+    pub fn parse(data: &mut Bytes) -> Self {
+        let top = data.get_i16();
+        let bottom = data.get_i16();
+        let left = data.get_i16();
+        let right = data.get_i16();
+
+        KeyerMask {
+            top,
+            bottom,
+            left,
+            right,
+        }
+    }
+
+    pub fn top(&self) -> i16 {
+        self.top
+    }
+
+    pub fn bottom(&self) -> i16 {
+        self.bottom
+    }
+
+    pub fn left(&self) -> i16 {
+        self.left
+    }
+
+    pub fn right(&self) -> i16 {
+        self.right
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_i16(self.top);
+        data.put_i16(self.bottom);
+        data.put_i16(self.left);
+        data.put_i16(self.right);
+    }
+}
+
+impl Display for KeyerMask {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Top: {} Bottom: {} Left: {} Right: {}",
+            self.top, self.bottom, self.left, self.right
+        )
+    }
+}
+
+/// Type, sources and mask of an upstream keyer (`KeBP`)
+#[derive(Debug, Clone)]
+pub struct KeyerBaseProperties {
+    me: u8,
+    keyer: u8,
+    key_type: KeyType,
+    can_fly: bool,
+    fly_enabled: bool,
+    fill_source: u16,
+    cut_source: u16,
+    mask_enabled: bool,
+    mask: KeyerMask,
+    unknown: UnknownBytes,
+}
+
+impl KeyerBaseProperties {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let mut unknown = UnknownBytes::new(data);
+        let me = data.get_u8();
+        let keyer = data.get_u8();
+        let key_type = data.get_u8().into();
+        unknown.skip(data, 2);
+        let can_fly = data.get_u8() == 1;
+        let fly_enabled = data.get_u8() == 1;
+        unknown.skip(data, 1);
+        let fill_source = data.get_u16();
+        let cut_source = data.get_u16();
+        let mask_enabled = data.get_u8() == 1;
+        unknown.skip(data, 1);
+        let mask = KeyerMask::parse(data);
+        unknown.skip_rest(data);
+
+        KeyerBaseProperties {
+            me,
+            keyer,
+            key_type,
+            can_fly,
+            fly_enabled,
+            fill_source,
+            cut_source,
+            mask_enabled,
+            mask,
+            unknown,
+        }
+    }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    pub fn keyer(&self) -> u8 {
+        self.keyer
+    }
+
+    pub fn key_type(&self) -> KeyType {
+        self.key_type
+    }
+
+    /// The keyer has a DVE that can fly the key
+    pub fn can_fly(&self) -> bool {
+        self.can_fly
+    }
+
+    pub fn fly_enabled(&self) -> bool {
+        self.fly_enabled
+    }
+
+    pub fn fill_source(&self) -> u16 {
+        self.fill_source
+    }
+
+    pub fn cut_source(&self) -> u16 {
+        self.cut_source
+    }
+
+    pub fn mask_enabled(&self) -> bool {
+        self.mask_enabled
+    }
+
+    pub fn mask(&self) -> KeyerMask {
+        self.mask
+    }
+
+    /// Bytes skipped by parsing, see [`UnknownBytes`]
+    pub fn unknown(&self) -> &UnknownBytes {
+        &self.unknown
+    }
+}
+
+impl Display for KeyerBaseProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Keyer: {} Type: {} Fly: {} Fill: {} Cut: {} Mask: {}",
+            self.me,
+            self.keyer,
+            self.key_type,
+            self.fly_enabled,
+            self.fill_source,
+            self.cut_source,
+            self.mask_enabled
+        )
+    }
+}
+
+/// Whether an upstream keyer is on air (`KeOn`)
+#[derive(Debug, Clone)]
+pub struct KeyerOnAir {
+    me: u8,
+    keyer: u8,
+    on_air: bool,
+}
+
+impl KeyerOnAir {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let me = data.get_u8();
+        let keyer = data.get_u8();
+        let on_air = data.get_u8() == 1;
+
+        KeyerOnAir { me, keyer, on_air }
+    }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    pub fn keyer(&self) -> u8 {
+        self.keyer
+    }
+
+    pub fn on_air(&self) -> bool {
+        self.on_air
+    }
+}
+
+impl Display for KeyerOnAir {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Keyer: {} On air: {}",
+            self.me, self.keyer, self.on_air
+        )
+    }
+}
+
+/// Take an upstream keyer on or off air (`CKOn`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetKeyerOnAir {
+    me: u8,
+    keyer: u8,
+    on_air: bool,
+}
+
+impl SetKeyerOnAir {
+    pub fn new(me: u8, keyer: u8, on_air: bool) -> Self {
+        SetKeyerOnAir { me, keyer, on_air }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.me);
+        data.put_u8(self.keyer);
+        data.put_u8(self.on_air as u8);
+        data.put_u8(0); // Padding
+    }
+}
+
+impl Display for SetKeyerOnAir {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Keyer: {} On air: {}",
+            self.me, self.keyer, self.on_air
+        )
+    }
+}
+
+/// Select the fill source of an upstream keyer (`CKeF`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetKeyerFillSource {
+    me: u8,
+    keyer: u8,
+    source_id: u16,
+}
+
+impl SetKeyerFillSource {
+    pub fn new(me: u8, keyer: u8, source_id: u16) -> Self {
+        SetKeyerFillSource {
+            me,
+            keyer,
+            source_id,
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.me);
+        data.put_u8(self.keyer);
+        data.put_u16(self.source_id);
+    }
+}
+
+impl Display for SetKeyerFillSource {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Keyer: {} Source: {}",
+            self.me, self.keyer, self.source_id
+        )
+    }
+}
+
+/// Select the cut (key) source of an upstream keyer (`CKeC`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetKeyerCutSource {
+    me: u8,
+    keyer: u8,
+    source_id: u16,
+}
+
+impl SetKeyerCutSource {
+    pub fn new(me: u8, keyer: u8, source_id: u16) -> Self {
+        SetKeyerCutSource {
+            me,
+            keyer,
+            source_id,
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.me);
+        data.put_u8(self.keyer);
+        data.put_u16(self.source_id);
+    }
+}
+
+impl Display for SetKeyerCutSource {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Keyer: {} Source: {}",
+            self.me, self.keyer, self.source_id
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct KeyerTypeMask: u8 {
+        const KEY_TYPE = 0x01;
+        const FLYING_KEY = 0x02;
+    }
+}
+
+/// Change the type of an upstream keyer (`CKTp`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetKeyerType {
+    mask: KeyerTypeMask,
+    me: u8,
+    keyer: u8,
+    key_type: KeyType,
+    flying_key: bool,
+}
+
+impl SetKeyerType {
+    pub fn new(me: u8, keyer: u8) -> Self {
+        SetKeyerType {
+            mask: KeyerTypeMask::empty(),
+            me,
+            keyer,
+            key_type: KeyType::Luma,
+            flying_key: false,
+        }
+    }
+
+    pub fn key_type(mut self, key_type: KeyType) -> Self {
+        self.mask |= KeyerTypeMask::KEY_TYPE;
+        self.key_type = key_type;
+        self
+    }
+
+    /// Apply the DVE to a luma, chroma or pattern key
+    pub fn flying_key(mut self, flying_key: bool) -> Self {
+        self.mask |= KeyerTypeMask::FLYING_KEY;
+        self.flying_key = flying_key;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(self.me);
+        data.put_u8(self.keyer);
+        data.put_u8(self.key_type.into());
+        data.put_u8(self.flying_key as u8);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetKeyerType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Keyer: {} Type: {} Flying key: {}",
+            self.me, self.keyer, self.key_type, self.flying_key
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct KeyerMaskMask: u8 {
+        const ENABLED = 0x01;
+        const TOP = 0x02;
+        const BOTTOM = 0x04;
+        const LEFT = 0x08;
+        const RIGHT = 0x10;
+    }
+}
+
+/// Change the rectangular mask of an upstream keyer (`CKMs`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetKeyerMask {
+    mask: KeyerMaskMask,
+    me: u8,
+    keyer: u8,
+    enabled: bool,
+    edges: KeyerMask,
+}
+
+impl SetKeyerMask {
+    pub fn new(me: u8, keyer: u8) -> Self {
+        SetKeyerMask {
+            mask: KeyerMaskMask::empty(),
+            me,
+            keyer,
+            enabled: false,
+            edges: KeyerMask::default(),
+        }
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.mask |= KeyerMaskMask::ENABLED;
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn edges(mut self, edges: KeyerMask) -> Self {
+        self.mask |=
+            KeyerMaskMask::TOP | KeyerMaskMask::BOTTOM | KeyerMaskMask::LEFT | KeyerMaskMask::RIGHT;
+        self.edges = edges;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(self.me);
+        data.put_u8(self.keyer);
+        data.put_u8(self.enabled as u8);
+        self.edges.serialize(data);
+    }
+}
+
+impl Display for SetKeyerMask {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Keyer: {} Enabled: {} {}",
+            self.me, self.keyer, self.enabled, self.edges
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct KeyerLumaMask: u8 {
+        const PRE_MULTIPLIED = 0x01;
+        const CLIP = 0x02;
+        const GAIN = 0x04;
+        const INVERT = 0x08;
+    }
+}
+
+/// Change the luma key parameters of an upstream keyer (`CKLm`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetKeyerLuma {
+    mask: KeyerLumaMask,
+    me: u8,
+    keyer: u8,
+    pre_multiplied: bool,
+    clip: u16,
+    gain: u16,
+    invert: bool,
+}
+
+impl SetKeyerLuma {
+    pub fn new(me: u8, keyer: u8) -> Self {
+        SetKeyerLuma {
+            mask: KeyerLumaMask::empty(),
+            me,
+            keyer,
+            pre_multiplied: false,
+            clip: 0,
+            gain: 0,
+            invert: false,
+        }
+    }
+
+    pub fn pre_multiplied(mut self, pre_multiplied: bool) -> Self {
+        self.mask |= KeyerLumaMask::PRE_MULTIPLIED;
+        self.pre_multiplied = pre_multiplied;
+        self
+    }
+
+    /// Clip level in 1/10 percent
+    pub fn clip(mut self, clip: u16) -> Self {
+        self.mask |= KeyerLumaMask::CLIP;
+        self.clip = clip;
+        self
+    }
+
+    /// Gain in 1/10 percent
+    pub fn gain(mut self, gain: u16) -> Self {
+        self.mask |= KeyerLumaMask::GAIN;
+        self.gain = gain;
+        self
+    }
+
+    pub fn invert(mut self, invert: bool) -> Self {
+        self.mask |= KeyerLumaMask::INVERT;
+        self.invert = invert;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(self.me);
+        data.put_u8(self.keyer);
+        data.put_u8(self.pre_multiplied as u8);
+        data.put_u16(self.clip);
+        data.put_u16(self.gain);
+        data.put_u8(self.invert as u8);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetKeyerLuma {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Keyer: {} Pre-multiplied: {} Clip: {} Gain: {} Invert: {}",
+            self.me, self.keyer, self.pre_multiplied, self.clip, self.gain, self.invert
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct KeyerChromaMask: u8 {
+        const HUE = 0x01;
+        const GAIN = 0x02;
+        const Y_SUPPRESS = 0x04;
+        const LIFT = 0x08;
+        const NARROW = 0x10;
+    }
+}
+
+/// Change the chroma key parameters of an upstream keyer (`CKCk`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetKeyerChroma {
+    mask: KeyerChromaMask,
+    me: u8,
+    keyer: u8,
+    hue: u16,
+    gain: u16,
+    y_suppress: u16,
+    lift: u16,
+    narrow: bool,
+}
+
+impl SetKeyerChroma {
+    pub fn new(me: u8, keyer: u8) -> Self {
+        SetKeyerChroma {
+            mask: KeyerChromaMask::empty(),
+            me,
+            keyer,
+            hue: 0,
+            gain: 0,
+            y_suppress: 0,
+            lift: 0,
+            narrow: false,
+        }
+    }
+
+    /// Hue in 1/10 degree
+    pub fn hue(mut self, hue: u16) -> Self {
+        self.mask |= KeyerChromaMask::HUE;
+        self.hue = hue;
+        self
+    }
+
+    /// Gain in 1/10 percent
+    pub fn gain(mut self, gain: u16) -> Self {
+        self.mask |= KeyerChromaMask::GAIN;
+        self.gain = gain;
+        self
+    }
+
+    /// Y suppress in 1/10 percent
+    pub fn y_suppress(mut self, y_suppress: u16) -> Self {
+        self.mask |= KeyerChromaMask::Y_SUPPRESS;
+        self.y_suppress = y_suppress;
+        self
+    }
+
+    /// Lift in 1/10 percent
+    pub fn lift(mut self, lift: u16) -> Self {
+        self.mask |= KeyerChromaMask::LIFT;
+        self.lift = lift;
+        self
+    }
+
+    pub fn narrow(mut self, narrow: bool) -> Self {
+        self.mask |= KeyerChromaMask::NARROW;
+        self.narrow = narrow;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(self.me);
+        data.put_u8(self.keyer);
+        data.put_u8(0); // Padding
+        data.put_u16(self.hue);
+        data.put_u16(self.gain);
+        data.put_u16(self.y_suppress);
+        data.put_u16(self.lift);
+        data.put_u8(self.narrow as u8);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetKeyerChroma {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Keyer: {} Hue: {} Gain: {} Y suppress: {} Lift: {} Narrow: {}",
+            self.me, self.keyer, self.hue, self.gain, self.y_suppress, self.lift, self.narrow
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct KeyerPatternMask: u8 {
+        const STYLE = 0x01;
+        const SIZE = 0x02;
+        const SYMMETRY = 0x04;
+        const SOFTNESS = 0x08;
+        const POSITION_X = 0x10;
+        const POSITION_Y = 0x20;
+        const INVERT = 0x40;
+    }
+}
+
+/// Change the pattern key parameters of an upstream keyer (`CKPt`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetKeyerPattern {
+    mask: KeyerPatternMask,
+    me: u8,
+    keyer: u8,
+    style: WipePattern,
+    size: u16,
+    symmetry: u16,
+    softness: u16,
+    position_x: u16,
+    position_y: u16,
+    invert: bool,
+}
+
+impl SetKeyerPattern {
+    pub fn new(me: u8, keyer: u8) -> Self {
+        SetKeyerPattern {
+            mask: KeyerPatternMask::empty(),
+            me,
+            keyer,
+            style: WipePattern::LeftToRightBar,
+            size: 0,
+            symmetry: 0,
+            softness: 0,
+            position_x: 0,
+            position_y: 0,
+            invert: false,
+        }
+    }
+
+    pub fn style(mut self, style: WipePattern) -> Self {
+        self.mask |= KeyerPatternMask::STYLE;
+        self.style = style;
+        self
+    }
+
+    /// Size in 1/100 percent
+    pub fn size(mut self, size: u16) -> Self {
+        self.mask |= KeyerPatternMask::SIZE;
+        self.size = size;
+        self
+    }
+
+    /// Symmetry in 1/100 percent
+    pub fn symmetry(mut self, symmetry: u16) -> Self {
+        self.mask |= KeyerPatternMask::SYMMETRY;
+        self.symmetry = symmetry;
+        self
+    }
+
+    /// Softness in 1/100 percent
+    pub fn softness(mut self, softness: u16) -> Self {
+        self.mask |= KeyerPatternMask::SOFTNESS;
+        self.softness = softness;
+        self
+    }
+
+    /// Center of the pattern in 1/10000 of the screen
+    pub fn position(mut self, x: u16, y: u16) -> Self {
+        self.mask |= KeyerPatternMask::POSITION_X | KeyerPatternMask::POSITION_Y;
+        self.position_x = x;
+        self.position_y = y;
+        self
+    }
+
+    pub fn invert(mut self, invert: bool) -> Self {
+        self.mask |= KeyerPatternMask::INVERT;
+        self.invert = invert;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(self.me);
+        data.put_u8(self.keyer);
+        data.put_u8(self.style.into());
+        data.put_u16(self.size);
+        data.put_u16(self.symmetry);
+        data.put_u16(self.softness);
+        data.put_u16(self.position_x);
+        data.put_u16(self.position_y);
+        data.put_u8(self.invert as u8);
+        data.put_u8(0); // Padding
+    }
+}
+
+impl Display for SetKeyerPattern {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Keyer: {} Style: {} Size: {} Symmetry: {} Softness: {} Position: {}x{} Invert: {}",
+            self.me,
+            self.keyer,
+            self.style,
+            self.size,
+            self.symmetry,
+            self.softness,
+            self.position_x,
+            self.position_y,
+            self.invert
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct KeyerDVEMask: u32 {
+        const SIZE_X = 1 << 0;
+        const SIZE_Y = 1 << 1;
+        const POSITION_X = 1 << 2;
+        const POSITION_Y = 1 << 3;
+        const ROTATION = 1 << 4;
+        const BORDER_ENABLED = 1 << 5;
+        const SHADOW_ENABLED = 1 << 6;
+        const MASK_ENABLED = 1 << 20;
+        const MASK_TOP = 1 << 21;
+        const MASK_BOTTOM = 1 << 22;
+        const MASK_LEFT = 1 << 23;
+        const MASK_RIGHT = 1 << 24;
+        const RATE = 1 << 25;
+    }
+}
+
+/// Change the DVE parameters of an upstream keyer (`CKDV`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetKeyerDVE {
+    mask: KeyerDVEMask,
+    me: u8,
+    keyer: u8,
+    size_x: i32,
+    size_y: i32,
+    position_x: i32,
+    position_y: i32,
+    rotation: i32,
+    border_enabled: bool,
+    shadow_enabled: bool,
+    mask_enabled: bool,
+    mask_edges: KeyerMask,
+    rate: u8,
+}
+
+impl SetKeyerDVE {
+    pub fn new(me: u8, keyer: u8) -> Self {
+        SetKeyerDVE {
+            mask: KeyerDVEMask::empty(),
+            me,
+            keyer,
+            size_x: 0,
+            size_y: 0,
+            position_x: 0,
+            position_y: 0,
+            rotation: 0,
+            border_enabled: false,
+            shadow_enabled: false,
+            mask_enabled: false,
+            mask_edges: KeyerMask::default(),
+            rate: 0,
+        }
+    }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    pub fn keyer(&self) -> u8 {
+        self.keyer
+    }
+
+    pub fn mask(&self) -> KeyerDVEMask {
+        self.mask
+    }
+
+    /// Scale in 1/1000, 1000 is full size
+    pub fn size(mut self, x: i32, y: i32) -> Self {
+        self.mask |= KeyerDVEMask::SIZE_X | KeyerDVEMask::SIZE_Y;
+        self.size_x = x;
+        self.size_y = y;
+        self
+    }
+
+    /// Position in 1/1000 of the screen from the center
+    pub fn position(mut self, x: i32, y: i32) -> Self {
+        self.mask |= KeyerDVEMask::POSITION_X | KeyerDVEMask::POSITION_Y;
+        self.position_x = x;
+        self.position_y = y;
+        self
+    }
+
+    /// Rotation in 1/10 degree
+    pub fn rotation(mut self, rotation: i32) -> Self {
+        self.mask |= KeyerDVEMask::ROTATION;
+        self.rotation = rotation;
+        self
+    }
+
+    pub fn border_enabled(mut self, enabled: bool) -> Self {
+        self.mask |= KeyerDVEMask::BORDER_ENABLED;
+        self.border_enabled = enabled;
+        self
+    }
+
+    pub fn shadow_enabled(mut self, enabled: bool) -> Self {
+        self.mask |= KeyerDVEMask::SHADOW_ENABLED;
+        self.shadow_enabled = enabled;
+        self
+    }
+
+    pub fn mask_enabled(mut self, enabled: bool) -> Self {
+        self.mask |= KeyerDVEMask::MASK_ENABLED;
+        self.mask_enabled = enabled;
+        self
+    }
+
+    pub fn mask_edges(mut self, edges: KeyerMask) -> Self {
+        self.mask |= KeyerDVEMask::MASK_TOP
+            | KeyerDVEMask::MASK_BOTTOM
+            | KeyerDVEMask::MASK_LEFT
+            | KeyerDVEMask::MASK_RIGHT;
+        self.mask_edges = edges;
+        self
+    }
+
+    /// Duration of a fly key transition in frames
+    pub fn rate(mut self, rate: u8) -> Self {
+        self.mask |= KeyerDVEMask::RATE;
+        self.rate = rate;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u32(self.mask.bits());
+        data.put_u8(self.me);
+        data.put_u8(self.keyer);
+        data.put_u16(0); // Padding
+        data.put_i32(self.size_x);
+        data.put_i32(self.size_y);
+        data.put_i32(self.position_x);
+        data.put_i32(self.position_y);
+        data.put_i32(self.rotation);
+        data.put_u8(self.border_enabled as u8);
+        data.put_u8(self.shadow_enabled as u8);
+        // Border and light source settings are left untouched by the mask
+        data.put_bytes(0, 21);
+        data.put_u8(self.mask_enabled as u8);
+        self.mask_edges.serialize(data);
+        data.put_u8(self.rate);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetKeyerDVE {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Keyer: {} Size: {}x{} Position: {}x{} Rotation: {} Rate: {}",
+            self.me,
+            self.keyer,
+            self.size_x,
+            self.size_y,
+            self.position_x,
+            self.position_y,
+            self.rotation,
+            self.rate
+        )
+    }
+}
+
+/// Stored position of a flying key, a fly key transition moves the key between them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum Keyframe {
+    A,
+    B,
+    Unknown(u8),
+}
+
+impl From<u8> for Keyframe {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => Keyframe::A,
+            2 => Keyframe::B,
+            u => Keyframe::Unknown(u),
+        }
+    }
+}
+
+impl From<Keyframe> for u8 {
+    fn from(value: Keyframe) -> Self {
+        match value {
+            Keyframe::A => 1,
+            Keyframe::B => 2,
+            Keyframe::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for Keyframe {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Keyframe::A => write!(f, "A"),
+            Keyframe::B => write!(f, "B"),
+            Keyframe::Unknown(u) => write!(f, "Unknown: {u}"),
+        }
+    }
+}
+
+/// Parameters of a keyframe of a flying key (`KKFP`)
+#[derive(Debug, Clone)]
+pub struct KeyerKeyframe {
+    me: u8,
+    keyer: u8,
+    keyframe: Keyframe,
+    size_x: i32,
+    size_y: i32,
+    position_x: i32,
+    position_y: i32,
+    rotation: i32,
+    border_outer_width: u16,
+    border_inner_width: u16,
+    border_outer_softness: u8,
+    border_inner_softness: u8,
+    border_bevel_softness: u8,
+    border_bevel_position: u8,
+    border_opacity: u8,
+    border_color: HslColor,
+    light_source_direction: u16,
+    light_source_altitude: u8,
+    mask_enabled: bool,
+    mask: KeyerMask,
+}
+
+impl KeyerKeyframe {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let me = data.get_u8();
+        let keyer = data.get_u8();
+        let keyframe = data.get_u8().into();
+        data.get_u8(); // Padding
+        let size_x = data.get_i32();
+        let size_y = data.get_i32();
+        let position_x = data.get_i32();
+        let position_y = data.get_i32();
+        let rotation = data.get_i32();
+        let border_outer_width = data.get_u16();
+        let border_inner_width = data.get_u16();
+        let border_outer_softness = data.get_u8();
+        let border_inner_softness = data.get_u8();
+        let border_bevel_softness = data.get_u8();
+        let border_bevel_position = data.get_u8();
+        let border_opacity = data.get_u8();
+        data.get_u8(); // Padding
+        let border_color = HslColor::parse(data);
+        let light_source_direction = data.get_u16();
+        let light_source_altitude = data.get_u8();
+        let mask_enabled = data.get_u8() == 1;
+        let mask = KeyerMask::parse(data);
+
+        KeyerKeyframe {
+            me,
+            keyer,
+            keyframe,
+            size_x,
+            size_y,
+            position_x,
+            position_y,
+            rotation,
+            border_outer_width,
+            border_inner_width,
+            border_outer_softness,
+            border_inner_softness,
+            border_bevel_softness,
+            border_bevel_position,
+            border_opacity,
+            border_color,
+            light_source_direction,
+            light_source_altitude,
+            mask_enabled,
+            mask,
+        }
+    }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    pub fn keyer(&self) -> u8 {
+        self.keyer
+    }
+
+    pub fn keyframe(&self) -> Keyframe {
+        self.keyframe
+    }
+
+    /// Scale in 1/1000, 1000 is full size
+    pub fn size(&self) -> (i32, i32) {
+        (self.size_x, self.size_y)
+    }
+
+    /// Position in 1/1000 of the screen from the center
+    pub fn position(&self) -> (i32, i32) {
+        (self.position_x, self.position_y)
+    }
+
+    /// Rotation in 1/10 degree
+    pub fn rotation(&self) -> i32 {
+        self.rotation
+    }
+
+    /// Outer and inner border width in 1/100
+    pub fn border_width(&self) -> (u16, u16) {
+        (self.border_outer_width, self.border_inner_width)
+    }
+
+    /// Outer and inner border softness in percent
+    pub fn border_softness(&self) -> (u8, u8) {
+        (self.border_outer_softness, self.border_inner_softness)
+    }
+
+    /// Bevel softness and position in percent
+    pub fn border_bevel(&self) -> (u8, u8) {
+        (self.border_bevel_softness, self.border_bevel_position)
+    }
+
+    /// Border opacity in percent
+    pub fn border_opacity(&self) -> u8 {
+        self.border_opacity
+    }
+
+    pub fn border_color(&self) -> HslColor {
+        self.border_color
+    }
+
+    /// Direction of the light source in 1/10 degree and its altitude
+    pub fn light_source(&self) -> (u16, u8) {
+        (self.light_source_direction, self.light_source_altitude)
+    }
+
+    pub fn mask_enabled(&self) -> bool {
+        self.mask_enabled
+    }
+
+    pub fn mask(&self) -> KeyerMask {
+        self.mask
+    }
+}
+
+impl Display for KeyerKeyframe {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Keyer: {} Keyframe: {} Size: {}x{} Position: {}x{} Rotation: {} Mask: {}",
+            self.me,
+            self.keyer,
+            self.keyframe,
+            self.size_x,
+            self.size_y,
+            self.position_x,
+            self.position_y,
+            self.rotation,
+            self.mask_enabled
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct KeyerKeyframeMask: u32 {
+        const SIZE_X = 1 << 0;
+        const SIZE_Y = 1 << 1;
+        const POSITION_X = 1 << 2;
+        const POSITION_Y = 1 << 3;
+        const ROTATION = 1 << 4;
+        const BORDER_OUTER_WIDTH = 1 << 5;
+        const BORDER_INNER_WIDTH = 1 << 6;
+        const BORDER_OUTER_SOFTNESS = 1 << 7;
+        const BORDER_INNER_SOFTNESS = 1 << 8;
+        const BORDER_BEVEL_SOFTNESS = 1 << 9;
+        const BORDER_BEVEL_POSITION = 1 << 10;
+        const BORDER_OPACITY = 1 << 11;
+        const BORDER_HUE = 1 << 12;
+        const BORDER_SATURATION = 1 << 13;
+        const BORDER_LUMA = 1 << 14;
+        const LIGHT_SOURCE_DIRECTION = 1 << 15;
+        const LIGHT_SOURCE_ALTITUDE = 1 << 16;
+        const MASK_ENABLED = 1 << 17;
+        const MASK_TOP = 1 << 18;
+        const MASK_BOTTOM = 1 << 19;
+        const MASK_LEFT = 1 << 20;
+        const MASK_RIGHT = 1 << 21;
+    }
+}
+
+/// Store parameters in a keyframe of a flying key (`CKFP`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetKeyerKeyframe {
+    mask: KeyerKeyframeMask,
+    me: u8,
+    keyer: u8,
+    keyframe: Keyframe,
+    size_x: i32,
+    size_y: i32,
+    position_x: i32,
+    position_y: i32,
+    rotation: i32,
+    border_outer_width: u16,
+    border_inner_width: u16,
+    border_outer_softness: u8,
+    border_inner_softness: u8,
+    border_bevel_softness: u8,
+    border_bevel_position: u8,
+    border_opacity: u8,
+    border_color: HslColor,
+    light_source_direction: u16,
+    light_source_altitude: u8,
+    mask_enabled: bool,
+    mask_edges: KeyerMask,
+}
+
+impl SetKeyerKeyframe {
+    pub fn new(me: u8, keyer: u8, keyframe: Keyframe) -> Self {
+        SetKeyerKeyframe {
+            mask: KeyerKeyframeMask::empty(),
+            me,
+            keyer,
+            keyframe,
+            size_x: 0,
+            size_y: 0,
+            position_x: 0,
+            position_y: 0,
+            rotation: 0,
+            border_outer_width: 0,
+            border_inner_width: 0,
+            border_outer_softness: 0,
+            border_inner_softness: 0,
+            border_bevel_softness: 0,
+            border_bevel_position: 0,
+            border_opacity: 0,
+            border_color: HslColor::default(),
+            light_source_direction: 0,
+            light_source_altitude: 0,
+            mask_enabled: false,
+            mask_edges: KeyerMask::default(),
+        }
+    }
+
+    /// Scale in 1/1000, 1000 is full size
+    pub fn size(mut self, x: i32, y: i32) -> Self {
+        self.mask |= KeyerKeyframeMask::SIZE_X | KeyerKeyframeMask::SIZE_Y;
+        self.size_x = x;
+        self.size_y = y;
+        self
+    }
+
+    /// Position in 1/1000 of the screen from the center
+    pub fn position(mut self, x: i32, y: i32) -> Self {
+        self.mask |= KeyerKeyframeMask::POSITION_X | KeyerKeyframeMask::POSITION_Y;
+        self.position_x = x;
+        self.position_y = y;
+        self
+    }
+
+    /// Rotation in 1/10 degree
+    pub fn rotation(mut self, rotation: i32) -> Self {
+        self.mask |= KeyerKeyframeMask::ROTATION;
+        self.rotation = rotation;
+        self
+    }
+
+    /// Outer and inner border width in 1/100
+    pub fn border_width(mut self, outer: u16, inner: u16) -> Self {
+        self.mask |= KeyerKeyframeMask::BORDER_OUTER_WIDTH | KeyerKeyframeMask::BORDER_INNER_WIDTH;
+        self.border_outer_width = outer;
+        self.border_inner_width = inner;
+        self
+    }
+
+    /// Outer and inner border softness in percent
+    pub fn border_softness(mut self, outer: u8, inner: u8) -> Self {
+        self.mask |=
+            KeyerKeyframeMask::BORDER_OUTER_SOFTNESS | KeyerKeyframeMask::BORDER_INNER_SOFTNESS;
+        self.border_outer_softness = outer;
+        self.border_inner_softness = inner;
+        self
+    }
+
+    /// Bevel softness and position in percent
+    pub fn border_bevel(mut self, softness: u8, position: u8) -> Self {
+        self.mask |=
+            KeyerKeyframeMask::BORDER_BEVEL_SOFTNESS | KeyerKeyframeMask::BORDER_BEVEL_POSITION;
+        self.border_bevel_softness = softness;
+        self.border_bevel_position = position;
+        self
+    }
+
+    /// Border opacity in percent
+    pub fn border_opacity(mut self, opacity: u8) -> Self {
+        self.mask |= KeyerKeyframeMask::BORDER_OPACITY;
+        self.border_opacity = opacity;
+        self
+    }
+
+    pub fn border_color(mut self, color: HslColor) -> Self {
+        self.mask |= KeyerKeyframeMask::BORDER_HUE
+            | KeyerKeyframeMask::BORDER_SATURATION
+            | KeyerKeyframeMask::BORDER_LUMA;
+        self.border_color = color;
+        self
+    }
+
+    /// Direction of the light source in 1/10 degree and its altitude
+    pub fn light_source(mut self, direction: u16, altitude: u8) -> Self {
+        self.mask |=
+            KeyerKeyframeMask::LIGHT_SOURCE_DIRECTION | KeyerKeyframeMask::LIGHT_SOURCE_ALTITUDE;
+        self.light_source_direction = direction;
+        self.light_source_altitude = altitude;
+        self
+    }
+
+    pub fn mask_enabled(mut self, enabled: bool) -> Self {
+        self.mask |= KeyerKeyframeMask::MASK_ENABLED;
+        self.mask_enabled = enabled;
+        self
+    }
+
+    pub fn mask_edges(mut self, edges: KeyerMask) -> Self {
+        self.mask |= KeyerKeyframeMask::MASK_TOP
+            | KeyerKeyframeMask::MASK_BOTTOM
+            | KeyerKeyframeMask::MASK_LEFT
+            | KeyerKeyframeMask::MASK_RIGHT;
+        self.mask_edges = edges;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u32(self.mask.bits());
+        data.put_u8(self.me);
+        data.put_u8(self.keyer);
+        data.put_u8(self.keyframe.into());
+        data.put_u8(0); // Padding
+        data.put_i32(self.size_x);
+        data.put_i32(self.size_y);
+        data.put_i32(self.position_x);
+        data.put_i32(self.position_y);
+        data.put_i32(self.rotation);
+        data.put_u16(self.border_outer_width);
+        data.put_u16(self.border_inner_width);
+        data.put_u8(self.border_outer_softness);
+        data.put_u8(self.border_inner_softness);
+        data.put_u8(self.border_bevel_softness);
+        data.put_u8(self.border_bevel_position);
+        data.put_u8(self.border_opacity);
+        data.put_u8(0); // Padding
+        self.border_color.serialize(data);
+        data.put_u16(self.light_source_direction);
+        data.put_u8(self.light_source_altitude);
+        data.put_u8(self.mask_enabled as u8);
+        self.mask_edges.serialize(data);
+    }
+}
+
+impl Display for SetKeyerKeyframe {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Keyer: {} Keyframe: {} Size: {}x{} Position: {}x{} Rotation: {}",
+            self.me,
+            self.keyer,
+            self.keyframe,
+            self.size_x,
+            self.size_y,
+            self.position_x,
+            self.position_y,
+            self.rotation
+        )
+    }
+}
+
+/// Fill and cut source of a downstream keyer (`DskB`)
+#[derive(Debug, Clone)]
+pub struct DownstreamKeyerSources {
+    keyer: u8,
+    fill_source: u16,
+    cut_source: u16,
+}
+
+impl DownstreamKeyerSources {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let keyer = data.get_u8();
+        data.get_u8(); // Padding
+        let fill_source = data.get_u16();
+        let cut_source = data.get_u16();
+
+        DownstreamKeyerSources {
+            keyer,
+            fill_source,
+            cut_source,
+        }
+    }
+
+    pub fn keyer(&self) -> u8 {
+        self.keyer
+    }
+
+    pub fn fill_source(&self) -> u16 {
+        self.fill_source
+    }
+
+    pub fn cut_source(&self) -> u16 {
+        self.cut_source
+    }
+}
+
+impl Display for DownstreamKeyerSources {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Keyer: {} Fill: {} Cut: {}",
+            self.keyer, self.fill_source, self.cut_source
+        )
+    }
+}
+
+/// On air and transition state of a downstream keyer (`DskS`)
+#[derive(Debug, Clone)]
+pub struct DownstreamKeyerState {
+    keyer: u8,
+    on_air: bool,
+    in_transition: bool,
+    auto_transitioning: bool,
+    frames_remaining: u8,
+}
+
+impl DownstreamKeyerState {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let keyer = data.get_u8();
+        let on_air = data.get_u8() == 1;
+        let in_transition = data.get_u8() == 1;
+        let auto_transitioning = data.get_u8() == 1;
+        let frames_remaining = data.get_u8();
+
+        DownstreamKeyerState {
+            keyer,
+            on_air,
+            in_transition,
+            auto_transitioning,
+            frames_remaining,
+        }
+    }
+
+    pub fn keyer(&self) -> u8 {
+        self.keyer
+    }
+
+    pub fn on_air(&self) -> bool {
+        self.on_air
+    }
+
+    /// The key is fading in or out
+    pub fn in_transition(&self) -> bool {
+        self.in_transition
+    }
+
+    pub fn auto_transitioning(&self) -> bool {
+        self.auto_transitioning
+    }
+
+    /// Frames left of the running transition
+    pub fn frames_remaining(&self) -> u8 {
+        self.frames_remaining
+    }
+}
+
+impl Display for DownstreamKeyerState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Keyer: {} On air: {} In transition: {} Auto: {} Frames remaining: {}",
+            self.keyer,
+            self.on_air,
+            self.in_transition,
+            self.auto_transitioning,
+            self.frames_remaining
+        )
+    }
+}
+
+/// Select the fill source of a downstream keyer (`CDsF`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetDownstreamKeyerFillSource {
+    keyer: u8,
+    source_id: u16,
+}
+
+impl SetDownstreamKeyerFillSource {
+    pub fn new(keyer: u8, source_id: u16) -> Self {
+        SetDownstreamKeyerFillSource { keyer, source_id }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.keyer);
+        data.put_u8(0); // Padding
+        data.put_u16(self.source_id);
+    }
+}
+
+impl Display for SetDownstreamKeyerFillSource {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Keyer: {} Source: {}", self.keyer, self.source_id)
+    }
+}
+
+/// Select the cut (key) source of a downstream keyer (`CDsC`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetDownstreamKeyerCutSource {
+    keyer: u8,
+    source_id: u16,
+}
+
+impl SetDownstreamKeyerCutSource {
+    pub fn new(keyer: u8, source_id: u16) -> Self {
+        SetDownstreamKeyerCutSource { keyer, source_id }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.keyer);
+        data.put_u8(0); // Padding
+        data.put_u16(self.source_id);
+    }
+}
+
+impl Display for SetDownstreamKeyerCutSource {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Keyer: {} Source: {}", self.keyer, self.source_id)
+    }
+}
+
+/// Take a downstream keyer on or off air without a transition (`CDsL`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetDownstreamKeyerOnAir {
+    keyer: u8,
+    on_air: bool,
+}
+
+impl SetDownstreamKeyerOnAir {
+    pub fn new(keyer: u8, on_air: bool) -> Self {
+        SetDownstreamKeyerOnAir { keyer, on_air }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.keyer);
+        data.put_u8(self.on_air as u8);
+        data.put_u16(0); // Padding
+    }
+}
+
+impl Display for SetDownstreamKeyerOnAir {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Keyer: {} On air: {}", self.keyer, self.on_air)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize_keyer_dve_ok() {
+        let mut data = BytesMut::new();
+        SetKeyerDVE::new(0, 1)
+            .size(500, 500)
+            .mask_edges(KeyerMask::new(1, 2, 3, 4).unwrap())
+            .rate(25)
+            .serialize(&mut data);
+
+        assert_eq!(data.len(), 64);
+        assert_eq!(data[..4], [0x03, 0xe0, 0x00, 0x03]);
+        assert_eq!(data[8..12], [0x00, 0x00, 0x01, 0xf4]);
+        assert_eq!(data[52..61], [0, 1, 0, 2, 0, 3, 0, 4, 25]);
+    }
+
+    #[test]
+    fn keyer_keyframe_ok() {
+        let mut data = Bytes::from_static(&[
+            0x00, 0x01, 0x02, 0x00, 0x00, 0x00, 0x01, 0xf4, 0x00, 0x00, 0x01, 0xf4, 0xff, 0xff,
+            0xfc, 0x18, 0x00, 0x00, 0x03, 0xe8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x32, 0x00, 0x00,
+            0x64, 0x00, 0x00, 0x00, 0x64, 0x00, 0x04, 0xb0, 0x03, 0xe8, 0x01, 0xf4, 0x00, 0x24,
+            0x3c, 0x01, 0x00, 0x01, 0x00, 0x02, 0x00, 0x03, 0x00, 0x04,
+        ]);
+        let keyframe = KeyerKeyframe::parse(&mut data);
+
+        assert_eq!((keyframe.me(), keyframe.keyer()), (0, 1));
+        assert_eq!(keyframe.keyframe(), Keyframe::B);
+        assert_eq!(keyframe.size(), (500, 500));
+        assert_eq!(keyframe.position(), (-1000, 1000));
+        assert_eq!(keyframe.border_width(), (50, 0));
+        assert_eq!(keyframe.border_color(), HslColor::new(1200, 1000, 500));
+        assert_eq!(keyframe.light_source(), (36, 60));
+        assert!(keyframe.mask_enabled());
+        assert_eq!(keyframe.mask(), KeyerMask::new(1, 2, 3, 4).unwrap());
+
+        let mut data = BytesMut::new();
+        SetKeyerKeyframe::new(0, 1, Keyframe::B)
+            .size(500, 500)
+            .position(-1000, 1000)
+            .serialize(&mut data);
+        assert_eq!(data.len(), 56);
+        assert_eq!(data[..8], [0x00, 0x00, 0x00, 0x0f, 0x00, 0x01, 0x02, 0x00]);
+        assert_eq!(data[16..20], [0xff, 0xff, 0xfc, 0x18]);
+    }
+
+    #[test]
+    fn keyer_base_properties_ok() {
+        let mut data = Bytes::from_static(&[
+            0x01, 0x00, 0x02, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x05, 0x00, 0x06, 0x01, 0x00,
+            0x0d, 0xac, 0xf2, 0x54, 0xe0, 0xc0, 0x1f, 0x40, 0x00, 0x00,
+        ]);
+        let properties = KeyerBaseProperties::parse(&mut data);
+
+        assert_eq!((properties.me(), properties.keyer()), (1, 0));
+        assert_eq!(properties.key_type(), KeyType::Pattern);
+        assert!(properties.can_fly() && properties.fly_enabled());
+        assert_eq!(properties.fill_source(), 5);
+        assert_eq!(properties.cut_source(), 6);
+        assert_eq!(
+            properties.mask(),
+            KeyerMask::new(3500, -3500, -8000, 8000).unwrap()
+        );
+    }
+
+    #[cfg(feature = "research")]
+    #[test]
+    fn keyer_base_properties_unknown_bytes() {
+        let mut data = Bytes::from_static(&[
+            0x01, 0x00, 0x02, 0xaa, 0xbb, 0x01, 0x01, 0xcc, 0x00, 0x05, 0x00, 0x06, 0x01, 0xdd,
+            0x0d, 0xac, 0xf2, 0x54, 0xe0, 0xc0, 0x1f, 0x40, 0xee, 0xff,
+        ]);
+        let properties = KeyerBaseProperties::parse(&mut data);
+        let unknown: Vec<(usize, &[u8])> = properties
+            .unknown()
+            .iter()
+            .map(|(offset, bytes)| (offset, bytes.as_ref()))
+            .collect();
+
+        assert_eq!(
+            unknown,
+            [
+                (3, &[0xaa, 0xbb][..]),
+                (7, &[0xcc][..]),
+                (13, &[0xdd][..]),
+                (22, &[0xee, 0xff][..])
+            ]
+        );
+        assert_eq!(
+            properties.unknown().to_string(),
+            "3: [AA BB] 7: [CC] 13: [DD] 22: [EE FF]"
+        );
+    }
+
+    #[test]
+    fn keyer_mask_range() {
+        assert!(KeyerMask::new(9000, -9000, -16000, 16000).is_ok());
+        assert!(matches!(
+            KeyerMask::new(9001, 0, 0, 0),
+            Err(Error::MaskOutOfRange(9001))
+        ));
+        assert!(matches!(
+            KeyerMask::new(0, 0, -16001, 0),
+            Err(Error::MaskOutOfRange(-16001))
+        ));
+    }
+}
@@ -1,137 +1,155 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "audio")]
+pub mod audio;
+#[cfg(feature = "client")]
+pub mod auxoutput;
+#[cfg(feature = "camera")]
+pub mod camera;
+pub mod capabilities;
+#[cfg(feature = "client")]
+mod client;
+pub mod color;
 pub mod command;
-mod multiview;
-mod packet;
+pub mod control;
+pub mod desiredstate;
+pub mod displayclock;
+#[cfg(feature = "audio")]
+pub mod fairlight;
+#[cfg(feature = "history")]
+mod history;
+pub mod hyperdeck;
+pub mod keyer;
+pub mod macros;
+// Frame conversion relies on floating point functions of std
+#[cfg(all(feature = "media-pool", feature = "std"))]
+pub mod media;
+pub mod mediaplayer;
+#[cfg(feature = "media-pool")]
+pub mod mediapool;
+#[cfg(feature = "client")]
+pub mod mixeffect;
+#[cfg(feature = "mock")]
+pub mod mock;
+pub mod multiview;
+pub mod packet;
 mod parser;
-mod source;
-mod systeminfo;
+#[cfg(feature = "recording")]
+pub mod recording;
+#[cfg(feature = "rest")]
+pub mod rest;
+#[cfg(feature = "client")]
+pub mod scheduler;
+#[cfg(feature = "show-file")]
+pub mod showfile;
+pub mod snapshot;
+pub mod source;
+#[cfg(feature = "streaming")]
+pub mod streaming;
+pub mod supersource;
+pub mod systeminfo;
 pub mod tally;
-mod transition;
+mod trace;
+pub mod transition;
+#[cfg(feature = "webhook")]
+pub mod webhook;
 
-use std::net::SocketAddr;
+use alloc::string::String;
 
-use bytes::BytesMut;
 use thiserror::Error;
-use tokio::{net::UdpSocket, sync::mpsc};
-use tracing::{debug, info};
 
-use crate::command::Command;
-use crate::packet::Packet;
+#[cfg(feature = "client")]
+pub use crate::client::{Ack, Connection, ConnectionOptions, Message};
+pub use crate::command::ParseError;
+use crate::{capabilities::DeviceModel, systeminfo::Version};
 
+/// Failures of the UDP transport to the switcher
+#[cfg(feature = "client")]
 #[derive(Error, Debug)]
-pub enum Error {
+pub enum TransportError {
     #[error("Address parsing failed")]
     AddrParseError(#[from] std::net::AddrParseError),
     #[error("ATEM connection failed")]
     SocketError(#[from] std::io::Error),
-
-    #[error("Parsing failed: {0}")]
-    CommandError(#[from] command::Error),
+    #[error("ATEM connection closed")]
+    ConnectionClosed,
 }
 
-pub enum Message {
-    Connected,
-    Disconnected(Error),
-    ParsingFailed(Error),
-    Command(Command),
+/// The switcher did not respond to a request as the protocol expects
+#[derive(Error, Debug)]
+pub enum ProtocolError {
+    #[error("Command not acknowledged by the switcher")]
+    NotAcknowledged,
+    #[error("Command not confirmed by the switcher state")]
+    NotConfirmed,
+    #[error("Data transfer failed with error code {0}")]
+    #[cfg(feature = "media-pool")]
+    TransferFailed(u8),
+    #[error("Lock of media pool store {0} not granted by the switcher")]
+    #[cfg(feature = "media-pool")]
+    MediaLockTimeout(u16),
 }
 
-pub struct Connection {
-    rx: mpsc::UnboundedReceiver<Message>,
+#[derive(Error, Debug)]
+pub enum Error {
+    #[cfg(feature = "client")]
+    #[error(transparent)]
+    Transport(#[from] TransportError),
+    #[error(transparent)]
+    Protocol(#[from] ProtocolError),
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+
+    #[error("Name is longer than {0} bytes")]
+    NameTooLong(usize),
+    #[error("Mask edge {0} is out of range")]
+    MaskOutOfRange(i16),
+    #[error("Time code of the switcher is unknown")]
+    #[cfg(feature = "client")]
+    UnknownTime,
+    #[error("Operations can only be scheduled less than a day ahead")]
+    #[cfg(feature = "client")]
+    DelayTooLong,
+    #[error("Macro {0} is not recorded")]
+    #[cfg(feature = "client")]
+    MacroNotRecorded(u16),
+    #[error("Macro {0} did not finish in time")]
+    #[cfg(feature = "client")]
+    MacroTimeout(u16),
+    #[error("Invalid show file: {0}")]
+    #[cfg(feature = "show-file")]
+    ShowFile(String),
+    #[error("Command {name} is not supported by protocol version {version}")]
+    UnsupportedCommand { name: String, version: Version },
+    #[error("Command {name} is not supported by the {model}")]
+    UnsupportedByModel { name: String, model: DeviceModel },
+
+    #[error("Video mode unknown")]
+    #[cfg(feature = "media-pool")]
+    UnknownVideoMode,
+    #[error("Data transfer size mismatch, expected {expected} bytes got {actual}")]
+    #[cfg(feature = "media-pool")]
+    TransferSizeMismatch { expected: usize, actual: usize },
+    #[error("Frame size mismatch, expected {expected} bytes got {actual}")]
+    #[cfg(feature = "media-pool")]
+    FrameSizeMismatch { expected: usize, actual: usize },
+    #[error("Frame width {0} is odd, pixels are converted in pairs")]
+    #[cfg(feature = "media-pool")]
+    OddFrameWidth(u32),
 }
 
-impl Connection {
-    /// Open a connection to a Blackmagic ATEM switcher at address
-    pub async fn open(address: &str) -> Result<Self, Error> {
-        let remote_addr: SocketAddr = format!("{}:9910", address).parse()?;
-        let local_addr: SocketAddr = "0.0.0.0:0".parse()?;
-
-        let socket = UdpSocket::bind(local_addr).await?;
-        socket.connect(remote_addr).await?;
-
-        info!("Local address: {}", socket.local_addr()?);
-        info!("ATEM switcher address: {}", remote_addr);
-
-        let (tx, rx) = mpsc::unbounded_channel();
-        tokio::task::spawn(async move { run(socket, tx).await });
-
-        Ok(Connection { rx })
-    }
-
-    pub async fn recv_message(&mut self) -> Option<Message> {
-        self.rx.recv().await
+#[cfg(feature = "client")]
+impl From<std::net::AddrParseError> for Error {
+    fn from(value: std::net::AddrParseError) -> Self {
+        TransportError::from(value).into()
     }
 }
 
-async fn send_hello_packet(socket: &UdpSocket) -> Result<(), Error> {
-    let packet = Packet::new_hello_packet();
-    socket.send(&packet.serialize()).await?;
-
-    Ok(())
-}
-
-async fn run(socket: UdpSocket, tx: mpsc::UnboundedSender<Message>) {
-    let mut packet_id = 0;
-
-    if let Err(e) = send_hello_packet(&socket).await {
-        let _ = tx.send(Message::Disconnected(e));
-        return;
+#[cfg(feature = "client")]
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        TransportError::from(value).into()
     }
-
-    loop {
-        let mut buf = BytesMut::with_capacity(1500);
-        let len = match socket.recv_buf(&mut buf).await {
-            Ok(len) => len,
-            Err(e) => {
-                let _ = tx.send(Message::Disconnected(e.into()));
-                return;
-            }
-        };
-
-        if len > 0 {
-            let mut packets = buf.freeze();
-
-            while !packets.is_empty() {
-                let packet = Packet::deserialize(&mut packets);
-
-                if packet.is_hello() {
-                    debug!("Recieved Hello packet");
-
-                    if let Err(e) = send_ack(&socket, packet.uid(), 0x0, packet.id()).await {
-                        let _ = tx.send(Message::Disconnected(e));
-                        return;
-                    }
-                    continue;
-                } else if packet.ack_request() {
-                    packet_id += 1;
-                    if let Err(e) = send_ack(&socket, packet.uid(), packet_id, packet.id()).await {
-                        let _ = tx.send(Message::Disconnected(e));
-                        return;
-                    }
-                }
-
-                if let Some(mut payload) = packet.payload() {
-                    while !payload.is_empty() {
-                        match Command::parse(&mut payload) {
-                            Ok(command) => {
-                                let _ = tx.send(Message::Command(command));
-                            }
-                            Err(e) => {
-                                let _ = tx.send(Message::ParsingFailed(e.into()));
-                            }
-                        }
-                    }
-                }
-            }
-        }
-    }
-}
-
-async fn send_ack(socket: &UdpSocket, uid: u16, packet_id: u16, ack_id: u16) -> Result<(), Error> {
-    let packet = Packet::new_ack(uid, ack_id, packet_id);
-
-    debug!("Send Ack for {}", ack_id);
-
-    socket.send(&packet.serialize()).await?;
-
-    Ok(())
 }
@@ -0,0 +1,696 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+#[cfg(feature = "client")]
+use core::time::Duration;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::command;
+#[cfg(feature = "client")]
+use crate::{command::Command, control::ControlCommand, Connection, Error, TransportError};
+
+const PROGRAM_INPUT: u16 = 0x0002;
+const PREVIEW_INPUT: u16 = 0x0003;
+const CUT: u16 = 0x000b;
+const AUTO: u16 = 0x000c;
+const SLEEP: u16 = 0x0007;
+const USER_WAIT: u16 = 0x0008;
+
+/// Index used when no macro is running or recording
+const NO_MACRO: u16 = 0xffff;
+
+/// Size of the length and id preceding every operation
+const OPERATION_HEADER_SIZE: usize = 4;
+
+/// Single operation of a macro as stored on the switcher. Operations not known by the crate are
+/// kept as raw data, so a downloaded macro can be uploaded again unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacroOperation {
+    ProgramInput {
+        me: u8,
+        source: u16,
+    },
+    PreviewInput {
+        me: u8,
+        source: u16,
+    },
+    Cut {
+        me: u8,
+    },
+    Auto {
+        me: u8,
+    },
+    /// Wait a number of frames before the next operation
+    Sleep {
+        frames: u32,
+    },
+    /// Pause until the user continues the macro
+    UserWait,
+    Unknown {
+        id: u16,
+        data: Bytes,
+    },
+}
+
+impl MacroOperation {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let length = (data.get_u16() as usize).max(OPERATION_HEADER_SIZE);
+        let id = data.get_u16();
+        let mut body = data.split_to((length - OPERATION_HEADER_SIZE).min(data.len()));
+
+        match (id, body.len()) {
+            (PROGRAM_INPUT, 4) => {
+                let me = body.get_u8();
+                body.get_u8(); // Padding
+                let source = body.get_u16();
+                MacroOperation::ProgramInput { me, source }
+            }
+            (PREVIEW_INPUT, 4) => {
+                let me = body.get_u8();
+                body.get_u8(); // Padding
+                let source = body.get_u16();
+                MacroOperation::PreviewInput { me, source }
+            }
+            (CUT, 4) => MacroOperation::Cut { me: body.get_u8() },
+            (AUTO, 4) => MacroOperation::Auto { me: body.get_u8() },
+            (SLEEP, 4) => MacroOperation::Sleep {
+                frames: body.get_u32(),
+            },
+            (USER_WAIT, 0) => MacroOperation::UserWait,
+            _ => MacroOperation::Unknown { id, data: body },
+        }
+    }
+
+    /// Parse every operation of a downloaded macro
+    pub fn parse_all(data: &mut Bytes) -> Vec<Self> {
+        let mut operations = Vec::new();
+        while data.remaining() >= OPERATION_HEADER_SIZE {
+            operations.push(MacroOperation::parse(data));
+        }
+
+        operations
+    }
+
+    /// Serialize operations in the layout of a macro for uploading
+    pub fn serialize_all(operations: &[MacroOperation], data: &mut BytesMut) {
+        for operation in operations {
+            operation.serialize(data);
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        let mut body = BytesMut::new();
+        let id = match self {
+            MacroOperation::ProgramInput { me, source } => {
+                body.put_u8(*me);
+                body.put_u8(0); // Padding
+                body.put_u16(*source);
+                PROGRAM_INPUT
+            }
+            MacroOperation::PreviewInput { me, source } => {
+                body.put_u8(*me);
+                body.put_u8(0); // Padding
+                body.put_u16(*source);
+                PREVIEW_INPUT
+            }
+            MacroOperation::Cut { me } => {
+                body.put_u8(*me);
+                body.put_bytes(0, 3); // Padding
+                CUT
+            }
+            MacroOperation::Auto { me } => {
+                body.put_u8(*me);
+                body.put_bytes(0, 3); // Padding
+                AUTO
+            }
+            MacroOperation::Sleep { frames } => {
+                body.put_u32(*frames);
+                SLEEP
+            }
+            MacroOperation::UserWait => USER_WAIT,
+            MacroOperation::Unknown { id, data } => {
+                body.put_slice(data);
+                *id
+            }
+        };
+
+        data.put_u16((body.len() + OPERATION_HEADER_SIZE) as u16);
+        data.put_u16(id);
+        data.put_slice(&body);
+    }
+}
+
+impl Display for MacroOperation {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MacroOperation::ProgramInput { me, source } => {
+                write!(f, "Program input ME: {me} Source: {source}")
+            }
+            MacroOperation::PreviewInput { me, source } => {
+                write!(f, "Preview input ME: {me} Source: {source}")
+            }
+            MacroOperation::Cut { me } => write!(f, "Cut ME: {me}"),
+            MacroOperation::Auto { me } => write!(f, "Auto ME: {me}"),
+            MacroOperation::Sleep { frames } => write!(f, "Sleep frames: {frames}"),
+            MacroOperation::UserWait => write!(f, "User wait"),
+            MacroOperation::Unknown { id, data } => {
+                write!(f, "Unknown ({id}) Data: {:02X?}", &data[..])
+            }
+        }
+    }
+}
+
+/// Sequence of switcher operations assembled into a macro, so repeatable sequences can be
+/// generated from code instead of being recorded on the switcher
+#[derive(Debug, Clone, Default)]
+pub struct MacroBuilder {
+    operations: Vec<MacroOperation>,
+}
+
+impl MacroBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn program_input(self, me: u8, source: u16) -> Self {
+        self.operation(MacroOperation::ProgramInput { me, source })
+    }
+
+    pub fn preview_input(self, me: u8, source: u16) -> Self {
+        self.operation(MacroOperation::PreviewInput { me, source })
+    }
+
+    pub fn cut(self, me: u8) -> Self {
+        self.operation(MacroOperation::Cut { me })
+    }
+
+    pub fn auto(self, me: u8) -> Self {
+        self.operation(MacroOperation::Auto { me })
+    }
+
+    /// Wait a number of frames before the next operation
+    pub fn sleep(self, frames: u32) -> Self {
+        self.operation(MacroOperation::Sleep { frames })
+    }
+
+    /// Pause until the user continues the macro
+    pub fn user_wait(self) -> Self {
+        self.operation(MacroOperation::UserWait)
+    }
+
+    /// Append any operation, e.g. one copied from a downloaded macro
+    pub fn operation(mut self, operation: MacroOperation) -> Self {
+        self.operations.push(operation);
+        self
+    }
+
+    pub fn operations(&self) -> &[MacroOperation] {
+        &self.operations
+    }
+
+    /// Operations in the format macros are stored in on the switcher
+    pub fn serialize(&self, data: &mut BytesMut) {
+        MacroOperation::serialize_all(&self.operations, data);
+    }
+}
+
+/// Name and description of a macro slot (`MPrp`)
+#[derive(Debug, Clone)]
+pub struct MacroProperties {
+    index: u16,
+    used: bool,
+    unsupported_operations: bool,
+    name: String,
+    description: String,
+}
+
+impl MacroProperties {
+    pub fn parse(data: &mut Bytes) -> Result<Self, command::ParseError> {
+        let index = data.get_u16();
+        let used = data.get_u8() > 0;
+        let unsupported_operations = data.get_u8() > 0;
+        let name_len = data.get_u16() as usize;
+        let description_len = data.get_u16() as usize;
+        let name = String::from_utf8(data.split_to(name_len.min(data.len())).to_vec())?;
+        let description =
+            String::from_utf8(data.split_to(description_len.min(data.len())).to_vec())?;
+
+        Ok(MacroProperties {
+            index,
+            used,
+            unsupported_operations,
+            name,
+            description,
+        })
+    }
+
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Whether a macro is recorded in the slot
+    pub fn used(&self) -> bool {
+        self.used
+    }
+
+    /// Whether the macro contains operations the switcher can't run
+    pub fn unsupported_operations(&self) -> bool {
+        self.unsupported_operations
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl Display for MacroProperties {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Index: {} Used: {} Name: {} Description: {}",
+            self.index, self.used, self.name, self.description
+        )
+    }
+}
+
+/// Macro currently running (`MRPr`)
+#[derive(Debug, Clone)]
+pub struct MacroRunStatus {
+    running: bool,
+    waiting: bool,
+    looping: bool,
+    index: u16,
+}
+
+impl MacroRunStatus {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let state = data.get_u8();
+        let looping = data.get_u8() > 0;
+        let index = data.get_u16();
+
+        MacroRunStatus {
+            running: state & 0x01 > 0,
+            waiting: state & 0x02 > 0,
+            looping,
+            index,
+        }
+    }
+
+    pub fn running(&self) -> bool {
+        self.running
+    }
+
+    /// Whether the running macro is waiting for the user to continue
+    pub fn waiting(&self) -> bool {
+        self.waiting
+    }
+
+    pub fn looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Index of the running macro
+    pub fn index(&self) -> Option<u16> {
+        (self.running && self.index != NO_MACRO).then_some(self.index)
+    }
+}
+
+impl Display for MacroRunStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Running: {} Waiting: {} Loop: {} Index: {}",
+            self.running, self.waiting, self.looping, self.index
+        )
+    }
+}
+
+/// Macro currently being recorded (`MRcS`)
+#[derive(Debug, Clone)]
+pub struct MacroRecordStatus {
+    recording: bool,
+    index: u16,
+}
+
+impl MacroRecordStatus {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let recording = data.get_u8() > 0;
+        data.get_u8(); // Skip
+        let index = data.get_u16();
+
+        MacroRecordStatus { recording, index }
+    }
+
+    pub fn recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Index of the macro being recorded
+    pub fn index(&self) -> Option<u16> {
+        (self.recording && self.index != NO_MACRO).then_some(self.index)
+    }
+}
+
+impl Display for MacroRecordStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Recording: {} Index: {}", self.recording, self.index)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum MacroAction {
+    Run,
+    Stop,
+    StopRecording,
+    InsertUserWait,
+    Continue,
+    Delete,
+}
+
+impl From<MacroAction> for u8 {
+    fn from(value: MacroAction) -> Self {
+        match value {
+            MacroAction::Run => 0,
+            MacroAction::Stop => 1,
+            MacroAction::StopRecording => 2,
+            MacroAction::InsertUserWait => 3,
+            MacroAction::Continue => 4,
+            MacroAction::Delete => 5,
+        }
+    }
+}
+
+impl Display for MacroAction {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MacroAction::Run => write!(f, "Run"),
+            MacroAction::Stop => write!(f, "Stop"),
+            MacroAction::StopRecording => write!(f, "Stop recording"),
+            MacroAction::InsertUserWait => write!(f, "Insert user wait"),
+            MacroAction::Continue => write!(f, "Continue"),
+            MacroAction::Delete => write!(f, "Delete"),
+        }
+    }
+}
+
+/// Run, stop or delete a macro (`MAct`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetMacroAction {
+    index: u16,
+    action: MacroAction,
+}
+
+impl SetMacroAction {
+    pub fn new(index: u16, action: MacroAction) -> Self {
+        SetMacroAction { index, action }
+    }
+
+    /// Action that doesn't apply to a specific macro, like stopping the running one
+    pub fn global(action: MacroAction) -> Self {
+        SetMacroAction {
+            index: NO_MACRO,
+            action,
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.index);
+        data.put_u8(self.action.into());
+        data.put_u8(0); // Padding
+    }
+}
+
+impl Display for SetMacroAction {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} Index: {}", self.action, self.index)
+    }
+}
+
+/// Start recording a macro into a slot (`MSRc`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct StartMacroRecording {
+    index: u16,
+    name: String,
+    description: String,
+}
+
+impl StartMacroRecording {
+    pub fn new(index: u16, name: &str, description: &str) -> Self {
+        StartMacroRecording {
+            index,
+            name: name.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        let start = data.len();
+
+        data.put_u16(self.index);
+        data.put_u16(self.name.len() as u16);
+        data.put_u16(self.description.len() as u16);
+        data.put_slice(self.name.as_bytes());
+        data.put_slice(self.description.as_bytes());
+
+        let len = (data.len() - start).next_multiple_of(4);
+        data.resize(start + len, 0);
+    }
+}
+
+impl Display for StartMacroRecording {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Index: {} Name: {} Description: {}",
+            self.index, self.name, self.description
+        )
+    }
+}
+
+#[cfg(feature = "client")]
+/// Macros of a connection, see [`Connection::macros`]
+pub struct Macros<'a> {
+    connection: &'a Connection,
+}
+
+#[cfg(feature = "client")]
+impl<'a> Macros<'a> {
+    pub(crate) fn new(connection: &'a Connection) -> Self {
+        Macros { connection }
+    }
+
+    /// Recorded macros as reported by the switcher, ordered by index
+    pub fn list(&self) -> Vec<MacroProperties> {
+        let system_info = self.connection.system_info();
+        let mut macros: Vec<MacroProperties> = system_info
+            .macros()
+            .filter(|properties| properties.used())
+            .cloned()
+            .collect();
+        macros.sort_by_key(|properties| properties.index());
+
+        macros
+    }
+
+    pub fn run(&self, index: u16) -> Result<(), Error> {
+        self.action(SetMacroAction::new(index, MacroAction::Run))
+    }
+
+    /// Run a macro and wait until it has finished, at most for `timeout`. Fails with
+    /// [`Error::MacroNotRecorded`] for an empty slot, which the switcher would silently ignore.
+    pub async fn run_to_completion(&self, index: u16, timeout: Duration) -> Result<(), Error> {
+        let used = self
+            .connection
+            .system_info()
+            .macro_properties(index)
+            .is_some_and(MacroProperties::used);
+        if !used {
+            return Err(Error::MacroNotRecorded(index));
+        }
+
+        let mut started = false;
+        let finished_rx = self.connection.observe(move |command| match command {
+            Command::MacroRunStatus(status) if status.index() == Some(index) => {
+                started = true;
+                false
+            }
+            Command::MacroRunStatus(status) => started && !status.running(),
+            _ => false,
+        });
+
+        self.run(index)?;
+
+        tokio::time::timeout(timeout, finished_rx)
+            .await
+            .map_err(|_| Error::MacroTimeout(index))?
+            .map_err(|_| TransportError::ConnectionClosed.into())
+    }
+
+    /// Wait until the running macro, if any, has finished
+    pub async fn wait(&self) -> Result<(), Error> {
+        let finished_rx = self.connection.observe(
+            |command| matches!(command, Command::MacroRunStatus(status) if !status.running()),
+        );
+
+        let running = self
+            .connection
+            .system_info()
+            .macro_run_status()
+            .is_some_and(|status| status.running());
+        if !running {
+            return Ok(());
+        }
+
+        finished_rx
+            .await
+            .map_err(|_| TransportError::ConnectionClosed.into())
+    }
+
+    /// Stop the running macro
+    pub fn stop(&self) -> Result<(), Error> {
+        self.action(SetMacroAction::global(MacroAction::Stop))
+    }
+
+    /// Continue a running macro waiting for the user
+    pub fn continue_running(&self) -> Result<(), Error> {
+        self.action(SetMacroAction::global(MacroAction::Continue))
+    }
+
+    pub fn delete(&self, index: u16) -> Result<(), Error> {
+        self.action(SetMacroAction::new(index, MacroAction::Delete))
+    }
+
+    /// Record the following operations into the macro slot until [`Macros::stop_recording`]
+    pub fn record(&self, index: u16, name: &str) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::StartMacroRecording(
+                StartMacroRecording::new(index, name, ""),
+            ))
+    }
+
+    #[cfg(feature = "media-pool")]
+    /// Replace the macro in a slot with the operations of the builder
+    pub async fn upload(
+        &self,
+        index: u16,
+        name: &str,
+        description: &str,
+        builder: &MacroBuilder,
+    ) -> Result<(), Error> {
+        self.connection
+            .media_pool()
+            .upload_macro(index, name, description, builder.operations())
+            .await
+    }
+
+    pub fn stop_recording(&self) -> Result<(), Error> {
+        self.action(SetMacroAction::global(MacroAction::StopRecording))
+    }
+
+    fn action(&self, action: SetMacroAction) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::MacroAction(action))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::{collection::vec, prelude::*};
+
+    fn operation() -> impl Strategy<Value = MacroOperation> {
+        let known = [PROGRAM_INPUT, PREVIEW_INPUT, CUT, AUTO, SLEEP, USER_WAIT];
+        prop_oneof![
+            (any::<u8>(), any::<u16>())
+                .prop_map(|(me, source)| MacroOperation::ProgramInput { me, source }),
+            (any::<u8>(), any::<u16>())
+                .prop_map(|(me, source)| MacroOperation::PreviewInput { me, source }),
+            any::<u8>().prop_map(|me| MacroOperation::Cut { me }),
+            any::<u8>().prop_map(|me| MacroOperation::Auto { me }),
+            any::<u32>().prop_map(|frames| MacroOperation::Sleep { frames }),
+            Just(MacroOperation::UserWait),
+            (
+                any::<u16>().prop_filter("known operation", move |id| !known.contains(id)),
+                vec(any::<u8>(), 0..64),
+            )
+                .prop_map(|(id, data)| MacroOperation::Unknown {
+                    id,
+                    data: data.into()
+                }),
+        ]
+    }
+
+    #[test]
+    fn macro_operation_round_trip_ok() {
+        let data: [u8; 24] = [
+            0x00, 0x08, 0x00, 0x03, 0x00, 0x00, 0x00, 0x05, 0x00, 0x08, 0x00, 0x0c, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x08, 0x12, 0x34, 0xaa, 0xbb, 0xcc, 0xdd,
+        ];
+        let mut bytes = Bytes::copy_from_slice(&data);
+
+        let operations = MacroOperation::parse_all(&mut bytes);
+
+        assert_eq!(
+            operations,
+            vec![
+                MacroOperation::PreviewInput { me: 0, source: 5 },
+                MacroOperation::Auto { me: 0 },
+                MacroOperation::Unknown {
+                    id: 0x1234,
+                    data: Bytes::from_static(&[0xaa, 0xbb, 0xcc, 0xdd]),
+                },
+            ]
+        );
+
+        let mut serialized = BytesMut::new();
+        for operation in &operations {
+            operation.serialize(&mut serialized);
+        }
+        assert_eq!(serialized.to_vec(), data);
+    }
+
+    #[test]
+    fn macro_builder_ok() {
+        let builder = MacroBuilder::new()
+            .preview_input(0, 5)
+            .auto(0)
+            .sleep(50)
+            .user_wait()
+            .cut(0);
+        let mut data = BytesMut::new();
+        builder.serialize(&mut data);
+
+        assert_eq!(
+            data.to_vec(),
+            [
+                0x00, 0x08, 0x00, 0x03, 0x00, 0x00, 0x00, 0x05, // Preview input
+                0x00, 0x08, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, // Auto
+                0x00, 0x08, 0x00, 0x07, 0x00, 0x00, 0x00, 0x32, // Sleep
+                0x00, 0x04, 0x00, 0x08, // User wait
+                0x00, 0x08, 0x00, 0x0b, 0x00, 0x00, 0x00, 0x00, // Cut
+            ]
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn macro_operation_round_trip(operations in vec(operation(), 0..16)) {
+            let mut data = BytesMut::new();
+            for operation in &operations {
+                operation.serialize(&mut data);
+            }
+
+            prop_assert_eq!(MacroOperation::parse_all(&mut data.freeze()), operations);
+        }
+    }
+}
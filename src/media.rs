@@ -0,0 +1,154 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::Error;
+
+// BT.709 luma coefficients
+const KR: f32 = 0.2126;
+const KB: f32 = 0.0722;
+const KG: f32 = 1.0 - KR - KB;
+
+// 10 bit video range
+const Y_OFFSET: f32 = 64.0;
+const Y_RANGE: f32 = 876.0;
+const C_OFFSET: f32 = 512.0;
+const C_RANGE: f32 = 896.0;
+
+/// Convert an 8 bit RGBA frame to the 10 bit 4:2:2 YCbCr with alpha format used by the media
+/// pool. Every pair of pixels is packed in two 32 bit words, each holding alpha, one of the
+/// shared chroma components and the luma of one pixel. The width must be even.
+pub fn convert_rgba_to_yuva10(data: &[u8], width: u32, height: u32) -> Result<Bytes, Error> {
+    check_frame_size(data, width, height)?;
+
+    let mut frame = BytesMut::with_capacity(data.len());
+    for pair in data.chunks_exact(8) {
+        let (y1, cb1, cr1, a1) = rgba_to_ycbcra(&pair[..4]);
+        let (y2, cb2, cr2, a2) = rgba_to_ycbcra(&pair[4..]);
+        let cb = (cb1 + cb2) / 2.0;
+        let cr = (cr1 + cr2) / 2.0;
+
+        frame.put_u32(pack(a1, C_OFFSET + C_RANGE * cb, y1));
+        frame.put_u32(pack(a2, C_OFFSET + C_RANGE * cr, y2));
+    }
+
+    Ok(frame.freeze())
+}
+
+/// Convert a frame downloaded from the media pool to 8 bit RGBA, the reverse of
+/// [`convert_rgba_to_yuva10`]
+pub fn convert_yuva10_to_rgba(data: &[u8], width: u32, height: u32) -> Result<Bytes, Error> {
+    check_frame_size(data, width, height)?;
+
+    let mut frame = BytesMut::with_capacity(data.len());
+    let mut data = data;
+    while data.has_remaining() {
+        let (a1, cb, y1) = unpack(data.get_u32());
+        let (a2, cr, y2) = unpack(data.get_u32());
+
+        for (y, a) in [(y1, a1), (y2, a2)] {
+            let y = (y - Y_OFFSET) / Y_RANGE;
+            let cb = (cb - C_OFFSET) / C_RANGE;
+            let cr = (cr - C_OFFSET) / C_RANGE;
+
+            let r = y + 2.0 * (1.0 - KR) * cr;
+            let b = y + 2.0 * (1.0 - KB) * cb;
+            let g = (y - KR * r - KB * b) / KG;
+
+            frame.put_u8(to_u8(r));
+            frame.put_u8(to_u8(g));
+            frame.put_u8(to_u8(b));
+            frame.put_u8(to_u8((a - Y_OFFSET) / Y_RANGE));
+        }
+    }
+
+    Ok(frame.freeze())
+}
+
+fn check_frame_size(data: &[u8], width: u32, height: u32) -> Result<(), Error> {
+    if !width.is_multiple_of(2) {
+        return Err(Error::OddFrameWidth(width));
+    }
+
+    // A frame too large to address can't match any data
+    let expected = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|pixels| pixels.checked_mul(4))
+        .unwrap_or(usize::MAX);
+    if data.len() != expected {
+        return Err(Error::FrameSizeMismatch {
+            expected,
+            actual: data.len(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Luma, Cb, Cr and alpha of a pixel, luma and alpha already scaled to 10 bit video range
+fn rgba_to_ycbcra(pixel: &[u8]) -> (f32, f32, f32, f32) {
+    let r = pixel[0] as f32 / 255.0;
+    let g = pixel[1] as f32 / 255.0;
+    let b = pixel[2] as f32 / 255.0;
+    let a = pixel[3] as f32 / 255.0;
+
+    let y = KR * r + KG * g + KB * b;
+    let cb = (b - y) / (2.0 * (1.0 - KB));
+    let cr = (r - y) / (2.0 * (1.0 - KR));
+
+    (Y_OFFSET + Y_RANGE * y, cb, cr, Y_OFFSET + Y_RANGE * a)
+}
+
+fn pack(a: f32, c: f32, y: f32) -> u32 {
+    let a = a.round() as u32 & 0x3ff;
+    let c = c.round() as u32 & 0x3ff;
+    let y = y.round() as u32 & 0x3ff;
+
+    (a << 20) | (c << 10) | y
+}
+
+fn unpack(word: u32) -> (f32, f32, f32) {
+    let a = (word >> 20) & 0x3ff;
+    let c = (word >> 10) & 0x3ff;
+    let y = word & 0x3ff;
+
+    (a as f32, c as f32, y as f32)
+}
+
+fn to_u8(value: f32) -> u8 {
+    (value * 255.0).round().clamp(0.0, 255.0) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_rgba_to_yuva10_ok() {
+        let rgba: [u8; 8] = [0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00];
+        // White opaque and black transparent, neutral chroma
+        let expected: [u8; 8] = [0x3a, 0xc8, 0x03, 0xac, 0x04, 0x08, 0x00, 0x40];
+
+        let frame = convert_rgba_to_yuva10(&rgba, 2, 1).unwrap();
+
+        assert_eq!(frame.to_vec(), expected);
+        assert_eq!(convert_yuva10_to_rgba(&frame, 2, 1).unwrap().to_vec(), rgba);
+    }
+
+    #[test]
+    fn convert_rgba_to_yuva10_size_mismatch() {
+        assert!(matches!(
+            convert_rgba_to_yuva10(&[0; 12], 2, 2),
+            Err(Error::FrameSizeMismatch {
+                expected: 16,
+                actual: 12
+            })
+        ));
+        assert!(matches!(
+            convert_rgba_to_yuva10(&[0; 12], 3, 1),
+            Err(Error::OddFrameWidth(3))
+        ));
+        assert!(matches!(
+            convert_yuva10_to_rgba(&[0; 8], u32::MAX - 1, u32::MAX),
+            Err(Error::FrameSizeMismatch { .. })
+        ));
+    }
+}
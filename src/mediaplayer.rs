@@ -0,0 +1,383 @@
+use core::fmt::Display;
+
+use bitflags::bitflags;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+#[cfg(feature = "client")]
+use crate::{control::ControlCommand, Connection, Error};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum MediaPlayerSourceType {
+    Still,
+    Clip,
+    Unknown(u8),
+}
+
+impl From<u8> for MediaPlayerSourceType {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => MediaPlayerSourceType::Still,
+            2 => MediaPlayerSourceType::Clip,
+            u => MediaPlayerSourceType::Unknown(u),
+        }
+    }
+}
+
+impl From<MediaPlayerSourceType> for u8 {
+    fn from(value: MediaPlayerSourceType) -> Self {
+        match value {
+            MediaPlayerSourceType::Still => 1,
+            MediaPlayerSourceType::Clip => 2,
+            MediaPlayerSourceType::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for MediaPlayerSourceType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MediaPlayerSourceType::Still => write!(f, "Still"),
+            MediaPlayerSourceType::Clip => write!(f, "Clip"),
+            MediaPlayerSourceType::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+/// Still or clip loaded in a media player (`MPCE`)
+#[derive(Debug, Clone)]
+pub struct MediaPlayerSource {
+    player: u8,
+    source_type: MediaPlayerSourceType,
+    still_index: u8,
+    clip_index: u8,
+}
+
+impl MediaPlayerSource {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let player = data.get_u8();
+        let source_type = data.get_u8().into();
+        let still_index = data.get_u8();
+        let clip_index = data.get_u8();
+
+        MediaPlayerSource {
+            player,
+            source_type,
+            still_index,
+            clip_index,
+        }
+    }
+
+    pub fn player(&self) -> u8 {
+        self.player
+    }
+
+    pub fn source_type(&self) -> MediaPlayerSourceType {
+        self.source_type
+    }
+
+    pub fn still_index(&self) -> u8 {
+        self.still_index
+    }
+
+    pub fn clip_index(&self) -> u8 {
+        self.clip_index
+    }
+}
+
+impl Display for MediaPlayerSource {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Player: {} Type: {} Still: {} Clip: {}",
+            self.player, self.source_type, self.still_index, self.clip_index
+        )
+    }
+}
+
+/// Playback state of a media player (`RCPS`)
+#[derive(Debug, Clone)]
+pub struct MediaPlayerStatus {
+    player: u8,
+    playing: bool,
+    looping: bool,
+    at_beginning: bool,
+    clip_frame: u16,
+}
+
+impl MediaPlayerStatus {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let player = data.get_u8();
+        let playing = data.get_u8() > 0;
+        let looping = data.get_u8() > 0;
+        let at_beginning = data.get_u8() > 0;
+        let clip_frame = data.get_u16();
+
+        MediaPlayerStatus {
+            player,
+            playing,
+            looping,
+            at_beginning,
+            clip_frame,
+        }
+    }
+
+    pub fn player(&self) -> u8 {
+        self.player
+    }
+
+    pub fn playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn looping(&self) -> bool {
+        self.looping
+    }
+
+    pub fn at_beginning(&self) -> bool {
+        self.at_beginning
+    }
+
+    pub fn clip_frame(&self) -> u16 {
+        self.clip_frame
+    }
+}
+
+impl Display for MediaPlayerStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Player: {} Playing: {} Loop: {} At beginning: {} Frame: {}",
+            self.player, self.playing, self.looping, self.at_beginning, self.clip_frame
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct MediaPlayerSourceMask: u8 {
+        const SOURCE_TYPE = 0x01;
+        const STILL_INDEX = 0x02;
+        const CLIP_INDEX = 0x04;
+    }
+}
+
+/// Load a still or clip into a media player (`MPSS`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetMediaPlayerSource {
+    mask: MediaPlayerSourceMask,
+    player: u8,
+    source_type: MediaPlayerSourceType,
+    still_index: u8,
+    clip_index: u8,
+}
+
+impl SetMediaPlayerSource {
+    pub fn new(player: u8) -> Self {
+        SetMediaPlayerSource {
+            mask: MediaPlayerSourceMask::empty(),
+            player,
+            source_type: MediaPlayerSourceType::Still,
+            still_index: 0,
+            clip_index: 0,
+        }
+    }
+
+    pub fn still(mut self, index: u8) -> Self {
+        self.mask |= MediaPlayerSourceMask::SOURCE_TYPE | MediaPlayerSourceMask::STILL_INDEX;
+        self.source_type = MediaPlayerSourceType::Still;
+        self.still_index = index;
+        self
+    }
+
+    pub fn clip(mut self, index: u8) -> Self {
+        self.mask |= MediaPlayerSourceMask::SOURCE_TYPE | MediaPlayerSourceMask::CLIP_INDEX;
+        self.source_type = MediaPlayerSourceType::Clip;
+        self.clip_index = index;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(self.player);
+        data.put_u8(self.source_type.into());
+        data.put_u8(self.still_index);
+        data.put_u8(self.clip_index);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetMediaPlayerSource {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Player: {} Type: {}", self.player, self.source_type)?;
+        match self.source_type {
+            MediaPlayerSourceType::Still => write!(f, " Still: {}", self.still_index),
+            MediaPlayerSourceType::Clip => write!(f, " Clip: {}", self.clip_index),
+            MediaPlayerSourceType::Unknown(_) => Ok(()),
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct MediaPlayerStatusMask: u8 {
+        const PLAYING = 0x01;
+        const LOOP = 0x02;
+        const AT_BEGINNING = 0x04;
+        const CLIP_FRAME = 0x08;
+    }
+}
+
+/// Control playback of a media player (`SCPS`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetMediaPlayerStatus {
+    mask: MediaPlayerStatusMask,
+    player: u8,
+    playing: bool,
+    looping: bool,
+    at_beginning: bool,
+    clip_frame: u16,
+}
+
+impl SetMediaPlayerStatus {
+    pub fn new(player: u8) -> Self {
+        SetMediaPlayerStatus {
+            mask: MediaPlayerStatusMask::empty(),
+            player,
+            playing: false,
+            looping: false,
+            at_beginning: false,
+            clip_frame: 0,
+        }
+    }
+
+    pub fn playing(mut self, playing: bool) -> Self {
+        self.mask |= MediaPlayerStatusMask::PLAYING;
+        self.playing = playing;
+        self
+    }
+
+    pub fn looping(mut self, looping: bool) -> Self {
+        self.mask |= MediaPlayerStatusMask::LOOP;
+        self.looping = looping;
+        self
+    }
+
+    /// Jump back to the first frame of the clip
+    pub fn at_beginning(mut self) -> Self {
+        self.mask |= MediaPlayerStatusMask::AT_BEGINNING;
+        self.at_beginning = true;
+        self
+    }
+
+    pub fn clip_frame(mut self, frame: u16) -> Self {
+        self.mask |= MediaPlayerStatusMask::CLIP_FRAME;
+        self.clip_frame = frame;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(self.player);
+        data.put_u8(self.playing as u8);
+        data.put_u8(self.looping as u8);
+        data.put_u8(self.at_beginning as u8);
+        data.put_u8(0); // Padding
+        data.put_u16(self.clip_frame);
+    }
+}
+
+impl Display for SetMediaPlayerStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Player: {} Playing: {} Loop: {} At beginning: {} Frame: {}",
+            self.player, self.playing, self.looping, self.at_beginning, self.clip_frame
+        )
+    }
+}
+
+#[cfg(feature = "client")]
+/// Media player of a connection, see [`Connection::media_player`]
+pub struct MediaPlayer<'a> {
+    connection: &'a Connection,
+    player: u8,
+}
+
+#[cfg(feature = "client")]
+impl<'a> MediaPlayer<'a> {
+    pub(crate) fn new(connection: &'a Connection, player: u8) -> Self {
+        MediaPlayer { connection, player }
+    }
+
+    pub fn index(&self) -> u8 {
+        self.player
+    }
+
+    /// Show a still from the media pool
+    pub fn show_still(&self, index: u8) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::MediaPlayerSource(
+                SetMediaPlayerSource::new(self.player).still(index),
+            ))
+    }
+
+    /// Show a clip from the media pool
+    pub fn show_clip(&self, index: u8) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::MediaPlayerSource(
+                SetMediaPlayerSource::new(self.player).clip(index),
+            ))
+    }
+
+    pub fn play(&self) -> Result<(), Error> {
+        self.set_status(SetMediaPlayerStatus::new(self.player).playing(true))
+    }
+
+    /// Stop playback and return to the first frame of the clip
+    pub fn stop(&self) -> Result<(), Error> {
+        self.set_status(
+            SetMediaPlayerStatus::new(self.player)
+                .playing(false)
+                .at_beginning(),
+        )
+    }
+
+    /// Pause playback at the current frame
+    pub fn pause(&self) -> Result<(), Error> {
+        self.set_status(SetMediaPlayerStatus::new(self.player).playing(false))
+    }
+
+    pub fn set_loop(&self, looping: bool) -> Result<(), Error> {
+        self.set_status(SetMediaPlayerStatus::new(self.player).looping(looping))
+    }
+
+    pub fn seek(&self, frame: u16) -> Result<(), Error> {
+        self.set_status(SetMediaPlayerStatus::new(self.player).clip_frame(frame))
+    }
+
+    /// Still or clip loaded in the media player as reported by the switcher
+    pub fn source(&self) -> Option<MediaPlayerSource> {
+        self.connection
+            .system_info()
+            .media_player_source(self.player)
+            .cloned()
+    }
+
+    /// Playback state of the media player as reported by the switcher
+    pub fn status(&self) -> Option<MediaPlayerStatus> {
+        self.connection
+            .system_info()
+            .media_player_status(self.player)
+            .cloned()
+    }
+
+    fn set_status(&self, status: SetMediaPlayerStatus) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::MediaPlayerStatus(status))
+    }
+}
@@ -0,0 +1,1111 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::{
+    command,
+    parser::{parse_str, put_str},
+};
+#[cfg(feature = "client")]
+use crate::{
+    command::Command, control::ControlCommand, macros::MacroOperation, Connection, Error,
+    ProtocolError, TransportError,
+};
+
+/// Store holding the stills, the clip stores follow
+#[cfg(feature = "client")]
+const STILL_STORE: u16 = 0;
+/// Store holding the macros
+#[cfg(feature = "client")]
+const MACRO_STORE: u16 = 0xffff;
+/// Largest chunk that fits in a single packet together with the headers
+#[cfg(feature = "client")]
+const MAX_CHUNK_SIZE: usize = 1392;
+/// Marks a run length encoded block: header, count and the repeated value, 64 bits each
+const RLE_HEADER: u64 = 0xfefe_fefe_fefe_fefe;
+
+/// Lock state of a media pool store, the stills store is 0 and the clip stores follow
+#[derive(Debug, Clone)]
+pub struct MediaLockState {
+    store: u16,
+    locked: bool,
+}
+
+impl MediaLockState {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let store = data.get_u16();
+        let locked = data.get_u8() == 1;
+
+        MediaLockState { store, locked }
+    }
+
+    pub fn store(&self) -> u16 {
+        self.store
+    }
+
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+}
+
+impl Display for MediaLockState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Store: {} Locked: {}", self.store, self.locked)
+    }
+}
+
+/// Sent by the switcher when a requested lock has been granted to this client
+#[derive(Debug, Clone)]
+pub struct MediaLockObtained {
+    store: u16,
+}
+
+impl MediaLockObtained {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let store = data.get_u16();
+
+        MediaLockObtained { store }
+    }
+
+    pub fn store(&self) -> u16 {
+        self.store
+    }
+}
+
+impl Display for MediaLockObtained {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Store: {}", self.store)
+    }
+}
+
+/// Request or release the lock of a media pool store (`LOCK`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetMediaLock {
+    store: u16,
+    locked: bool,
+}
+
+impl SetMediaLock {
+    pub fn new(store: u16, locked: bool) -> Self {
+        SetMediaLock { store, locked }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.store);
+        data.put_u8(self.locked as u8);
+        data.put_u8(0); // Padding
+    }
+}
+
+impl Display for SetMediaLock {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Store: {} Locked: {}", self.store, self.locked)
+    }
+}
+
+/// Chunk of data in an ongoing data transfer (`FTDa`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct TransferData {
+    transfer_id: u16,
+    #[cfg_attr(feature = "arbitrary", arbitrary(with = crate::parser::arbitrary_bytes))]
+    data: Bytes,
+}
+
+impl TransferData {
+    pub fn new(transfer_id: u16, data: Bytes) -> Self {
+        TransferData { transfer_id, data }
+    }
+
+    pub fn parse(data: &mut Bytes) -> Self {
+        let transfer_id = data.get_u16();
+        let size = data.get_u16() as usize;
+        let data = data.split_to(size.min(data.len()));
+
+        TransferData { transfer_id, data }
+    }
+
+    pub fn transfer_id(&self) -> u16 {
+        self.transfer_id
+    }
+
+    pub fn data(&self) -> &Bytes {
+        &self.data
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.transfer_id);
+        data.put_u16(self.data.len() as u16);
+        data.put_slice(&self.data);
+    }
+}
+
+impl Display for TransferData {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Id: {} Size: {}", self.transfer_id, self.data.len())
+    }
+}
+
+/// Sent by the switcher when it is ready to receive more chunks of an upload (`FTCD`)
+#[derive(Debug, Clone)]
+pub struct TransferContinue {
+    transfer_id: u16,
+    chunk_size: u16,
+    chunk_count: u16,
+}
+
+impl TransferContinue {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let transfer_id = data.get_u16();
+        data.advance(4); // Unknown
+        let chunk_size = data.get_u16();
+        let chunk_count = data.get_u16();
+
+        TransferContinue {
+            transfer_id,
+            chunk_size,
+            chunk_count,
+        }
+    }
+
+    pub fn transfer_id(&self) -> u16 {
+        self.transfer_id
+    }
+
+    /// Largest chunk the switcher accepts, including the chunk header
+    pub fn chunk_size(&self) -> u16 {
+        self.chunk_size
+    }
+
+    /// Number of chunks to send before waiting for the next continue
+    pub fn chunk_count(&self) -> u16 {
+        self.chunk_count
+    }
+}
+
+impl Display for TransferContinue {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Id: {} Chunk size: {} Chunk count: {}",
+            self.transfer_id, self.chunk_size, self.chunk_count
+        )
+    }
+}
+
+/// Sent by the switcher when all data of a transfer has been sent or received (`FTDC`)
+#[derive(Debug, Clone)]
+pub struct TransferComplete {
+    transfer_id: u16,
+}
+
+impl TransferComplete {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let transfer_id = data.get_u16();
+
+        TransferComplete { transfer_id }
+    }
+
+    pub fn transfer_id(&self) -> u16 {
+        self.transfer_id
+    }
+}
+
+impl Display for TransferComplete {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Id: {}", self.transfer_id)
+    }
+}
+
+/// Sent by the switcher when a transfer was aborted (`FTDE`)
+#[derive(Debug, Clone)]
+pub struct TransferError {
+    transfer_id: u16,
+    code: u8,
+}
+
+impl TransferError {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let transfer_id = data.get_u16();
+        let code = data.get_u8();
+
+        TransferError { transfer_id, code }
+    }
+
+    pub fn transfer_id(&self) -> u16 {
+        self.transfer_id
+    }
+
+    pub fn code(&self) -> u8 {
+        self.code
+    }
+}
+
+impl Display for TransferError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Id: {} Code: {}", self.transfer_id, self.code)
+    }
+}
+
+/// Kind of data requested in a download
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum DownloadType {
+    Still,
+    Macro,
+}
+
+impl From<DownloadType> for u16 {
+    fn from(value: DownloadType) -> Self {
+        match value {
+            DownloadType::Still => 0x00f9,
+            DownloadType::Macro => 0x0003,
+        }
+    }
+}
+
+impl Display for DownloadType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DownloadType::Still => write!(f, "Still"),
+            DownloadType::Macro => write!(f, "Macro"),
+        }
+    }
+}
+
+/// Ask the switcher to send the content of a media pool slot (`FTSU`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RequestDownload {
+    transfer_id: u16,
+    store: u16,
+    index: u16,
+    download_type: DownloadType,
+}
+
+impl RequestDownload {
+    pub fn new(transfer_id: u16, store: u16, index: u16, download_type: DownloadType) -> Self {
+        RequestDownload {
+            transfer_id,
+            store,
+            index,
+            download_type,
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.transfer_id);
+        data.put_u16(self.store);
+        data.put_u16(0); // Padding
+        data.put_u16(self.index);
+        data.put_u16(self.download_type.into());
+        data.put_u16(0); // Padding
+    }
+}
+
+impl Display for RequestDownload {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Id: {} Store: {} Index: {} Type: {}",
+            self.transfer_id, self.store, self.index, self.download_type
+        )
+    }
+}
+
+/// Kind of data sent in an upload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum UploadMode {
+    /// Replace the content of the slot, used for frames and macros
+    Write,
+    /// Audio of a clip
+    Audio,
+}
+
+impl From<UploadMode> for u16 {
+    fn from(value: UploadMode) -> Self {
+        match value {
+            UploadMode::Write => 0x0001,
+            UploadMode::Audio => 0x0100,
+        }
+    }
+}
+
+impl Display for UploadMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            UploadMode::Write => write!(f, "Write"),
+            UploadMode::Audio => write!(f, "Audio"),
+        }
+    }
+}
+
+/// Ask the switcher to receive data for a media pool slot (`FTSD`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RequestUpload {
+    transfer_id: u16,
+    store: u16,
+    index: u16,
+    size: u32,
+    mode: UploadMode,
+}
+
+impl RequestUpload {
+    pub fn new(transfer_id: u16, store: u16, index: u16, size: u32, mode: UploadMode) -> Self {
+        RequestUpload {
+            transfer_id,
+            store,
+            index,
+            size,
+            mode,
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.transfer_id);
+        data.put_u16(self.store);
+        data.put_u16(0); // Padding
+        data.put_u16(self.index);
+        data.put_u32(self.size);
+        data.put_u16(self.mode.into());
+        data.put_u16(0); // Padding
+    }
+}
+
+impl Display for RequestUpload {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Id: {} Store: {} Index: {} Size: {} Mode: {}",
+            self.transfer_id, self.store, self.index, self.size, self.mode
+        )
+    }
+}
+
+/// Name and description of the uploaded data, sent after the last chunk (`FTFD`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct TransferFileDescription {
+    transfer_id: u16,
+    name: String,
+    description: String,
+}
+
+impl TransferFileDescription {
+    /// The name is cut to 64 bytes and the description to 128 bytes
+    pub fn new(transfer_id: u16, name: &str, description: &str) -> Self {
+        TransferFileDescription {
+            transfer_id,
+            name: name.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.transfer_id);
+        put_str(data, &self.name, 64);
+        put_str(data, &self.description, 128);
+        data.put_bytes(0, 16); // MD5 hash of the data, not verified by the switcher
+        data.put_u16(0); // Padding
+    }
+}
+
+impl Display for TransferFileDescription {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Id: {} Name: {} Description: {}",
+            self.transfer_id, self.name, self.description
+        )
+    }
+}
+
+/// Acknowledge a received chunk of a download (`FTUA`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct TransferAck {
+    transfer_id: u16,
+    index: u8,
+}
+
+impl TransferAck {
+    pub fn new(transfer_id: u16, index: u8) -> Self {
+        TransferAck { transfer_id, index }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.transfer_id);
+        data.put_u8(self.index);
+        data.put_u8(0); // Padding
+    }
+}
+
+impl Display for TransferAck {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Id: {} Index: {}", self.transfer_id, self.index)
+    }
+}
+
+/// Content of a still slot in the media pool (`MPfe`)
+#[derive(Debug, Clone)]
+pub struct MediaStill {
+    index: u16,
+    used: bool,
+    hash: [u8; 16],
+    name: Option<String>,
+}
+
+impl MediaStill {
+    pub fn parse(data: &mut Bytes) -> Result<Self, command::ParseError> {
+        data.get_u8(); // Store, always the stills
+        data.get_u8(); // Padding
+        let index = data.get_u16();
+        let used = data.get_u8() == 1;
+        let mut hash = [0; 16];
+        data.copy_to_slice(&mut hash);
+        data.advance(2); // Padding
+        let name_len = data.get_u8() as usize;
+        let name = parse_str(&mut data.split_to(name_len.min(data.len())))?;
+
+        Ok(MediaStill {
+            index,
+            used,
+            hash,
+            name,
+        })
+    }
+
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    pub fn used(&self) -> bool {
+        self.used
+    }
+
+    /// MD5 hash of the frame data
+    pub fn hash(&self) -> &[u8; 16] {
+        &self.hash
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl Display for MediaStill {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Index: {} Used: {} Name: {}",
+            self.index,
+            self.used,
+            self.name.as_deref().unwrap_or("")
+        )
+    }
+}
+
+/// Content of a clip slot in the media pool (`MPCS`)
+#[derive(Debug, Clone)]
+pub struct MediaClip {
+    index: u8,
+    used: bool,
+    name: Option<String>,
+    frame_count: u16,
+}
+
+impl MediaClip {
+    pub fn parse(data: &mut Bytes) -> Result<Self, command::ParseError> {
+        let index = data.get_u8();
+        let used = data.get_u8() == 1;
+        let name = parse_str(&mut data.split_to(64))?;
+        let frame_count = data.get_u16();
+
+        Ok(MediaClip {
+            index,
+            used,
+            name,
+            frame_count,
+        })
+    }
+
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    pub fn used(&self) -> bool {
+        self.used
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn frame_count(&self) -> u16 {
+        self.frame_count
+    }
+}
+
+impl Display for MediaClip {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Index: {} Used: {} Name: {} Frames: {}",
+            self.index,
+            self.used,
+            self.name.as_deref().unwrap_or(""),
+            self.frame_count
+        )
+    }
+}
+
+/// Remove the still from a slot in the media pool (`CSTL`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ClearMediaStill {
+    index: u16,
+}
+
+impl ClearMediaStill {
+    pub fn new(index: u16) -> Self {
+        ClearMediaStill { index }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.index);
+        data.put_bytes(0, 2); // Padding
+    }
+}
+
+impl Display for ClearMediaStill {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Index: {}", self.index)
+    }
+}
+
+/// Remove the clip from a slot in the media pool (`CMPC`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ClearMediaClip {
+    index: u8,
+}
+
+impl ClearMediaClip {
+    pub fn new(index: u8) -> Self {
+        ClearMediaClip { index }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.index);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for ClearMediaClip {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Index: {}", self.index)
+    }
+}
+
+/// Rename a still in the media pool (`SMPS`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetMediaStillName {
+    index: u16,
+    name: String,
+}
+
+impl SetMediaStillName {
+    /// The name is cut to 64 bytes
+    pub fn new(index: u16, name: &str) -> Self {
+        SetMediaStillName {
+            index,
+            name: name.to_string(),
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.index);
+        put_str(data, &self.name, 64);
+        data.put_bytes(0, 2); // Padding
+    }
+}
+
+impl Display for SetMediaStillName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Index: {} Name: {}", self.index, self.name)
+    }
+}
+
+/// Change the name and length of a clip in the media pool (`SMPC`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetMediaClip {
+    index: u8,
+    name: String,
+    frame_count: u16,
+}
+
+impl SetMediaClip {
+    /// The name is cut to 44 bytes
+    pub fn new(index: u8, name: &str, frame_count: u16) -> Self {
+        SetMediaClip {
+            index,
+            name: name.to_string(),
+            frame_count,
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.index);
+        put_str(data, &self.name, 44);
+        data.put_bytes(0, 21); // Padding
+        data.put_u16(self.frame_count);
+    }
+}
+
+impl Display for SetMediaClip {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Index: {} Name: {} Frames: {}",
+            self.index, self.name, self.frame_count
+        )
+    }
+}
+
+#[cfg(feature = "client")]
+/// Media pool transfers of a connection, see [`Connection::media_pool`]
+pub struct MediaPool<'a> {
+    connection: &'a Connection,
+}
+
+#[cfg(feature = "client")]
+impl<'a> MediaPool<'a> {
+    pub(crate) fn new(connection: &'a Connection) -> Self {
+        MediaPool { connection }
+    }
+
+    pub fn clear_still(&self, index: u16) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::ClearMediaStill(ClearMediaStill::new(index)))
+    }
+
+    pub fn clear_clip(&self, index: u8) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::ClearMediaClip(ClearMediaClip::new(index)))
+    }
+
+    pub fn set_still_name(&self, index: u16, name: &str) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::MediaStillName(SetMediaStillName::new(
+                index, name,
+            )))
+    }
+
+    /// Rename a clip, the frame count is the number of frames used from the clip slot
+    pub fn set_clip(&self, index: u8, name: &str, frame_count: u16) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::MediaClip(SetMediaClip::new(
+                index,
+                name,
+                frame_count,
+            )))
+    }
+
+    /// Download a still from the media pool. The frame is returned as 10 bit 4:2:2 YCbCr with
+    /// alpha, four bytes per pixel, in the current video mode of the switcher.
+    pub async fn download_still(&self, index: u16) -> Result<Bytes, Error> {
+        let expected = self.frame_size()?;
+
+        self.connection.acquire_media_lock(STILL_STORE).await?;
+        let result = self.download(STILL_STORE, index, DownloadType::Still).await;
+        self.connection.release_media_lock(STILL_STORE)?;
+
+        let frame = decode_rle(&result?);
+        if frame.len() != expected {
+            return Err(Error::TransferSizeMismatch {
+                expected,
+                actual: frame.len(),
+            });
+        }
+
+        Ok(frame)
+    }
+
+    /// Upload a still to the media pool. The frame has the same format as returned by
+    /// [`MediaPool::download_still`] and must match the current video mode of the switcher.
+    pub async fn upload_still(&self, index: u16, name: &str, frame: &[u8]) -> Result<(), Error> {
+        let expected = self.frame_size()?;
+        if frame.len() != expected {
+            return Err(Error::TransferSizeMismatch {
+                expected,
+                actual: frame.len(),
+            });
+        }
+
+        self.connection.acquire_media_lock(STILL_STORE).await?;
+        let result = self
+            .upload(
+                UploadTarget::new(STILL_STORE, index, UploadMode::Write),
+                name,
+                "",
+                frame.len() as u32,
+                encode_rle(frame),
+            )
+            .await;
+        self.connection.release_media_lock(STILL_STORE)?;
+
+        result
+    }
+
+    /// Upload a motion clip, e.g. for a stinger transition, to a clip slot of the media pool.
+    /// The frames have the same format as stills and the clip length is set to the number of
+    /// frames.
+    pub async fn upload_clip(&self, index: u8, name: &str, frames: &[&[u8]]) -> Result<(), Error> {
+        let expected = self.frame_size()?;
+        if let Some(frame) = frames.iter().find(|frame| frame.len() != expected) {
+            return Err(Error::TransferSizeMismatch {
+                expected,
+                actual: frame.len(),
+            });
+        }
+
+        let store = clip_store(index);
+        self.connection.acquire_media_lock(store).await?;
+        let result = async {
+            self.clear_clip(index)?;
+            for (frame_index, frame) in frames.iter().enumerate() {
+                self.upload(
+                    UploadTarget::new(store, frame_index as u16, UploadMode::Write),
+                    "",
+                    "",
+                    frame.len() as u32,
+                    encode_rle(frame),
+                )
+                .await?;
+            }
+            self.set_clip(index, name, frames.len() as u16)
+        }
+        .await;
+        self.connection.release_media_lock(store)?;
+
+        result
+    }
+
+    /// Upload the audio of a clip slot, 48 kHz stereo 24 bit big endian PCM samples
+    pub async fn upload_clip_audio(
+        &self,
+        index: u8,
+        name: &str,
+        audio: &[u8],
+    ) -> Result<(), Error> {
+        let store = clip_store(index);
+        self.connection.acquire_media_lock(store).await?;
+        let result = self
+            .upload(
+                UploadTarget::new(store, 0, UploadMode::Audio),
+                name,
+                "",
+                audio.len() as u32,
+                Bytes::copy_from_slice(audio),
+            )
+            .await;
+        self.connection.release_media_lock(store)?;
+
+        result
+    }
+
+    /// Download the operations of a macro
+    pub async fn download_macro(&self, index: u16) -> Result<Vec<MacroOperation>, Error> {
+        self.connection.acquire_media_lock(MACRO_STORE).await?;
+        let result = self.download(MACRO_STORE, index, DownloadType::Macro).await;
+        self.connection.release_media_lock(MACRO_STORE)?;
+
+        Ok(MacroOperation::parse_all(&mut result?))
+    }
+
+    /// Replace the macro in a slot with the operations
+    pub async fn upload_macro(
+        &self,
+        index: u16,
+        name: &str,
+        description: &str,
+        operations: &[MacroOperation],
+    ) -> Result<(), Error> {
+        let mut data = BytesMut::new();
+        MacroOperation::serialize_all(operations, &mut data);
+
+        self.connection.acquire_media_lock(MACRO_STORE).await?;
+        let result = self
+            .upload(
+                UploadTarget::new(MACRO_STORE, index, UploadMode::Write),
+                name,
+                description,
+                data.len() as u32,
+                data.freeze(),
+            )
+            .await;
+        self.connection.release_media_lock(MACRO_STORE)?;
+
+        result
+    }
+
+    /// Size in bytes of a frame in the current video mode
+    fn frame_size(&self) -> Result<usize, Error> {
+        let (width, height) = self
+            .connection
+            .system_info()
+            .video_mode()
+            .and_then(|mode| mode.resolution())
+            .ok_or(Error::UnknownVideoMode)?;
+
+        Ok((width * height * 4) as usize)
+    }
+
+    async fn download(
+        &self,
+        store: u16,
+        index: u16,
+        download_type: DownloadType,
+    ) -> Result<Bytes, Error> {
+        let (transfer_id, mut rx) = self.connection.start_transfer();
+        let result = async {
+            self.connection
+                .queue_command(ControlCommand::RequestDownload(RequestDownload::new(
+                    transfer_id,
+                    store,
+                    index,
+                    download_type,
+                )))?;
+
+            let mut data = BytesMut::new();
+            loop {
+                match rx.recv().await {
+                    Some(Command::TransferData(chunk)) => {
+                        data.extend_from_slice(chunk.data());
+                        self.connection.queue_command(ControlCommand::TransferAck(
+                            TransferAck::new(transfer_id, index as u8),
+                        ))?;
+                    }
+                    Some(Command::TransferComplete(_)) => return Ok(data.freeze()),
+                    Some(Command::TransferError(error)) => {
+                        return Err(ProtocolError::TransferFailed(error.code()).into())
+                    }
+                    Some(_) => {}
+                    None => return Err(TransportError::ConnectionClosed.into()),
+                }
+            }
+        }
+        .await;
+        self.connection.finish_transfer(transfer_id);
+
+        result
+    }
+
+    async fn upload(
+        &self,
+        target: UploadTarget,
+        name: &str,
+        description: &str,
+        size: u32,
+        mut data: Bytes,
+    ) -> Result<(), Error> {
+        let (transfer_id, mut rx) = self.connection.start_transfer();
+        let result = async {
+            self.connection
+                .queue_command(ControlCommand::RequestUpload(RequestUpload::new(
+                    transfer_id,
+                    target.store,
+                    target.index,
+                    size,
+                    target.mode,
+                )))?;
+
+            let mut described = false;
+            loop {
+                match rx.recv().await {
+                    Some(Command::TransferContinue(next)) => {
+                        // Chunks are a multiple of the 8 byte words used by the encoding
+                        let chunk_size = (next.chunk_size() as usize)
+                            .saturating_sub(4)
+                            .min(MAX_CHUNK_SIZE)
+                            / 8
+                            * 8;
+                        for _ in 0..next.chunk_count() {
+                            if data.is_empty() {
+                                break;
+                            }
+                            let chunk = data.split_to(chunk_size.min(data.len()));
+                            self.connection.queue_command(ControlCommand::TransferData(
+                                TransferData::new(transfer_id, chunk),
+                            ))?;
+                        }
+
+                        if data.is_empty() && !described {
+                            described = true;
+                            self.connection.queue_command(
+                                ControlCommand::TransferFileDescription(
+                                    TransferFileDescription::new(transfer_id, name, description),
+                                ),
+                            )?;
+                        }
+                    }
+                    Some(Command::TransferComplete(_)) => return Ok(()),
+                    Some(Command::TransferError(error)) => {
+                        return Err(ProtocolError::TransferFailed(error.code()).into())
+                    }
+                    Some(_) => {}
+                    None => return Err(TransportError::ConnectionClosed.into()),
+                }
+            }
+        }
+        .await;
+        self.connection.finish_transfer(transfer_id);
+
+        result
+    }
+}
+
+#[cfg(feature = "client")]
+/// Slot an upload is written to
+struct UploadTarget {
+    store: u16,
+    index: u16,
+    mode: UploadMode,
+}
+
+#[cfg(feature = "client")]
+impl UploadTarget {
+    fn new(store: u16, index: u16, mode: UploadMode) -> Self {
+        UploadTarget { store, index, mode }
+    }
+}
+
+#[cfg(feature = "client")]
+/// Store of a clip slot, the clip stores follow the stills store
+fn clip_store(index: u8) -> u16 {
+    STILL_STORE + 1 + index as u16
+}
+
+/// Expand the run length encoding used for frames in the media pool
+pub fn decode_rle(data: &[u8]) -> Bytes {
+    let mut frame = BytesMut::with_capacity(data.len());
+    let mut words = data.chunks_exact(8);
+
+    while let Some(word) = words.next() {
+        if word == RLE_HEADER.to_be_bytes() {
+            let count = words
+                .next()
+                .map(|c| u64::from_be_bytes(c.try_into().unwrap()));
+            let value = words.next();
+            if let (Some(count), Some(value)) = (count, value) {
+                for _ in 0..count {
+                    frame.put_slice(value);
+                }
+            }
+        } else {
+            frame.put_slice(word);
+        }
+    }
+
+    frame.freeze()
+}
+
+/// Run length encode a frame, runs of three or more identical words are compressed
+pub fn encode_rle(data: &[u8]) -> Bytes {
+    let mut encoded = BytesMut::with_capacity(data.len());
+    let words: Vec<&[u8]> = data.chunks(8).collect();
+
+    let mut i = 0;
+    while i < words.len() {
+        let word = words[i];
+        let count = words[i..].iter().take_while(|w| **w == word).count();
+
+        if count > 2 {
+            encoded.put_u64(RLE_HEADER);
+            encoded.put_u64(count as u64);
+            encoded.put_slice(word);
+        } else {
+            for _ in 0..count {
+                encoded.put_slice(word);
+            }
+        }
+        i += count;
+    }
+
+    encoded.freeze()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::{collection::vec, prelude::*};
+
+    #[test]
+    fn decode_rle_ok() {
+        let data: [u8; 32] = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe,
+            0xfe, 0xfe, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x11, 0x12, 0x13, 0x14,
+            0x15, 0x16, 0x17, 0x18,
+        ];
+        let expected: [u8; 24] = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16,
+            0x17, 0x18, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18,
+        ];
+
+        assert_eq!(decode_rle(&data).to_vec(), expected);
+    }
+
+    #[test]
+    fn encode_rle_ok() {
+        let frame: [u8; 40] = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16,
+            0x17, 0x18, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x11, 0x12, 0x13, 0x14,
+            0x15, 0x16, 0x17, 0x18, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+        ];
+        let expected: [u8; 40] = [
+            0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe,
+            0xfe, 0xfe, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x11, 0x12, 0x13, 0x14,
+            0x15, 0x16, 0x17, 0x18, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+        ];
+
+        let encoded = encode_rle(&frame);
+
+        assert_eq!(encoded.to_vec(), expected);
+        assert_eq!(decode_rle(&encoded).to_vec(), frame);
+    }
+
+    proptest! {
+        #[test]
+        fn transfer_data_round_trip(transfer_id: u16, data in vec(any::<u8>(), 0..1024)) {
+            let transfer_data = TransferData::new(transfer_id, data.into());
+
+            let mut serialized = BytesMut::new();
+            transfer_data.serialize(&mut serialized);
+            let parsed = TransferData::parse(&mut serialized.freeze());
+
+            prop_assert_eq!(parsed.transfer_id(), transfer_id);
+            prop_assert_eq!(parsed.data(), transfer_data.data());
+        }
+
+        #[test]
+        fn rle_round_trip(frame in vec(any::<u64>(), 0..64)) {
+            // Runs of repeated pixels are what the encoding compresses
+            let frame: Vec<u8> = frame
+                .iter()
+                .flat_map(|value| [*value; 3])
+                .flat_map(u64::to_be_bytes)
+                .collect();
+
+            prop_assert_eq!(decode_rle(&encode_rle(&frame)).to_vec(), frame);
+        }
+    }
+}
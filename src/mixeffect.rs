@@ -0,0 +1,349 @@
+use core::{fmt::Display, time::Duration};
+
+use tokio::sync::{mpsc, watch};
+
+use crate::{
+    command::{
+        Command, SetPreviewInput, SetProgramInput, SetTransitionPosition, TransitionPosition,
+    },
+    control::ControlCommand,
+    keyer::{
+        KeyType, Keyframe, SetKeyerChroma, SetKeyerCutSource, SetKeyerDVE, SetKeyerFillSource,
+        SetKeyerKeyframe, SetKeyerLuma, SetKeyerMask, SetKeyerOnAir, SetKeyerPattern, SetKeyerType,
+    },
+    systeminfo::TransitionEdge,
+    transition::{
+        AutoTransition, Cut, Easing, FadeToBlack, SetTransitionStyle, Transition, TransitionLayers,
+        TransitionStyle, TransitionStyleSelection,
+    },
+    Ack, Connection, Error,
+};
+
+/// Change of a mix effect block reported by the switcher, see [`MixEffect::subscribe`]
+#[derive(Debug, Clone)]
+pub enum MixEffectEvent {
+    Program(u16),
+    Preview(u16),
+    TransitionPosition(TransitionPosition),
+    /// The transition position left 0, reported before the position itself
+    TransitionStarted,
+    /// The transition position returned to 0, reported after the position itself
+    TransitionCompleted,
+    TransitionStyle(TransitionStyleSelection),
+}
+
+impl Display for MixEffectEvent {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MixEffectEvent::Program(source) => write!(f, "Program: {source}"),
+            MixEffectEvent::Preview(source) => write!(f, "Preview: {source}"),
+            MixEffectEvent::TransitionPosition(position) => {
+                write!(f, "Transition position: {position}")
+            }
+            MixEffectEvent::TransitionStarted => write!(f, "Transition started"),
+            MixEffectEvent::TransitionCompleted => write!(f, "Transition completed"),
+            MixEffectEvent::TransitionStyle(selection) => {
+                write!(f, "Transition style: {selection}")
+            }
+        }
+    }
+}
+
+/// Mix effect block of a connection, see [`Connection::mix_effect`]
+pub struct MixEffect<'a> {
+    connection: &'a Connection,
+    me: u8,
+}
+
+impl<'a> MixEffect<'a> {
+    pub(crate) fn new(connection: &'a Connection, me: u8) -> Self {
+        MixEffect { connection, me }
+    }
+
+    pub fn index(&self) -> u8 {
+        self.me
+    }
+
+    /// Control an upstream keyer of the mix effect block, the first one is 0
+    pub fn keyer(&self, keyer: u8) -> UpstreamKeyer<'a> {
+        UpstreamKeyer {
+            connection: self.connection,
+            me: self.me,
+            keyer,
+        }
+    }
+
+    /// Cut preview to program
+    pub fn cut(&self) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::Cut(Cut::new(self.me)))
+    }
+
+    /// Take preview to program using the selected transition
+    pub fn auto(&self) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::AutoTransition(AutoTransition::new(self.me)))
+    }
+
+    pub fn set_program(&self, source: u16) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::ProgramInput(SetProgramInput::new(
+                self.me, source,
+            )))
+    }
+
+    pub fn set_preview(&self, source: u16) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::PreviewInput(SetPreviewInput::new(
+                self.me, source,
+            )))
+    }
+
+    /// Select the style used by the next auto transition
+    pub fn transition_style(&self, style: TransitionStyle) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::TransitionStyle(
+                SetTransitionStyle::new(self.me).style(style),
+            ))
+    }
+
+    /// Select the layers included in the next transition
+    pub fn next_transition(&self, layers: TransitionLayers) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::TransitionStyle(
+                SetTransitionStyle::new(self.me).next_transition(layers),
+            ))
+    }
+
+    /// Move the transition like the T-bar, position ranges from 0 to 10000
+    pub fn set_transition_position(&self, position: u16) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::TransitionPosition(
+                SetTransitionPosition::new(self.me, position),
+            ))
+    }
+
+    /// Apply the style, layers and rate of the next transition in one packet. A rate without a
+    /// style is set for the style currently selected on the switcher.
+    pub fn set_transition(&self, transition: &Transition) -> Ack {
+        let current_style = self
+            .connection
+            .system_info()
+            .transition_style(self.me)
+            .map(|selection| selection.next_style());
+
+        self.connection
+            .send_batch(transition.commands(self.me, current_style))
+    }
+
+    /// Fade the program output to black, or back from black
+    pub fn ftb(&self) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::FadeToBlack(FadeToBlack::new(self.me)))
+    }
+
+    /// Receive the changes the switcher reports for this mix effect block only, changes of
+    /// other blocks don't wake the receiver. Drop the receiver to stop listening.
+    pub fn subscribe(&self) -> mpsc::UnboundedReceiver<MixEffectEvent> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let me = self.me;
+        self.connection
+            .listen_with_transitions(move |command, edge| {
+                let event = match command {
+                    Command::TransitionPosition(position) if position.me() == me => {
+                        let event = MixEffectEvent::TransitionPosition(position.clone());
+                        let events = match edge {
+                            Some(TransitionEdge::Started(_)) => {
+                                [Some(MixEffectEvent::TransitionStarted), Some(event)]
+                            }
+                            Some(TransitionEdge::Completed(_)) => {
+                                [Some(event), Some(MixEffectEvent::TransitionCompleted)]
+                            }
+                            None => [Some(event), None],
+                        };
+                        return events
+                            .into_iter()
+                            .flatten()
+                            .all(|event| tx.send(event).is_ok());
+                    }
+                    Command::ProgramInput(selection) if selection.me() == me => {
+                        MixEffectEvent::Program(selection.source_id())
+                    }
+                    Command::PreviewInput(selection) if selection.me() == me => {
+                        MixEffectEvent::Preview(selection.source_id())
+                    }
+                    Command::TransitionStyleSelection(selection) if selection.me() == me => {
+                        MixEffectEvent::TransitionStyle(selection.clone())
+                    }
+                    _ => return !tx.is_closed(),
+                };
+                tx.send(event).is_ok()
+            });
+
+        rx
+    }
+
+    /// Current transition position of the mix effect block, updated as the switcher reports it.
+    /// Unlike [`MixEffect::subscribe`] intermediate positions are skipped, so a UI can sample it
+    /// at its own refresh rate. Drop the receiver to stop updating it.
+    pub fn watch_transition_position(&self) -> watch::Receiver<Option<TransitionPosition>> {
+        // Keep the state locked until listening so no update can be missed in between
+        let system_info = self.connection.system_info();
+        let (tx, rx) = watch::channel(system_info.transition_position(self.me).cloned());
+        let me = self.me;
+        self.connection.listen(move |command| match command {
+            Command::TransitionPosition(position) if position.me() == me => {
+                tx.send(Some(position.clone())).is_ok()
+            }
+            _ => !tx.is_closed(),
+        });
+        drop(system_info);
+
+        rx
+    }
+
+    /// Receive the progress of the transitions of this mix effect block from 0.0 to 1.0, one
+    /// value for each position reported by the switcher. Drop the receiver to stop listening.
+    pub fn transition_progress(&self) -> mpsc::UnboundedReceiver<f32> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let me = self.me;
+        self.connection.listen(move |command| match command {
+            Command::TransitionPosition(position) if position.me() == me => {
+                tx.send(position.normalized()).is_ok()
+            }
+            _ => !tx.is_closed(),
+        });
+
+        rx
+    }
+
+    /// Move the transition from start to end over `duration`, like a slow hand on the T-bar.
+    /// The position is set once per frame of the current video mode, or 25 times a second if
+    /// the video mode isn't known yet.
+    pub async fn move_transition(&self, duration: Duration, easing: Easing) -> Result<(), Error> {
+        let (numerator, denominator) = self
+            .connection
+            .system_info()
+            .video_mode()
+            .and_then(|mode| mode.frame_rate())
+            .unwrap_or((25, 1));
+        let frame = Duration::from_secs(denominator as u64) / numerator;
+        let steps = (duration.as_nanos() / frame.as_nanos()).max(1);
+
+        let mut interval = tokio::time::interval(frame);
+        // The first tick completes immediately, the first position is set one frame in
+        interval.tick().await;
+        for position in easing.positions(u32::try_from(steps).unwrap_or(u32::MAX)) {
+            interval.tick().await;
+            self.set_transition_position(position)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Upstream keyer of a mix effect block, see [`MixEffect::keyer`]
+pub struct UpstreamKeyer<'a> {
+    connection: &'a Connection,
+    me: u8,
+    keyer: u8,
+}
+
+impl UpstreamKeyer<'_> {
+    pub fn index(&self) -> u8 {
+        self.keyer
+    }
+
+    pub fn set_on_air(&self, on_air: bool) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::KeyerOnAir(SetKeyerOnAir::new(
+                self.me, self.keyer, on_air,
+            )))
+    }
+
+    pub fn set_fill_source(&self, source: u16) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::KeyerFillSource(SetKeyerFillSource::new(
+                self.me, self.keyer, source,
+            )))
+    }
+
+    pub fn set_cut_source(&self, source: u16) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::KeyerCutSource(SetKeyerCutSource::new(
+                self.me, self.keyer, source,
+            )))
+    }
+
+    pub fn set_type(&self, key_type: KeyType) -> Result<(), Error> {
+        self.connection.queue_command(ControlCommand::KeyerType(
+            SetKeyerType::new(self.me, self.keyer).key_type(key_type),
+        ))
+    }
+
+    /// Apply the DVE to a luma, chroma or pattern key
+    pub fn set_flying_key(&self, flying_key: bool) -> Result<(), Error> {
+        self.connection.queue_command(ControlCommand::KeyerType(
+            SetKeyerType::new(self.me, self.keyer).flying_key(flying_key),
+        ))
+    }
+
+    /// Change the mask, only the properties set by `mask` are changed
+    pub fn set_mask(&self, mask: impl FnOnce(SetKeyerMask) -> SetKeyerMask) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::KeyerMask(mask(SetKeyerMask::new(
+                self.me, self.keyer,
+            ))))
+    }
+
+    /// Change the luma key parameters, only the properties set by `luma` are changed
+    pub fn set_luma(&self, luma: impl FnOnce(SetKeyerLuma) -> SetKeyerLuma) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::KeyerLuma(luma(SetKeyerLuma::new(
+                self.me, self.keyer,
+            ))))
+    }
+
+    /// Change the chroma key parameters, only the properties set by `chroma` are changed
+    pub fn set_chroma(
+        &self,
+        chroma: impl FnOnce(SetKeyerChroma) -> SetKeyerChroma,
+    ) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::KeyerChroma(chroma(SetKeyerChroma::new(
+                self.me, self.keyer,
+            ))))
+    }
+
+    /// Change the pattern key parameters, only the properties set by `pattern` are changed
+    pub fn set_pattern(
+        &self,
+        pattern: impl FnOnce(SetKeyerPattern) -> SetKeyerPattern,
+    ) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::KeyerPattern(pattern(SetKeyerPattern::new(
+                self.me, self.keyer,
+            ))))
+    }
+
+    /// Change the DVE parameters, only the properties set by `dve` are changed
+    pub fn set_dve(&self, dve: impl FnOnce(SetKeyerDVE) -> SetKeyerDVE) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::KeyerDVE(dve(SetKeyerDVE::new(
+                self.me, self.keyer,
+            ))))
+    }
+
+    /// Store parameters in a keyframe of the flying key, only the properties set by `keyframe`
+    /// are changed
+    pub fn set_keyframe(
+        &self,
+        keyframe: Keyframe,
+        parameters: impl FnOnce(SetKeyerKeyframe) -> SetKeyerKeyframe,
+    ) -> Result<(), Error> {
+        self.connection
+            .queue_command(ControlCommand::KeyerKeyframe(parameters(
+                SetKeyerKeyframe::new(self.me, self.keyer, keyframe),
+            )))
+    }
+}
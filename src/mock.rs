@@ -0,0 +1,748 @@
+//! In-process stand-in for a switcher, for integration tests of applications built on the crate.
+//!
+//! [`MockServer`] speaks the UDP protocol on the loopback interface. It answers the hello of a
+//! [`Connection`](crate::Connection), sends the initial state of its [`DeviceProfile`] and
+//! acknowledges every packet it receives. Commands the profiled model doesn't support are
+//! ignored like a real switcher would, so the same test can be run against an ATEM Mini and an
+//! ATEM Constellation 8K to check the application copes with both.
+//!
+//! The state kept by the server is limited to the program and preview inputs, the aux outputs
+//! and the input names. Their set-commands are answered with the matching state command, so
+//! [`Connection::send_confirmed`](crate::Connection::send_confirmed) works against the server.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), atem_rs::Error> {
+//! use atem_rs::{
+//!     mock::{DeviceProfile, MockServer},
+//!     Connection,
+//! };
+//!
+//! let server = MockServer::start(DeviceProfile::atem_mini()).await?;
+//! let connection = Connection::open(&server.address().to_string()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    fmt::Display,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use tokio::{net::UdpSocket, task::JoinHandle};
+
+use crate::{
+    packet::{Packet, MAX_PAYLOAD_SIZE},
+    parser::{put_str, truncate_str},
+    source::{Input, SourceType},
+    systeminfo::Version,
+    trace::debug,
+    Error,
+};
+
+/// Session assigned to the client once the hello has been answered
+const SESSION_ID: u16 = 0x8001;
+const LONG_NAME_SIZE: usize = 20;
+const SHORT_NAME_SIZE: usize = 4;
+const RECV_BUFFER_SIZE: usize = 2048;
+
+/// Commands sent by the crate that every profiled model handles
+const COMMON_COMMANDS: [&[u8; 4]; 15] = [
+    b"CPgI", b"CPvI", b"DCut", b"DAut", b"CTPs", b"CTTp", b"CAuS", b"CInL", b"CClV", b"CKeF",
+    b"CKeC", b"CKOn", b"CDsF", b"CDsC", b"CDsL",
+];
+
+/// Source reported by a profiled switcher (`InPr`)
+#[derive(Debug, Clone)]
+pub struct ProfileSource {
+    id: u16,
+    long_name: String,
+    short_name: String,
+    source_type: SourceType,
+}
+
+impl ProfileSource {
+    /// Long names are cut to 20 bytes and short names to 4 bytes
+    pub fn new(id: u16, long_name: &str, short_name: &str, source_type: SourceType) -> Self {
+        ProfileSource {
+            id,
+            long_name: truncate_str(long_name, LONG_NAME_SIZE).to_string(),
+            short_name: truncate_str(short_name, SHORT_NAME_SIZE).to_string(),
+            source_type,
+        }
+    }
+
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    pub fn long_name(&self) -> &str {
+        &self.long_name
+    }
+
+    pub fn short_name(&self) -> &str {
+        &self.short_name
+    }
+
+    pub fn source_type(&self) -> &SourceType {
+        &self.source_type
+    }
+}
+
+impl Display for ProfileSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Source {}: {} ({}), {}",
+            self.id, self.long_name, self.short_name, self.source_type
+        )
+    }
+}
+
+/// Model a [`MockServer`] pretends to be: the product name and firmware, the topology, the
+/// sources and the set-commands it handles
+#[derive(Debug, Clone)]
+pub struct DeviceProfile {
+    product: String,
+    version: Version,
+    me_count: u8,
+    dsk_count: u8,
+    aux_count: u8,
+    mediaplayer_count: u8,
+    multiviewer_count: u8,
+    dve_count: u8,
+    stinger_count: u8,
+    supersource_count: u8,
+    talkback_count: u8,
+    external_port: Input,
+    sources: Vec<ProfileSource>,
+    supported_commands: Vec<[u8; 4]>,
+}
+
+impl DeviceProfile {
+    /// Switcher without mix effect blocks, sources or supported commands
+    pub fn new(product: &str, version: Version) -> Self {
+        DeviceProfile {
+            product: product.to_string(),
+            version,
+            me_count: 0,
+            dsk_count: 0,
+            aux_count: 0,
+            mediaplayer_count: 0,
+            multiviewer_count: 0,
+            dve_count: 0,
+            stinger_count: 0,
+            supersource_count: 0,
+            talkback_count: 0,
+            external_port: Input::Hdmi,
+            sources: Vec::new(),
+            supported_commands: Vec::new(),
+        }
+    }
+
+    /// ATEM Mini: one mix effect block, four HDMI inputs, one aux output and no multiview
+    pub fn atem_mini() -> Self {
+        let mut profile = DeviceProfile::new("ATEM Mini", Version::new(2, 30))
+            .mix_effects(1)
+            .downstream_keyers(1)
+            .aux_outputs(1)
+            .media_players(1)
+            .dves(1)
+            .external_port(Input::Hdmi)
+            .supported_commands(COMMON_COMMANDS);
+        profile = profile.internal_sources();
+        profile = (1..=4).fold(profile, |profile, input| {
+            profile.source(ProfileSource::new(
+                input,
+                &format!("Camera {input}"),
+                &format!("Cam{input}"),
+                SourceType::External,
+            ))
+        });
+        profile.outputs()
+    }
+
+    /// ATEM Constellation 8K: four mix effect blocks, 40 SDI inputs, 24 aux outputs, four
+    /// multiviews and a SuperSource
+    pub fn constellation_8k() -> Self {
+        let mut profile = DeviceProfile::new("ATEM Constellation 8K", Version::new(2, 30))
+            .mix_effects(4)
+            .downstream_keyers(4)
+            .aux_outputs(24)
+            .media_players(4)
+            .multiviewers(4)
+            .dves(4)
+            .stingers(4)
+            .supersources(1)
+            .talkbacks(1)
+            .external_port(Input::Sdi)
+            .supported_commands(COMMON_COMMANDS)
+            .supported_commands([b"CMvI", b"CMvP", b"VuMC", b"SaMw", b"CSBP", b"CSSc"]);
+        profile = profile.internal_sources();
+        profile = (1..=40).fold(profile, |profile, input| {
+            profile.source(ProfileSource::new(
+                input,
+                &format!("Camera {input}"),
+                &format!("C{input}"),
+                SourceType::External,
+            ))
+        });
+        profile = profile.source(ProfileSource::new(
+            6000,
+            "Super Source",
+            "SSrc",
+            SourceType::SuperSource,
+        ));
+        profile.outputs()
+    }
+
+    pub fn mix_effects(mut self, count: u8) -> Self {
+        self.me_count = count;
+        self
+    }
+
+    pub fn downstream_keyers(mut self, count: u8) -> Self {
+        self.dsk_count = count;
+        self
+    }
+
+    pub fn aux_outputs(mut self, count: u8) -> Self {
+        self.aux_count = count;
+        self
+    }
+
+    pub fn media_players(mut self, count: u8) -> Self {
+        self.mediaplayer_count = count;
+        self
+    }
+
+    pub fn multiviewers(mut self, count: u8) -> Self {
+        self.multiviewer_count = count;
+        self
+    }
+
+    pub fn dves(mut self, count: u8) -> Self {
+        self.dve_count = count;
+        self
+    }
+
+    pub fn stingers(mut self, count: u8) -> Self {
+        self.stinger_count = count;
+        self
+    }
+
+    pub fn supersources(mut self, count: u8) -> Self {
+        self.supersource_count = count;
+        self
+    }
+
+    pub fn talkbacks(mut self, count: u8) -> Self {
+        self.talkback_count = count;
+        self
+    }
+
+    /// Port reported for the external inputs
+    pub fn external_port(mut self, port: Input) -> Self {
+        self.external_port = port;
+        self
+    }
+
+    /// Add a source, replacing an earlier one with the same id
+    pub fn source(mut self, source: ProfileSource) -> Self {
+        self.sources.retain(|existing| existing.id != source.id);
+        self.sources.push(source);
+        self
+    }
+
+    /// Set-commands the switcher handles, others are acknowledged but ignored
+    pub fn supported_commands<'a>(mut self, names: impl IntoIterator<Item = &'a [u8; 4]>) -> Self {
+        for name in names {
+            if !self.supported_commands.contains(name) {
+                self.supported_commands.push(*name);
+            }
+        }
+        self
+    }
+
+    pub fn product(&self) -> &str {
+        &self.product
+    }
+
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    pub fn me_count(&self) -> u8 {
+        self.me_count
+    }
+
+    pub fn aux_count(&self) -> u8 {
+        self.aux_count
+    }
+
+    pub fn sources(&self) -> &[ProfileSource] {
+        &self.sources
+    }
+
+    pub fn supports(&self, name: &[u8; 4]) -> bool {
+        self.supported_commands.contains(name)
+    }
+
+    /// Black, color bars, the color generators and the media players
+    fn internal_sources(mut self) -> Self {
+        self = self
+            .source(ProfileSource::new(0, "Black", "Blk", SourceType::Black))
+            .source(ProfileSource::new(
+                1000,
+                "Color Bars",
+                "Bars",
+                SourceType::ColorBars,
+            ));
+        for index in 1..=2 {
+            self = self.source(ProfileSource::new(
+                2000 + index,
+                &format!("Color {index}"),
+                &format!("Col{index}"),
+                SourceType::ColorGenerator,
+            ));
+        }
+        for player in 1..=self.mediaplayer_count as u16 {
+            self = self
+                .source(ProfileSource::new(
+                    3000 + player * 10,
+                    &format!("Media Player {player}"),
+                    &format!("MP{player}"),
+                    SourceType::MediaPlayerFill,
+                ))
+                .source(ProfileSource::new(
+                    3001 + player * 10,
+                    &format!("Media Player {player} Key"),
+                    &format!("MP{player}K"),
+                    SourceType::MediaPlayerKey,
+                ));
+        }
+        self
+    }
+
+    /// Program and preview of every mix effect block and the aux outputs
+    fn outputs(mut self) -> Self {
+        for me in 1..=self.me_count as u16 {
+            self = self
+                .source(ProfileSource::new(
+                    10000 + me * 10,
+                    &format!("Program {me}"),
+                    &format!("Pgm{me}"),
+                    SourceType::MEOutput,
+                ))
+                .source(ProfileSource::new(
+                    10001 + me * 10,
+                    &format!("Preview {me}"),
+                    &format!("Pvw{me}"),
+                    SourceType::MEOutput,
+                ));
+        }
+        for aux in 1..=self.aux_count as u16 {
+            self = self.source(ProfileSource::new(
+                8000 + aux,
+                &format!("Output {aux}"),
+                &format!("Out{aux}"),
+                SourceType::Auxiliary,
+            ));
+        }
+        self
+    }
+}
+
+impl Display for DeviceProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({}) MEs: {} Sources: {} Aux: {}",
+            self.product,
+            self.version,
+            self.me_count,
+            self.sources.len(),
+            self.aux_count
+        )
+    }
+}
+
+/// Command received by a [`MockServer`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceivedCommand {
+    name: [u8; 4],
+    applied: bool,
+}
+
+impl ReceivedCommand {
+    pub fn name(&self) -> &[u8; 4] {
+        &self.name
+    }
+
+    /// The profiled model supports the command and its parameters were valid
+    pub fn applied(&self) -> bool {
+        self.applied
+    }
+}
+
+impl Display for ReceivedCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} Applied: {}",
+            String::from_utf8_lossy(&self.name),
+            self.applied
+        )
+    }
+}
+
+/// Switcher serving one client at a time on the loopback interface, stops when dropped
+pub struct MockServer {
+    address: SocketAddr,
+    received: Arc<Mutex<Vec<ReceivedCommand>>>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Listen on a free port of the loopback interface
+    pub async fn start(profile: DeviceProfile) -> Result<Self, Error> {
+        let socket = UdpSocket::bind("127.0.0.1:0").await?;
+        let address = socket.local_addr()?;
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let task = tokio::spawn(serve(socket, profile, received.clone()));
+
+        Ok(MockServer {
+            address,
+            received,
+            task,
+        })
+    }
+
+    /// Address to pass to [`Connection::open`](crate::Connection::open)
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Every command received so far, in order
+    pub fn received(&self) -> Vec<ReceivedCommand> {
+        self.received.lock().unwrap().clone()
+    }
+
+    /// Names of the received commands that were ignored
+    pub fn ignored(&self) -> Vec<[u8; 4]> {
+        self.received
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|command| !command.applied)
+            .map(|command| command.name)
+            .collect()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// State of the switcher changed by the commands the server handles
+struct SwitcherState {
+    profile: DeviceProfile,
+    program: Vec<u16>,
+    preview: Vec<u16>,
+    aux: Vec<u16>,
+}
+
+impl SwitcherState {
+    fn new(profile: DeviceProfile) -> Self {
+        SwitcherState {
+            program: vec![1; profile.me_count as usize],
+            preview: vec![2; profile.me_count as usize],
+            aux: vec![10010; profile.aux_count as usize],
+            profile,
+        }
+    }
+
+    /// Commands describing the whole state, as sent after the hello
+    fn dump(&self) -> Vec<BytesMut> {
+        let profile = &self.profile;
+        let mut commands = Vec::new();
+
+        let mut data = BytesMut::new();
+        data.put_u16(profile.version.major());
+        data.put_u16(profile.version.minor());
+        commands.push(command(b"_ver", data));
+
+        let mut data = BytesMut::new();
+        put_str(&mut data, &profile.product, 44);
+        commands.push(command(b"_pin", data));
+
+        let mut data = BytesMut::new();
+        data.put_u8(profile.me_count);
+        data.put_u8(profile.sources.len() as u8);
+        data.put_u8(profile.dsk_count);
+        data.put_u8(profile.aux_count);
+        data.put_u8(0); // Mix minus outputs
+        data.put_u8(profile.mediaplayer_count);
+        data.put_u8(profile.multiviewer_count);
+        data.put_u8(0); // RS-485
+        data.put_u8(0); // HyperDecks
+        data.put_u8(profile.dve_count);
+        data.put_u8(profile.stinger_count);
+        data.put_u8(profile.supersource_count);
+        data.put_u8(0); // Unknown
+        data.put_u8(profile.talkback_count);
+        data.put_bytes(0, 2); // SDI outputs and scalers
+        commands.push(command(b"_top", data));
+
+        for source in &profile.sources {
+            commands.push(self.source(source));
+        }
+        for (aux, source) in self.aux.iter().enumerate() {
+            commands.push(selection(b"AuxS", aux as u8, *source));
+        }
+        for me in 0..profile.me_count {
+            commands.push(selection(b"PrgI", me, self.program[me as usize]));
+            commands.push(selection(b"PrvI", me, self.preview[me as usize]));
+        }
+        commands.push(command(b"InCm", BytesMut::from(&[1u8, 0, 0, 0][..])));
+
+        commands
+    }
+
+    fn source(&self, source: &ProfileSource) -> BytesMut {
+        let (port, active) = match source.source_type {
+            SourceType::External => (
+                u16::from(self.profile.external_port),
+                self.profile.external_port,
+            ),
+            _ => (u16::from(Input::Internal), Input::Internal),
+        };
+        let mut data = BytesMut::new();
+        data.put_u16(source.id);
+        put_str(&mut data, &source.long_name, LONG_NAME_SIZE);
+        put_str(&mut data, &source.short_name, SHORT_NAME_SIZE);
+        data.put_u16(0); // Padding
+        data.put_u16(port);
+        data.put_u16(active.into());
+        data.put_u8(source.source_type.clone().into());
+        data.put_u8(0); // Padding
+        data.put_u8(0); // Available functions
+        data.put_u8(((1u16 << self.profile.me_count) - 1) as u8);
+        command(b"InPr", data)
+    }
+
+    /// Apply a set-command, answering with the state commands it changed. Returns false if the
+    /// command was ignored.
+    fn apply(&mut self, name: &[u8; 4], mut data: Bytes, answer: &mut Vec<BytesMut>) -> bool {
+        if !self.profile.supports(name) {
+            return false;
+        }
+
+        match name {
+            b"CPgI" | b"CPvI" if data.len() >= 4 => {
+                let me = data.get_u8();
+                data.advance(1); // Padding
+                let source = data.get_u16();
+                if me >= self.profile.me_count || !self.has_source(source) {
+                    return false;
+                }
+
+                if name == b"CPgI" {
+                    self.program[me as usize] = source;
+                    answer.push(selection(b"PrgI", me, source));
+                } else {
+                    self.preview[me as usize] = source;
+                    answer.push(selection(b"PrvI", me, source));
+                }
+            }
+            b"DCut" if data.len() >= 4 => {
+                let me = data.get_u8();
+                if me >= self.profile.me_count {
+                    return false;
+                }
+
+                let me_index = me as usize;
+                std::mem::swap(&mut self.program[me_index], &mut self.preview[me_index]);
+                answer.push(selection(b"PrgI", me, self.program[me_index]));
+                answer.push(selection(b"PrvI", me, self.preview[me_index]));
+            }
+            b"CAuS" if data.len() >= 4 => {
+                data.advance(1); // Mask
+                let aux = data.get_u8();
+                let source = data.get_u16();
+                if aux >= self.profile.aux_count || !self.has_source(source) {
+                    return false;
+                }
+
+                self.aux[aux as usize] = source;
+                answer.push(selection(b"AuxS", aux, source));
+            }
+            b"CInL" if data.len() >= 32 => {
+                let mask = data.get_u8();
+                data.advance(1); // Padding
+                let id = data.get_u16();
+                let long_name = name_from(&data[..LONG_NAME_SIZE]);
+                let short_name = name_from(&data[LONG_NAME_SIZE..LONG_NAME_SIZE + SHORT_NAME_SIZE]);
+                let Some(source) = self
+                    .profile
+                    .sources
+                    .iter_mut()
+                    .find(|source| source.id == id)
+                else {
+                    return false;
+                };
+
+                if mask & 0x01 != 0 {
+                    source.long_name = long_name;
+                }
+                if mask & 0x02 != 0 {
+                    source.short_name = short_name;
+                }
+                let source = source.clone();
+                answer.push(self.source(&source));
+            }
+            b"CPgI" | b"CPvI" | b"DCut" | b"CAuS" | b"CInL" => return false,
+            // Supported, but without state kept by the server
+            _ => {}
+        }
+
+        true
+    }
+
+    fn has_source(&self, id: u16) -> bool {
+        self.profile.sources.iter().any(|source| source.id == id)
+    }
+}
+
+async fn serve(
+    socket: UdpSocket,
+    profile: DeviceProfile,
+    received: Arc<Mutex<Vec<ReceivedCommand>>>,
+) {
+    let mut state = SwitcherState::new(profile);
+    let mut client = None;
+    let mut packet_id: u16 = 0;
+    // Id of the last packet received from the client, resent packets are only acknowledged
+    let mut last_id: u16 = 0;
+    let mut state_sent = false;
+
+    let mut buf = BytesMut::with_capacity(RECV_BUFFER_SIZE);
+    loop {
+        buf.reserve(RECV_BUFFER_SIZE);
+        let Ok((_, peer)) = socket.recv_buf_from(&mut buf).await else {
+            return;
+        };
+        let mut packets = buf.split().freeze();
+
+        while let Ok(packet) = Packet::deserialize(&mut packets) {
+            let mut answer = Vec::new();
+
+            if packet.is_hello() {
+                debug!(client = peer; "Mock server received hello");
+                client = Some(peer);
+                packet_id = 0;
+                last_id = 0;
+                state_sent = false;
+                let accept = Packet::new_hello_accept_packet(packet.uid());
+                if socket.send_to(&accept.serialize(), peer).await.is_err() {
+                    return;
+                }
+                continue;
+            }
+            if client != Some(peer) {
+                continue;
+            }
+
+            // The client acknowledges the answer to its hello, it is ready for the state
+            if packet.is_ack() && !state_sent {
+                state_sent = true;
+                answer = state.dump();
+            }
+
+            if packet.ack_request() {
+                let ack = Packet::new_ack(SESSION_ID, packet.id(), 0);
+                if socket.send_to(&ack.serialize(), peer).await.is_err() {
+                    return;
+                }
+
+                if packet.id().wrapping_sub(last_id) < 0x8000 && packet.id() != last_id {
+                    last_id = packet.id();
+                    let mut payload = packet.payload().unwrap_or_default();
+                    while payload.len() >= 8 {
+                        let size = payload.get_u16() as usize;
+                        payload.advance(2);
+                        let mut name = [0; 4];
+                        payload.copy_to_slice(&mut name);
+                        let data = payload.split_to(size.saturating_sub(8).min(payload.len()));
+
+                        let applied = state.apply(&name, data, &mut answer);
+                        debug!(
+                            command = String::from_utf8_lossy(&name),
+                            applied = applied;
+                            "Mock server received command"
+                        );
+                        received
+                            .lock()
+                            .unwrap()
+                            .push(ReceivedCommand { name, applied });
+                    }
+                }
+            }
+
+            for payload in payloads(answer) {
+                packet_id = packet_id.wrapping_add(1);
+                let packet = Packet::new_command(SESSION_ID, packet_id, payload);
+                if socket.send_to(&packet.serialize(), peer).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+/// Combine commands into as few packet payloads as possible
+fn payloads(commands: Vec<BytesMut>) -> Vec<Bytes> {
+    let mut payloads = Vec::new();
+    let mut payload = BytesMut::new();
+    for command in commands {
+        if !payload.is_empty() && payload.len() + command.len() > MAX_PAYLOAD_SIZE {
+            payloads.push(payload.split().freeze());
+        }
+        payload.extend_from_slice(&command);
+    }
+    if !payload.is_empty() {
+        payloads.push(payload.freeze());
+    }
+
+    payloads
+}
+
+/// Command with its 8 byte header
+fn command(name: &[u8; 4], data: BytesMut) -> BytesMut {
+    let mut command = BytesMut::with_capacity(data.len() + 8);
+    command.put_u16(data.len() as u16 + 8);
+    command.put_u16(0);
+    command.put_slice(name);
+    command.put_slice(&data);
+    command
+}
+
+/// Source selected by a mix effect block or aux output
+fn selection(name: &[u8; 4], index: u8, source: u16) -> BytesMut {
+    let mut data = BytesMut::new();
+    data.put_u8(index);
+    data.put_u8(0); // Padding
+    data.put_u16(source);
+    command(name, data)
+}
+
+fn name_from(data: &[u8]) -> String {
+    let end = data
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(data.len());
+    String::from_utf8_lossy(&data[..end]).into_owned()
+}
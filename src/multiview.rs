@@ -1,6 +1,9 @@
-use std::fmt::Display;
+use core::fmt::Display;
 
-use bytes::{Buf, Bytes};
+use bitflags::bitflags;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::systeminfo::VideoMode;
 
 pub struct MultiViewInput {
     multiview: u8,
@@ -20,10 +23,57 @@ impl MultiViewInput {
             source,
         }
     }
+
+    pub fn multiview(&self) -> u8 {
+        self.multiview
+    }
+
+    pub fn window(&self) -> u8 {
+        self.window
+    }
+
+    pub fn source(&self) -> u16 {
+        self.source
+    }
 }
 
 impl Display for MultiViewInput {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Multiview: {} Window: {} Source: {}",
+            self.multiview, self.window, self.source
+        )
+    }
+}
+
+/// Route a source to a multiview window (`CMvI`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetMultiViewInput {
+    multiview: u8,
+    window: u8,
+    source: u16,
+}
+
+impl SetMultiViewInput {
+    pub fn new(multiview: u8, window: u8, source: u16) -> Self {
+        SetMultiViewInput {
+            multiview,
+            window,
+            source,
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.multiview);
+        data.put_u8(self.window);
+        data.put_u16(self.source);
+    }
+}
+
+impl Display for SetMultiViewInput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "Multiview: {} Window: {} Source: {}",
@@ -50,10 +100,58 @@ impl MultiViewVU {
             enabled,
         }
     }
+
+    pub fn multiview(&self) -> u8 {
+        self.multiview
+    }
+
+    pub fn window(&self) -> u8 {
+        self.window
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
 }
 
 impl Display for MultiViewVU {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Multiview: {} Window: {} Enabled: {}",
+            self.multiview, self.window, self.enabled
+        )
+    }
+}
+
+/// Enable or disable the VU meter overlay of a multiview window (`VuMC`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetMultiViewVU {
+    multiview: u8,
+    window: u8,
+    enabled: bool,
+}
+
+impl SetMultiViewVU {
+    pub fn new(multiview: u8, window: u8, enabled: bool) -> Self {
+        SetMultiViewVU {
+            multiview,
+            window,
+            enabled,
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.multiview);
+        data.put_u8(self.window);
+        data.put_u8(self.enabled as u8);
+        data.put_u8(0); // Padding
+    }
+}
+
+impl Display for SetMultiViewVU {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "Multiview: {} Window: {} Enabled: {}",
@@ -80,10 +178,22 @@ impl MultiViewSafeArea {
             enabled,
         }
     }
+
+    pub fn multiview(&self) -> u8 {
+        self.multiview
+    }
+
+    pub fn window(&self) -> u8 {
+        self.window
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
 }
 
 impl Display for MultiViewSafeArea {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "Multiview: {} Window: {} Enabled: {}",
@@ -92,16 +202,147 @@ impl Display for MultiViewSafeArea {
     }
 }
 
+/// Enable or disable the safe area overlay of a multiview window (`SaMw`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetMultiViewSafeArea {
+    multiview: u8,
+    window: u8,
+    enabled: bool,
+}
+
+impl SetMultiViewSafeArea {
+    pub fn new(multiview: u8, window: u8, enabled: bool) -> Self {
+        SetMultiViewSafeArea {
+            multiview,
+            window,
+            enabled,
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.multiview);
+        data.put_u8(self.window);
+        data.put_u8(self.enabled as u8);
+        data.put_u8(0); // Padding
+    }
+}
+
+impl Display for SetMultiViewSafeArea {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Multiview: {} Window: {} Enabled: {}",
+            self.multiview, self.window, self.enabled
+        )
+    }
+}
+
+bitflags! {
+    /// Quadrants of a multiview split into four small windows
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct SplitQuadrants: u8 {
+        const TOP_LEFT = 0x01;
+        const TOP_RIGHT = 0x02;
+        const BOTTOM_LEFT = 0x04;
+        const BOTTOM_RIGHT = 0x08;
+    }
+}
+
+/// Arrangement of the windows of a multiview. Firmware 8.0 and later describe the layout as
+/// the quadrants split into small windows, any combination that isn't a standard layout is
+/// `Custom`. Older firmware only has the four program layouts numbered 0 to 3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum MultiViewLayoutStyle {
+    /// Four large windows in a 2x2 grid
+    Default,
+    /// Large program and preview on top, small windows in the bottom quadrants
+    ProgramTop,
+    /// Large program and preview at the bottom, small windows in the top quadrants
+    ProgramBottom,
+    /// Large program and preview on the left, small windows in the right quadrants
+    ProgramLeft,
+    /// Large program and preview on the right, small windows in the left quadrants
+    ProgramRight,
+    Custom(SplitQuadrants),
+}
+
+impl MultiViewLayoutStyle {
+    /// Quadrants split into four small windows
+    pub fn split_quadrants(&self) -> SplitQuadrants {
+        match self {
+            MultiViewLayoutStyle::Default => SplitQuadrants::empty(),
+            MultiViewLayoutStyle::ProgramTop => {
+                SplitQuadrants::BOTTOM_LEFT | SplitQuadrants::BOTTOM_RIGHT
+            }
+            MultiViewLayoutStyle::ProgramBottom => {
+                SplitQuadrants::TOP_LEFT | SplitQuadrants::TOP_RIGHT
+            }
+            MultiViewLayoutStyle::ProgramLeft => {
+                SplitQuadrants::TOP_RIGHT | SplitQuadrants::BOTTOM_RIGHT
+            }
+            MultiViewLayoutStyle::ProgramRight => {
+                SplitQuadrants::TOP_LEFT | SplitQuadrants::BOTTOM_LEFT
+            }
+            MultiViewLayoutStyle::Custom(quadrants) => *quadrants,
+        }
+    }
+}
+
+impl From<SplitQuadrants> for MultiViewLayoutStyle {
+    fn from(value: SplitQuadrants) -> Self {
+        [
+            MultiViewLayoutStyle::Default,
+            MultiViewLayoutStyle::ProgramTop,
+            MultiViewLayoutStyle::ProgramBottom,
+            MultiViewLayoutStyle::ProgramLeft,
+            MultiViewLayoutStyle::ProgramRight,
+        ]
+        .into_iter()
+        .find(|style| style.split_quadrants() == value)
+        .unwrap_or(MultiViewLayoutStyle::Custom(value))
+    }
+}
+
+impl From<u8> for MultiViewLayoutStyle {
+    fn from(value: u8) -> Self {
+        SplitQuadrants::from_bits_retain(value).into()
+    }
+}
+
+impl From<MultiViewLayoutStyle> for u8 {
+    fn from(value: MultiViewLayoutStyle) -> Self {
+        value.split_quadrants().bits()
+    }
+}
+
+impl Display for MultiViewLayoutStyle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MultiViewLayoutStyle::Default => write!(f, "Default"),
+            MultiViewLayoutStyle::ProgramTop => write!(f, "Program top"),
+            MultiViewLayoutStyle::ProgramBottom => write!(f, "Program bottom"),
+            MultiViewLayoutStyle::ProgramLeft => write!(f, "Program left"),
+            MultiViewLayoutStyle::ProgramRight => write!(f, "Program right"),
+            MultiViewLayoutStyle::Custom(quadrants) => {
+                write!(f, "Custom ({:#04x})", quadrants.bits())
+            }
+        }
+    }
+}
+
 pub struct MultiViewLayout {
     multiview: u8,
-    layout: u8,
+    layout: MultiViewLayoutStyle,
     flip_program: bool,
 }
 
 impl MultiViewLayout {
     pub fn parse(data: &mut Bytes) -> Self {
         let multiview = data.get_u8();
-        let layout = data.get_u8();
+        let layout = data.get_u8().into();
         let flip_program = data.get_u8() == 1;
 
         MultiViewLayout {
@@ -110,10 +351,23 @@ impl MultiViewLayout {
             flip_program,
         }
     }
+
+    pub fn multiview(&self) -> u8 {
+        self.multiview
+    }
+
+    pub fn layout(&self) -> MultiViewLayoutStyle {
+        self.layout
+    }
+
+    /// Program and preview windows are swapped
+    pub fn flip_program(&self) -> bool {
+        self.flip_program
+    }
 }
 
 impl Display for MultiViewLayout {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "Multiview: {} Layout: {} Flip program: {}",
@@ -121,3 +375,116 @@ impl Display for MultiViewLayout {
         )
     }
 }
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct MultiViewLayoutMask: u8 {
+        const LAYOUT = 0x01;
+        const FLIP_PROGRAM = 0x02;
+    }
+}
+
+/// Change the layout of a multiview (`CMvP`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetMultiViewLayout {
+    mask: MultiViewLayoutMask,
+    multiview: u8,
+    layout: MultiViewLayoutStyle,
+    flip_program: bool,
+}
+
+impl SetMultiViewLayout {
+    pub fn new(multiview: u8) -> Self {
+        SetMultiViewLayout {
+            mask: MultiViewLayoutMask::empty(),
+            multiview,
+            layout: MultiViewLayoutStyle::Default,
+            flip_program: false,
+        }
+    }
+
+    pub fn layout(mut self, layout: MultiViewLayoutStyle) -> Self {
+        self.mask |= MultiViewLayoutMask::LAYOUT;
+        self.layout = layout;
+        self
+    }
+
+    /// Swap the program and preview windows
+    pub fn flip_program(mut self, flip_program: bool) -> Self {
+        self.mask |= MultiViewLayoutMask::FLIP_PROGRAM;
+        self.flip_program = flip_program;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(self.multiview);
+        data.put_u8(self.layout.into());
+        data.put_u8(self.flip_program as u8);
+    }
+}
+
+impl Display for SetMultiViewLayout {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Multiview: {} Layout: {} Flip program: {}",
+            self.multiview, self.layout, self.flip_program
+        )
+    }
+}
+
+/// Video standard a multiview output is running at
+#[derive(Debug, Clone)]
+pub struct MultiViewVideoMode {
+    multiview: u8,
+    mode: VideoMode,
+}
+
+impl MultiViewVideoMode {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let multiview = data.get_u8();
+        let mode = data.get_u8().into();
+
+        MultiViewVideoMode { multiview, mode }
+    }
+
+    pub fn multiview(&self) -> u8 {
+        self.multiview
+    }
+
+    pub fn mode(&self) -> VideoMode {
+        self.mode
+    }
+}
+
+impl Display for MultiViewVideoMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Multiview: {} Video mode: {}", self.multiview, self.mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiview_layout_style() {
+        assert_eq!(MultiViewLayoutStyle::from(0), MultiViewLayoutStyle::Default);
+        assert_eq!(
+            MultiViewLayoutStyle::from(12),
+            MultiViewLayoutStyle::ProgramTop
+        );
+        assert_eq!(
+            MultiViewLayoutStyle::from(5),
+            MultiViewLayoutStyle::ProgramRight
+        );
+        assert_eq!(
+            MultiViewLayoutStyle::from(1),
+            MultiViewLayoutStyle::Custom(SplitQuadrants::TOP_LEFT)
+        );
+        assert_eq!(u8::from(MultiViewLayoutStyle::ProgramLeft), 10);
+    }
+}
@@ -1,10 +1,18 @@
+use alloc::vec;
+
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 
+use crate::ParseError;
+
 const HEADER_SIZE: u16 = 0x0c;
+/// Largest payload the switcher accepts in a single packet
+pub const MAX_PAYLOAD_SIZE: usize = 1416 - HEADER_SIZE as usize;
+
+/// Largest packet that can be described by the eleven bit size in the header
+const MAX_PACKET_SIZE: u16 = 0x07ff;
 
 const PACKET_FLAG_ACK_REQUEST: u8 = 0x01;
 const PACKET_FLAG_HELLO: u8 = 0x02;
-#[allow(dead_code)]
 const PACKET_FLAG_RESEND: u8 = 0x04;
 const PACKET_FLAG_ACK: u8 = 0x10;
 
@@ -33,6 +41,20 @@ impl Packet {
         Packet::new(PACKET_FLAG_ACK, uid, ack_id, id, None)
     }
 
+    pub fn new_command(uid: u16, id: u16, payload: Bytes) -> Self {
+        Packet::new(PACKET_FLAG_ACK_REQUEST, uid, 0x0000, id, Some(payload))
+    }
+
+    pub fn new_command_resend(uid: u16, id: u16, payload: Bytes) -> Self {
+        Packet::new(
+            PACKET_FLAG_ACK_REQUEST | PACKET_FLAG_RESEND,
+            uid,
+            0x0000,
+            id,
+            Some(payload),
+        )
+    }
+
     pub fn serialize(&self) -> Bytes {
         let mut bytes = BytesMut::new();
 
@@ -56,29 +78,39 @@ impl Packet {
         bytes.freeze()
     }
 
-    pub fn deserialize(packet: &mut Bytes) -> Self {
+    /// Take the next packet from a datagram. Fails if the header is incomplete or declares more
+    /// data than received, the rest of the datagram should then be dropped.
+    pub fn deserialize(packet: &mut Bytes) -> Result<Self, ParseError> {
+        if packet.len() < HEADER_SIZE as usize {
+            return Err(ParseError::InvalidSize);
+        }
+
         let flag_size = packet.get_u16();
         let flags = ((flag_size & 0xf800) >> 11) as u8;
-        let size = flag_size & 0x07ff;
+        let size = flag_size & MAX_PACKET_SIZE;
         let uid = packet.get_u16();
         let ack_id = packet.get_u16();
         packet.get_u32();
         let id = packet.get_u16();
 
-        let payload_size = size - HEADER_SIZE;
+        let payload_size = size.wrapping_sub(HEADER_SIZE);
+        if size < HEADER_SIZE || payload_size as usize > packet.len() {
+            return Err(ParseError::InvalidSize);
+        }
+
         let payload = if payload_size > 0 {
             Some(packet.split_to(payload_size as usize))
         } else {
             None
         };
 
-        Packet {
+        Ok(Packet {
             flags,
             uid,
             ack_id,
             id,
             payload,
-        }
+        })
     }
 
     pub fn id(&self) -> u16 {
@@ -93,6 +125,14 @@ impl Packet {
         self.flags & PACKET_FLAG_ACK_REQUEST > 0
     }
 
+    pub fn is_ack(&self) -> bool {
+        self.flags & PACKET_FLAG_ACK > 0
+    }
+
+    pub fn ack_id(&self) -> u16 {
+        self.ack_id
+    }
+
     pub fn is_hello(&self) -> bool {
         self.flags & PACKET_FLAG_HELLO > 0
     }
@@ -106,11 +146,40 @@ impl Packet {
 
         Packet::new(PACKET_FLAG_HELLO, 0x1337, 0x0000, 0x0000, Some(hello_data))
     }
+
+    /// Answer of the switcher accepting the hello packet of a client
+    pub fn new_hello_accept_packet(uid: u16) -> Self {
+        let hello_data = Bytes::from(vec![0x02u8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+        Packet::new(PACKET_FLAG_HELLO, uid, 0x0000, 0x0000, Some(hello_data))
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Packet {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        // Only what the header can describe: five flag bits and an eleven bit size
+        let flags = u.int_in_range(0..=0x1f)?;
+        let uid = u.arbitrary()?;
+        let ack_id = u.arbitrary()?;
+        let id = u.arbitrary()?;
+        let payload: &[u8] = u.arbitrary()?;
+        let payload = &payload[..payload.len().min((MAX_PACKET_SIZE - HEADER_SIZE) as usize)];
+
+        Ok(Packet::new(
+            flags,
+            uid,
+            ack_id,
+            id,
+            (!payload.is_empty()).then(|| Bytes::copy_from_slice(payload)),
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::{collection::vec, option, prelude::*};
 
     #[test]
     fn packet_serialize_ok() {
@@ -141,7 +210,7 @@ mod tests {
         buf.extend_from_slice(&data);
 
         let mut packets = buf.freeze();
-        let packet = Packet::deserialize(&mut packets);
+        let packet = Packet::deserialize(&mut packets).unwrap();
 
         let mut hello_data = BytesMut::new();
         hello_data.extend_from_slice(&[0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
@@ -155,4 +224,33 @@ mod tests {
 
         assert_eq!(packet, expected);
     }
+
+    #[test]
+    fn packet_deserialize_truncated() {
+        let data: [u8; HEADER_SIZE as usize + 0x04] = [
+            0x10, 0x14, 0x57, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00,
+            0x00, 0x00,
+        ];
+
+        assert!(Packet::deserialize(&mut Bytes::copy_from_slice(&data[..6])).is_err());
+        assert!(Packet::deserialize(&mut Bytes::copy_from_slice(&data)).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn packet_round_trip(
+            flags in 0u8..0x20,
+            uid: u16,
+            ack_id: u16,
+            id: u16,
+            payload in option::of(vec(any::<u8>(), 1..(MAX_PACKET_SIZE - HEADER_SIZE) as usize)),
+        ) {
+            let packet = Packet::new(flags, uid, ack_id, id, payload.map(Bytes::from));
+
+            let mut data = packet.serialize();
+
+            prop_assert_eq!(Packet::deserialize(&mut data)?, packet);
+            prop_assert!(data.is_empty());
+        }
+    }
 }
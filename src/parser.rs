@@ -1,6 +1,8 @@
-use bytes::Bytes;
+use alloc::string::String;
 
-pub fn parse_str(data: &mut Bytes) -> Result<Option<String>, std::string::FromUtf8Error> {
+use bytes::{BufMut, Bytes, BytesMut};
+
+pub fn parse_str(data: &mut Bytes) -> Result<Option<String>, alloc::string::FromUtf8Error> {
     let mut data = data.splitn(2, |b| *b == b'\0');
 
     if let Some(str) = data.next() {
@@ -9,3 +11,44 @@ pub fn parse_str(data: &mut Bytes) -> Result<Option<String>, std::string::FromUt
         Ok(None)
     }
 }
+
+/// Arbitrary data for `Bytes` fields, which don't implement `Arbitrary` themselves
+#[cfg(all(feature = "arbitrary", feature = "media-pool"))]
+pub fn arbitrary_bytes(u: &mut arbitrary::Unstructured) -> arbitrary::Result<Bytes> {
+    Ok(Bytes::copy_from_slice(u.arbitrary()?))
+}
+
+/// Write a string into a fixed size, zero padded, field. A longer string is cut at the last
+/// character that fits, so the field stays valid UTF-8.
+pub fn put_str(data: &mut BytesMut, value: &str, size: usize) {
+    let value = truncate_str(value, size);
+
+    data.put_slice(value.as_bytes());
+    data.put_bytes(0, size - value.len());
+}
+
+/// Longest prefix of a string that fits in size bytes without splitting a character
+pub fn truncate_str(value: &str, size: usize) -> &str {
+    let mut end = value.len().min(size);
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    &value[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn put_str_truncates_at_char_boundary() {
+        let mut data = BytesMut::new();
+        put_str(&mut data, "Kamera å", 8);
+        assert_eq!(&data[..], b"Kamera \0");
+
+        let mut data = BytesMut::new();
+        put_str(&mut data, "CAM", 4);
+        assert_eq!(&data[..], b"CAM\0");
+    }
+}
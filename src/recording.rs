@@ -0,0 +1,437 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt::Display, time::Duration};
+
+use bitflags::bitflags;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::{
+    command,
+    parser::{parse_str, put_str},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingState {
+    Idle,
+    Recording,
+    Stopping,
+    Unknown(u16),
+}
+
+impl From<u16> for RecordingState {
+    fn from(value: u16) -> Self {
+        match value {
+            0 => RecordingState::Idle,
+            1 => RecordingState::Recording,
+            128 => RecordingState::Stopping,
+            u => RecordingState::Unknown(u),
+        }
+    }
+}
+
+impl From<RecordingState> for u16 {
+    fn from(value: RecordingState) -> Self {
+        match value {
+            RecordingState::Idle => 0,
+            RecordingState::Recording => 1,
+            RecordingState::Stopping => 128,
+            RecordingState::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for RecordingState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RecordingState::Idle => write!(f, "Idle"),
+            RecordingState::Recording => write!(f, "Recording"),
+            RecordingState::Stopping => write!(f, "Stopping"),
+            RecordingState::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingError {
+    None,
+    NoMedia,
+    MediaFull,
+    MediaError,
+    MediaUnformatted,
+    DroppingFrames,
+    Unknown(u16),
+}
+
+impl From<u16> for RecordingError {
+    fn from(value: u16) -> Self {
+        match value {
+            0 => RecordingError::NoMedia,
+            2 => RecordingError::None,
+            4 => RecordingError::MediaFull,
+            8 => RecordingError::MediaError,
+            16 => RecordingError::MediaUnformatted,
+            32 => RecordingError::DroppingFrames,
+            u => RecordingError::Unknown(u),
+        }
+    }
+}
+
+impl Display for RecordingError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RecordingError::None => write!(f, "None"),
+            RecordingError::NoMedia => write!(f, "No media"),
+            RecordingError::MediaFull => write!(f, "Media full"),
+            RecordingError::MediaError => write!(f, "Media error"),
+            RecordingError::MediaUnformatted => write!(f, "Media unformatted"),
+            RecordingError::DroppingFrames => write!(f, "Dropping frames"),
+            RecordingError::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordingStatus {
+    state: RecordingState,
+    error: RecordingError,
+    time_available: u32,
+}
+
+impl RecordingStatus {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let state = data.get_u16();
+        let error = data.get_u16();
+        let time_available = data.get_u32();
+
+        RecordingStatus {
+            state: state.into(),
+            error: error.into(),
+            time_available,
+        }
+    }
+
+    pub fn state(&self) -> RecordingState {
+        self.state
+    }
+
+    pub fn error(&self) -> RecordingError {
+        self.error
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.state == RecordingState::Recording
+    }
+
+    /// Recording time left on the active disks
+    pub fn time_available(&self) -> Duration {
+        Duration::from_secs(self.time_available as u64)
+    }
+}
+
+impl Display for RecordingStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "State: {} Error: {} Time available: {}s",
+            self.state, self.error, self.time_available
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordingTimeCode {
+    hour: u8,
+    minute: u8,
+    second: u8,
+    frame: u8,
+    drop_frame: bool,
+}
+
+impl RecordingTimeCode {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let hour = data.get_u8();
+        let minute = data.get_u8();
+        let second = data.get_u8();
+        let frame = data.get_u8();
+        let drop_frame = data.get_u8() == 1;
+
+        RecordingTimeCode {
+            hour,
+            minute,
+            second,
+            frame,
+            drop_frame,
+        }
+    }
+
+    /// Time spent recording, with whole second resolution
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.hour as u64 * 3600 + self.minute as u64 * 60 + self.second as u64)
+    }
+}
+
+impl Display for RecordingTimeCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let separator = if self.drop_frame { ';' } else { ':' };
+
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hour, self.minute, self.second, separator, self.frame
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct RecordingDiskFlags: u16 {
+        const IDLE = 0x0001;
+        const UNFORMATTED = 0x0002;
+        const ACTIVE = 0x0004;
+        const RECORDING = 0x0008;
+        const REMOVED = 0x0020;
+    }
+}
+
+impl Display for RecordingDiskFlags {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut output = Vec::new();
+
+        if self.contains(RecordingDiskFlags::IDLE) {
+            output.push("Idle");
+        }
+        if self.contains(RecordingDiskFlags::UNFORMATTED) {
+            output.push("Unformatted");
+        }
+        if self.contains(RecordingDiskFlags::ACTIVE) {
+            output.push("Active");
+        }
+        if self.contains(RecordingDiskFlags::RECORDING) {
+            output.push("Recording");
+        }
+        if self.contains(RecordingDiskFlags::REMOVED) {
+            output.push("Removed");
+        }
+
+        write!(f, "{}", output.join(", "))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordingDisk {
+    id: u32,
+    time_available: u32,
+    flags: RecordingDiskFlags,
+    volume_name: Option<String>,
+}
+
+impl RecordingDisk {
+    pub fn parse(data: &mut Bytes) -> Result<Self, command::ParseError> {
+        let id = data.get_u32();
+        let time_available = data.get_u32();
+        let flags = RecordingDiskFlags::from_bits_retain(data.get_u16());
+        let volume_name = parse_str(&mut data.split_to(64))?;
+
+        Ok(RecordingDisk {
+            id,
+            time_available,
+            flags,
+            volume_name,
+        })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Recording time left on this disk
+    pub fn time_available(&self) -> Duration {
+        Duration::from_secs(self.time_available as u64)
+    }
+
+    pub fn flags(&self) -> RecordingDiskFlags {
+        self.flags
+    }
+
+    pub fn volume_name(&self) -> Option<&str> {
+        self.volume_name.as_deref()
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.flags.contains(RecordingDiskFlags::REMOVED)
+    }
+}
+
+impl Display for RecordingDisk {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Disk {}: {} Time available: {}s [{}]",
+            self.id,
+            self.volume_name.as_deref().unwrap_or(""),
+            self.time_available,
+            self.flags
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecordingSettings {
+    filename: Option<String>,
+    working_set_1: u32,
+    working_set_2: u32,
+    record_all_inputs: bool,
+}
+
+impl RecordingSettings {
+    pub fn parse(data: &mut Bytes) -> Result<Self, command::ParseError> {
+        let filename = parse_str(&mut data.split_to(128))?;
+        let working_set_1 = data.get_u32();
+        let working_set_2 = data.get_u32();
+        let record_all_inputs = data.get_u8() == 1;
+
+        Ok(RecordingSettings {
+            filename,
+            working_set_1,
+            working_set_2,
+            record_all_inputs,
+        })
+    }
+
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_deref()
+    }
+
+    pub fn working_set(&self) -> (u32, u32) {
+        (self.working_set_1, self.working_set_2)
+    }
+
+    /// True when every input is recorded as a separate ISO file, only on ISO models
+    pub fn record_all_inputs(&self) -> bool {
+        self.record_all_inputs
+    }
+}
+
+impl Display for RecordingSettings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Filename: {} Working set: {}, {} Record all inputs: {}",
+            self.filename.as_deref().unwrap_or(""),
+            self.working_set_1,
+            self.working_set_2,
+            self.record_all_inputs
+        )
+    }
+}
+
+/// Start or stop recording (`RcTM`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetRecording {
+    enabled: bool,
+}
+
+impl SetRecording {
+    pub fn new(enabled: bool) -> Self {
+        SetRecording { enabled }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.enabled as u8);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetRecording {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Enabled: {}", self.enabled)
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct RecordingSettingsMask: u8 {
+        const FILENAME = 0x01;
+        const WORKING_SET_1 = 0x02;
+        const WORKING_SET_2 = 0x04;
+        const RECORD_ALL_INPUTS = 0x08;
+    }
+}
+
+/// Change the recording settings (`CRMS`)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetRecordingSettings {
+    mask: RecordingSettingsMask,
+    filename: String,
+    working_set_1: u32,
+    working_set_2: u32,
+    record_all_inputs: bool,
+}
+
+impl SetRecordingSettings {
+    pub fn new() -> Self {
+        SetRecordingSettings::default()
+    }
+
+    /// Cut to 128 bytes
+    pub fn filename(mut self, filename: &str) -> Self {
+        self.mask |= RecordingSettingsMask::FILENAME;
+        self.filename = filename.to_string();
+        self
+    }
+
+    /// Disks to record to, identified by the disk id reported in the disk status
+    pub fn working_set(mut self, disk_1: u32, disk_2: u32) -> Self {
+        self.mask |= RecordingSettingsMask::WORKING_SET_1 | RecordingSettingsMask::WORKING_SET_2;
+        self.working_set_1 = disk_1;
+        self.working_set_2 = disk_2;
+        self
+    }
+
+    /// Record every input as a separate ISO file, only on ISO models
+    pub fn record_all_inputs(mut self, enabled: bool) -> Self {
+        self.mask |= RecordingSettingsMask::RECORD_ALL_INPUTS;
+        self.record_all_inputs = enabled;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        put_str(data, &self.filename, 128);
+        data.put_bytes(0, 3); // Padding
+        data.put_u32(self.working_set_1);
+        data.put_u32(self.working_set_2);
+        data.put_u8(self.record_all_inputs as u8);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetRecordingSettings {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Filename: {} Working set: {}, {} Record all inputs: {}",
+            self.filename, self.working_set_1, self.working_set_2, self.record_all_inputs
+        )
+    }
+}
+
+/// Make the switcher continue recording on the next disk in the working set (`RMSp`)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SwitchRecordingDisk;
+
+impl SwitchRecordingDisk {
+    pub fn serialize(&self, _data: &mut BytesMut) {}
+}
+
+impl Display for SwitchRecordingDisk {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Switch disk")
+    }
+}
@@ -0,0 +1,260 @@
+//! Minimal REST API for controlling the switcher over HTTP, so panels and scripts can use it
+//! without linking the crate. Requests that select a source take a JSON body like
+//! `{"source": 1}`.
+//!
+//! - `GET /state` current state of the switcher
+//! - `POST /me/{me}/program` and `POST /me/{me}/preview` select a source
+//! - `POST /me/{me}/cut` and `POST /me/{me}/auto` take preview to program
+//! - `POST /aux/{aux}` route a source to an aux output
+//! - `POST /macro/{index}/run` run a macro
+//! - `POST /stream/start` and `POST /stream/stop` control the streaming encoder
+
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+
+use crate::{
+    command::{SetAuxSource, SetPreviewInput, SetProgramInput},
+    control::ControlCommand,
+    transition::{AutoTransition, Cut},
+    Connection, Error, ProtocolError,
+};
+
+/// Time to wait for the switcher to confirm a source change
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(2);
+
+type SharedConnection = Arc<Connection>;
+
+#[derive(Debug, Deserialize)]
+struct SourceRequest {
+    source: u16,
+}
+
+struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn not_found(message: String) -> Self {
+        ApiError {
+            status: StatusCode::NOT_FOUND,
+            message,
+        }
+    }
+}
+
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        let status = match error {
+            Error::Protocol(ProtocolError::NotConfirmed) => StatusCode::GATEWAY_TIMEOUT,
+            Error::UnsupportedCommand { .. } => StatusCode::NOT_IMPLEMENTED,
+            _ => StatusCode::BAD_GATEWAY,
+        };
+
+        ApiError {
+            status,
+            message: error.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, Json(json!({ "error": self.message }))).into_response()
+    }
+}
+
+/// Routes of the API backed by the connection. The connection no longer queues messages for
+/// [`Connection::recv_message`], the API only relies on the state it keeps.
+pub fn router(mut connection: Connection) -> Router {
+    connection.close_messages();
+
+    let router = Router::new()
+        .route("/state", get(state))
+        .route("/me/{me}/program", post(program))
+        .route("/me/{me}/preview", post(preview))
+        .route("/me/{me}/cut", post(cut))
+        .route("/me/{me}/auto", post(auto))
+        .route("/aux/{aux}", post(aux))
+        .route("/macro/{index}/run", post(run_macro));
+    #[cfg(feature = "streaming")]
+    let router = router
+        .route("/stream/start", post(start_streaming))
+        .route("/stream/stop", post(stop_streaming));
+
+    router.with_state(Arc::new(connection))
+}
+
+/// Serve the API on address, e.g. `0.0.0.0:8080`, until an error occurs
+pub async fn serve(connection: Connection, address: &str) -> Result<(), Error> {
+    let listener = TcpListener::bind(address).await?;
+    axum::serve(listener, router(connection)).await?;
+
+    Ok(())
+}
+
+async fn state(State(connection): State<SharedConnection>) -> Json<Value> {
+    let system_info = connection.system_info();
+    let capabilities = system_info.capabilities();
+
+    let mix_effects: Vec<Value> = (0..capabilities.me_count())
+        .map(|me| {
+            json!({
+                "program": system_info.program_input(me),
+                "preview": system_info.preview_input(me),
+                "transition_position": system_info
+                    .transition_position(me)
+                    .map(|position| position.position()),
+            })
+        })
+        .collect();
+    let aux: Vec<Value> = (0..capabilities.aux_count())
+        .map(|aux| json!({ "source": system_info.aux_source(aux) }))
+        .collect();
+    let sources: Vec<Value> = system_info
+        .sources()
+        .map(|source| {
+            json!({
+                "id": source.id(),
+                "name": source.name(),
+                "short_name": source.short_name(),
+            })
+        })
+        .collect();
+
+    Json(json!({
+        "product": system_info.product(),
+        "model": capabilities.model().to_string(),
+        "version": system_info.version().to_string(),
+        "mix_effects": mix_effects,
+        "aux": aux,
+        "sources": sources,
+    }))
+}
+
+async fn program(
+    State(connection): State<SharedConnection>,
+    Path(me): Path<u8>,
+    Json(request): Json<SourceRequest>,
+) -> Result<StatusCode, ApiError> {
+    check_me(&connection, me)?;
+    connection
+        .send_confirmed(
+            ControlCommand::ProgramInput(SetProgramInput::new(me, request.source)),
+            CONFIRM_TIMEOUT,
+        )
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn preview(
+    State(connection): State<SharedConnection>,
+    Path(me): Path<u8>,
+    Json(request): Json<SourceRequest>,
+) -> Result<StatusCode, ApiError> {
+    check_me(&connection, me)?;
+    connection
+        .send_confirmed(
+            ControlCommand::PreviewInput(SetPreviewInput::new(me, request.source)),
+            CONFIRM_TIMEOUT,
+        )
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn cut(
+    State(connection): State<SharedConnection>,
+    Path(me): Path<u8>,
+) -> Result<StatusCode, ApiError> {
+    check_me(&connection, me)?;
+    connection
+        .send_command(ControlCommand::Cut(Cut::new(me)))
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn auto(
+    State(connection): State<SharedConnection>,
+    Path(me): Path<u8>,
+) -> Result<StatusCode, ApiError> {
+    check_me(&connection, me)?;
+    connection
+        .send_command(ControlCommand::AutoTransition(AutoTransition::new(me)))
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn aux(
+    State(connection): State<SharedConnection>,
+    Path(aux): Path<u8>,
+    Json(request): Json<SourceRequest>,
+) -> Result<StatusCode, ApiError> {
+    let aux_count = connection.system_info().capabilities().aux_count();
+    if aux >= aux_count {
+        return Err(ApiError::not_found(format!("No aux output {aux}")));
+    }
+    connection
+        .send_confirmed(
+            ControlCommand::AuxSource(SetAuxSource::new(aux, request.source)),
+            CONFIRM_TIMEOUT,
+        )
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn run_macro(
+    State(connection): State<SharedConnection>,
+    Path(index): Path<u16>,
+) -> Result<StatusCode, ApiError> {
+    let used = connection
+        .system_info()
+        .macro_properties(index)
+        .is_some_and(|properties| properties.used());
+    if !used {
+        return Err(ApiError::not_found(format!("No macro {index}")));
+    }
+    connection.macros().run(index)?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+#[cfg(feature = "streaming")]
+async fn start_streaming(
+    State(connection): State<SharedConnection>,
+) -> Result<StatusCode, ApiError> {
+    connection.start_streaming()?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+#[cfg(feature = "streaming")]
+async fn stop_streaming(
+    State(connection): State<SharedConnection>,
+) -> Result<StatusCode, ApiError> {
+    connection.stop_streaming()?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+fn check_me(connection: &Connection, me: u8) -> Result<(), ApiError> {
+    if me >= connection.system_info().capabilities().me_count() {
+        return Err(ApiError::not_found(format!("No mix effect block {me}")));
+    }
+
+    Ok(())
+}
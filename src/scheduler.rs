@@ -0,0 +1,182 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use tokio::sync::mpsc;
+
+use crate::{
+    command::{Command, Time},
+    control::{CommandBatch, ControlCommand},
+    systeminfo::{RequestTime, VideoMode},
+    Error,
+};
+
+/// Time between asking the switcher for its time code while an operation is pending
+const TIME_REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// When a scheduled operation runs, measured by the time code of the switcher rather than the
+/// local clock, see [`crate::Connection::schedule`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduleAt {
+    /// Once the time code reaches the time, the next day if it has already passed today
+    Time(Time),
+    /// Once the time code has advanced by the duration, less than a day
+    After(Duration),
+}
+
+/// Operation waiting for its time, see [`crate::Connection::schedule`]
+#[derive(Debug)]
+pub struct ScheduledOperation {
+    finished: Arc<AtomicBool>,
+}
+
+impl ScheduledOperation {
+    /// Drop the operation if it hasn't run yet
+    pub fn cancel(&self) {
+        self.finished.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the operation has been sent or cancelled
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+}
+
+/// Reached once the time code passes the target. A target before the start is reached after
+/// the time code has wrapped around at midnight.
+struct Deadline {
+    start: Time,
+    target: Time,
+    passed_midnight: bool,
+}
+
+impl Deadline {
+    fn new(start: Time, at: ScheduleAt, mode: VideoMode) -> Result<Self, Error> {
+        let target = match at {
+            ScheduleAt::Time(time) => time,
+            ScheduleAt::After(delay) if delay >= DAY => return Err(Error::DelayTooLong),
+            ScheduleAt::After(delay) => {
+                let mut target = start.to_duration(mode).ok_or(Error::UnknownTime)? + delay;
+                if target >= DAY {
+                    target -= DAY;
+                }
+                Time::from_duration(target, mode).ok_or(Error::UnknownTime)?
+            }
+        };
+
+        Ok(Deadline {
+            start,
+            target,
+            passed_midnight: false,
+        })
+    }
+
+    fn reached(&mut self, time: Time) -> bool {
+        if self.target < self.start {
+            self.passed_midnight |= time < self.start;
+            self.passed_midnight && time >= self.target
+        } else {
+            // Time is only sampled now and then, it can wrap past midnight without a sample
+            // between the target and the end of the day
+            time >= self.target || time < self.start
+        }
+    }
+}
+
+/// Send the batch on the first time code at or past the scheduled time, asking the switcher
+/// for its time code until then
+pub(crate) fn schedule<L>(
+    start: Time,
+    at: ScheduleAt,
+    mode: VideoMode,
+    batch: CommandBatch,
+    command_tx: mpsc::UnboundedSender<CommandBatch>,
+    listen: L,
+) -> Result<ScheduledOperation, Error>
+where
+    L: FnOnce(Box<dyn FnMut(&Command) -> bool + Send>),
+{
+    let mut deadline = Deadline::new(start, at, mode)?;
+    let finished = Arc::new(AtomicBool::new(false));
+
+    let listener_finished = finished.clone();
+    let listener_tx = command_tx.clone();
+    let mut batch = Some(batch);
+    listen(Box::new(move |command| {
+        if listener_finished.load(Ordering::Relaxed) {
+            return false;
+        }
+        match command {
+            Command::Time(time) if deadline.reached(*time) => {
+                listener_finished.store(true, Ordering::Relaxed);
+                if let Some(batch) = batch.take() {
+                    let _ = listener_tx.send(batch);
+                }
+                false
+            }
+            _ => true,
+        }
+    }));
+
+    let request_finished = finished.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(TIME_REQUEST_INTERVAL);
+        while !request_finished.load(Ordering::Relaxed) {
+            interval.tick().await;
+            let request = ControlCommand::RequestTime(RequestTime).into();
+            if command_tx.send(request).is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(ScheduledOperation { finished })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deadline_past_midnight() {
+        let start = Time::new(23, 59, 50, 0);
+        let mut deadline = Deadline::new(
+            start,
+            ScheduleAt::After(Duration::from_secs(20)),
+            VideoMode::Res1080p25,
+        )
+        .unwrap();
+
+        assert_eq!(deadline.target, Time::new(0, 0, 10, 0));
+        assert!(!deadline.reached(Time::new(23, 59, 59, 24)));
+        assert!(!deadline.reached(Time::new(0, 0, 9, 24)));
+        assert!(deadline.reached(Time::new(0, 0, 10, 0)));
+
+        assert!(matches!(
+            Deadline::new(
+                start,
+                ScheduleAt::After(Duration::from_secs(24 * 60 * 60)),
+                VideoMode::Res1080p25
+            ),
+            Err(Error::DelayTooLong)
+        ));
+    }
+
+    #[test]
+    fn deadline_end_of_day_missed() {
+        let mut deadline = Deadline::new(
+            Time::new(23, 59, 58, 0),
+            ScheduleAt::Time(Time::new(23, 59, 59, 20)),
+            VideoMode::Res1080p25,
+        )
+        .unwrap();
+
+        assert!(!deadline.reached(Time::new(23, 59, 59, 10)));
+        assert!(deadline.reached(Time::new(0, 0, 0, 5)));
+    }
+}
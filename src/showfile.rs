@@ -0,0 +1,495 @@
+//! Import and export of the XML settings files saved by ATEM Software Control, so a show can be
+//! staged in the official app and applied from Rust, or the other way around. The files hold far
+//! more settings than the crate knows about, only these are converted and the rest is ignored:
+//!
+//! - `Profile/Settings/Inputs/Input` with `id`, `longName` and `shortName`
+//! - `Profile/MixEffectBlocks/MixEffectBlock` with `index`, containing `Program` and `Preview`
+//!   with `input`, `TransitionStyle` with `style` and `nextSelection`, e.g.
+//!   `nextSelection="Background, Key1"`, and `UpstreamKeys/UpstreamKey` with `index`,
+//!   `fillInput` and `keyInput`
+//! - `Profile/ColorGenerators/ColorGenerator` with `index`, `hue` in degrees and `saturation`
+//!   and `luma` in percent
+//! - `Profile/AuxOutputs/AuxOutput` with `index` and `input`
+
+use std::{fmt::Display, io, str::FromStr};
+
+use quick_xml::{
+    events::{BytesDecl, BytesStart, Event},
+    Reader, Writer,
+};
+
+use crate::{
+    color::HslColor,
+    desiredstate::DesiredState,
+    systeminfo::SystemInfo,
+    transition::{TransitionLayers, TransitionStyle},
+    Error,
+};
+
+const LAYER_NAMES: [(TransitionLayers, &str); 5] = [
+    (TransitionLayers::BACKGROUND, "Background"),
+    (TransitionLayers::KEY1, "Key1"),
+    (TransitionLayers::KEY2, "Key2"),
+    (TransitionLayers::KEY3, "Key3"),
+    (TransitionLayers::KEY4, "Key4"),
+];
+
+/// State described by a settings file, applying it brings the switcher to the saved show.
+/// Elements are only taken from the places listed above, elements with the same name elsewhere
+/// in the file describe other settings.
+pub fn import(xml: &str) -> Result<DesiredState, Error> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+    let mut state = DesiredState::new();
+    // Names of the enclosing elements separated by slashes
+    let mut path = String::new();
+    let mut me = None;
+
+    loop {
+        match reader.read_event().map_err(invalid)? {
+            Event::Start(element) => {
+                if path == "Profile/MixEffectBlocks" && element.name().as_ref() == b"MixEffectBlock"
+                {
+                    me = Some(required(&element, "index")?);
+                }
+                state = import_element(state, &element, &path, me)?;
+                if !path.is_empty() {
+                    path.push('/');
+                }
+                path.push_str(&String::from_utf8_lossy(element.name().as_ref()));
+            }
+            Event::Empty(element) => state = import_element(state, &element, &path, me)?,
+            Event::End(_) => {
+                if path == "Profile/MixEffectBlocks/MixEffectBlock" {
+                    me = None;
+                }
+                path.truncate(path.rfind('/').unwrap_or_default());
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(state)
+}
+
+fn import_element(
+    state: DesiredState,
+    element: &BytesStart,
+    path: &str,
+    me: Option<u8>,
+) -> Result<DesiredState, Error> {
+    let in_me = || {
+        me.ok_or_else(|| {
+            Error::ShowFile(format!(
+                "{} outside of a MixEffectBlock",
+                String::from_utf8_lossy(element.name().as_ref())
+            ))
+        })
+    };
+
+    let state = match (path, element.name().as_ref()) {
+        ("Profile/Settings/Inputs", b"Input") => {
+            let long_name: Option<String> = attribute(element, "longName")?;
+            let short_name: Option<String> = attribute(element, "shortName")?;
+            if long_name.is_none() && short_name.is_none() {
+                return Ok(state);
+            }
+            state.input_names(
+                required(element, "id")?,
+                long_name.as_deref().unwrap_or_default(),
+                short_name.as_deref().unwrap_or_default(),
+            )?
+        }
+        ("Profile/MixEffectBlocks/MixEffectBlock", b"Program") => {
+            state.program_input(in_me()?, required(element, "input")?)
+        }
+        ("Profile/MixEffectBlocks/MixEffectBlock", b"Preview") => {
+            state.preview_input(in_me()?, required(element, "input")?)
+        }
+        ("Profile/MixEffectBlocks/MixEffectBlock", b"TransitionStyle") => {
+            let me = in_me()?;
+            let mut state = state;
+            if let Some(style) = attribute::<String>(element, "style")? {
+                state = state.transition_style(me, parse_style(&style)?);
+            }
+            if let Some(selection) = attribute::<String>(element, "nextSelection")? {
+                state = state.transition_layers(me, parse_layers(&selection)?);
+            }
+            state
+        }
+        ("Profile/MixEffectBlocks/MixEffectBlock/UpstreamKeys", b"UpstreamKey") => {
+            let me = in_me()?;
+            let keyer = required(element, "index")?;
+            let mut state = state;
+            if let Some(source) = attribute(element, "fillInput")? {
+                state = state.keyer_fill_source(me, keyer, source);
+            }
+            if let Some(source) = attribute(element, "keyInput")? {
+                state = state.keyer_cut_source(me, keyer, source);
+            }
+            state
+        }
+        ("Profile/ColorGenerators", b"ColorGenerator") => {
+            let hue: f32 = required(element, "hue")?;
+            let saturation: f32 = required(element, "saturation")?;
+            let luma: f32 = required(element, "luma")?;
+            state.color_generator(
+                required(element, "index")?,
+                HslColor::new(to_tenths(hue), to_tenths(saturation), to_tenths(luma)),
+            )
+        }
+        ("Profile/AuxOutputs", b"AuxOutput") => {
+            state.aux_source(required(element, "index")?, required(element, "input")?)
+        }
+        _ => state,
+    };
+
+    Ok(state)
+}
+
+/// Settings file with the state the switcher has reported, for loading into ATEM Software
+/// Control or [`import`]
+pub fn export(system_info: &SystemInfo) -> Result<String, Error> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 4);
+    write_profile(&mut writer, system_info).map_err(invalid)?;
+
+    String::from_utf8(writer.into_inner()).map_err(invalid)
+}
+
+fn write_profile(writer: &mut Writer<Vec<u8>>, system_info: &SystemInfo) -> io::Result<()> {
+    let capabilities = system_info.capabilities();
+    let version = system_info.version();
+
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    writer
+        .create_element("Profile")
+        .with_attributes([
+            ("majorVersion", version.major().to_string().as_str()),
+            ("minorVersion", version.minor().to_string().as_str()),
+            ("product", system_info.product()),
+        ])
+        .write_inner_content(|writer| {
+            writer
+                .create_element("Settings")
+                .write_inner_content(|writer| {
+                    writer
+                        .create_element("Inputs")
+                        .write_inner_content(|writer| {
+                            for source in system_info.sources() {
+                                writer
+                                    .create_element("Input")
+                                    .with_attributes([
+                                        ("id", source.id().to_string().as_str()),
+                                        ("shortName", source.short_name().unwrap_or_default()),
+                                        ("longName", source.long_name().unwrap_or_default()),
+                                    ])
+                                    .write_empty()?;
+                            }
+                            Ok(())
+                        })?;
+                    Ok(())
+                })?;
+
+            writer
+                .create_element("MixEffectBlocks")
+                .write_inner_content(|writer| {
+                    for me in 0..capabilities.me_count() {
+                        write_mix_effect_block(writer, system_info, me)?;
+                    }
+                    Ok(())
+                })?;
+
+            writer
+                .create_element("ColorGenerators")
+                .write_inner_content(|writer| {
+                    for (index, color) in system_info.color_generators() {
+                        writer
+                            .create_element("ColorGenerator")
+                            .with_attributes([
+                                ("index", index.to_string().as_str()),
+                                ("hue", format_tenths(color.hue()).as_str()),
+                                ("saturation", format_tenths(color.saturation()).as_str()),
+                                ("luma", format_tenths(color.luma()).as_str()),
+                            ])
+                            .write_empty()?;
+                    }
+                    Ok(())
+                })?;
+
+            writer
+                .create_element("AuxOutputs")
+                .write_inner_content(|writer| {
+                    for aux in 0..capabilities.aux_count() {
+                        if let Some(source) = system_info.aux_source(aux) {
+                            writer
+                                .create_element("AuxOutput")
+                                .with_attributes([
+                                    ("index", aux.to_string().as_str()),
+                                    ("input", source.to_string().as_str()),
+                                ])
+                                .write_empty()?;
+                        }
+                    }
+                    Ok(())
+                })?;
+
+            Ok(())
+        })?;
+
+    Ok(())
+}
+
+fn write_mix_effect_block(
+    writer: &mut Writer<Vec<u8>>,
+    system_info: &SystemInfo,
+    me: u8,
+) -> io::Result<()> {
+    writer
+        .create_element("MixEffectBlock")
+        .with_attribute(("index", me.to_string().as_str()))
+        .write_inner_content(|writer| {
+            if let Some(source) = system_info.program_input(me) {
+                writer
+                    .create_element("Program")
+                    .with_attribute(("input", source.to_string().as_str()))
+                    .write_empty()?;
+            }
+            if let Some(source) = system_info.preview_input(me) {
+                writer
+                    .create_element("Preview")
+                    .with_attribute(("input", source.to_string().as_str()))
+                    .write_empty()?;
+            }
+            if let Some(selection) = system_info.transition_style(me) {
+                let layers = format_layers(selection.next_layers());
+                // Styles the show file has no name for are left out rather than written in a
+                // form import would reject
+                let style = format_style(selection.next_style());
+                writer
+                    .create_element("TransitionStyle")
+                    .with_attributes(style.map(|style| ("style", style)))
+                    .with_attribute(("nextSelection", layers.as_str()))
+                    .write_empty()?;
+            }
+            writer
+                .create_element("UpstreamKeys")
+                .write_inner_content(|writer| {
+                    for keyer in system_info.keyers().filter(|keyer| keyer.me() == me) {
+                        writer
+                            .create_element("UpstreamKey")
+                            .with_attributes([
+                                ("index", keyer.keyer().to_string().as_str()),
+                                ("fillInput", keyer.fill_source().to_string().as_str()),
+                                ("keyInput", keyer.cut_source().to_string().as_str()),
+                            ])
+                            .write_empty()?;
+                    }
+                    Ok(())
+                })?;
+            Ok(())
+        })?;
+
+    Ok(())
+}
+
+fn attribute<T: FromStr>(element: &BytesStart, name: &str) -> Result<Option<T>, Error> {
+    let Some(attribute) = element.try_get_attribute(name).map_err(invalid)? else {
+        return Ok(None);
+    };
+    let value = attribute.unescape_value().map_err(invalid)?;
+
+    value.parse().map(Some).map_err(|_| {
+        Error::ShowFile(format!(
+            "Invalid {name} \"{value}\" of {}",
+            String::from_utf8_lossy(element.name().as_ref())
+        ))
+    })
+}
+
+fn required<T: FromStr>(element: &BytesStart, name: &str) -> Result<T, Error> {
+    attribute(element, name)?.ok_or_else(|| {
+        Error::ShowFile(format!(
+            "Missing {name} of {}",
+            String::from_utf8_lossy(element.name().as_ref())
+        ))
+    })
+}
+
+fn parse_style(style: &str) -> Result<TransitionStyle, Error> {
+    match style {
+        "Mix" => Ok(TransitionStyle::Mix),
+        "Dip" => Ok(TransitionStyle::Dip),
+        "Wipe" => Ok(TransitionStyle::Wipe),
+        "DVE" => Ok(TransitionStyle::Dve),
+        "Stinger" => Ok(TransitionStyle::Stinger),
+        _ => Err(Error::ShowFile(format!("Unknown transition style {style}"))),
+    }
+}
+
+fn format_style(style: TransitionStyle) -> Option<&'static str> {
+    match style {
+        TransitionStyle::Mix => Some("Mix"),
+        TransitionStyle::Dip => Some("Dip"),
+        TransitionStyle::Wipe => Some("Wipe"),
+        TransitionStyle::Dve => Some("DVE"),
+        TransitionStyle::Stinger => Some("Stinger"),
+        TransitionStyle::Unknown(_) => None,
+    }
+}
+
+fn parse_layers(selection: &str) -> Result<TransitionLayers, Error> {
+    selection
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .try_fold(TransitionLayers::empty(), |layers, name| {
+            LAYER_NAMES
+                .iter()
+                .find(|(_, layer_name)| *layer_name == name)
+                .map(|(layer, _)| layers | *layer)
+                .ok_or_else(|| Error::ShowFile(format!("Unknown transition layer {name}")))
+        })
+}
+
+fn format_layers(layers: TransitionLayers) -> String {
+    LAYER_NAMES
+        .iter()
+        .filter(|(layer, _)| layers.contains(*layer))
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn to_tenths(value: f32) -> u16 {
+    (value * 10.0).round().max(0.0) as u16
+}
+
+fn format_tenths(value: u16) -> String {
+    format!("{:.1}", value as f32 / 10.0)
+}
+
+fn invalid(error: impl Display) -> Error {
+    Error::ShowFile(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::{command::test_command, control::ControlCommand, systeminfo::Topology};
+
+    #[test]
+    fn import_ok() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <Profile majorVersion="1" minorVersion="5" product="ATEM Mini Pro">
+                <Settings>
+                    <Inputs>
+                        <Input id="1" shortName="CAM1" longName="Camera 1"/>
+                    </Inputs>
+                </Settings>
+                <MixEffectBlocks>
+                    <MixEffectBlock index="0">
+                        <Program input="1"/>
+                        <Preview input="2"/>
+                        <TransitionStyle style="Dip" nextSelection="Background, Key1"/>
+                        <UpstreamKeys>
+                            <UpstreamKey index="0" fillInput="3010" keyInput="3011"/>
+                        </UpstreamKeys>
+                    </MixEffectBlock>
+                </MixEffectBlocks>
+                <ColorGenerators>
+                    <ColorGenerator index="0" hue="120.0" saturation="100.0" luma="50.0"/>
+                </ColorGenerators>
+                <AuxOutputs>
+                    <AuxOutput index="0" input="10010"/>
+                </AuxOutputs>
+            </Profile>"#;
+
+        let names: Vec<&[u8; 4]> = import(xml)
+            .unwrap()
+            .commands(&SystemInfo::default())
+            .iter()
+            .map(ControlCommand::name)
+            .collect();
+        assert_eq!(
+            names,
+            [b"CInL", b"CClV", b"CTTp", b"CKeF", b"CKeC", b"CPvI", b"CPgI", b"CAuS"]
+        );
+
+        assert!(matches!(
+            import(
+                r#"<Profile><MixEffectBlocks><MixEffectBlock><Program input="1"/></MixEffectBlock></MixEffectBlocks></Profile>"#
+            ),
+            Err(Error::ShowFile(_))
+        ));
+    }
+
+    #[test]
+    fn import_ignores_elements_elsewhere() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <Profile majorVersion="1" minorVersion="5" product="ATEM Mini Pro">
+                <Settings>
+                    <Inputs>
+                        <Input id="1" externalPortType="HDMI"/>
+                    </Inputs>
+                    <MultiViews>
+                        <MultiView index="0">
+                            <Windows>
+                                <Window index="2">
+                                    <Input id="2" longName="Camera 2"/>
+                                </Window>
+                            </Windows>
+                        </MultiView>
+                    </MultiViews>
+                </Settings>
+                <Input id="3" longName="Camera 3"/>
+                <Program input="1"/>
+                <AuxOutput index="0" input="1"/>
+            </Profile>"#;
+
+        assert!(import(xml)
+            .unwrap()
+            .commands(&SystemInfo::default())
+            .is_empty());
+    }
+
+    #[test]
+    fn export_round_trip() {
+        let mut system_info = SystemInfo::default();
+        system_info.set_topology(Topology::parse(&mut Bytes::from_static(&[
+            1, 10, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ])));
+        system_info.set_program_input(0, 1);
+        system_info.set_preview_input(0, 2);
+        system_info.set_aux_source(0, 3);
+        system_info.set_color_generator(1, HslColor::new(1200, 1000, 500));
+
+        let xml = export(&system_info).unwrap();
+        assert!(xml.contains(r#"<Program input="1"/>"#));
+        assert!(import(&xml).unwrap().commands(&system_info).is_empty());
+    }
+
+    #[test]
+    fn export_transition_style_round_trip() {
+        let mut system_info = SystemInfo::default();
+        system_info.set_topology(Topology::parse(&mut Bytes::from_static(&[
+            1, 10, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ])));
+
+        for style in 0..=4 {
+            system_info.update(&test_command(b"TrSS", &[0, 0, 1, style, 3, 0, 0, 0]));
+            let xml = export(&system_info).unwrap();
+            let state = import(&xml).unwrap();
+            assert!(state.commands(&system_info).is_empty(), "{xml}");
+            assert!(!state.commands(&SystemInfo::default()).is_empty());
+        }
+
+        // Styles the show file can't name are left out, the layers are still restored
+        system_info.update(&test_command(b"TrSS", &[0, 0, 1, 9, 3, 0, 0, 0]));
+        let xml = export(&system_info).unwrap();
+        assert!(!xml.contains("style="));
+        assert!(import(&xml).unwrap().commands(&system_info).is_empty());
+    }
+}
@@ -0,0 +1,232 @@
+use bitflags::bitflags;
+
+use crate::{desiredstate::DesiredState, systeminfo::SystemInfo};
+#[cfg(feature = "client")]
+use crate::{Connection, Error};
+
+bitflags! {
+    /// Parts of the switcher state a snapshot covers
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct SnapshotScope: u8 {
+        /// Program, preview and aux sources
+        const ROUTING = 0x01;
+        /// Sources and on air state of the upstream and downstream keyers
+        const KEYERS = 0x02;
+        /// Style and layers of the next transition
+        const TRANSITIONS = 0x04;
+        /// Faders, balance and mix options of the classic or Fairlight mixer, the classic monitor
+        /// output and Fairlight input levels
+        #[cfg(feature = "audio")]
+        const AUDIO = 0x08;
+        /// Sources and layout of the SuperSource boxes
+        const SUPERSOURCE = 0x10;
+    }
+}
+
+/// Look of the switcher saved to be recalled later, like the memories of a switcher panel
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    scope: SnapshotScope,
+    state: DesiredState,
+}
+
+impl Snapshot {
+    /// Save the parts of the current state selected by scope. Only what the switcher has
+    /// reported is saved, so capture once the initial state has been received.
+    pub fn capture(system_info: &SystemInfo, scope: SnapshotScope) -> Self {
+        let capabilities = system_info.capabilities();
+        let mut state = DesiredState::new();
+
+        if scope.contains(SnapshotScope::ROUTING) {
+            for me in 0..capabilities.me_count() {
+                if let Some(source) = system_info.preview_input(me) {
+                    state = state.preview_input(me, source);
+                }
+                if let Some(source) = system_info.program_input(me) {
+                    state = state.program_input(me, source);
+                }
+            }
+            for aux in 0..capabilities.aux_count() {
+                if let Some(source) = system_info.aux_source(aux) {
+                    state = state.aux_source(aux, source);
+                }
+            }
+        }
+
+        if scope.contains(SnapshotScope::KEYERS) {
+            for keyer in system_info.keyers() {
+                state = state
+                    .keyer_fill_source(keyer.me(), keyer.keyer(), keyer.fill_source())
+                    .keyer_cut_source(keyer.me(), keyer.keyer(), keyer.cut_source());
+                if let Some(on_air) = system_info.keyer_on_air(keyer.me(), keyer.keyer()) {
+                    state = state.keyer_on_air(keyer.me(), keyer.keyer(), on_air);
+                }
+            }
+            for keyer in 0..capabilities.dsk_count() {
+                if let Some(sources) = system_info.downstream_keyer_sources(keyer) {
+                    state = state
+                        .downstream_keyer_fill_source(keyer, sources.fill_source())
+                        .downstream_keyer_cut_source(keyer, sources.cut_source());
+                }
+                if let Some(keyer_state) = system_info.downstream_keyer_state(keyer) {
+                    state = state.downstream_keyer_on_air(keyer, keyer_state.on_air());
+                }
+            }
+        }
+
+        if scope.contains(SnapshotScope::SUPERSOURCE) {
+            for ssbox in system_info.supersource_boxes() {
+                state = state.supersource_box(ssbox.clone());
+            }
+        }
+
+        if scope.contains(SnapshotScope::TRANSITIONS) {
+            for me in 0..capabilities.me_count() {
+                if let Some(selection) = system_info.transition_style(me) {
+                    state = state
+                        .transition_style(me, selection.next_style())
+                        .transition_layers(me, selection.next_layers());
+                }
+            }
+        }
+
+        #[cfg(feature = "audio")]
+        if scope.contains(SnapshotScope::AUDIO) {
+            if let Some(monitor) = system_info.audio_monitor() {
+                state = state.audio_monitor(monitor.clone());
+            }
+            for input in system_info.audio_mixer_inputs() {
+                state = state.audio_mixer_input(input.clone());
+            }
+            if let Some(master) = system_info.audio_mixer_master() {
+                state = state.audio_mixer_master(master.clone());
+            }
+            for input in system_info.fairlight_inputs() {
+                state = state.fairlight_input_level(input.input(), input.level());
+            }
+            for source in system_info.fairlight_sources() {
+                state = state.fairlight_source(source.clone());
+            }
+            if let Some(master) = system_info.fairlight_master() {
+                state = state.fairlight_master(master.clone());
+            }
+        }
+
+        Snapshot { scope, state }
+    }
+
+    pub fn scope(&self) -> SnapshotScope {
+        self.scope
+    }
+
+    /// Saved state, e.g. to extend it before applying it
+    pub fn state(&self) -> &DesiredState {
+        &self.state
+    }
+
+    #[cfg(feature = "client")]
+    /// Recall the snapshot, only the parts that changed since it was captured are sent
+    pub async fn apply(&self, connection: &Connection) -> Result<(), Error> {
+        connection.apply_state(&self.state).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use bytes::Bytes;
+
+    use super::*;
+    use crate::{command::test_command, control::ControlCommand, systeminfo::Topology};
+
+    #[test]
+    fn capture_routing() {
+        let mut system_info = SystemInfo::default();
+        system_info.set_topology(Topology::parse(&mut Bytes::from_static(&[
+            1, 10, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ])));
+        system_info.set_program_input(0, 1);
+        system_info.set_preview_input(0, 2);
+        system_info.set_aux_source(0, 3);
+
+        let snapshot = Snapshot::capture(&system_info, SnapshotScope::ROUTING);
+        assert!(snapshot.state().commands(&system_info).is_empty());
+
+        let names: Vec<&[u8; 4]> = snapshot
+            .state()
+            .commands(&SystemInfo::default())
+            .iter()
+            .map(ControlCommand::name)
+            .collect();
+        assert_eq!(names, [b"CPvI", b"CPgI", b"CAuS"]);
+    }
+
+    #[test]
+    fn capture_keyers_and_supersource() {
+        let mut system_info = SystemInfo::default();
+        system_info.set_topology(Topology::parse(&mut Bytes::from_static(&[
+            1, 10, 1, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0,
+        ])));
+        system_info.update(&test_command(
+            b"KeBP",
+            &[
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            ],
+        ));
+        system_info.set_keyer_on_air(0, 0, true);
+        system_info.update(&test_command(b"DskB", &[0, 0, 0, 5, 0, 6]));
+        system_info.update(&test_command(b"DskS", &[0, 1, 0, 0, 0]));
+        let mut ssbox = [0u8; 24];
+        ssbox[..6].copy_from_slice(&[0, 1, 1, 0, 0, 7]);
+        system_info.update(&test_command(b"SSBP", &ssbox));
+
+        let snapshot = Snapshot::capture(
+            &system_info,
+            SnapshotScope::KEYERS | SnapshotScope::SUPERSOURCE,
+        );
+        assert!(snapshot.state().commands(&system_info).is_empty());
+
+        let names: Vec<&[u8; 4]> = snapshot
+            .state()
+            .commands(&SystemInfo::default())
+            .iter()
+            .map(ControlCommand::name)
+            .collect();
+        assert_eq!(
+            names,
+            [b"CKeF", b"CKeC", b"CDsF", b"CDsC", b"CSBP", b"CKOn", b"CDsL"]
+        );
+    }
+
+    #[cfg(feature = "audio")]
+    #[test]
+    fn capture_audio() {
+        let mut system_info = SystemInfo::default();
+        let mut input = [0u8; 16];
+        input[..2].copy_from_slice(&[0, 1]);
+        input[8..14].copy_from_slice(&[2, 0, 0x80, 0x00, 0xfc, 0x18]);
+        system_info.update(&test_command(b"AMIP", &input));
+        system_info.update(&test_command(
+            b"AMMO",
+            &[0x40, 0x00, 0x03, 0xe8, 0, 0, 0, 0],
+        ));
+        let mut source = [0u8; 52];
+        source[..2].copy_from_slice(&[0, 2]);
+        source[8..16].copy_from_slice(&(-65280i64).to_be_bytes());
+        source[40..42].copy_from_slice(&(-500i16).to_be_bytes());
+        source[44..48].copy_from_slice(&(-1000i32).to_be_bytes());
+        source[48..50].copy_from_slice(&[0x07, 0x04]);
+        system_info.update(&test_command(b"FASP", &source));
+        let mut master = [0u8; 20];
+        master[12..16].copy_from_slice(&(-600i32).to_be_bytes());
+        system_info.update(&test_command(b"FAMP", &master));
+
+        let snapshot = Snapshot::capture(&system_info, SnapshotScope::AUDIO);
+        assert!(snapshot.state().commands(&system_info).is_empty());
+
+        let commands = snapshot.state().commands(&SystemInfo::default());
+        let names: Vec<&[u8; 4]> = commands.iter().map(ControlCommand::name).collect();
+        assert_eq!(names, [b"CAMI", b"CAMM", b"CFSP", b"CFMP"]);
+    }
+}
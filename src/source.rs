@@ -1,11 +1,22 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
 use bitflags::bitflags;
-use bytes::{Buf, Bytes};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 
-use std::fmt;
+use core::fmt;
 
-use crate::{command, parser::parse_str};
+use crate::{
+    command,
+    parser::{parse_str, put_str},
+    Error,
+};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum Input {
     Sdi,
     Hdmi,
@@ -60,7 +71,7 @@ impl fmt::Display for Input {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum SourceType {
     External,
     Black,
@@ -259,7 +270,7 @@ impl fmt::Display for MixEffectFlags {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Source {
     id: u16,
     name: Option<String>,
@@ -272,17 +283,17 @@ pub struct Source {
 }
 
 impl Source {
-    pub fn parse(data: &mut Bytes) -> Result<Self, command::Error> {
+    pub fn parse(data: &mut Bytes) -> Result<Self, command::ParseError> {
         let id = data.get_u16();
         let name = parse_str(&mut data.split_to(20))?;
         let short_name = parse_str(&mut data.split_to(4))?;
         data.get_u16(); // Skip 2 bytes
-        let available_inputs = InputFlags::from_bits(data.get_u16()).unwrap();
+        let available_inputs = InputFlags::from_bits_truncate(data.get_u16());
         let active_input = data.get_u16().into();
         let source_type = data.get_u8().into();
         data.get_u8(); // Skip byte
-        let available_functions = FunctionFlags::from_bits(data.get_u8()).unwrap();
-        let available_on_me = MixEffectFlags::from_bits(data.get_u8()).unwrap();
+        let available_functions = FunctionFlags::from_bits_truncate(data.get_u8());
+        let available_on_me = MixEffectFlags::from_bits_truncate(data.get_u8());
 
         Ok(Source {
             id,
@@ -299,6 +310,28 @@ impl Source {
     pub fn id(&self) -> u16 {
         self.id
     }
+
+    /// Long name, falls back to the short name if the long name is empty
+    pub fn name(&self) -> Option<&str> {
+        self.name
+            .as_deref()
+            .filter(|name| !name.is_empty())
+            .or_else(|| self.short_name())
+    }
+
+    /// Long name without falling back to the short name
+    pub fn long_name(&self) -> Option<&str> {
+        self.name.as_deref().filter(|name| !name.is_empty())
+    }
+
+    pub fn short_name(&self) -> Option<&str> {
+        self.short_name.as_deref().filter(|name| !name.is_empty())
+    }
+
+    /// External port used by the input
+    pub fn active_input(&self) -> Input {
+        self.active_input
+    }
 }
 
 impl fmt::Display for Source {
@@ -318,3 +351,120 @@ impl fmt::Display for Source {
         )
     }
 }
+
+const LONG_NAME_SIZE: usize = 20;
+const SHORT_NAME_SIZE: usize = 4;
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct InputPropertiesMask: u8 {
+        const LONG_NAME = 0x01;
+        const SHORT_NAME = 0x02;
+        const EXTERNAL_PORT_TYPE = 0x04;
+    }
+}
+
+/// Change the names and external port of an input (`CInL`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetInputProperties {
+    mask: InputPropertiesMask,
+    id: u16,
+    long_name: String,
+    short_name: String,
+    external_port_type: Input,
+}
+
+impl SetInputProperties {
+    pub fn new(id: u16) -> Self {
+        SetInputProperties {
+            mask: InputPropertiesMask::empty(),
+            id,
+            long_name: String::new(),
+            short_name: String::new(),
+            external_port_type: Input::Unknown(0),
+        }
+    }
+
+    /// Name of the input, at most 20 bytes
+    pub fn long_name(mut self, name: &str) -> Result<Self, Error> {
+        if name.len() > LONG_NAME_SIZE {
+            return Err(Error::NameTooLong(LONG_NAME_SIZE));
+        }
+
+        self.mask |= InputPropertiesMask::LONG_NAME;
+        self.long_name = name.to_string();
+        Ok(self)
+    }
+
+    /// Short name of the input, at most 4 bytes
+    pub fn short_name(mut self, name: &str) -> Result<Self, Error> {
+        if name.len() > SHORT_NAME_SIZE {
+            return Err(Error::NameTooLong(SHORT_NAME_SIZE));
+        }
+
+        self.mask |= InputPropertiesMask::SHORT_NAME;
+        self.short_name = name.to_string();
+        Ok(self)
+    }
+
+    /// Port to use on inputs with more than one available, e.g. SDI or HDMI
+    pub fn external_port_type(mut self, port: Input) -> Self {
+        self.mask |= InputPropertiesMask::EXTERNAL_PORT_TYPE;
+        self.external_port_type = port;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(0); // Padding
+        data.put_u16(self.id);
+        put_str(data, &self.long_name, LONG_NAME_SIZE);
+        put_str(data, &self.short_name, SHORT_NAME_SIZE);
+        data.put_u16(self.external_port_type.into());
+        data.put_bytes(0, 2); // Padding
+    }
+}
+
+impl fmt::Display for SetInputProperties {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Source {}: {} ({}), {}",
+            self.id, self.long_name, self.short_name, self.external_port_type
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_input_properties_serialize_ok() {
+        let command = SetInputProperties::new(1)
+            .long_name("Camera 1")
+            .unwrap()
+            .short_name("CAM1")
+            .unwrap();
+        let expected: [u8; 32] = [
+            0x03, 0x00, 0x00, 0x01, b'C', b'a', b'm', b'e', b'r', b'a', b' ', b'1', 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, b'C', b'A', b'M', b'1',
+            0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let mut data = BytesMut::new();
+        command.serialize(&mut data);
+
+        assert_eq!(data.to_vec(), expected);
+    }
+
+    #[test]
+    fn set_input_properties_name_too_long() {
+        assert!(SetInputProperties::new(1)
+            .long_name("A name longer than 20")
+            .is_err());
+        assert!(SetInputProperties::new(1).short_name("CAM10").is_err());
+    }
+}
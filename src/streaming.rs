@@ -0,0 +1,515 @@
+use alloc::string::{String, ToString};
+use core::{fmt::Display, time::Duration};
+
+use bitflags::bitflags;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use crate::{
+    command,
+    parser::{parse_str, put_str},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamingState {
+    Idle,
+    Connecting,
+    Streaming,
+    Stopping,
+    Unknown(u16),
+}
+
+impl From<u16> for StreamingState {
+    fn from(value: u16) -> Self {
+        match value {
+            1 => StreamingState::Idle,
+            2 => StreamingState::Connecting,
+            4 => StreamingState::Streaming,
+            32 => StreamingState::Stopping,
+            u => StreamingState::Unknown(u),
+        }
+    }
+}
+
+impl From<StreamingState> for u16 {
+    fn from(value: StreamingState) -> Self {
+        match value {
+            StreamingState::Idle => 1,
+            StreamingState::Connecting => 2,
+            StreamingState::Streaming => 4,
+            StreamingState::Stopping => 32,
+            StreamingState::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for StreamingState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            StreamingState::Idle => write!(f, "Idle"),
+            StreamingState::Connecting => write!(f, "Connecting"),
+            StreamingState::Streaming => write!(f, "Streaming"),
+            StreamingState::Stopping => write!(f, "Stopping"),
+            StreamingState::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamingError {
+    None,
+    InvalidState,
+    Unknown(u16),
+}
+
+impl From<u16> for StreamingError {
+    fn from(value: u16) -> Self {
+        match value {
+            0 => StreamingError::None,
+            16 => StreamingError::InvalidState,
+            u => StreamingError::Unknown(u),
+        }
+    }
+}
+
+impl Display for StreamingError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            StreamingError::None => write!(f, "None"),
+            StreamingError::InvalidState => write!(f, "Invalid state"),
+            StreamingError::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StreamingStatus {
+    state: StreamingState,
+    error: StreamingError,
+}
+
+impl StreamingStatus {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let state = data.get_u16();
+        let error = data.get_u16();
+
+        StreamingStatus {
+            state: state.into(),
+            error: error.into(),
+        }
+    }
+
+    pub fn state(&self) -> StreamingState {
+        self.state
+    }
+
+    pub fn error(&self) -> StreamingError {
+        self.error
+    }
+
+    /// True while the switcher is streaming to the configured service
+    pub fn is_live(&self) -> bool {
+        self.state == StreamingState::Streaming
+    }
+}
+
+impl Display for StreamingStatus {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "State: {} Error: {}", self.state, self.error)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StreamingService {
+    name: Option<String>,
+    url: Option<String>,
+    key: Option<String>,
+    video_bitrates: [u32; 2],
+}
+
+impl StreamingService {
+    pub fn parse(data: &mut Bytes) -> Result<Self, command::ParseError> {
+        let name = parse_str(&mut data.split_to(64))?;
+        let url = parse_str(&mut data.split_to(512))?;
+        let key = parse_str(&mut data.split_to(512))?;
+        let video_bitrates = [data.get_u32(), data.get_u32()];
+
+        Ok(StreamingService {
+            name,
+            url,
+            key,
+            video_bitrates,
+        })
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn url(&self) -> Option<&str> {
+        self.url.as_deref()
+    }
+
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    /// Low and high video bitrates in bits per second
+    pub fn video_bitrates(&self) -> [u32; 2] {
+        self.video_bitrates
+    }
+}
+
+impl Display for StreamingService {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Service: {} URL: {} Video bitrates: {}/{}",
+            self.name.as_deref().unwrap_or(""),
+            self.url.as_deref().unwrap_or(""),
+            self.video_bitrates[0],
+            self.video_bitrates[1]
+        )
+    }
+}
+
+/// Audio bitrates of the streaming encoder (`STAB`)
+#[derive(Debug, Clone)]
+pub struct StreamingAudioBitrates {
+    low: u32,
+    high: u32,
+}
+
+impl StreamingAudioBitrates {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let low = data.get_u32();
+        let high = data.get_u32();
+
+        StreamingAudioBitrates { low, high }
+    }
+
+    /// Low and high audio bitrates in bits per second
+    pub fn bitrates(&self) -> [u32; 2] {
+        [self.low, self.high]
+    }
+}
+
+impl Display for StreamingAudioBitrates {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Audio bitrates: {}/{}", self.low, self.high)
+    }
+}
+
+/// Credentials used to authenticate with the streaming service (`SAth`)
+#[derive(Debug, Clone)]
+pub struct StreamingAuthentication {
+    username: Option<String>,
+    password: Option<String>,
+}
+
+impl StreamingAuthentication {
+    pub fn parse(data: &mut Bytes) -> Result<Self, command::ParseError> {
+        let username = parse_str(&mut data.split_to(64))?;
+        let password = parse_str(&mut data.split_to(64))?;
+
+        Ok(StreamingAuthentication { username, password })
+    }
+
+    pub fn username(&self) -> Option<&str> {
+        self.username.as_deref()
+    }
+
+    pub fn password(&self) -> Option<&str> {
+        self.password.as_deref()
+    }
+}
+
+impl Display for StreamingAuthentication {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // Keep the password out of logs
+        write!(f, "Username: {}", self.username.as_deref().unwrap_or(""))
+    }
+}
+
+/// Low latency mode of the streaming encoder (`SLow`)
+#[derive(Debug, Clone)]
+pub struct StreamingLowLatency {
+    enabled: bool,
+}
+
+impl StreamingLowLatency {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let enabled = data.get_u8() == 1;
+
+        StreamingLowLatency { enabled }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl Display for StreamingLowLatency {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Enabled: {}", self.enabled)
+    }
+}
+
+/// Periodic encoder statistics while streaming
+#[derive(Debug, Clone)]
+pub struct StreamingStats {
+    encoding_bitrate: u32,
+    cache_used: u16,
+}
+
+impl StreamingStats {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let encoding_bitrate = data.get_u32();
+        let cache_used = data.get_u16();
+
+        StreamingStats {
+            encoding_bitrate,
+            cache_used,
+        }
+    }
+
+    /// Current encoding bitrate in bits per second
+    pub fn encoding_bitrate(&self) -> u32 {
+        self.encoding_bitrate
+    }
+
+    /// Percentage of the outgoing stream cache in use, rises when the uplink can't keep up
+    pub fn cache_used(&self) -> u16 {
+        self.cache_used
+    }
+}
+
+impl Display for StreamingStats {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Bitrate: {} Cache used: {}%",
+            self.encoding_bitrate, self.cache_used
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StreamingTimeCode {
+    hour: u8,
+    minute: u8,
+    second: u8,
+    frame: u8,
+    drop_frame: bool,
+}
+
+impl StreamingTimeCode {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let hour = data.get_u8();
+        let minute = data.get_u8();
+        let second = data.get_u8();
+        let frame = data.get_u8();
+        let drop_frame = data.get_u8() == 1;
+
+        StreamingTimeCode {
+            hour,
+            minute,
+            second,
+            frame,
+            drop_frame,
+        }
+    }
+
+    /// Time spent streaming, with whole second resolution
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.hour as u64 * 3600 + self.minute as u64 * 60 + self.second as u64)
+    }
+}
+
+impl Display for StreamingTimeCode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let separator = if self.drop_frame { ';' } else { ':' };
+
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hour, self.minute, self.second, separator, self.frame
+        )
+    }
+}
+
+/// Start or stop streaming (`StrR`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetStreaming {
+    enabled: bool,
+}
+
+impl SetStreaming {
+    pub fn new(enabled: bool) -> Self {
+        SetStreaming { enabled }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.enabled as u8);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetStreaming {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Enabled: {}", self.enabled)
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct StreamingServiceMask: u8 {
+        const NAME = 0x01;
+        const URL = 0x02;
+        const KEY = 0x04;
+        const VIDEO_BITRATES = 0x08;
+    }
+}
+
+/// Change the streaming service configuration (`CRSS`). The name is cut to 64 bytes, the URL
+/// and key to 512 bytes.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetStreamingService {
+    mask: StreamingServiceMask,
+    name: String,
+    url: String,
+    key: String,
+    video_bitrates: [u32; 2],
+}
+
+impl SetStreamingService {
+    pub fn new() -> Self {
+        SetStreamingService::default()
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.mask |= StreamingServiceMask::NAME;
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn url(mut self, url: &str) -> Self {
+        self.mask |= StreamingServiceMask::URL;
+        self.url = url.to_string();
+        self
+    }
+
+    pub fn key(mut self, key: &str) -> Self {
+        self.mask |= StreamingServiceMask::KEY;
+        self.key = key.to_string();
+        self
+    }
+
+    /// Low and high video bitrates in bits per second
+    pub fn video_bitrates(mut self, low: u32, high: u32) -> Self {
+        self.mask |= StreamingServiceMask::VIDEO_BITRATES;
+        self.video_bitrates = [low, high];
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        put_str(data, &self.name, 64);
+        put_str(data, &self.url, 512);
+        put_str(data, &self.key, 512);
+        data.put_bytes(0, 3); // Padding
+        data.put_u32(self.video_bitrates[0]);
+        data.put_u32(self.video_bitrates[1]);
+    }
+}
+
+impl Display for SetStreamingService {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Service: {} URL: {} Video bitrates: {}/{}",
+            self.name, self.url, self.video_bitrates[0], self.video_bitrates[1]
+        )
+    }
+}
+
+/// Change the audio bitrates of the streaming encoder (`CSAB`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetStreamingAudioBitrates {
+    low: u32,
+    high: u32,
+}
+
+impl SetStreamingAudioBitrates {
+    /// Low and high audio bitrates in bits per second
+    pub fn new(low: u32, high: u32) -> Self {
+        SetStreamingAudioBitrates { low, high }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u32(self.low);
+        data.put_u32(self.high);
+    }
+}
+
+impl Display for SetStreamingAudioBitrates {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Audio bitrates: {}/{}", self.low, self.high)
+    }
+}
+
+/// Change the credentials used to authenticate with the streaming service (`CAth`), the
+/// username and password are cut to 64 bytes each
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetStreamingAuthentication {
+    username: String,
+    password: String,
+}
+
+impl SetStreamingAuthentication {
+    pub fn new(username: &str, password: &str) -> Self {
+        SetStreamingAuthentication {
+            username: username.to_string(),
+            password: password.to_string(),
+        }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        put_str(data, &self.username, 64);
+        put_str(data, &self.password, 64);
+    }
+}
+
+impl Display for SetStreamingAuthentication {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Username: {}", self.username)
+    }
+}
+
+/// Enable or disable the low latency mode of the streaming encoder (`CSLw`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetStreamingLowLatency {
+    enabled: bool,
+}
+
+impl SetStreamingLowLatency {
+    pub fn new(enabled: bool) -> Self {
+        SetStreamingLowLatency { enabled }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.enabled as u8);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for SetStreamingLowLatency {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Enabled: {}", self.enabled)
+    }
+}
@@ -0,0 +1,248 @@
+use core::fmt::Display;
+
+use bitflags::bitflags;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+/// Source id of the first SuperSource, the second one is 6001 and so on
+pub const SUPERSOURCE_BASE: u16 = 6000;
+
+/// Box of a SuperSource (`SSBP`), as laid out by firmware 8.0 and later. Positions are in
+/// 1/100 of the screen from the center, the size in 1/1000 and the crop in 1/1000 of the
+/// box.
+#[derive(Debug, Clone)]
+pub struct SuperSourceBox {
+    supersource: u8,
+    index: u8,
+    enabled: bool,
+    source: u16,
+    position_x: i16,
+    position_y: i16,
+    size: u16,
+    cropped: bool,
+    crop_top: u16,
+    crop_bottom: u16,
+    crop_left: u16,
+    crop_right: u16,
+}
+
+impl SuperSourceBox {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let supersource = data.get_u8();
+        let index = data.get_u8();
+        let enabled = data.get_u8() > 0;
+        data.advance(1); // Padding
+        let source = data.get_u16();
+        let position_x = data.get_i16();
+        let position_y = data.get_i16();
+        let size = data.get_u16();
+        let cropped = data.get_u8() > 0;
+        data.advance(1); // Padding
+        let crop_top = data.get_u16();
+        let crop_bottom = data.get_u16();
+        let crop_left = data.get_u16();
+        let crop_right = data.get_u16();
+
+        SuperSourceBox {
+            supersource,
+            index,
+            enabled,
+            source,
+            position_x,
+            position_y,
+            size,
+            cropped,
+            crop_top,
+            crop_bottom,
+            crop_left,
+            crop_right,
+        }
+    }
+
+    pub fn supersource(&self) -> u8 {
+        self.supersource
+    }
+
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn source(&self) -> u16 {
+        self.source
+    }
+
+    pub fn position(&self) -> (i16, i16) {
+        (self.position_x, self.position_y)
+    }
+
+    pub fn size(&self) -> u16 {
+        self.size
+    }
+
+    pub fn cropped(&self) -> bool {
+        self.cropped
+    }
+
+    /// Crop of the top, bottom, left and right edge
+    pub fn crop(&self) -> (u16, u16, u16, u16) {
+        (
+            self.crop_top,
+            self.crop_bottom,
+            self.crop_left,
+            self.crop_right,
+        )
+    }
+}
+
+impl Display for SuperSourceBox {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "SuperSource: {} Box: {} Enabled: {} Source: {} Position: {}x{} Size: {}",
+            self.supersource,
+            self.index,
+            self.enabled,
+            self.source,
+            self.position_x,
+            self.position_y,
+            self.size
+        )
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct SuperSourceBoxMask: u16 {
+        const ENABLED = 0x0001;
+        const SOURCE = 0x0002;
+        const POSITION_X = 0x0004;
+        const POSITION_Y = 0x0008;
+        const SIZE = 0x0010;
+        const CROPPED = 0x0020;
+        const CROP_TOP = 0x0040;
+        const CROP_BOTTOM = 0x0080;
+        const CROP_LEFT = 0x0100;
+        const CROP_RIGHT = 0x0200;
+    }
+}
+
+/// Change a box of a SuperSource (`CSBP`), in the units of [`SuperSourceBox`]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetSuperSourceBox {
+    mask: SuperSourceBoxMask,
+    supersource: u8,
+    index: u8,
+    enabled: bool,
+    source: u16,
+    position_x: i16,
+    position_y: i16,
+    size: u16,
+    cropped: bool,
+    crop_top: u16,
+    crop_bottom: u16,
+    crop_left: u16,
+    crop_right: u16,
+}
+
+impl SetSuperSourceBox {
+    pub fn new(supersource: u8, index: u8) -> Self {
+        SetSuperSourceBox {
+            mask: SuperSourceBoxMask::empty(),
+            supersource,
+            index,
+            enabled: false,
+            source: 0,
+            position_x: 0,
+            position_y: 0,
+            size: 0,
+            cropped: false,
+            crop_top: 0,
+            crop_bottom: 0,
+            crop_left: 0,
+            crop_right: 0,
+        }
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.mask |= SuperSourceBoxMask::ENABLED;
+        self.enabled = enabled;
+        self
+    }
+
+    pub fn source(mut self, source: u16) -> Self {
+        self.mask |= SuperSourceBoxMask::SOURCE;
+        self.source = source;
+        self
+    }
+
+    pub fn position(mut self, x: i16, y: i16) -> Self {
+        self.mask |= SuperSourceBoxMask::POSITION_X | SuperSourceBoxMask::POSITION_Y;
+        self.position_x = x;
+        self.position_y = y;
+        self
+    }
+
+    pub fn size(mut self, size: u16) -> Self {
+        self.mask |= SuperSourceBoxMask::SIZE;
+        self.size = size;
+        self
+    }
+
+    pub fn cropped(mut self, cropped: bool) -> Self {
+        self.mask |= SuperSourceBoxMask::CROPPED;
+        self.cropped = cropped;
+        self
+    }
+
+    /// Crop of the top, bottom, left and right edge
+    pub fn crop(mut self, top: u16, bottom: u16, left: u16, right: u16) -> Self {
+        self.mask |= SuperSourceBoxMask::CROP_TOP
+            | SuperSourceBoxMask::CROP_BOTTOM
+            | SuperSourceBoxMask::CROP_LEFT
+            | SuperSourceBoxMask::CROP_RIGHT;
+        self.crop_top = top;
+        self.crop_bottom = bottom;
+        self.crop_left = left;
+        self.crop_right = right;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(self.mask.bits());
+        data.put_u8(self.supersource);
+        data.put_u8(self.index);
+        data.put_u8(self.enabled as u8);
+        data.put_u8(0); // Padding
+        data.put_u16(self.source);
+        data.put_i16(self.position_x);
+        data.put_i16(self.position_y);
+        data.put_u16(self.size);
+        data.put_u8(self.cropped as u8);
+        data.put_u8(0); // Padding
+        data.put_u16(self.crop_top);
+        data.put_u16(self.crop_bottom);
+        data.put_u16(self.crop_left);
+        data.put_u16(self.crop_right);
+    }
+}
+
+impl Display for SetSuperSourceBox {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "SuperSource: {} Box: {} Enabled: {} Source: {} Position: {}x{} Size: {}",
+            self.supersource,
+            self.index,
+            self.enabled,
+            self.source,
+            self.position_x,
+            self.position_y,
+            self.size
+        )
+    }
+}
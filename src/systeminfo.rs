@@ -1,21 +1,198 @@
+use alloc::{boxed::Box, collections::BTreeMap, format, string::String, vec::Vec};
 use core::fmt;
-use std::collections::HashMap;
 
-use bytes::{Buf, Bytes};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 
-use crate::source::Source;
+#[cfg(feature = "media-pool")]
+use crate::mediapool::{MediaClip, MediaStill};
+#[cfg(feature = "recording")]
+use crate::recording::{RecordingDisk, RecordingSettings, RecordingStatus, RecordingTimeCode};
+#[cfg(feature = "streaming")]
+use crate::streaming::StreamingService;
+#[cfg(feature = "audio")]
+use crate::{
+    audio::{AudioMixerInput, AudioMixerMaster, AudioMonitor},
+    fairlight::{
+        AudioSourceId, FairlightInputProperties, FairlightMasterProperties,
+        FairlightSourceProperties,
+    },
+};
+use crate::{
+    capabilities::Capabilities,
+    color::HslColor,
+    command::{Command, Time, TransitionPosition, UnknownBytes},
+    keyer::{
+        DownstreamKeyerSources, DownstreamKeyerState, KeyerBaseProperties, KeyerKeyframe, Keyframe,
+    },
+    macros::{MacroProperties, MacroRecordStatus, MacroRunStatus},
+    mediaplayer::{MediaPlayerSource, MediaPlayerStatus},
+    multiview::MultiViewVideoMode,
+    source::Source,
+    supersource::SuperSourceBox,
+    tally::{OnAirTally, TallyConfig, TallySources},
+    transition::{FadeToBlackState, TransitionStyleSelection},
+};
 
 #[derive(Debug, Default)]
 pub struct SystemInfo {
     product: Box<str>,
     version: Version,
     topology: Topology,
+    me_configs: BTreeMap<u8, MeConfig>,
+    supersource_configs: BTreeMap<u8, SuperSourceConfig>,
+    supersource_boxes: BTreeMap<(u8, u8), SuperSourceBox>,
+    audio_mixer_config: Option<AudioMixerConfig>,
+    #[cfg(feature = "audio")]
+    audio_monitor: Option<AudioMonitor>,
+    #[cfg(feature = "audio")]
+    audio_mixer_inputs: BTreeMap<u16, AudioMixerInput>,
+    #[cfg(feature = "audio")]
+    audio_mixer_master: Option<AudioMixerMaster>,
+    fairlight_mixer_config: Option<FairlightMixerConfig>,
+    fairlight_channel_config: Option<FairlightChannelConfig>,
+    #[cfg(feature = "audio")]
+    fairlight_inputs: BTreeMap<u16, FairlightInputProperties>,
+    #[cfg(feature = "audio")]
+    fairlight_sources: BTreeMap<AudioSourceId, FairlightSourceProperties>,
+    #[cfg(feature = "audio")]
+    fairlight_master: Option<FairlightMasterProperties>,
+    macro_pool_config: MacroPoolConfig,
+    video_mode: Option<VideoMode>,
+    time: Option<Time>,
+    multiview_video_modes: BTreeMap<u8, MultiViewVideoMode>,
+    #[cfg(feature = "media-pool")]
+    media_stills: BTreeMap<u16, MediaStill>,
+    #[cfg(feature = "media-pool")]
+    media_clips: BTreeMap<u8, MediaClip>,
+    program_inputs: BTreeMap<u8, u16>,
+    preview_inputs: BTreeMap<u8, u16>,
+    transition_positions: BTreeMap<u8, TransitionPosition>,
+    aux_sources: BTreeMap<u8, u16>,
+    transition_styles: BTreeMap<u8, TransitionStyleSelection>,
+    keyers: BTreeMap<(u8, u8), KeyerBaseProperties>,
+    keyers_on_air: BTreeMap<(u8, u8), bool>,
+    keyer_keyframes: BTreeMap<(u8, u8, u8), KeyerKeyframe>,
+    downstream_keyer_sources: BTreeMap<u8, DownstreamKeyerSources>,
+    downstream_keyer_states: BTreeMap<u8, DownstreamKeyerState>,
+    fade_to_black_states: BTreeMap<u8, FadeToBlackState>,
+    media_player_sources: BTreeMap<u8, MediaPlayerSource>,
+    media_player_statuses: BTreeMap<u8, MediaPlayerStatus>,
+    macros: BTreeMap<u16, MacroProperties>,
+    macro_run_status: Option<MacroRunStatus>,
+    macro_record_status: Option<MacroRecordStatus>,
 
-    sources: HashMap<u16, Source>,
+    sources: BTreeMap<u16, Source>,
+    color_generators: BTreeMap<u8, HslColor>,
+    tally_config: TallyConfig,
+    tally_sources: Option<TallySources>,
+
+    #[cfg(feature = "recording")]
+    recording_status: Option<RecordingStatus>,
+    #[cfg(feature = "recording")]
+    recording_timecode: Option<RecordingTimeCode>,
+    #[cfg(feature = "recording")]
+    recording_disks: BTreeMap<u32, RecordingDisk>,
+    #[cfg(feature = "recording")]
+    recording_settings: Option<RecordingSettings>,
+
+    #[cfg(feature = "streaming")]
+    streaming_service: Option<StreamingService>,
 }
 
-#[allow(dead_code)]
 impl SystemInfo {
+    /// Update the stored state from a command received from the switcher, returns the start or
+    /// end of a transition when the command is a transition position crossing 0
+    pub fn update(&mut self, command: &Command) -> Option<TransitionEdge> {
+        match command {
+            Command::Product(product) => self.set_product(product),
+            Command::Version(version) => self.set_version(*version),
+            Command::Topology(topology) => self.set_topology(topology.clone()),
+            Command::MeConfig(config) => self.set_me_config(config.clone()),
+            Command::SuperSourceConfig(config) => self.set_supersource_config(config.clone()),
+            Command::AudioMixerConfig(config) => self.set_audio_mixer_config(config.clone()),
+            #[cfg(feature = "audio")]
+            Command::AudioMonitor(monitor) => self.set_audio_monitor(monitor.clone()),
+            #[cfg(feature = "audio")]
+            Command::AudioMixerInput(input) => self.set_audio_mixer_input(input.clone()),
+            #[cfg(feature = "audio")]
+            Command::AudioMixerMaster(master) => self.set_audio_mixer_master(master.clone()),
+            Command::MacroPoolConfig(config) => self.set_macro_pool_config(config.clone()),
+            Command::FairlightMixerConfig(config) => {
+                self.set_fairlight_mixer_config(config.clone())
+            }
+            Command::FairlightChannelConfig(config) => {
+                self.set_fairlight_channel_config(config.clone())
+            }
+            #[cfg(feature = "audio")]
+            Command::FairlightInputProperties(properties) => {
+                self.set_fairlight_input(properties.clone())
+            }
+            #[cfg(feature = "audio")]
+            Command::FairlightSourceProperties(properties) => {
+                self.set_fairlight_source(properties.clone())
+            }
+            #[cfg(feature = "audio")]
+            Command::FairlightMasterProperties(properties) => {
+                self.set_fairlight_master(properties.clone())
+            }
+            Command::VideoMode(mode) => self.set_video_mode(*mode),
+            Command::Time(time) => self.set_time(*time),
+            Command::MultiViewVideoMode(mode) => self.set_multiview_video_mode(mode.clone()),
+            #[cfg(feature = "media-pool")]
+            Command::MediaStill(still) => self.set_media_still(still.clone()),
+            #[cfg(feature = "media-pool")]
+            Command::MediaClip(clip) => self.set_media_clip(clip.clone()),
+            Command::MediaPlayerSource(source) => self.set_media_player_source(source.clone()),
+            Command::MediaPlayerStatus(status) => self.set_media_player_status(status.clone()),
+            Command::MacroProperties(properties) => self.set_macro(properties.clone()),
+            Command::MacroRunStatus(status) => self.set_macro_run_status(status.clone()),
+            Command::MacroRecordStatus(status) => self.set_macro_record_status(status.clone()),
+            Command::TransitionStyleSelection(selection) => {
+                self.set_transition_style(selection.clone())
+            }
+            Command::Source(source) => self.set_source(source.clone()),
+            Command::KeyerBaseProperties(properties) => self.set_keyer(properties.clone()),
+            Command::KeyerOnAir(on_air) => {
+                self.set_keyer_on_air(on_air.me(), on_air.keyer(), on_air.on_air())
+            }
+            Command::KeyerKeyframe(keyframe) => self.set_keyer_keyframe(keyframe.clone()),
+            Command::DownstreamKeyerSources(sources) => {
+                self.set_downstream_keyer_sources(sources.clone())
+            }
+            Command::DownstreamKeyerState(state) => self.set_downstream_keyer_state(state.clone()),
+            Command::FadeToBlackState(state) => self.set_fade_to_black_state(state.clone()),
+            Command::SuperSourceBox(ssbox) => self.set_supersource_box(ssbox.clone()),
+            Command::ColorGenerator(generator) => {
+                self.set_color_generator(generator.index(), generator.color())
+            }
+            Command::ProgramInput(selection) => {
+                self.set_program_input(selection.me(), selection.source_id())
+            }
+            Command::PreviewInput(selection) => {
+                self.set_preview_input(selection.me(), selection.source_id())
+            }
+            Command::TransitionPosition(position) => {
+                return self.set_transition_position(position.clone());
+            }
+            Command::AuxSource(source) => self.set_aux_source(source.aux(), source.source_id()),
+            Command::TallyConfig(config) => self.set_tally_config(config.clone()),
+            Command::TallySources(tally) => self.set_tally_sources(tally.clone()),
+            #[cfg(feature = "recording")]
+            Command::RecordingStatus(status) => self.set_recording_status(status.clone()),
+            #[cfg(feature = "recording")]
+            Command::RecordingTimeCode(timecode) => self.set_recording_timecode(timecode.clone()),
+            #[cfg(feature = "recording")]
+            Command::RecordingDisk(disk) => self.set_recording_disk(disk.clone()),
+            #[cfg(feature = "recording")]
+            Command::RecordingSettings(settings) => self.set_recording_settings(settings.clone()),
+            #[cfg(feature = "streaming")]
+            Command::StreamingService(service) => self.set_streaming_service(service.clone()),
+            _ => {}
+        }
+
+        None
+    }
+
     pub fn set_product(&mut self, description: &str) {
         self.product = description.into();
     }
@@ -40,6 +217,399 @@ impl SystemInfo {
         &self.topology
     }
 
+    /// What the switcher supports, complete once the initial state has been received
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::from(self)
+    }
+
+    pub fn set_me_config(&mut self, config: MeConfig) {
+        self.me_configs.insert(config.me(), config);
+    }
+
+    pub fn me_config(&self, me: u8) -> Option<&MeConfig> {
+        self.me_configs.get(&me)
+    }
+
+    pub fn set_supersource_config(&mut self, config: SuperSourceConfig) {
+        self.supersource_configs
+            .insert(config.supersource(), config);
+    }
+
+    pub fn supersource_config(&self, supersource: u8) -> Option<&SuperSourceConfig> {
+        self.supersource_configs.get(&supersource)
+    }
+
+    pub fn set_supersource_box(&mut self, ssbox: SuperSourceBox) {
+        self.supersource_boxes
+            .insert((ssbox.supersource(), ssbox.index()), ssbox);
+    }
+
+    /// Source and layout of a box of a SuperSource
+    pub fn supersource_box(&self, supersource: u8, index: u8) -> Option<&SuperSourceBox> {
+        self.supersource_boxes.get(&(supersource, index))
+    }
+
+    pub fn supersource_boxes(&self) -> impl Iterator<Item = &SuperSourceBox> {
+        self.supersource_boxes.values()
+    }
+
+    pub fn set_audio_mixer_config(&mut self, config: AudioMixerConfig) {
+        self.audio_mixer_config = Some(config);
+    }
+
+    /// Classic audio mixer capabilities, None on Fairlight models
+    pub fn audio_mixer_config(&self) -> Option<&AudioMixerConfig> {
+        self.audio_mixer_config.as_ref()
+    }
+
+    #[cfg(feature = "audio")]
+    pub fn set_audio_monitor(&mut self, monitor: AudioMonitor) {
+        self.audio_monitor = Some(monitor);
+    }
+
+    #[cfg(feature = "audio")]
+    /// Monitor output of the classic audio mixer, None on Fairlight models
+    pub fn audio_monitor(&self) -> Option<&AudioMonitor> {
+        self.audio_monitor.as_ref()
+    }
+
+    #[cfg(feature = "audio")]
+    pub fn set_audio_mixer_input(&mut self, input: AudioMixerInput) {
+        self.audio_mixer_inputs.insert(input.input(), input);
+    }
+
+    #[cfg(feature = "audio")]
+    /// Fader of an input of the classic audio mixer
+    pub fn audio_mixer_input(&self, input: u16) -> Option<&AudioMixerInput> {
+        self.audio_mixer_inputs.get(&input)
+    }
+
+    #[cfg(feature = "audio")]
+    /// Faders of every input of the classic audio mixer, ordered by input
+    pub fn audio_mixer_inputs(&self) -> impl Iterator<Item = &AudioMixerInput> {
+        self.audio_mixer_inputs.values()
+    }
+
+    #[cfg(feature = "audio")]
+    pub fn set_audio_mixer_master(&mut self, master: AudioMixerMaster) {
+        self.audio_mixer_master = Some(master);
+    }
+
+    #[cfg(feature = "audio")]
+    /// Master fader of the classic audio mixer, None on Fairlight models
+    pub fn audio_mixer_master(&self) -> Option<&AudioMixerMaster> {
+        self.audio_mixer_master.as_ref()
+    }
+
+    pub fn set_fairlight_mixer_config(&mut self, config: FairlightMixerConfig) {
+        self.fairlight_mixer_config = Some(config);
+    }
+
+    /// Fairlight mixer channel counts, None on classic audio models
+    pub fn fairlight_mixer_config(&self) -> Option<&FairlightMixerConfig> {
+        self.fairlight_mixer_config.as_ref()
+    }
+
+    pub fn set_fairlight_channel_config(&mut self, config: FairlightChannelConfig) {
+        self.fairlight_channel_config = Some(config);
+    }
+
+    pub fn fairlight_channel_config(&self) -> Option<&FairlightChannelConfig> {
+        self.fairlight_channel_config.as_ref()
+    }
+
+    #[cfg(feature = "audio")]
+    pub fn set_fairlight_input(&mut self, properties: FairlightInputProperties) {
+        self.fairlight_inputs.insert(properties.input(), properties);
+    }
+
+    #[cfg(feature = "audio")]
+    pub fn fairlight_input(&self, input: u16) -> Option<&FairlightInputProperties> {
+        self.fairlight_inputs.get(&input)
+    }
+
+    #[cfg(feature = "audio")]
+    /// Properties of every Fairlight input, ordered by input
+    pub fn fairlight_inputs(&self) -> impl Iterator<Item = &FairlightInputProperties> {
+        self.fairlight_inputs.values()
+    }
+
+    #[cfg(feature = "audio")]
+    pub fn set_fairlight_source(&mut self, properties: FairlightSourceProperties) {
+        self.fairlight_sources
+            .insert(properties.source(), properties);
+    }
+
+    #[cfg(feature = "audio")]
+    pub fn fairlight_source(&self, source: AudioSourceId) -> Option<&FairlightSourceProperties> {
+        self.fairlight_sources.get(&source)
+    }
+
+    #[cfg(feature = "audio")]
+    /// Properties of every Fairlight source, ordered by input and source
+    pub fn fairlight_sources(&self) -> impl Iterator<Item = &FairlightSourceProperties> {
+        self.fairlight_sources.values()
+    }
+
+    #[cfg(feature = "audio")]
+    pub fn set_fairlight_master(&mut self, properties: FairlightMasterProperties) {
+        self.fairlight_master = Some(properties);
+    }
+
+    #[cfg(feature = "audio")]
+    /// Master fader of the Fairlight mixer, None on classic audio models
+    pub fn fairlight_master(&self) -> Option<&FairlightMasterProperties> {
+        self.fairlight_master.as_ref()
+    }
+
+    pub fn set_macro_pool_config(&mut self, config: MacroPoolConfig) {
+        self.macro_pool_config = config;
+    }
+
+    pub fn macro_pool_config(&self) -> &MacroPoolConfig {
+        &self.macro_pool_config
+    }
+
+    pub fn set_video_mode(&mut self, mode: VideoMode) {
+        self.video_mode = Some(mode);
+    }
+
+    pub fn video_mode(&self) -> Option<VideoMode> {
+        self.video_mode
+    }
+
+    pub fn set_time(&mut self, time: Time) {
+        self.time = Some(time);
+    }
+
+    /// Latest time code reported by the switcher, sent every frame or on request
+    pub fn time(&self) -> Option<Time> {
+        self.time
+    }
+
+    pub fn set_multiview_video_mode(&mut self, mode: MultiViewVideoMode) {
+        self.multiview_video_modes.insert(mode.multiview(), mode);
+    }
+
+    pub fn multiview_video_mode(&self, multiview: u8) -> Option<&MultiViewVideoMode> {
+        self.multiview_video_modes.get(&multiview)
+    }
+
+    #[cfg(feature = "media-pool")]
+    pub fn set_media_still(&mut self, still: MediaStill) {
+        self.media_stills.insert(still.index(), still);
+    }
+
+    #[cfg(feature = "media-pool")]
+    pub fn media_still(&self, index: u16) -> Option<&MediaStill> {
+        self.media_stills.get(&index)
+    }
+
+    #[cfg(feature = "media-pool")]
+    pub fn set_media_clip(&mut self, clip: MediaClip) {
+        self.media_clips.insert(clip.index(), clip);
+    }
+
+    #[cfg(feature = "media-pool")]
+    pub fn media_clip(&self, index: u8) -> Option<&MediaClip> {
+        self.media_clips.get(&index)
+    }
+
+    pub fn set_media_player_source(&mut self, source: MediaPlayerSource) {
+        self.media_player_sources.insert(source.player(), source);
+    }
+
+    pub fn media_player_source(&self, player: u8) -> Option<&MediaPlayerSource> {
+        self.media_player_sources.get(&player)
+    }
+
+    pub fn set_media_player_status(&mut self, status: MediaPlayerStatus) {
+        self.media_player_statuses.insert(status.player(), status);
+    }
+
+    pub fn media_player_status(&self, player: u8) -> Option<&MediaPlayerStatus> {
+        self.media_player_statuses.get(&player)
+    }
+
+    pub fn set_macro(&mut self, properties: MacroProperties) {
+        self.macros.insert(properties.index(), properties);
+    }
+
+    pub fn macro_properties(&self, index: u16) -> Option<&MacroProperties> {
+        self.macros.get(&index)
+    }
+
+    /// Properties of every macro slot, in no particular order
+    pub fn macros(&self) -> impl Iterator<Item = &MacroProperties> {
+        self.macros.values()
+    }
+
+    pub fn set_macro_run_status(&mut self, status: MacroRunStatus) {
+        self.macro_run_status = Some(status);
+    }
+
+    pub fn macro_run_status(&self) -> Option<&MacroRunStatus> {
+        self.macro_run_status.as_ref()
+    }
+
+    pub fn set_macro_record_status(&mut self, status: MacroRecordStatus) {
+        self.macro_record_status = Some(status);
+    }
+
+    pub fn macro_record_status(&self) -> Option<&MacroRecordStatus> {
+        self.macro_record_status.as_ref()
+    }
+
+    pub fn set_transition_style(&mut self, selection: TransitionStyleSelection) {
+        self.transition_styles.insert(selection.me(), selection);
+    }
+
+    /// Style and layers of the next transition of the mix effect block
+    pub fn transition_style(&self, me: u8) -> Option<&TransitionStyleSelection> {
+        self.transition_styles.get(&me)
+    }
+
+    pub fn set_keyer(&mut self, properties: KeyerBaseProperties) {
+        self.keyers
+            .insert((properties.me(), properties.keyer()), properties);
+    }
+
+    /// Type, sources and mask of an upstream keyer of the mix effect block
+    pub fn keyer(&self, me: u8, keyer: u8) -> Option<&KeyerBaseProperties> {
+        self.keyers.get(&(me, keyer))
+    }
+
+    /// Base properties of every upstream keyer, ordered by mix effect block and keyer
+    pub fn keyers(&self) -> impl Iterator<Item = &KeyerBaseProperties> {
+        self.keyers.values()
+    }
+
+    pub fn set_keyer_on_air(&mut self, me: u8, keyer: u8, on_air: bool) {
+        self.keyers_on_air.insert((me, keyer), on_air);
+    }
+
+    /// Whether an upstream keyer of the mix effect block is on air
+    pub fn keyer_on_air(&self, me: u8, keyer: u8) -> Option<bool> {
+        self.keyers_on_air.get(&(me, keyer)).copied()
+    }
+
+    pub fn set_keyer_keyframe(&mut self, keyframe: KeyerKeyframe) {
+        self.keyer_keyframes.insert(
+            (keyframe.me(), keyframe.keyer(), keyframe.keyframe().into()),
+            keyframe,
+        );
+    }
+
+    /// Stored keyframe of the flying key of an upstream keyer
+    pub fn keyer_keyframe(&self, me: u8, keyer: u8, keyframe: Keyframe) -> Option<&KeyerKeyframe> {
+        self.keyer_keyframes.get(&(me, keyer, keyframe.into()))
+    }
+
+    pub fn set_downstream_keyer_sources(&mut self, sources: DownstreamKeyerSources) {
+        self.downstream_keyer_sources
+            .insert(sources.keyer(), sources);
+    }
+
+    /// Fill and cut source of a downstream keyer
+    pub fn downstream_keyer_sources(&self, keyer: u8) -> Option<&DownstreamKeyerSources> {
+        self.downstream_keyer_sources.get(&keyer)
+    }
+
+    pub fn set_downstream_keyer_state(&mut self, state: DownstreamKeyerState) {
+        self.downstream_keyer_states.insert(state.keyer(), state);
+    }
+
+    /// On air and transition state of a downstream keyer
+    pub fn downstream_keyer_state(&self, keyer: u8) -> Option<&DownstreamKeyerState> {
+        self.downstream_keyer_states.get(&keyer)
+    }
+
+    /// State of every downstream keyer, ordered by keyer
+    pub fn downstream_keyer_states(&self) -> impl Iterator<Item = &DownstreamKeyerState> {
+        self.downstream_keyer_states.values()
+    }
+
+    pub fn set_fade_to_black_state(&mut self, state: FadeToBlackState) {
+        self.fade_to_black_states.insert(state.me(), state);
+    }
+
+    /// Fade to black state of the mix effect block
+    pub fn fade_to_black_state(&self, me: u8) -> Option<&FadeToBlackState> {
+        self.fade_to_black_states.get(&me)
+    }
+
+    /// Sources actually on program and preview, see [`OnAirTally`]
+    pub fn on_air_tally(&self) -> OnAirTally {
+        OnAirTally::compute(self)
+    }
+
+    pub fn set_program_input(&mut self, me: u8, source: u16) {
+        self.program_inputs.insert(me, source);
+    }
+
+    /// Source on program of the mix effect block
+    pub fn program_input(&self, me: u8) -> Option<u16> {
+        self.program_inputs.get(&me).copied()
+    }
+
+    pub fn set_preview_input(&mut self, me: u8, source: u16) {
+        self.preview_inputs.insert(me, source);
+    }
+
+    /// Source on preview of the mix effect block
+    pub fn preview_input(&self, me: u8) -> Option<u16> {
+        self.preview_inputs.get(&me).copied()
+    }
+
+    /// Store the transition position, returns the start or end of a transition when the
+    /// position leaves or returns to 0
+    pub fn set_transition_position(
+        &mut self,
+        position: TransitionPosition,
+    ) -> Option<TransitionEdge> {
+        let me = position.me();
+        let running = position.is_running();
+        let was_running = self
+            .transition_positions
+            .insert(me, position)
+            .is_some_and(|previous| previous.is_running());
+
+        match (was_running, running) {
+            (false, true) => Some(TransitionEdge::Started(me)),
+            (true, false) => Some(TransitionEdge::Completed(me)),
+            _ => None,
+        }
+    }
+
+    /// Progress of the transition of the mix effect block
+    pub fn transition_position(&self, me: u8) -> Option<&TransitionPosition> {
+        self.transition_positions.get(&me)
+    }
+
+    pub fn set_aux_source(&mut self, aux: u8, source: u16) {
+        self.aux_sources.insert(aux, source);
+    }
+
+    /// Source routed to the aux output
+    pub fn aux_source(&self, aux: u8) -> Option<u16> {
+        self.aux_sources.get(&aux).copied()
+    }
+
+    pub fn set_color_generator(&mut self, index: u8, color: HslColor) {
+        self.color_generators.insert(index, color);
+    }
+
+    pub fn color_generator(&self, index: u8) -> Option<HslColor> {
+        self.color_generators.get(&index).copied()
+    }
+
+    /// Index and color of every color generator, ordered by index
+    pub fn color_generators(&self) -> impl Iterator<Item = (u8, HslColor)> + '_ {
+        self.color_generators
+            .iter()
+            .map(|(&index, &color)| (index, color))
+    }
+
     pub fn set_source(&mut self, source: Source) {
         self.sources.insert(source.id(), source);
     }
@@ -47,21 +617,152 @@ impl SystemInfo {
     pub fn source(&self, id: u16) -> Option<&Source> {
         self.sources.get(&id)
     }
+
+    /// Inputs and internal sources of the switcher, ordered by id
+    pub fn sources(&self) -> impl Iterator<Item = &Source> {
+        self.sources.values()
+    }
+
+    pub fn set_tally_config(&mut self, config: TallyConfig) {
+        self.tally_config = config;
+    }
+
+    pub fn tally_config(&self) -> &TallyConfig {
+        &self.tally_config
+    }
+
+    pub fn set_tally_sources(&mut self, tally: TallySources) {
+        self.tally_sources = Some(tally);
+    }
+
+    /// Program and preview tally of every source
+    pub fn tally_sources(&self) -> Option<&TallySources> {
+        self.tally_sources.as_ref()
+    }
+
+    #[cfg(feature = "recording")]
+    pub fn set_recording_status(&mut self, status: RecordingStatus) {
+        self.recording_status = Some(status);
+    }
+
+    #[cfg(feature = "recording")]
+    pub fn recording_status(&self) -> Option<&RecordingStatus> {
+        self.recording_status.as_ref()
+    }
+
+    #[cfg(feature = "recording")]
+    pub fn set_recording_timecode(&mut self, timecode: RecordingTimeCode) {
+        self.recording_timecode = Some(timecode);
+    }
+
+    #[cfg(feature = "recording")]
+    pub fn recording_timecode(&self) -> Option<&RecordingTimeCode> {
+        self.recording_timecode.as_ref()
+    }
+
+    #[cfg(feature = "recording")]
+    /// Store the disk status, removed disks are dropped from the state
+    pub fn set_recording_disk(&mut self, disk: RecordingDisk) {
+        if disk.is_removed() {
+            self.recording_disks.remove(&disk.id());
+        } else {
+            self.recording_disks.insert(disk.id(), disk);
+        }
+    }
+
+    #[cfg(feature = "recording")]
+    pub fn recording_disk(&self, id: u32) -> Option<&RecordingDisk> {
+        self.recording_disks.get(&id)
+    }
+
+    #[cfg(feature = "recording")]
+    pub fn recording_disks(&self) -> impl Iterator<Item = &RecordingDisk> {
+        self.recording_disks.values()
+    }
+
+    #[cfg(feature = "recording")]
+    pub fn set_recording_settings(&mut self, settings: RecordingSettings) {
+        self.recording_settings = Some(settings);
+    }
+
+    #[cfg(feature = "recording")]
+    pub fn recording_settings(&self) -> Option<&RecordingSettings> {
+        self.recording_settings.as_ref()
+    }
+
+    #[cfg(feature = "streaming")]
+    pub fn set_streaming_service(&mut self, service: StreamingService) {
+        self.streaming_service = Some(service);
+    }
+
+    /// Streaming service settings, None on models that can't stream
+    #[cfg(feature = "streaming")]
+    pub fn streaming_service(&self) -> Option<&StreamingService> {
+        self.streaming_service.as_ref()
+    }
 }
 
-#[derive(Debug, Default)]
+/// Start or end of the transition of a mix effect block, see [`SystemInfo::update`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionEdge {
+    /// The transition position of the mix effect block left 0
+    Started(u8),
+    /// The transition position of the mix effect block returned to 0
+    Completed(u8),
+}
+
+impl TransitionEdge {
+    pub fn me(&self) -> u8 {
+        match self {
+            TransitionEdge::Started(me) | TransitionEdge::Completed(me) => *me,
+        }
+    }
+}
+
+impl fmt::Display for TransitionEdge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransitionEdge::Started(me) => write!(f, "Transition started on M/E {me}"),
+            TransitionEdge::Completed(me) => write!(f, "Transition completed on M/E {me}"),
+        }
+    }
+}
+
+/// Protocol version spoken by the switcher (`_ver`), it changes with the firmware but is not the
+/// firmware version itself
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Version {
     major: u16,
     minor: u16,
 }
 
 impl Version {
+    /// Firmware 8.1.1, which added streaming and recording for the ATEM Mini Pro
+    pub const V8_1_1: Version = Version::new(2, 30);
+
+    pub const fn new(major: u16, minor: u16) -> Self {
+        Version { major, minor }
+    }
+
+    /// Whether the version has been received from the switcher
+    pub fn is_known(&self) -> bool {
+        *self != Version::default()
+    }
+
     pub fn parse(data: &mut Bytes) -> Self {
         Version {
             major: data.get_u16(),
             minor: data.get_u16(),
         }
     }
+
+    pub fn major(&self) -> u16 {
+        self.major
+    }
+
+    pub fn minor(&self) -> u16 {
+        self.minor
+    }
 }
 
 impl fmt::Display for Version {
@@ -70,7 +771,7 @@ impl fmt::Display for Version {
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct Topology {
     me_count: u8,
     source_count: u8,
@@ -87,6 +788,7 @@ pub struct Topology {
     talkback_count: u8,
     sdi_count: u8,
     scalers_available: u8,
+    unknown: UnknownBytes,
 }
 
 impl fmt::Display for Topology {
@@ -99,6 +801,7 @@ impl fmt::Display for Topology {
 
 impl Topology {
     pub fn parse(data: &mut Bytes) -> Self {
+        let mut unknown = UnknownBytes::new(data);
         let me_count = data.get_u8();
         let source_count = data.get_u8();
         let dsk_count = data.get_u8();
@@ -111,10 +814,20 @@ impl Topology {
         let dve_count = data.get_u8();
         let stinger_count = data.get_u8();
         let supersource_count = data.get_u8();
-        data.get_u8(); // Unknown
+        unknown.skip(data, 1);
         let talkback_count = data.get_u8();
-        let sdi_count = data.get_u8(); // Not verified
-        let scalers_available = data.get_u8(); // Not verified
+        // Not verified, only read if the switcher sends them
+        let sdi_count = if data.has_remaining() {
+            data.get_u8()
+        } else {
+            0
+        };
+        let scalers_available = if data.has_remaining() {
+            data.get_u8()
+        } else {
+            0
+        };
+        unknown.skip_rest(data);
 
         Topology {
             me_count,
@@ -132,8 +845,83 @@ impl Topology {
             talkback_count,
             sdi_count,
             scalers_available,
+            unknown,
         }
     }
+
+    /// Whether the topology has been received from the switcher, every switcher has at least
+    /// one mix effect block
+    pub fn is_known(&self) -> bool {
+        self.me_count > 0
+    }
+
+    pub fn me_count(&self) -> u8 {
+        self.me_count
+    }
+
+    pub fn source_count(&self) -> u8 {
+        self.source_count
+    }
+
+    /// Number of downstream keyers
+    pub fn dsk_count(&self) -> u8 {
+        self.dsk_count
+    }
+
+    pub fn aux_count(&self) -> u8 {
+        self.aux_count
+    }
+
+    pub fn mixminus_output_count(&self) -> u8 {
+        self.mixminus_output_count
+    }
+
+    pub fn mediaplayer_count(&self) -> u8 {
+        self.mediaplayer_count
+    }
+
+    pub fn multiviewer_count(&self) -> u8 {
+        self.multiviewer_count
+    }
+
+    pub fn rs485_count(&self) -> u8 {
+        self.rs485_count
+    }
+
+    pub fn hyperdeck_count(&self) -> u8 {
+        self.hyperdeck_count
+    }
+
+    pub fn dve_count(&self) -> u8 {
+        self.dve_count
+    }
+
+    pub fn stinger_count(&self) -> u8 {
+        self.stinger_count
+    }
+
+    pub fn supersource_count(&self) -> u8 {
+        self.supersource_count
+    }
+
+    pub fn talkback_count(&self) -> u8 {
+        self.talkback_count
+    }
+
+    /// Not verified
+    pub fn sdi_count(&self) -> u8 {
+        self.sdi_count
+    }
+
+    /// Not verified
+    pub fn scalers_available(&self) -> u8 {
+        self.scalers_available
+    }
+
+    /// Bytes skipped by parsing, see [`UnknownBytes`]
+    pub fn unknown(&self) -> &UnknownBytes {
+        &self.unknown
+    }
 }
 
 pub struct PowerState {
@@ -153,11 +941,13 @@ impl PowerState {
 }
 
 impl fmt::Display for PowerState {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "Primary: {} Secondary: {}", self.primary, self.secondary)
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum TimeCodeType {
     FreeRunning,
     TimeOfDay,
@@ -185,7 +975,7 @@ impl From<TimeCodeType> for u8 {
 }
 
 impl fmt::Display for TimeCodeType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             TimeCodeType::FreeRunning => write!(f, "Free running"),
             TimeCodeType::TimeOfDay => write!(f, "Time of day"),
@@ -206,14 +996,94 @@ impl TimeCodeState {
             timecode_type: timecode_type.into(),
         }
     }
+
+    pub fn timecode_type(&self) -> TimeCodeType {
+        self.timecode_type
+    }
 }
 
 impl fmt::Display for TimeCodeState {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.timecode_type)
+    }
+}
+
+/// Change the time code mode (`CTCC`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetTimeCodeMode {
+    timecode_type: TimeCodeType,
+}
+
+impl SetTimeCodeMode {
+    pub fn new(timecode_type: TimeCodeType) -> Self {
+        SetTimeCodeMode { timecode_type }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.timecode_type.into());
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl fmt::Display for SetTimeCodeMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}", self.timecode_type)
     }
 }
 
+/// Ask the switcher to send the current time code (`TiRq`)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct RequestTime;
+
+impl RequestTime {
+    pub fn serialize(&self, _data: &mut BytesMut) {}
+}
+
+impl fmt::Display for RequestTime {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Request time")
+    }
+}
+
+/// Store the current configuration as the state used when the switcher powers on (`SRsv`)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SaveStartupState;
+
+impl SaveStartupState {
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_bytes(0, 4); // Padding
+    }
+}
+
+impl fmt::Display for SaveStartupState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Save startup state")
+    }
+}
+
+/// Clear the stored power on state, the switcher starts with the default configuration (`SRcl`)
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ClearStartupState;
+
+impl ClearStartupState {
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_bytes(0, 4); // Padding
+    }
+}
+
+impl fmt::Display for ClearStartupState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Clear startup state")
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum VideoMode {
     NTSC,
     PAL,
@@ -252,6 +1122,81 @@ impl VideoMode {
     pub fn parse(data: &mut Bytes) -> Self {
         data.get_u8().into()
     }
+
+    /// Frames per second as numerator and denominator, e.g. 30000 / 1001 for 29.97. Interlaced
+    /// modes count frames, not fields.
+    pub fn frame_rate(&self) -> Option<(u32, u32)> {
+        match self {
+            VideoMode::Res1080p23_98 | VideoMode::Res4K23_98 | VideoMode::Res8K23_98 => {
+                Some((24000, 1001))
+            }
+            VideoMode::Res1080p24 | VideoMode::Res4K24 | VideoMode::Res8K24 => Some((24, 1)),
+            VideoMode::PAL
+            | VideoMode::PALWidescreen
+            | VideoMode::Res1080i50
+            | VideoMode::Res1080p25
+            | VideoMode::Res4K25
+            | VideoMode::Res8K25 => Some((25, 1)),
+            VideoMode::NTSC
+            | VideoMode::NTSCWidescreen
+            | VideoMode::Res1080i59_94
+            | VideoMode::Res1080p29_97
+            | VideoMode::Res4K29_97
+            | VideoMode::Res8K29_97 => Some((30000, 1001)),
+            VideoMode::Res1080i60 | VideoMode::Res1080p30 => Some((30, 1)),
+            VideoMode::Res720p50
+            | VideoMode::Res1080p50
+            | VideoMode::Res4K50
+            | VideoMode::Res8K50 => Some((50, 1)),
+            VideoMode::Res720p59_94
+            | VideoMode::Res1080p59_94
+            | VideoMode::Res4K59_94
+            | VideoMode::Res8K59_94 => Some((60000, 1001)),
+            VideoMode::Res720p60 | VideoMode::Res1080p60 => Some((60, 1)),
+            VideoMode::Unknown(_) => None,
+        }
+    }
+
+    /// Frames per second of the time code, the frame rate rounded up to a whole number
+    pub fn timecode_rate(&self) -> Option<u32> {
+        self.frame_rate()
+            .map(|(numerator, denominator)| numerator.div_ceil(denominator))
+    }
+
+    /// Width and height of a frame in pixels
+    pub fn resolution(&self) -> Option<(u32, u32)> {
+        match self {
+            VideoMode::NTSC | VideoMode::NTSCWidescreen => Some((720, 486)),
+            VideoMode::PAL | VideoMode::PALWidescreen => Some((720, 576)),
+            VideoMode::Res720p50 | VideoMode::Res720p59_94 | VideoMode::Res720p60 => {
+                Some((1280, 720))
+            }
+            VideoMode::Res1080i50
+            | VideoMode::Res1080i59_94
+            | VideoMode::Res1080i60
+            | VideoMode::Res1080p23_98
+            | VideoMode::Res1080p24
+            | VideoMode::Res1080p25
+            | VideoMode::Res1080p29_97
+            | VideoMode::Res1080p30
+            | VideoMode::Res1080p50
+            | VideoMode::Res1080p59_94
+            | VideoMode::Res1080p60 => Some((1920, 1080)),
+            VideoMode::Res4K23_98
+            | VideoMode::Res4K24
+            | VideoMode::Res4K25
+            | VideoMode::Res4K29_97
+            | VideoMode::Res4K50
+            | VideoMode::Res4K59_94 => Some((3840, 2160)),
+            VideoMode::Res8K23_98
+            | VideoMode::Res8K24
+            | VideoMode::Res8K25
+            | VideoMode::Res8K29_97
+            | VideoMode::Res8K50
+            | VideoMode::Res8K59_94 => Some((7680, 4320)),
+            VideoMode::Unknown(_) => None,
+        }
+    }
 }
 
 impl From<u8> for VideoMode {
@@ -331,7 +1276,7 @@ impl From<VideoMode> for u8 {
 }
 
 impl fmt::Display for VideoMode {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             VideoMode::NTSC => write!(f, "NTSC"),
             VideoMode::PAL => write!(f, "PAL"),
@@ -368,6 +1313,104 @@ impl fmt::Display for VideoMode {
     }
 }
 
+/// Change the video mode of the switcher (`CVdM`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetVideoMode {
+    mode: VideoMode,
+}
+
+impl SetVideoMode {
+    pub fn new(mode: VideoMode) -> Self {
+        SetVideoMode { mode }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mode.into());
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl fmt::Display for SetVideoMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.mode)
+    }
+}
+
+/// How HD video is converted on the SD down-converted outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum DownConvertMode {
+    CenterCut,
+    Letterbox,
+    Anamorphic,
+    Unknown(u8),
+}
+
+impl DownConvertMode {
+    pub fn parse(data: &mut Bytes) -> Self {
+        data.get_u8().into()
+    }
+}
+
+impl From<u8> for DownConvertMode {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => DownConvertMode::CenterCut,
+            1 => DownConvertMode::Letterbox,
+            2 => DownConvertMode::Anamorphic,
+            u => DownConvertMode::Unknown(u),
+        }
+    }
+}
+
+impl From<DownConvertMode> for u8 {
+    fn from(value: DownConvertMode) -> Self {
+        match value {
+            DownConvertMode::CenterCut => 0,
+            DownConvertMode::Letterbox => 1,
+            DownConvertMode::Anamorphic => 2,
+            DownConvertMode::Unknown(u) => u,
+        }
+    }
+}
+
+impl fmt::Display for DownConvertMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            DownConvertMode::CenterCut => write!(f, "Center cut"),
+            DownConvertMode::Letterbox => write!(f, "Letterbox"),
+            DownConvertMode::Anamorphic => write!(f, "Anamorphic"),
+            DownConvertMode::Unknown(u) => write!(f, "Unknown down convert mode: {u}"),
+        }
+    }
+}
+
+/// Change the down convert mode of the SD outputs (`CDcO`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetDownConvertMode {
+    mode: DownConvertMode,
+}
+
+impl SetDownConvertMode {
+    pub fn new(mode: DownConvertMode) -> Self {
+        SetDownConvertMode { mode }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mode.into());
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl fmt::Display for SetDownConvertMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.mode)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct MeConfig {
     me: u8,
     key_count: u8,
@@ -380,10 +1423,19 @@ impl MeConfig {
 
         MeConfig { me, key_count }
     }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    /// Number of upstream keyers of the mix effect block
+    pub fn key_count(&self) -> u8 {
+        self.key_count
+    }
 }
 
 impl fmt::Display for MeConfig {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "ME: {} Keys: {}", self.me, self.key_count)
     }
 }
@@ -403,11 +1455,195 @@ impl MediaPlayerConfig {
 }
 
 impl fmt::Display for MediaPlayerConfig {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "Stills: {} Clips: {}", self.stills, self.clips)
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct SuperSourceConfig {
+    supersource: u8,
+    box_count: u8,
+}
+
+impl SuperSourceConfig {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let supersource = data.get_u8();
+        data.get_u8(); // Padding
+        let box_count = data.get_u8();
+
+        SuperSourceConfig {
+            supersource,
+            box_count,
+        }
+    }
+
+    pub fn supersource(&self) -> u8 {
+        self.supersource
+    }
+
+    pub fn box_count(&self) -> u8 {
+        self.box_count
+    }
+}
+
+impl fmt::Display for SuperSourceConfig {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "SuperSource: {} Boxes: {}",
+            self.supersource, self.box_count
+        )
+    }
+}
+
+/// Capabilities of the classic audio mixer
+#[derive(Debug, Clone, Default)]
+pub struct AudioMixerConfig {
+    input_count: u8,
+    has_monitor: bool,
+    has_headphones: bool,
+}
+
+impl AudioMixerConfig {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let input_count = data.get_u8();
+        let has_monitor = data.get_u8() > 0;
+        let has_headphones = data.get_u8() > 0;
+
+        AudioMixerConfig {
+            input_count,
+            has_monitor,
+            has_headphones,
+        }
+    }
+
+    pub fn input_count(&self) -> u8 {
+        self.input_count
+    }
+
+    pub fn has_monitor(&self) -> bool {
+        self.has_monitor
+    }
+
+    pub fn has_headphones(&self) -> bool {
+        self.has_headphones
+    }
+}
+
+impl fmt::Display for AudioMixerConfig {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Inputs: {} Monitor: {} Headphones: {}",
+            self.input_count, self.has_monitor, self.has_headphones
+        )
+    }
+}
+
+/// Channel counts of the Fairlight audio mixer
+#[derive(Debug, Clone, Default)]
+pub struct FairlightMixerConfig {
+    input_count: u8,
+    monitor_count: u8,
+}
+
+impl FairlightMixerConfig {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let input_count = data.get_u8();
+        let monitor_count = data.get_u8();
+
+        FairlightMixerConfig {
+            input_count,
+            monitor_count,
+        }
+    }
+
+    pub fn input_count(&self) -> u8 {
+        self.input_count
+    }
+
+    pub fn monitor_count(&self) -> u8 {
+        self.monitor_count
+    }
+}
+
+impl fmt::Display for FairlightMixerConfig {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Inputs: {} Monitors: {}",
+            self.input_count, self.monitor_count
+        )
+    }
+}
+
+/// Processing available on each Fairlight channel
+#[derive(Debug, Clone, Default)]
+pub struct FairlightChannelConfig {
+    eq_band_count: u8,
+    has_dynamics: bool,
+}
+
+impl FairlightChannelConfig {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let eq_band_count = data.get_u8();
+        let has_dynamics = data.get_u8() > 0; // Not verified
+
+        FairlightChannelConfig {
+            eq_band_count,
+            has_dynamics,
+        }
+    }
+
+    pub fn eq_band_count(&self) -> u8 {
+        self.eq_band_count
+    }
+
+    pub fn has_dynamics(&self) -> bool {
+        self.has_dynamics
+    }
+
+    /// True if an EQ set-command for the band is understood by the switcher
+    pub fn supports_eq_band(&self, band: u8) -> bool {
+        band < self.eq_band_count
+    }
+}
+
+impl fmt::Display for FairlightChannelConfig {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "EQ bands: {} Dynamics: {}",
+            self.eq_band_count, self.has_dynamics
+        )
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MacroPoolConfig {
+    macro_count: u8,
+}
+
+impl MacroPoolConfig {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let macro_count = data.get_u8();
+
+        MacroPoolConfig { macro_count }
+    }
+
+    /// Number of macro slots on the switcher
+    pub fn macro_count(&self) -> u8 {
+        self.macro_count
+    }
+}
+
+impl fmt::Display for MacroPoolConfig {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Macros: {}", self.macro_count)
+    }
+}
+
 pub struct VideoModeInfo {
     mode: VideoMode,
     multiview_modes: u32,
@@ -415,6 +1651,9 @@ pub struct VideoModeInfo {
     requires_reconfig: bool,
 }
 
+/// Size of a video mode entry in `_VMC`
+const VIDEO_MODE_INFO_SIZE: usize = 13;
+
 impl VideoModeInfo {
     pub fn parse(data: &mut Bytes) -> Self {
         data.get_u16(); //Padding
@@ -434,7 +1673,7 @@ impl VideoModeInfo {
 }
 
 impl fmt::Display for VideoModeInfo {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "Mode: {} Multiview modes: {} Down converter modes: {} Reconfig needed: {}",
@@ -449,9 +1688,9 @@ pub struct VideoModeConfig {
 
 impl VideoModeConfig {
     pub fn parse(data: &mut Bytes) -> Self {
-        let count = data.get_u16();
+        let count = (data.get_u16() as usize).min(data.remaining() / VIDEO_MODE_INFO_SIZE);
 
-        let mut video_modes = Vec::default();
+        let mut video_modes = Vec::with_capacity(count);
 
         for _ in 0..count {
             video_modes.push(VideoModeInfo::parse(data));
@@ -462,7 +1701,7 @@ impl VideoModeConfig {
 }
 
 impl fmt::Display for VideoModeConfig {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "{}",
@@ -474,3 +1713,30 @@ impl fmt::Display for VideoModeConfig {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::test_command;
+
+    #[test]
+    fn transition_edges() {
+        let position = |me: u8, position: u8| test_command(b"TrPs", &[me, 1, 0, 0, position, 0]);
+        let mut system_info = SystemInfo::default();
+
+        assert_eq!(system_info.update(&position(0, 0)), None);
+        assert_eq!(
+            system_info.update(&position(0, 10)),
+            Some(TransitionEdge::Started(0))
+        );
+        assert_eq!(
+            system_info.update(&position(1, 10)),
+            Some(TransitionEdge::Started(1))
+        );
+        assert_eq!(system_info.update(&position(0, 20)), None);
+        assert_eq!(
+            system_info.update(&position(0, 0)),
+            Some(TransitionEdge::Completed(0))
+        );
+    }
+}
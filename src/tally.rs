@@ -1,15 +1,42 @@
-use std::fmt::Display;
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
 
 use bytes::{Buf, Bytes};
 
-#[derive(Default, Debug)]
+use crate::{
+    keyer::KeyType, supersource::SUPERSOURCE_BASE, systeminfo::SystemInfo,
+    transition::TransitionLayers,
+};
+
+#[cfg(feature = "client")]
+use {
+    alloc::boxed::Box,
+    std::time::Duration,
+    tokio::time::{sleep, sleep_until, Instant},
+};
+
+#[cfg(feature = "client")]
+use crate::{
+    command::Command,
+    control::ControlCommand,
+    systeminfo::RequestTime,
+    trace::{debug, info},
+    Connection, Error, Message, TransportError,
+};
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct TallyState {
     program: bool,
     preview: bool,
 }
 
 impl Display for TallyState {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "Program: {} Preview: {}", self.program, self.preview)
     }
 }
@@ -18,16 +45,26 @@ impl TallyState {
     pub fn new(program: bool, preview: bool) -> Self {
         TallyState { program, preview }
     }
+
+    pub fn program(&self) -> bool {
+        self.program
+    }
+
+    pub fn preview(&self) -> bool {
+        self.preview
+    }
 }
 
+#[derive(Debug, Clone)]
 pub struct TallyInputs {
     tally_states: Vec<TallyState>,
 }
 
 impl TallyInputs {
     pub fn parse(data: &mut Bytes) -> Self {
-        let count = data.get_u16();
-        let mut tally_states: Vec<TallyState> = Vec::default();
+        // Large switchers report hundreds of inputs, never read past the received data
+        let count = (data.get_u16() as usize).min(data.remaining());
+        let mut tally_states: Vec<TallyState> = Vec::with_capacity(count);
 
         for _ in 0..count {
             let byte = data.get_u8();
@@ -36,10 +73,42 @@ impl TallyInputs {
 
         TallyInputs { tally_states }
     }
+
+    /// Number of inputs reported
+    pub fn len(&self) -> usize {
+        self.tally_states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tally_states.is_empty()
+    }
+
+    /// Tally state of an input, inputs are numbered from 1
+    pub fn get(&self, input: u16) -> Option<&TallyState> {
+        (input as usize)
+            .checked_sub(1)
+            .and_then(|index| self.tally_states.get(index))
+    }
+
+    pub fn is_program(&self, input: u16) -> bool {
+        self.get(input).is_some_and(TallyState::program)
+    }
+
+    pub fn is_preview(&self, input: u16) -> bool {
+        self.get(input).is_some_and(TallyState::preview)
+    }
+
+    /// Iterate over input numbers, starting at 1, and their tally state
+    pub fn iter(&self) -> impl Iterator<Item = (u16, &TallyState)> {
+        self.tally_states
+            .iter()
+            .enumerate()
+            .map(|(index, state)| (index as u16 + 1, state))
+    }
 }
 
 impl Display for TallyInputs {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let state_str = self
             .tally_states
             .iter()
@@ -51,6 +120,33 @@ impl Display for TallyInputs {
     }
 }
 
+/// Tally capabilities of the switcher
+#[derive(Debug, Clone, Default)]
+pub struct TallyConfig {
+    channel_count: u8,
+}
+
+impl TallyConfig {
+    pub fn parse(data: &mut Bytes) -> Self {
+        data.get_u32(); // Unknown
+        let channel_count = data.get_u8();
+
+        TallyConfig { channel_count }
+    }
+
+    /// Number of tally channels, matches the number of entries in TallyInputs
+    pub fn channel_count(&self) -> u8 {
+        self.channel_count
+    }
+}
+
+impl Display for TallyConfig {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "Channels: {}", self.channel_count)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct SourceTally {
     source_id: u16,
     state: TallyState,
@@ -60,22 +156,32 @@ impl SourceTally {
     pub fn new(source_id: u16, state: TallyState) -> Self {
         SourceTally { source_id, state }
     }
+
+    pub fn source_id(&self) -> u16 {
+        self.source_id
+    }
+
+    pub fn state(&self) -> &TallyState {
+        &self.state
+    }
 }
 
 impl Display for SourceTally {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "Source: {} {}", self.source_id, self.state)
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct TallySources {
     tally_states: Vec<SourceTally>,
 }
 
 impl TallySources {
     pub fn parse(data: &mut Bytes) -> Self {
-        let count = data.get_u16();
-        let mut tally_states: Vec<SourceTally> = Vec::default();
+        // Source id and state byte per source
+        let count = (data.get_u16() as usize).min(data.remaining() / 3);
+        let mut tally_states: Vec<SourceTally> = Vec::with_capacity(count);
 
         for _ in 0..count {
             let source_id = data.get_u16();
@@ -88,10 +194,25 @@ impl TallySources {
 
         TallySources { tally_states }
     }
+
+    /// Tally state of a source, e.g. a camera input or media player
+    pub fn state_for(&self, source_id: u16) -> Option<&TallyState> {
+        self.tally_states
+            .iter()
+            .find(|tally| tally.source_id == source_id)
+            .map(SourceTally::state)
+    }
+
+    /// Iterate over source ids and their tally state
+    pub fn iter(&self) -> impl Iterator<Item = (u16, &TallyState)> {
+        self.tally_states
+            .iter()
+            .map(|tally| (tally.source_id, &tally.state))
+    }
 }
 
 impl Display for TallySources {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let state_str = self
             .tally_states
             .iter()
@@ -102,3 +223,411 @@ impl Display for TallySources {
         write!(f, "{}", state_str)
     }
 }
+
+/// Source id of the program output of the first mix effect block, the program output of
+/// ME 2 is 10020 and so on
+const ME_PROGRAM_BASE: u16 = 10010;
+
+/// Sources actually on program and preview, derived from the state of the mix effect blocks
+/// instead of the tally the switcher reports. Follows the program and preview bus, upstream
+/// keyers on air, downstream keyers, running transitions and fade to black, and resolves mix
+/// effect blocks and the enabled boxes of SuperSources used as a source of the first one.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OnAirTally {
+    states: BTreeMap<u16, TallyState>,
+}
+
+impl OnAirTally {
+    pub(crate) fn compute(system_info: &SystemInfo) -> Self {
+        let mut tally = OnAirTally::default();
+        for source in resolve(system_info, program_sources(system_info, 0)) {
+            tally.states.entry(source).or_default().program = true;
+        }
+        for source in resolve(system_info, preview_sources(system_info, 0)) {
+            tally.states.entry(source).or_default().preview = true;
+        }
+        tally
+    }
+
+    /// Tally state of a source, sources neither on program nor on preview are off
+    pub fn state_for(&self, source_id: u16) -> TallyState {
+        self.states.get(&source_id).copied().unwrap_or_default()
+    }
+
+    pub fn is_program(&self, source_id: u16) -> bool {
+        self.state_for(source_id).program()
+    }
+
+    pub fn is_preview(&self, source_id: u16) -> bool {
+        self.state_for(source_id).preview()
+    }
+
+    /// Iterate over the sources on program or preview and their tally state
+    pub fn iter(&self) -> impl Iterator<Item = (u16, &TallyState)> {
+        self.states
+            .iter()
+            .map(|(&source_id, state)| (source_id, state))
+    }
+}
+
+impl Display for OnAirTally {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let state_str = self
+            .states
+            .iter()
+            .map(|(source_id, state)| format!("Source: {} {}", source_id, state))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        write!(f, "{}", state_str)
+    }
+}
+
+/// Mix effect block whose program output the source is
+fn me_program(source_id: u16) -> Option<u8> {
+    let offset = source_id.checked_sub(ME_PROGRAM_BASE)?;
+    if offset % 10 != 0 {
+        return None;
+    }
+    u8::try_from(offset / 10).ok()
+}
+
+/// SuperSource a source id refers to
+fn supersource(source_id: u16) -> Option<u8> {
+    u8::try_from(source_id.checked_sub(SUPERSOURCE_BASE)?).ok()
+}
+
+/// Replace the program outputs of mix effect blocks and SuperSources by the sources they show
+fn resolve(system_info: &SystemInfo, mut pending: Vec<u16>) -> BTreeSet<u16> {
+    let mut sources = BTreeSet::new();
+    while let Some(source) = pending.pop() {
+        if !sources.insert(source) {
+            continue;
+        }
+        if let Some(me) = me_program(source) {
+            pending.extend(program_sources(system_info, me));
+        }
+        if let Some(supersource) = supersource(source) {
+            pending.extend(
+                system_info
+                    .supersource_boxes()
+                    .filter(|ssbox| ssbox.supersource() == supersource && ssbox.enabled())
+                    .map(|ssbox| ssbox.source()),
+            );
+        }
+    }
+    sources
+}
+
+/// Sources visible on the program output of a mix effect block
+fn program_sources(system_info: &SystemInfo, me: u8) -> Vec<u16> {
+    let mut sources = Vec::new();
+    if system_info
+        .fade_to_black_state(me)
+        .is_some_and(|ftb| ftb.fully_black() && !ftb.in_transition())
+    {
+        return sources;
+    }
+
+    let layers = next_layers(system_info, me);
+    let running = system_info
+        .transition_position(me)
+        .is_some_and(|position| position.is_running());
+
+    sources.extend(system_info.program_input(me));
+    if running && layers.contains(TransitionLayers::BACKGROUND) {
+        sources.extend(system_info.preview_input(me));
+    }
+    for keyer in system_info.keyers().filter(|keyer| keyer.me() == me) {
+        let on_air = system_info
+            .keyer_on_air(me, keyer.keyer())
+            .unwrap_or_default();
+        if on_air || (running && layers.contains(key_layer(keyer.keyer()))) {
+            sources.push(keyer.fill_source());
+            if keyer.key_type() == KeyType::Luma {
+                sources.push(keyer.cut_source());
+            }
+        }
+    }
+
+    // Downstream keyers are on the program output of the first mix effect block only
+    if me == 0 {
+        for state in system_info.downstream_keyer_states() {
+            if !state.on_air() && !state.in_transition() {
+                continue;
+            }
+            if let Some(keyer) = system_info.downstream_keyer_sources(state.keyer()) {
+                sources.push(keyer.fill_source());
+                sources.push(keyer.cut_source());
+            }
+        }
+    }
+
+    sources
+}
+
+/// Sources visible on the preview output of a mix effect block, as they will be after the
+/// next transition
+fn preview_sources(system_info: &SystemInfo, me: u8) -> Vec<u16> {
+    let layers = next_layers(system_info, me);
+    let mut sources = Vec::new();
+
+    if layers.contains(TransitionLayers::BACKGROUND) {
+        sources.extend(system_info.preview_input(me));
+    } else {
+        sources.extend(system_info.program_input(me));
+    }
+    for keyer in system_info.keyers().filter(|keyer| keyer.me() == me) {
+        let on_air = system_info
+            .keyer_on_air(me, keyer.keyer())
+            .unwrap_or_default();
+        if on_air != layers.contains(key_layer(keyer.keyer())) {
+            sources.push(keyer.fill_source());
+            if keyer.key_type() == KeyType::Luma {
+                sources.push(keyer.cut_source());
+            }
+        }
+    }
+
+    sources
+}
+
+/// Layers of the next transition, only the background until the switcher reported them
+fn next_layers(system_info: &SystemInfo, me: u8) -> TransitionLayers {
+    system_info
+        .transition_style(me)
+        .map_or(TransitionLayers::BACKGROUND, |style| style.next_layers())
+}
+
+fn key_layer(keyer: u8) -> TransitionLayers {
+    let bits = TransitionLayers::KEY1.bits().checked_shl(keyer.into());
+    TransitionLayers::from_bits_truncate(bits.unwrap_or_default())
+}
+
+#[cfg(feature = "client")]
+/// Time to wait before connecting again after the connection was lost
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(2);
+#[cfg(feature = "client")]
+/// Interval between checks that the switcher still acknowledges commands, it doesn't close
+/// the connection when it goes away
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(1);
+
+#[cfg(feature = "client")]
+type LightCallback = Box<dyn FnMut(u16, TallyState) + Send>;
+
+#[cfg(feature = "client")]
+/// Drives tally lights from a switcher. Keeps a connection open and reconnects when it is
+/// lost, maps switcher sources to lights and debounces changes before they are passed to the
+/// callback. Lights are switched off while the switcher can't be reached.
+pub struct TallyEngine {
+    address: String,
+    lights: BTreeMap<u16, u16>,
+    debounce: Duration,
+    reconnect_interval: Duration,
+    callback: LightCallback,
+    reported: BTreeMap<u16, TallyState>,
+}
+
+#[cfg(feature = "client")]
+impl TallyEngine {
+    /// The callback is called with the light and its new state every time a light changes
+    pub fn new<F>(address: &str, callback: F) -> Self
+    where
+        F: FnMut(u16, TallyState) + Send + 'static,
+    {
+        TallyEngine {
+            address: address.into(),
+            lights: BTreeMap::new(),
+            debounce: Duration::ZERO,
+            reconnect_interval: RECONNECT_INTERVAL,
+            callback: Box::new(callback),
+            reported: BTreeMap::new(),
+        }
+    }
+
+    /// Show the tally of a switcher source on a light, several lights can follow one source
+    pub fn light(mut self, light: u16, source: u16) -> Self {
+        self.lights.insert(light, source);
+        self
+    }
+
+    /// Only pass on a change once the tally has been stable this long, hides the flicker while
+    /// a transition swaps program and preview
+    pub fn debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    pub fn reconnect_interval(mut self, interval: Duration) -> Self {
+        self.reconnect_interval = interval;
+        self
+    }
+
+    /// Drive the lights until the address turns out to be invalid, every other error leads to
+    /// a new connection attempt
+    pub async fn run(mut self) -> Result<(), Error> {
+        let lights: Vec<u16> = self.lights.keys().copied().collect();
+        for light in lights {
+            self.report(light, TallyState::default());
+        }
+
+        loop {
+            match self.session().await {
+                Err(e @ Error::Transport(TransportError::AddrParseError(_))) => return Err(e),
+                Err(e) => info!("Tally connection lost: {}", e),
+                Ok(()) => {}
+            }
+
+            let lights: Vec<u16> = self.lights.keys().copied().collect();
+            for light in lights {
+                self.report(light, TallyState::default());
+            }
+            sleep(self.reconnect_interval).await;
+        }
+    }
+
+    async fn session(&mut self) -> Result<(), Error> {
+        let mut connection = Connection::open(&self.address).await?;
+        let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+        // Light, changed state and when it was first seen
+        let mut pending: BTreeMap<u16, (TallyState, Instant)> = BTreeMap::new();
+
+        loop {
+            let due = pending
+                .values()
+                .map(|(_, since)| *since + self.debounce)
+                .min();
+
+            tokio::select! {
+                message = connection.recv_message() => match message {
+                    Some(Message::Command(Command::TallySources(tally))) => {
+                        let now = Instant::now();
+                        for (&light, &source) in &self.lights {
+                            let state = tally.state_for(source).copied().unwrap_or_default();
+                            if self.reported.get(&light) == Some(&state) {
+                                pending.remove(&light);
+                            } else if pending.get(&light).map(|(pending, _)| *pending) != Some(state) {
+                                pending.insert(light, (state, now));
+                            }
+                        }
+                    }
+                    Some(Message::Disconnected(e)) => return Err(e.into()),
+                    None => return Err(TransportError::ConnectionClosed.into()),
+                    Some(_) => {}
+                },
+                _ = sleep_until(due.unwrap_or_else(Instant::now)), if due.is_some() => {
+                    let now = Instant::now();
+                    let settled: Vec<(u16, TallyState)> = pending
+                        .iter()
+                        .filter(|(_, (_, since))| *since + self.debounce <= now)
+                        .map(|(&light, &(state, _))| (light, state))
+                        .collect();
+                    for (light, state) in settled {
+                        pending.remove(&light);
+                        self.report(light, state);
+                    }
+                }
+                _ = keepalive.tick() => {
+                    connection
+                        .send_command(ControlCommand::RequestTime(RequestTime))
+                        .await?;
+                }
+            }
+        }
+    }
+
+    fn report(&mut self, light: u16, state: TallyState) {
+        if self.reported.insert(light, state) != Some(state) {
+            debug!(light = light; "Tally {}", state);
+            (self.callback)(light, state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command::test_command;
+
+    #[test]
+    fn tally_inputs_ok() {
+        let mut data = Bytes::from_static(&[0x00, 0x03, 0x01, 0x02, 0x03]);
+        let tally = TallyInputs::parse(&mut data);
+
+        assert_eq!(tally.len(), 3);
+        assert!(tally.get(0).is_none());
+        assert!(tally.is_program(1) && !tally.is_preview(1));
+        assert!(!tally.is_program(2) && tally.is_preview(2));
+        assert_eq!(tally.get(3), Some(&TallyState::new(true, true)));
+        assert!(!tally.is_program(4));
+        assert_eq!(
+            tally.iter().map(|(input, _)| input).collect::<Vec<_>>(),
+            [1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn tally_sources_ok() {
+        let mut data = Bytes::from_static(&[0x00, 0x02, 0x00, 0x01, 0x01, 0x0b, 0xc2, 0x02]);
+        let tally = TallySources::parse(&mut data);
+
+        assert_eq!(tally.state_for(1), Some(&TallyState::new(true, false)));
+        assert_eq!(tally.state_for(3010), Some(&TallyState::new(false, true)));
+        assert!(tally.state_for(2).is_none());
+        assert_eq!(
+            tally
+                .iter()
+                .map(|(source_id, _)| source_id)
+                .collect::<Vec<_>>(),
+            [1, 3010]
+        );
+    }
+
+    #[test]
+    fn on_air_tally_ok() {
+        let mut system_info = SystemInfo::default();
+        system_info.set_program_input(0, 1);
+        system_info.set_preview_input(0, 2);
+        system_info.update(&test_command(
+            b"DskB",
+            &[0x00, 0x00, 0x00, 0x03, 0x00, 0x04],
+        ));
+        system_info.update(&test_command(b"DskS", &[0x00, 0x01, 0x00, 0x00, 0x00]));
+
+        let tally = system_info.on_air_tally();
+        assert_eq!(tally.state_for(1), TallyState::new(true, false));
+        assert_eq!(tally.state_for(2), TallyState::new(false, true));
+        assert!(tally.is_program(3) && tally.is_program(4));
+
+        // Both sources are on program while the transition runs
+        system_info.update(&test_command(
+            b"TrPs",
+            &[0x00, 0x01, 0x0c, 0x00, 0x13, 0x88],
+        ));
+        assert!(system_info.on_air_tally().is_program(2));
+
+        system_info.update(&test_command(b"FtbS", &[0x00, 0x01, 0x00, 0x00]));
+        assert_eq!(system_info.on_air_tally().iter().count(), 1);
+        system_info.update(&test_command(b"FtbS", &[0x00, 0x00, 0x00, 0x00]));
+
+        // The program output of ME 2 used on ME 1
+        system_info.set_program_input(0, 10020);
+        system_info.set_program_input(1, 5);
+        let tally = system_info.on_air_tally();
+        assert!(tally.is_program(10020) && tally.is_program(5));
+
+        // Only the enabled boxes of the SuperSource
+        let ssbox = |index: u8, enabled: u8, source: u8| {
+            let mut payload = [0u8; 24];
+            payload[..6].copy_from_slice(&[0x00, index, enabled, 0x00, 0x00, source]);
+            test_command(b"SSBP", &payload)
+        };
+        system_info.set_program_input(0, 6000);
+        system_info.update(&ssbox(0, 1, 6));
+        system_info.update(&ssbox(1, 0, 7));
+        system_info.update(&ssbox(2, 1, 8));
+        let tally = system_info.on_air_tally();
+        assert!(tally.is_program(6000) && tally.is_program(6) && tally.is_program(8));
+        assert!(!tally.is_program(7));
+    }
+}
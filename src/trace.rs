@@ -0,0 +1,56 @@
+//! Logging that goes to `tracing` or `log` depending on the enabled feature, or nowhere if
+//! neither is enabled. Events take optional structured fields before a `;` followed by the
+//! message, e.g. `debug!(packet_id = id; "Send packet")`. Field values are recorded using their
+//! `Display` implementation.
+
+#[cfg(feature = "tracing")]
+macro_rules! event {
+    ($level:ident, $($field:ident = $value:expr),+ ; $($arg:tt)+) => {
+        ::tracing::$level!($($field = %$value),+, $($arg)+)
+    };
+    ($level:ident, $($arg:tt)+) => {
+        ::tracing::$level!($($arg)+)
+    };
+}
+
+#[cfg(all(feature = "log", not(feature = "tracing")))]
+macro_rules! event {
+    ($level:ident, $($field:ident = $value:expr),+ ; $($arg:tt)+) => {
+        ::log::$level!(
+            concat!("{}" $(, " ", stringify!($field), "={}")+),
+            format_args!($($arg)+)
+            $(, $value)+
+        )
+    };
+    ($level:ident, $($arg:tt)+) => {
+        ::log::$level!($($arg)+)
+    };
+}
+
+#[cfg(not(any(feature = "tracing", feature = "log")))]
+macro_rules! event {
+    ($level:ident, $($field:ident = $value:expr),+ ; $($arg:tt)+) => {{
+        $(let _ = &$value;)+
+        let _ = format_args!($($arg)+);
+    }};
+    ($level:ident, $($arg:tt)+) => {{
+        let _ = format_args!($($arg)+);
+    }};
+}
+
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        $crate::trace::event!(debug, $($arg)+)
+    };
+}
+
+#[cfg(feature = "client")]
+macro_rules! info {
+    ($($arg:tt)+) => {
+        $crate::trace::event!(info, $($arg)+)
+    };
+}
+
+#[cfg(feature = "client")]
+pub(crate) use info;
+pub(crate) use {debug, event};
@@ -1,7 +1,15 @@
-use std::fmt::Display;
+use core::fmt::Display;
 
-use bytes::{Buf, Bytes};
+use bitflags::bitflags;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 
+use crate::{
+    command::UnknownBytes,
+    control::{CommandBatch, ControlCommand},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
 pub enum TransitionStyle {
     Mix,
     Dip,
@@ -38,7 +46,7 @@ impl From<TransitionStyle> for u8 {
 }
 
 impl Display for TransitionStyle {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             TransitionStyle::Mix => write!(f, "Mix"),
             TransitionStyle::Dip => write!(f, "Dip"),
@@ -50,6 +58,7 @@ impl Display for TransitionStyle {
     }
 }
 
+#[derive(Debug, Clone)]
 pub struct TransitionStyleSelection {
     me: u8,
     current_style: TransitionStyle,
@@ -76,8 +85,24 @@ impl TransitionStyleSelection {
     }
 }
 
+impl TransitionStyleSelection {
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    /// Style used by the next transition
+    pub fn next_style(&self) -> TransitionStyle {
+        self.next_style
+    }
+
+    /// Layers included in the next transition
+    pub fn next_layers(&self) -> TransitionLayers {
+        TransitionLayers::from_bits_retain(self.next_selection)
+    }
+}
+
 impl Display for TransitionStyleSelection {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "ME: {} Current style: {} Current selection: {} Next style: {} Next selection: {}",
@@ -105,7 +130,7 @@ impl TransitionPreview {
 }
 
 impl Display for TransitionPreview {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "ME: {} Enabled: {}", self.me, self.enabled)
     }
 }
@@ -122,10 +147,19 @@ impl TransitionMix {
 
         Self { me, rate }
     }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    /// Duration of the transition in frames
+    pub fn rate(&self) -> u8 {
+        self.rate
+    }
 }
 
 impl Display for TransitionMix {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "ME: {} Rate: {}", self.me, self.rate)
     }
 }
@@ -144,10 +178,24 @@ impl TransitionDip {
 
         Self { me, rate, source }
     }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    /// Duration of the transition in frames
+    pub fn rate(&self) -> u8 {
+        self.rate
+    }
+
+    /// Source dipped through
+    pub fn source(&self) -> u16 {
+        self.source
+    }
 }
 
 impl Display for TransitionDip {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "ME: {} Rate: {} Source: {}",
@@ -156,10 +204,114 @@ impl Display for TransitionDip {
     }
 }
 
+/// SMPTE wipe patterns, in the order shown by the switcher software. Used by wipe transitions
+/// and pattern keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub enum WipePattern {
+    LeftToRightBar,
+    TopToBottomBar,
+    HorizontalBarnDoor,
+    VerticalBarnDoor,
+    CornersInFourBox,
+    RectangleIris,
+    DiamondIris,
+    CircleIris,
+    TopLeftBox,
+    TopRightBox,
+    BottomRightBox,
+    BottomLeftBox,
+    TopCenterBox,
+    RightCenterBox,
+    BottomCenterBox,
+    LeftCenterBox,
+    TopLeftDiagonal,
+    TopRightDiagonal,
+    Unknown(u8),
+}
+
+impl From<u8> for WipePattern {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => WipePattern::LeftToRightBar,
+            1 => WipePattern::TopToBottomBar,
+            2 => WipePattern::HorizontalBarnDoor,
+            3 => WipePattern::VerticalBarnDoor,
+            4 => WipePattern::CornersInFourBox,
+            5 => WipePattern::RectangleIris,
+            6 => WipePattern::DiamondIris,
+            7 => WipePattern::CircleIris,
+            8 => WipePattern::TopLeftBox,
+            9 => WipePattern::TopRightBox,
+            10 => WipePattern::BottomRightBox,
+            11 => WipePattern::BottomLeftBox,
+            12 => WipePattern::TopCenterBox,
+            13 => WipePattern::RightCenterBox,
+            14 => WipePattern::BottomCenterBox,
+            15 => WipePattern::LeftCenterBox,
+            16 => WipePattern::TopLeftDiagonal,
+            17 => WipePattern::TopRightDiagonal,
+            u => WipePattern::Unknown(u),
+        }
+    }
+}
+
+impl From<WipePattern> for u8 {
+    fn from(value: WipePattern) -> Self {
+        match value {
+            WipePattern::LeftToRightBar => 0,
+            WipePattern::TopToBottomBar => 1,
+            WipePattern::HorizontalBarnDoor => 2,
+            WipePattern::VerticalBarnDoor => 3,
+            WipePattern::CornersInFourBox => 4,
+            WipePattern::RectangleIris => 5,
+            WipePattern::DiamondIris => 6,
+            WipePattern::CircleIris => 7,
+            WipePattern::TopLeftBox => 8,
+            WipePattern::TopRightBox => 9,
+            WipePattern::BottomRightBox => 10,
+            WipePattern::BottomLeftBox => 11,
+            WipePattern::TopCenterBox => 12,
+            WipePattern::RightCenterBox => 13,
+            WipePattern::BottomCenterBox => 14,
+            WipePattern::LeftCenterBox => 15,
+            WipePattern::TopLeftDiagonal => 16,
+            WipePattern::TopRightDiagonal => 17,
+            WipePattern::Unknown(u) => u,
+        }
+    }
+}
+
+impl Display for WipePattern {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            WipePattern::LeftToRightBar => write!(f, "Left to right bar"),
+            WipePattern::TopToBottomBar => write!(f, "Top to bottom bar"),
+            WipePattern::HorizontalBarnDoor => write!(f, "Horizontal barn door"),
+            WipePattern::VerticalBarnDoor => write!(f, "Vertical barn door"),
+            WipePattern::CornersInFourBox => write!(f, "Corners in four box"),
+            WipePattern::RectangleIris => write!(f, "Rectangle iris"),
+            WipePattern::DiamondIris => write!(f, "Diamond iris"),
+            WipePattern::CircleIris => write!(f, "Circle iris"),
+            WipePattern::TopLeftBox => write!(f, "Top left box"),
+            WipePattern::TopRightBox => write!(f, "Top right box"),
+            WipePattern::BottomRightBox => write!(f, "Bottom right box"),
+            WipePattern::BottomLeftBox => write!(f, "Bottom left box"),
+            WipePattern::TopCenterBox => write!(f, "Top center box"),
+            WipePattern::RightCenterBox => write!(f, "Right center box"),
+            WipePattern::BottomCenterBox => write!(f, "Bottom center box"),
+            WipePattern::LeftCenterBox => write!(f, "Left center box"),
+            WipePattern::TopLeftDiagonal => write!(f, "Top left diagonal"),
+            WipePattern::TopRightDiagonal => write!(f, "Top right diagonal"),
+            WipePattern::Unknown(u) => write!(f, "Unknown ({u})"),
+        }
+    }
+}
+
 pub struct TransitionWipe {
     me: u8,
     rate: u8,
-    pattern: u8,
+    pattern: WipePattern,
     border_width: u16,
     border_fill_source: u16,
     symmetry: u16,
@@ -174,7 +326,7 @@ impl TransitionWipe {
     pub fn parse(data: &mut Bytes) -> Self {
         let me = data.get_u8();
         let rate = data.get_u8();
-        let pattern = data.get_u8();
+        let pattern = data.get_u8().into();
         data.get_u8(); // Unknown
         let border_width = data.get_u16();
         let border_fill_source = data.get_u16();
@@ -199,10 +351,23 @@ impl TransitionWipe {
             flip,
         }
     }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    /// Duration of the transition in frames
+    pub fn rate(&self) -> u8 {
+        self.rate
+    }
+
+    pub fn pattern(&self) -> WipePattern {
+        self.pattern
+    }
 }
 
 impl Display for TransitionWipe {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "ME: {} Rate: {} Pattern: {} Border width: {} Border fill source: {} Symmetry: {} Softness {} Origin X: {} Origin Y: {} Reverse: {} Flip: {}",
             self.me, self.rate, self.pattern, self.border_width, self.border_fill_source, self.symmetry,
             self.softness, self.origin_x, self.origin_y, self.reverse, self.flip)
@@ -222,13 +387,15 @@ pub struct TransitionDVE {
     key_invert: bool,
     reverse: bool,
     flip: bool,
+    unknown: UnknownBytes,
 }
 
 impl TransitionDVE {
     pub fn parse(data: &mut Bytes) -> Self {
+        let mut unknown = UnknownBytes::new(data);
         let me = data.get_u8();
         let rate = data.get_u8();
-        data.get_u8(); // Unknown
+        unknown.skip(data, 1);
         let style = data.get_u8();
         let fill_source = data.get_u16();
         let key_source = data.get_u16();
@@ -239,6 +406,7 @@ impl TransitionDVE {
         let key_invert = data.get_u8() == 1;
         let reverse = data.get_u8() == 1;
         let flip = data.get_u8() == 1;
+        unknown.skip_rest(data);
 
         Self {
             me,
@@ -253,12 +421,27 @@ impl TransitionDVE {
             key_invert,
             reverse,
             flip,
+            unknown,
         }
     }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    /// Duration of the transition in frames
+    pub fn rate(&self) -> u8 {
+        self.rate
+    }
+
+    /// Bytes skipped by parsing, see [`UnknownBytes`]
+    pub fn unknown(&self) -> &UnknownBytes {
+        &self.unknown
+    }
 }
 
 impl Display for TransitionDVE {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(f, "ME: {} Rate: {} Style: {} Fill source: {} Key Source: {} Key enabled: {} Key premultiplied: {} Key clip: {} Key gain: {} Key invert: {} Reverse: {} Flip: {}",
             self.me, self.rate, self.style, self.fill_source, self.key_source, self.key_enabled, self.key_premultiplied,
             self.key_clip, self.key_gain, self.key_invert, self.reverse, self.flip)
@@ -267,27 +450,30 @@ impl Display for TransitionDVE {
 
 pub struct TransitionStinger {
     me: u8,
-    source: u16,
+    source: u8,
     key_premultiplied: bool,
     key_clip: u16,
     key_gain: u16,
     key_invert: bool,
     pre_roll: u16,
     clip_duration: u16,
+    trigger_point: u16,
     rate: u16,
 }
 
 impl TransitionStinger {
     pub fn parse(data: &mut Bytes) -> Self {
-        // TODO: Verify that this is correct
         let me = data.get_u8();
-        let source = data.get_u16();
+        let source = data.get_u8();
         let key_premultiplied = data.get_u8() == 1;
+        data.get_u8(); // Padding
         let key_clip = data.get_u16();
         let key_gain = data.get_u16();
         let key_invert = data.get_u8() == 1;
+        data.get_u8(); // Padding
         let pre_roll = data.get_u16();
         let clip_duration = data.get_u16();
+        let trigger_point = data.get_u16();
         let rate = data.get_u16();
 
         Self {
@@ -299,15 +485,580 @@ impl TransitionStinger {
             key_invert,
             pre_roll,
             clip_duration,
+            trigger_point,
             rate,
         }
     }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    /// Media player playing the stinger clip
+    pub fn source(&self) -> u8 {
+        self.source
+    }
+
+    /// Frame of the clip at which the background is cut
+    pub fn trigger_point(&self) -> u16 {
+        self.trigger_point
+    }
+
+    /// Duration of the mix in frames
+    pub fn rate(&self) -> u16 {
+        self.rate
+    }
 }
 
 impl Display for TransitionStinger {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "ME: {} Source: {} Key premultiplied: {} Key clip: {} Key gain: {} Key invert: {} Pre-roll: {} Clip duration: {} Rate: {}",
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ME: {} Source: {} Key premultiplied: {} Key clip: {} Key gain: {} Key invert: {} Pre-roll: {} Clip duration: {} Trigger point: {} Rate: {}",
             self.me, self.source, self.key_premultiplied, self.key_clip, self.key_gain, self.key_invert,
-            self.pre_roll, self.clip_duration, self.rate)
+            self.pre_roll, self.clip_duration, self.trigger_point, self.rate)
+    }
+}
+
+bitflags! {
+    /// Layers included in the next transition
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct TransitionLayers: u8 {
+        const BACKGROUND = 0x01;
+        const KEY1 = 0x02;
+        const KEY2 = 0x04;
+        const KEY3 = 0x08;
+        const KEY4 = 0x10;
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct TransitionStyleMask: u8 {
+        const STYLE = 0x01;
+        const NEXT_TRANSITION = 0x02;
+    }
+}
+
+/// Select the style and layers of the next transition of a mix effect block (`CTTp`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetTransitionStyle {
+    mask: TransitionStyleMask,
+    me: u8,
+    style: TransitionStyle,
+    next_transition: TransitionLayers,
+}
+
+impl SetTransitionStyle {
+    pub fn new(me: u8) -> Self {
+        SetTransitionStyle {
+            mask: TransitionStyleMask::empty(),
+            me,
+            style: TransitionStyle::Mix,
+            next_transition: TransitionLayers::empty(),
+        }
+    }
+
+    pub fn style(mut self, style: TransitionStyle) -> Self {
+        self.mask |= TransitionStyleMask::STYLE;
+        self.style = style;
+        self
+    }
+
+    pub fn next_transition(mut self, layers: TransitionLayers) -> Self {
+        self.mask |= TransitionStyleMask::NEXT_TRANSITION;
+        self.next_transition = layers;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(self.me);
+        data.put_u8(self.style.into());
+        data.put_u8(self.next_transition.bits());
+    }
+}
+
+impl Display for SetTransitionStyle {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ME: {}", self.me)?;
+        if self.mask.contains(TransitionStyleMask::STYLE) {
+            write!(f, " Style: {}", self.style)?;
+        }
+        if self.mask.contains(TransitionStyleMask::NEXT_TRANSITION) {
+            write!(f, " Next transition: {:?}", self.next_transition)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Cut between program and preview of a mix effect block (`DCut`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct Cut {
+    me: u8,
+}
+
+impl Cut {
+    pub fn new(me: u8) -> Self {
+        Cut { me }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.me);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for Cut {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ME: {}", self.me)
+    }
+}
+
+/// Run the selected transition of a mix effect block (`DAut`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct AutoTransition {
+    me: u8,
+}
+
+impl AutoTransition {
+    pub fn new(me: u8) -> Self {
+        AutoTransition { me }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.me);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for AutoTransition {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ME: {}", self.me)
+    }
+}
+
+/// Fade the program output of a mix effect block to or from black (`FtbA`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct FadeToBlack {
+    me: u8,
+}
+
+impl FadeToBlack {
+    pub fn new(me: u8) -> Self {
+        FadeToBlack { me }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.me);
+        data.put_bytes(0, 3); // Padding
+    }
+}
+
+impl Display for FadeToBlack {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ME: {}", self.me)
+    }
+}
+
+/// Fade to black state of a mix effect block (`FtbS`)
+#[derive(Debug, Clone)]
+pub struct FadeToBlackState {
+    me: u8,
+    fully_black: bool,
+    in_transition: bool,
+    frames_remaining: u8,
+}
+
+impl FadeToBlackState {
+    pub fn parse(data: &mut Bytes) -> Self {
+        let me = data.get_u8();
+        let fully_black = data.get_u8() == 1;
+        let in_transition = data.get_u8() == 1;
+        let frames_remaining = data.get_u8();
+
+        FadeToBlackState {
+            me,
+            fully_black,
+            in_transition,
+            frames_remaining,
+        }
+    }
+
+    pub fn me(&self) -> u8 {
+        self.me
+    }
+
+    /// The program output is completely faded to black
+    pub fn fully_black(&self) -> bool {
+        self.fully_black
+    }
+
+    /// The program output is fading to or from black
+    pub fn in_transition(&self) -> bool {
+        self.in_transition
+    }
+
+    /// Frames left of the running fade
+    pub fn frames_remaining(&self) -> u8 {
+        self.frames_remaining
+    }
+}
+
+impl Display for FadeToBlackState {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Fully black: {} In transition: {} Frames remaining: {}",
+            self.me, self.fully_black, self.in_transition, self.frames_remaining
+        )
+    }
+}
+
+/// Change the rate of the mix transition (`CTMx`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetTransitionMix {
+    me: u8,
+    rate: u8,
+}
+
+impl SetTransitionMix {
+    pub fn new(me: u8, rate: u8) -> Self {
+        SetTransitionMix { me, rate }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.me);
+        data.put_u8(self.rate);
+        data.put_u16(0); // Padding
+    }
+}
+
+impl Display for SetTransitionMix {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ME: {} Rate: {}", self.me, self.rate)
+    }
+}
+
+bitflags! {
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+    pub struct TransitionDipMask: u8 {
+        const RATE = 0x01;
+        const SOURCE = 0x02;
+    }
+}
+
+/// Change the dip transition (`CTDp`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetTransitionDip {
+    mask: TransitionDipMask,
+    me: u8,
+    rate: u8,
+    source: u16,
+}
+
+impl SetTransitionDip {
+    pub fn new(me: u8) -> Self {
+        SetTransitionDip {
+            mask: TransitionDipMask::empty(),
+            me,
+            rate: 0,
+            source: 0,
+        }
+    }
+
+    pub fn rate(mut self, rate: u8) -> Self {
+        self.mask |= TransitionDipMask::RATE;
+        self.rate = rate;
+        self
+    }
+
+    /// Source dipped through
+    pub fn source(mut self, source: u16) -> Self {
+        self.mask |= TransitionDipMask::SOURCE;
+        self.source = source;
+        self
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u8(self.mask.bits());
+        data.put_u8(self.me);
+        data.put_u8(self.rate);
+        data.put_u8(0); // Padding
+        data.put_u16(self.source);
+        data.put_u16(0); // Padding
+    }
+}
+
+impl Display for SetTransitionDip {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "ME: {} Rate: {} Source: {}",
+            self.me, self.rate, self.source
+        )
+    }
+}
+
+/// Change the rate of the wipe transition (`CTWp`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetTransitionWipeRate {
+    me: u8,
+    rate: u8,
+}
+
+impl SetTransitionWipeRate {
+    pub fn new(me: u8, rate: u8) -> Self {
+        SetTransitionWipeRate { me, rate }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(0x0001); // Mask, only the rate
+        data.put_u8(self.me);
+        data.put_u8(self.rate);
+        data.put_bytes(0, 16); // Pattern, border, position and direction
+    }
+}
+
+impl Display for SetTransitionWipeRate {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ME: {} Rate: {}", self.me, self.rate)
+    }
+}
+
+/// Change the rate of the DVE transition (`CTDv`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetTransitionDVERate {
+    me: u8,
+    rate: u8,
+}
+
+impl SetTransitionDVERate {
+    pub fn new(me: u8, rate: u8) -> Self {
+        SetTransitionDVERate { me, rate }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(0x0001); // Mask, only the rate
+        data.put_u8(self.me);
+        data.put_u8(self.rate);
+        data.put_bytes(0, 16); // Style, key and direction
+    }
+}
+
+impl Display for SetTransitionDVERate {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ME: {} Rate: {}", self.me, self.rate)
+    }
+}
+
+/// Change the rate of the mix used by the stinger transition (`CTSt`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct SetTransitionStingerRate {
+    me: u8,
+    rate: u16,
+}
+
+impl SetTransitionStingerRate {
+    pub fn new(me: u8, rate: u16) -> Self {
+        SetTransitionStingerRate { me, rate }
+    }
+
+    pub fn serialize(&self, data: &mut BytesMut) {
+        data.put_u16(0x0100); // Mask, only the mix rate
+        data.put_u8(self.me);
+        data.put_bytes(0, 15); // Source, key, pre-roll, duration and trigger point
+        data.put_u16(self.rate);
+    }
+}
+
+impl Display for SetTransitionStingerRate {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ME: {} Rate: {}", self.me, self.rate)
+    }
+}
+
+/// Declarative description of the next transition of a mix effect block, see
+/// [`crate::mixeffect::MixEffect::set_transition`]
+#[derive(Debug, Clone, Default)]
+pub struct Transition {
+    style: Option<TransitionStyle>,
+    layers: Option<TransitionLayers>,
+    rate: Option<u8>,
+}
+
+impl Transition {
+    pub fn builder() -> Self {
+        Self::default()
+    }
+
+    pub fn style(mut self, style: TransitionStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Layers included in the transition
+    pub fn layers(mut self, layers: TransitionLayers) -> Self {
+        self.layers = Some(layers);
+        self
+    }
+
+    /// Duration of the transition in frames
+    pub fn rate(mut self, rate: u8) -> Self {
+        self.rate = Some(rate);
+        self
+    }
+
+    /// Commands applying the transition to the mix effect block. The rate is set for the style
+    /// of the transition, or `current_style` when the transition doesn't set one.
+    pub fn commands(&self, me: u8, current_style: Option<TransitionStyle>) -> CommandBatch {
+        let mut batch = CommandBatch::new();
+
+        if self.style.is_some() || self.layers.is_some() {
+            let mut style = SetTransitionStyle::new(me);
+            if let Some(transition_style) = self.style {
+                style = style.style(transition_style);
+            }
+            if let Some(layers) = self.layers {
+                style = style.next_transition(layers);
+            }
+            batch = batch.command(ControlCommand::TransitionStyle(style));
+        }
+
+        if let Some(rate) = self.rate {
+            let rate_command = match self.style.or(current_style) {
+                Some(TransitionStyle::Mix) => Some(ControlCommand::TransitionMix(
+                    SetTransitionMix::new(me, rate),
+                )),
+                Some(TransitionStyle::Dip) => Some(ControlCommand::TransitionDip(
+                    SetTransitionDip::new(me).rate(rate),
+                )),
+                Some(TransitionStyle::Wipe) => Some(ControlCommand::TransitionWipeRate(
+                    SetTransitionWipeRate::new(me, rate),
+                )),
+                Some(TransitionStyle::Dve) => Some(ControlCommand::TransitionDVERate(
+                    SetTransitionDVERate::new(me, rate),
+                )),
+                Some(TransitionStyle::Stinger) => Some(ControlCommand::TransitionStingerRate(
+                    SetTransitionStingerRate::new(me, rate as u16),
+                )),
+                Some(TransitionStyle::Unknown(_)) | None => None,
+            };
+            if let Some(command) = rate_command {
+                batch = batch.command(command);
+            }
+        }
+
+        batch
+    }
+}
+
+/// Curve of an automated transition move, see
+/// [`crate::mixeffect::MixEffect::move_transition`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Starts slowly and speeds up
+    EaseIn,
+    /// Starts quickly and slows down
+    EaseOut,
+    /// Starts and ends slowly
+    EaseInOut,
+}
+
+impl Easing {
+    /// Eased progress for the elapsed part `t` of the move, both from 0.0 to 1.0
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => {
+                let rest = 1.0 - t;
+                1.0 - rest * rest * rest
+            }
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+
+    /// Transition positions, from 0 to 10000, of a move taking `steps` steps. The last position
+    /// is always 10000, which completes the transition.
+    pub fn positions(self, steps: u32) -> impl Iterator<Item = u16> {
+        let steps = steps.max(1);
+        (1..=steps).map(move |step| {
+            let progress = self.apply(step as f32 / steps as f32);
+            (progress * 10000.0 + 0.5) as u16
+        })
+    }
+}
+
+impl Display for Easing {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Easing::Linear => write!(f, "Linear"),
+            Easing::EaseIn => write!(f, "Ease in"),
+            Easing::EaseOut => write!(f, "Ease out"),
+            Easing::EaseInOut => write!(f, "Ease in and out"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transition_commands_ok() {
+        let transition = Transition::builder()
+            .style(TransitionStyle::Dip)
+            .layers(TransitionLayers::BACKGROUND | TransitionLayers::KEY1)
+            .rate(30);
+
+        let data = transition
+            .commands(1, Some(TransitionStyle::Mix))
+            .serialize();
+        assert_eq!(
+            data[..],
+            [
+                0x00, 0x0c, 0x00, 0x00, b'C', b'T', b'T', b'p', 0x03, 0x01, 0x01, 0x03, //
+                0x00, 0x10, 0x00, 0x00, b'C', b'T', b'D', b'p', 0x01, 0x01, 0x1e, 0x00, 0x00, 0x00,
+                0x00, 0x00,
+            ]
+        );
+
+        // Only the rate of the current style
+        let batch = Transition::builder()
+            .rate(30)
+            .commands(0, Some(TransitionStyle::Mix));
+        assert_eq!(batch.commands().len(), 1);
+        assert_eq!(batch.commands()[0].name(), b"CTMx");
+    }
+
+    #[test]
+    fn easing_positions() {
+        let linear = Easing::Linear.positions(4).collect::<Vec<_>>();
+        assert_eq!(linear, [2500, 5000, 7500, 10000]);
+
+        for easing in [Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+            let positions = easing.positions(25).collect::<Vec<_>>();
+            assert!(positions.windows(2).all(|pair| pair[0] <= pair[1]));
+            assert_eq!(positions.last(), Some(&10000));
+        }
+        assert!(Easing::EaseIn.apply(0.25) < 0.25);
+        assert!(Easing::EaseOut.apply(0.25) > 0.25);
+        assert_eq!(Easing::EaseInOut.apply(0.5), 0.5);
+        assert_eq!(Easing::Linear.positions(0).collect::<Vec<_>>(), [10000]);
     }
 }
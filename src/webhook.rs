@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+#[cfg(feature = "recording")]
+use std::{collections::BTreeSet, time::Duration};
+
+use bitflags::bitflags;
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+use crate::{command::Command, trace::info, Connection};
+
+#[cfg(feature = "recording")]
+/// Recording time left on a disk below which it is reported as low
+const DISK_LOW_THRESHOLD: Duration = Duration::from_secs(10 * 60);
+
+bitflags! {
+    /// State changes a webhook is notified about
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct WebhookEvents: u8 {
+        /// A mix effect block has a new program source
+        const PROGRAM = 0x01;
+        /// Streaming started or stopped
+        #[cfg(feature = "streaming")]
+        const STREAMING = 0x02;
+        /// Recording time left on a disk dropped below the threshold
+        #[cfg(feature = "recording")]
+        const RECORDING_DISK_LOW = 0x04;
+    }
+}
+
+struct Webhook {
+    url: String,
+    events: WebhookEvents,
+}
+
+/// Webhook URLs that receive a JSON POST when selected state of the switcher changes, e.g.
+/// `{"event": "program_changed", "me": 0, "source": 2}`
+#[cfg_attr(not(feature = "recording"), derive(Default))]
+pub struct Webhooks {
+    hooks: Vec<Webhook>,
+    #[cfg(feature = "recording")]
+    disk_low_threshold: Duration,
+}
+
+#[cfg(feature = "recording")]
+impl Default for Webhooks {
+    fn default() -> Self {
+        Webhooks {
+            hooks: Vec::new(),
+            disk_low_threshold: DISK_LOW_THRESHOLD,
+        }
+    }
+}
+
+impl Webhooks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(mut self, url: &str, events: WebhookEvents) -> Self {
+        self.hooks.push(Webhook {
+            url: url.into(),
+            events,
+        });
+        self
+    }
+
+    #[cfg(feature = "recording")]
+    /// Recording time left on a disk below which it is reported as low, 10 minutes by default
+    pub fn disk_low_threshold(mut self, threshold: Duration) -> Self {
+        self.disk_low_threshold = threshold;
+        self
+    }
+
+    /// Start notifying the webhooks about changes reported by the switcher until the
+    /// connection is closed. Requests are sent one at a time from a background task, failed
+    /// requests are logged and not retried.
+    pub fn watch(self, connection: &Connection) {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut tracker = EventTracker {
+            #[cfg(feature = "recording")]
+            disk_low_threshold: self.disk_low_threshold,
+            ..Default::default()
+        };
+        connection.listen(move |command| {
+            if let Some(event) = tracker.event(command) {
+                return tx.send(event).is_ok();
+            }
+            !tx.is_closed()
+        });
+
+        tokio::spawn(async move {
+            let client = reqwest::Client::new();
+            while let Some((event, payload)) = rx.recv().await {
+                for hook in self
+                    .hooks
+                    .iter()
+                    .filter(|hook| hook.events.intersects(event))
+                {
+                    let result = client
+                        .post(&hook.url)
+                        .json(&payload)
+                        .send()
+                        .await
+                        .and_then(|response| response.error_for_status());
+                    if let Err(e) = result {
+                        info!(url = hook.url; "Webhook failed: {}", e);
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Turns the commands received from the switcher into webhook events. The state sent right
+/// after connecting is only recorded, events are for changes.
+#[derive(Default)]
+struct EventTracker {
+    programs: BTreeMap<u8, u16>,
+    #[cfg(feature = "streaming")]
+    live: Option<bool>,
+    #[cfg(feature = "recording")]
+    disk_low_threshold: Duration,
+    #[cfg(feature = "recording")]
+    low_disks: BTreeSet<u32>,
+}
+
+impl EventTracker {
+    fn event(&mut self, command: &Command) -> Option<(WebhookEvents, Value)> {
+        match command {
+            Command::ProgramInput(selection) => {
+                let me = selection.me();
+                let source = selection.source_id();
+                match self.programs.insert(me, source) {
+                    Some(previous) if previous != source => Some((
+                        WebhookEvents::PROGRAM,
+                        json!({ "event": "program_changed", "me": me, "source": source }),
+                    )),
+                    _ => None,
+                }
+            }
+            #[cfg(feature = "streaming")]
+            Command::StreamingStatus(status) => {
+                let live = status.is_live();
+                match self.live.replace(live) {
+                    Some(previous) if previous != live => {
+                        let event = if live {
+                            "stream_started"
+                        } else {
+                            "stream_stopped"
+                        };
+                        Some((WebhookEvents::STREAMING, json!({ "event": event })))
+                    }
+                    _ => None,
+                }
+            }
+            #[cfg(feature = "recording")]
+            Command::RecordingDisk(disk) => {
+                let low = !disk.is_removed() && disk.time_available() < self.disk_low_threshold;
+                if !low {
+                    self.low_disks.remove(&disk.id());
+                    return None;
+                }
+                // Only report a disk again once it has had enough time available in between
+                self.low_disks.insert(disk.id()).then(|| {
+                    (
+                        WebhookEvents::RECORDING_DISK_LOW,
+                        json!({
+                            "event": "recording_disk_low",
+                            "disk": disk.id(),
+                            "volume_name": disk.volume_name(),
+                            "seconds_available": disk.time_available().as_secs(),
+                        }),
+                    )
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+
+    #[test]
+    fn program_changed_event() {
+        let mut tracker = EventTracker::default();
+        let program = |source: u8| {
+            Command::parse(&mut Bytes::copy_from_slice(&[
+                0x00, 0x0c, 0x00, 0x00, b'P', b'r', b'g', b'I', 0x00, 0x00, 0x00, source,
+            ]))
+            .unwrap()
+        };
+
+        assert!(tracker.event(&program(1)).is_none());
+        assert!(tracker.event(&program(1)).is_none());
+        assert_eq!(
+            tracker.event(&program(2)),
+            Some((
+                WebhookEvents::PROGRAM,
+                json!({ "event": "program_changed", "me": 0, "source": 2 })
+            ))
+        );
+    }
+}
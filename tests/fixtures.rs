@@ -0,0 +1,133 @@
+//! Initial state dumps of real switchers must parse, see `tests/fixtures/README.md`
+
+use std::{fs, path::Path};
+
+use atem_rs::{
+    capabilities::{Capabilities, DeviceModel},
+    command::Command,
+    systeminfo::SystemInfo,
+    ParseError,
+};
+use bytes::Bytes;
+
+/// What a capture of a model must show once parsed
+struct Expected {
+    model: DeviceModel,
+    me_count: u8,
+    keyer_count: u8,
+    multiviewer_count: u8,
+    supersource_count: u8,
+    fairlight: bool,
+}
+
+impl Expected {
+    fn check(&self, name: &str, capabilities: &Capabilities) {
+        assert_eq!(capabilities.model(), self.model, "{name}");
+        assert_eq!(capabilities.me_count(), self.me_count, "{name}");
+        assert_eq!(capabilities.keyer_count(0), self.keyer_count, "{name}");
+        assert_eq!(
+            capabilities.multiviewer_count(),
+            self.multiviewer_count,
+            "{name}"
+        );
+        assert_eq!(
+            capabilities.supersource_count(),
+            self.supersource_count,
+            "{name}"
+        );
+        assert_eq!(capabilities.has_fairlight(), self.fairlight, "{name}");
+    }
+}
+
+/// Models the corpus must hold a capture of, in any firmware version
+const MODELS: [(&str, Expected); 4] = [
+    (
+        "atem-mini",
+        Expected {
+            model: DeviceModel::Mini,
+            me_count: 1,
+            keyer_count: 1,
+            multiviewer_count: 0,
+            supersource_count: 0,
+            fairlight: true,
+        },
+    ),
+    (
+        "atem-mini-extreme-iso",
+        Expected {
+            model: DeviceModel::MiniExtremeIso,
+            me_count: 1,
+            keyer_count: 4,
+            multiviewer_count: 1,
+            supersource_count: 1,
+            fairlight: true,
+        },
+    ),
+    (
+        "atem-television-studio-hd",
+        Expected {
+            model: DeviceModel::TelevisionStudioHd,
+            me_count: 1,
+            keyer_count: 1,
+            multiviewer_count: 1,
+            supersource_count: 0,
+            fairlight: false,
+        },
+    ),
+    (
+        "atem-constellation-8k",
+        Expected {
+            model: DeviceModel::Constellation,
+            me_count: 4,
+            keyer_count: 4,
+            multiviewer_count: 4,
+            supersource_count: 1,
+            fairlight: true,
+        },
+    ),
+];
+
+#[test]
+fn fixtures_parse() {
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+    let mut captures: Vec<_> = fs::read_dir(fixtures)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "bin"))
+        .collect();
+    captures.sort();
+
+    let model_of = |path: &Path| {
+        let stem = path.file_stem().unwrap().to_string_lossy();
+        let (name, _firmware) = stem.rsplit_once('-')?;
+        MODELS.iter().find(|(model, _)| *model == name)
+    };
+    for (model, _) in &MODELS {
+        assert!(
+            captures
+                .iter()
+                .any(|path| model_of(path).is_some_and(|(name, _)| name == model)),
+            "No capture of {model} in tests/fixtures"
+        );
+    }
+
+    for path in captures {
+        let mut payload = Bytes::from(fs::read(&path).unwrap());
+        let mut system_info = SystemInfo::default();
+        while !payload.is_empty() {
+            match Command::parse(&mut payload) {
+                Ok(command) => {
+                    system_info.update(&command);
+                }
+                // Commands the crate doesn't handle are fine, known commands must parse
+                Err(ParseError::Command { source, .. })
+                    if matches!(*source, ParseError::UnknownCommand(_)) => {}
+                Err(e) => panic!("{}: {}", path.display(), e),
+            }
+        }
+
+        if let Some((_, expected)) = model_of(&path) {
+            expected.check(&path.display().to_string(), &system_info.capabilities());
+        }
+    }
+}
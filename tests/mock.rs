@@ -0,0 +1,121 @@
+//! Applications see the differences between models through the mock server profiles
+
+use std::time::Duration;
+
+use atem_rs::{
+    capabilities::DeviceModel,
+    command::{SetAuxSource, SetProgramInput},
+    control::ControlCommand,
+    mock::{DeviceProfile, MockServer},
+    multiview::SetMultiViewInput,
+    Connection, Error, ProtocolError,
+};
+use tokio::time::timeout;
+
+const CONFIRM_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Connect and wait until the initial state has been received, it ends with the program and
+/// preview inputs of the last mix effect block
+async fn connect(server: &MockServer, profile: &DeviceProfile) -> Connection {
+    let mut connection = Connection::open(&server.address().to_string())
+        .await
+        .unwrap();
+    let last_me = profile.me_count() - 1;
+    timeout(Duration::from_secs(5), async {
+        while connection.system_info().preview_input(last_me).is_none() {
+            connection.recv_message().await.unwrap();
+        }
+    })
+    .await
+    .unwrap();
+
+    connection
+}
+
+#[tokio::test]
+async fn mock_profiles_initial_state() {
+    for profile in [
+        DeviceProfile::atem_mini(),
+        DeviceProfile::constellation_8k(),
+    ] {
+        let server = MockServer::start(profile.clone()).await.unwrap();
+        let connection = connect(&server, &profile).await;
+
+        let system_info = connection.system_info();
+        assert_eq!(system_info.product(), profile.product());
+        assert_eq!(*system_info.version(), profile.version());
+        assert_eq!(system_info.capabilities().me_count(), profile.me_count());
+        assert_eq!(system_info.capabilities().aux_count(), profile.aux_count());
+        assert_eq!(system_info.sources().count(), profile.sources().len());
+        assert_eq!(system_info.program_input(0), Some(1));
+    }
+}
+
+#[tokio::test]
+async fn mock_profiles_commands() {
+    let profile = DeviceProfile::atem_mini();
+    let server = MockServer::start(profile.clone()).await.unwrap();
+    let connection = connect(&server, &profile).await;
+
+    let program = |me, source| ControlCommand::ProgramInput(SetProgramInput::new(me, source));
+    connection
+        .send_confirmed(program(0, 3), CONFIRM_TIMEOUT)
+        .await
+        .unwrap();
+    // The Mini has a single mix effect block, which only the switcher checks
+    assert!(matches!(
+        connection
+            .send_confirmed(program(1, 3), CONFIRM_TIMEOUT)
+            .await,
+        Err(Error::Protocol(ProtocolError::NotConfirmed))
+    ));
+    // It has no multiview either, the command is rejected without being sent
+    assert!(matches!(
+        connection
+            .send_command(ControlCommand::MultiViewInput(SetMultiViewInput::new(
+                0, 2, 4,
+            )))
+            .await,
+        Err(Error::UnsupportedByModel {
+            model: DeviceModel::Mini,
+            ..
+        })
+    ));
+    assert!(matches!(
+        connection.set_multiview_input(0, 2, 4),
+        Err(Error::UnsupportedByModel { .. })
+    ));
+    assert_eq!(server.ignored(), [*b"CPgI"]);
+
+    let profile = DeviceProfile::constellation_8k();
+    let server = MockServer::start(profile.clone()).await.unwrap();
+    let connection = connect(&server, &profile).await;
+
+    connection
+        .send_confirmed(program(3, 40), CONFIRM_TIMEOUT)
+        .await
+        .unwrap();
+    connection
+        .send_confirmed(
+            ControlCommand::AuxSource(SetAuxSource::new(23, 6000)),
+            CONFIRM_TIMEOUT,
+        )
+        .await
+        .unwrap();
+    connection.set_multiview_input(0, 2, 4).unwrap();
+    connection.set_input_name(40, "Stage", "STG").unwrap();
+    timeout(Duration::from_secs(5), async {
+        while connection
+            .system_info()
+            .source(40)
+            .and_then(|source| source.long_name())
+            != Some("Stage")
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+    assert!(server.ignored().is_empty());
+    assert_eq!(server.received().len(), 4);
+}